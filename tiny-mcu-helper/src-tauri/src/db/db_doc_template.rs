@@ -0,0 +1,193 @@
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+// 文档模板；正文按版本保存在 doc_template_versions 中
+pub const DOC_TEMPLATE_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS doc_templates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)";
+
+// 模板的历史版本，version 从 1 开始递增
+pub const DOC_TEMPLATE_VERSION_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS doc_template_versions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    template_id INTEGER NOT NULL,
+    version INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    comment TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (template_id, version)
+)";
+
+/// 模板列表项（含最新版本号）
+#[derive(Debug, Clone, Serialize)]
+pub struct DocTemplateRecord {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub version: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 模板版本（不含正文）
+#[derive(Debug, Clone, Serialize)]
+pub struct DocTemplateVersionRecord {
+    pub version: i64,
+    pub comment: Option<String>,
+    pub created_at: String,
+}
+
+type DocTemplateRow = (i64, String, Option<String>, i64, String, String);
+
+fn to_record(row: DocTemplateRow) -> DocTemplateRecord {
+    DocTemplateRecord {
+        id: row.0,
+        name: row.1,
+        description: row.2,
+        version: row.3,
+        created_at: row.4,
+        updated_at: row.5,
+    }
+}
+
+const DOC_TEMPLATE_COLUMNS: &str = "t.id, t.name, t.description, \
+    (SELECT COALESCE(MAX(version), 0) FROM doc_template_versions v WHERE v.template_id = t.id), \
+    CAST(t.created_at AS TEXT), CAST(t.updated_at AS TEXT)";
+
+// 列出全部模板
+pub async fn db_doc_template_list(pool: &SqlitePool) -> Result<Vec<DocTemplateRecord>, String> {
+    let rows: Vec<DocTemplateRow> =
+        sqlx::query_as(&format!("SELECT {} FROM doc_templates t ORDER BY t.name", DOC_TEMPLATE_COLUMNS))
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("查询文档模板失败: {}", e))?;
+    Ok(rows.into_iter().map(to_record).collect())
+}
+
+// 读取模板指定版本的正文（version 为空时取最新版本）
+pub async fn db_doc_template_get(
+    pool: &SqlitePool,
+    id: i64,
+    version: Option<i64>,
+) -> Result<Option<(DocTemplateRecord, i64, String)>, String> {
+    let row: Option<DocTemplateRow> =
+        sqlx::query_as(&format!("SELECT {} FROM doc_templates t WHERE t.id = ?", DOC_TEMPLATE_COLUMNS))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("读取文档模板失败: {}", e))?;
+    let record = match row {
+        Some(row) => to_record(row),
+        None => return Ok(None),
+    };
+    let content: Option<(i64, String)> = sqlx::query_as(
+        "SELECT version, content FROM doc_template_versions WHERE template_id = ? AND version = ?",
+    )
+    .bind(id)
+    .bind(version.unwrap_or(record.version))
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| format!("读取文档模板失败: {}", e))?;
+    Ok(content.map(|(version, content)| (record, version, content)))
+}
+
+// 列出模板的全部版本（新版本在前）
+pub async fn db_doc_template_versions(pool: &SqlitePool, id: i64) -> Result<Vec<DocTemplateVersionRecord>, String> {
+    let rows: Vec<(i64, Option<String>, String)> = sqlx::query_as(
+        "SELECT version, comment, CAST(created_at AS TEXT) FROM doc_template_versions \
+         WHERE template_id = ? ORDER BY version DESC",
+    )
+    .bind(id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("查询模板版本失败: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .map(|r| DocTemplateVersionRecord {
+            version: r.0,
+            comment: r.1,
+            created_at: r.2,
+        })
+        .collect())
+}
+
+// 新建（id 为空）或更新模板；正文与最新版本不同时追加新版本，返回 (模板 id, 当前版本号)
+pub async fn db_doc_template_save(
+    pool: &SqlitePool,
+    id: Option<i64>,
+    name: &str,
+    description: Option<&str>,
+    content: &str,
+    comment: Option<&str>,
+) -> Result<(i64, i64), String> {
+    let mut tx = pool.begin().await.map_err(|e| format!("保存文档模板失败: {}", e))?;
+    let result = match id {
+        Some(id) => sqlx::query(
+            "UPDATE doc_templates SET name = ?, description = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(name)
+        .bind(description)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map(|r| (r.rows_affected(), id)),
+        None => sqlx::query("INSERT INTO doc_templates (name, description) VALUES (?, ?)")
+            .bind(name)
+            .bind(description)
+            .execute(&mut *tx)
+            .await
+            .map(|r| (r.rows_affected(), r.last_insert_rowid())),
+    };
+    let id = match result {
+        Ok((0, id)) => return Err(format!("文档模板 {} 不存在", id)),
+        Ok((_, id)) => id,
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return Err(format!("文档模板名称已存在: {}", name))
+        }
+        Err(e) => return Err(format!("保存文档模板失败: {}", e)),
+    };
+
+    let latest: Option<(i64, String)> = sqlx::query_as(
+        "SELECT version, content FROM doc_template_versions WHERE template_id = ? ORDER BY version DESC LIMIT 1",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("读取文档模板失败: {}", e))?;
+    let version = match latest {
+        Some((version, latest)) if latest == content => version,
+        latest => {
+            let version = latest.map(|l| l.0).unwrap_or(0) + 1;
+            sqlx::query("INSERT INTO doc_template_versions (template_id, version, content, comment) VALUES (?, ?, ?, ?)")
+                .bind(id)
+                .bind(version)
+                .bind(content)
+                .bind(comment)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("保存模板版本失败: {}", e))?;
+            version
+        }
+    };
+    tx.commit().await.map_err(|e| format!("保存文档模板失败: {}", e))?;
+    Ok((id, version))
+}
+
+// 删除模板及其全部版本
+pub async fn db_doc_template_delete(pool: &SqlitePool, id: i64) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| format!("删除文档模板失败: {}", e))?;
+    for sql in [
+        "DELETE FROM doc_template_versions WHERE template_id = ?",
+        "DELETE FROM doc_templates WHERE id = ?",
+    ] {
+        sqlx::query(sql)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("删除文档模板失败: {}", e))?;
+    }
+    tx.commit().await.map_err(|e| format!("删除文档模板失败: {}", e))
+}
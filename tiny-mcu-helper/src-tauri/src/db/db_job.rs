@@ -0,0 +1,156 @@
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+// 后台任务记录；spec 保存提交时的任务 JSON，results 保存每一项的执行结果 JSON
+pub const JOB_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    status TEXT NOT NULL,
+    spec TEXT NOT NULL,
+    total INTEGER NOT NULL DEFAULT 0,
+    done INTEGER NOT NULL DEFAULT 0,
+    failed INTEGER NOT NULL DEFAULT 0,
+    message TEXT,
+    results TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    started_at DATETIME,
+    finished_at DATETIME
+)";
+
+/// 任务列表项（不含 spec 与 results）
+#[derive(Debug, Clone, Serialize)]
+pub struct JobRecord {
+    pub id: i64,
+    pub title: String,
+    pub status: String,
+    pub total: i64,
+    pub done: i64,
+    pub failed: i64,
+    pub message: Option<String>,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+type JobRow = (i64, String, String, i64, i64, i64, Option<String>, String, Option<String>, Option<String>);
+// JobRow 之后追加 spec 与 results
+type JobDetailRow = (i64, String, String, i64, i64, i64, Option<String>, String, Option<String>, Option<String>, String, Option<String>);
+
+fn to_record(row: JobRow) -> JobRecord {
+    JobRecord {
+        id: row.0,
+        title: row.1,
+        status: row.2,
+        total: row.3,
+        done: row.4,
+        failed: row.5,
+        message: row.6,
+        created_at: row.7,
+        started_at: row.8,
+        finished_at: row.9,
+    }
+}
+
+const JOB_COLUMNS: &str = "id, title, status, total, done, failed, message, CAST(created_at AS TEXT), \
+    CAST(started_at AS TEXT), CAST(finished_at AS TEXT)";
+
+// 新建任务（状态为 queued），返回任务 id
+pub async fn db_job_insert(pool: &SqlitePool, title: &str, spec: &str, total: i64) -> Result<i64, String> {
+    sqlx::query("INSERT INTO jobs (title, status, spec, total) VALUES (?, 'queued', ?, ?)")
+        .bind(title)
+        .bind(spec)
+        .bind(total)
+        .execute(pool)
+        .await
+        .map(|r| r.last_insert_rowid())
+        .map_err(|e| format!("创建任务失败: {}", e))
+}
+
+// 标记任务开始执行
+pub async fn db_job_start(pool: &SqlitePool, id: i64) -> Result<(), String> {
+    sqlx::query("UPDATE jobs SET status = 'running', started_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("更新任务状态失败: {}", e))?;
+    Ok(())
+}
+
+// 更新进度与已完成项的结果
+pub async fn db_job_progress(pool: &SqlitePool, id: i64, done: i64, failed: i64, results: &str) -> Result<(), String> {
+    sqlx::query("UPDATE jobs SET done = ?, failed = ?, results = ? WHERE id = ?")
+        .bind(done)
+        .bind(failed)
+        .bind(results)
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("更新任务进度失败: {}", e))?;
+    Ok(())
+}
+
+// 任务结束（completed / failed / cancelled）
+pub async fn db_job_finish(pool: &SqlitePool, id: i64, status: &str, message: Option<&str>) -> Result<(), String> {
+    sqlx::query("UPDATE jobs SET status = ?, message = ?, finished_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(status)
+        .bind(message)
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("更新任务状态失败: {}", e))?;
+    Ok(())
+}
+
+// 启动时把上次未结束的任务标记为 interrupted，返回受影响的条数
+pub async fn db_job_mark_interrupted(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+    sqlx::query(
+        "UPDATE jobs SET status = 'interrupted', message = '应用退出时任务未完成', finished_at = CURRENT_TIMESTAMP \
+         WHERE status IN ('queued', 'running')",
+    )
+    .execute(pool)
+    .await
+    .map(|r| r.rows_affected())
+}
+
+// 按创建时间倒序列出任务
+pub async fn db_job_list(pool: &SqlitePool, limit: i64) -> Result<Vec<JobRecord>, String> {
+    let rows: Vec<JobRow> = sqlx::query_as(&format!("SELECT {} FROM jobs ORDER BY id DESC LIMIT ?", JOB_COLUMNS))
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("查询任务失败: {}", e))?;
+    Ok(rows.into_iter().map(to_record).collect())
+}
+
+// 读取任务记录及 spec、results JSON
+pub async fn db_job_get(pool: &SqlitePool, id: i64) -> Result<Option<(JobRecord, String, Option<String>)>, String> {
+    let row: Option<JobDetailRow> =
+        sqlx::query_as(&format!("SELECT {}, spec, results FROM jobs WHERE id = ?", JOB_COLUMNS))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("读取任务失败: {}", e))?;
+    Ok(row.map(|r| {
+        let record = to_record((r.0, r.1, r.2, r.3, r.4, r.5, r.6, r.7, r.8, r.9));
+        (record, r.10, r.11)
+    }))
+}
+
+// 删除单个已结束的任务
+pub async fn db_job_delete(pool: &SqlitePool, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM jobs WHERE id = ? AND status NOT IN ('queued', 'running')")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("删除任务失败: {}", e))?;
+    Ok(())
+}
+
+// 清空已结束的任务历史，返回删除条数
+pub async fn db_job_clear_history(pool: &SqlitePool) -> Result<u64, String> {
+    sqlx::query("DELETE FROM jobs WHERE status NOT IN ('queued', 'running')")
+        .execute(pool)
+        .await
+        .map(|r| r.rows_affected())
+        .map_err(|e| format!("清空任务历史失败: {}", e))
+}
@@ -0,0 +1,102 @@
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+// 内存布局（区域 + 分区），以 JSON 保存
+pub const MEMORY_LAYOUT_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS memory_layouts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    model TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)";
+
+/// 布局列表项（不含模型 JSON）
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryLayoutRecord {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+type MemoryLayoutRow = (i64, String, Option<String>, String, String);
+
+fn to_record(row: MemoryLayoutRow) -> MemoryLayoutRecord {
+    MemoryLayoutRecord {
+        id: row.0,
+        name: row.1,
+        description: row.2,
+        created_at: row.3,
+        updated_at: row.4,
+    }
+}
+
+const MEMORY_LAYOUT_COLUMNS: &str =
+    "id, name, description, CAST(created_at AS TEXT), CAST(updated_at AS TEXT)";
+
+// 列出全部内存布局
+pub async fn db_memory_layout_list(pool: &SqlitePool) -> Result<Vec<MemoryLayoutRecord>, String> {
+    let rows: Vec<MemoryLayoutRow> =
+        sqlx::query_as(&format!("SELECT {} FROM memory_layouts ORDER BY name", MEMORY_LAYOUT_COLUMNS))
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("查询内存布局失败: {}", e))?;
+    Ok(rows.into_iter().map(to_record).collect())
+}
+
+// 读取布局模型 JSON
+pub async fn db_memory_layout_get_model(pool: &SqlitePool, id: i64) -> Result<Option<String>, String> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT model FROM memory_layouts WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("读取内存布局失败: {}", e))?;
+    Ok(row.map(|r| r.0))
+}
+
+// 新建（id 为空）或更新布局，返回记录 id
+pub async fn db_memory_layout_save(
+    pool: &SqlitePool,
+    id: Option<i64>,
+    name: &str,
+    description: Option<&str>,
+    model: &str,
+) -> Result<i64, String> {
+    let result = match id {
+        Some(id) => sqlx::query(
+            "UPDATE memory_layouts SET name = ?, description = ?, model = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(name)
+        .bind(description)
+        .bind(model)
+        .bind(id)
+        .execute(pool)
+        .await
+        .map(|r| (r.rows_affected(), id)),
+        None => sqlx::query("INSERT INTO memory_layouts (name, description, model) VALUES (?, ?, ?)")
+            .bind(name)
+            .bind(description)
+            .bind(model)
+            .execute(pool)
+            .await
+            .map(|r| (r.rows_affected(), r.last_insert_rowid())),
+    };
+    match result {
+        Ok((0, id)) => Err(format!("内存布局 {} 不存在", id)),
+        Ok((_, id)) => Ok(id),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => Err(format!("内存布局名称已存在: {}", name)),
+        Err(e) => Err(format!("保存内存布局失败: {}", e)),
+    }
+}
+
+// 删除布局
+pub async fn db_memory_layout_delete(pool: &SqlitePool, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM memory_layouts WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("删除内存布局失败: {}", e))?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::time::Duration;
+use tauri::{App, Manager};
+
+use crate::db::{
+    db_job_mark_interrupted, DOC_TEMPLATE_SCHEMA, DOC_TEMPLATE_VERSION_SCHEMA, JOB_SCHEMA, MEMORY_LAYOUT_SCHEMA,
+    REGISTER_MAP_SCHEMA, SVD_SCHEMA,
+};
+
+// 与 tauri-plugin-sql 共用同一个数据库文件（"sqlite:todo.db" 位于应用配置目录）
+pub const APP_DB_FILE: &str = "todo.db";
+
+// Rust 侧使用的数据表，启动时按顺序创建
+const APP_SCHEMAS: &[&str] = &[
+    SVD_SCHEMA,
+    REGISTER_MAP_SCHEMA,
+    MEMORY_LAYOUT_SCHEMA,
+    JOB_SCHEMA,
+    DOC_TEMPLATE_SCHEMA,
+    DOC_TEMPLATE_VERSION_SCHEMA,
+];
+
+/// Rust 侧共享的数据库连接池
+pub struct DbState {
+    pub pool: SqlitePool,
+}
+
+// 初始化数据库连接并建表
+pub fn init_db(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    let db_dir = app.path().app_config_dir()?;
+    std::fs::create_dir_all(&db_dir)?;
+    let options = SqliteConnectOptions::new()
+        .filename(db_dir.join(APP_DB_FILE))
+        .create_if_missing(true)
+        .busy_timeout(Duration::from_secs(5));
+
+    let pool = tauri::async_runtime::block_on(async {
+        let pool = SqlitePoolOptions::new().max_connections(4).connect_with(options).await?;
+        for schema in APP_SCHEMAS {
+            sqlx::query(schema).execute(&pool).await?;
+        }
+        // 上次退出时未完成的后台任务
+        db_job_mark_interrupted(&pool).await?;
+        Ok::<_, sqlx::Error>(pool)
+    })?;
+
+    app.manage(DbState { pool });
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+// 用户自建的寄存器映射，模型与 SVD 器件结构相同，以 JSON 保存
+pub const REGISTER_MAP_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS register_maps (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    model TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)";
+
+/// 映射列表项（不含模型 JSON）
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisterMapRecord {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+type RegisterMapRow = (i64, String, Option<String>, String, String);
+
+fn to_record(row: RegisterMapRow) -> RegisterMapRecord {
+    RegisterMapRecord {
+        id: row.0,
+        name: row.1,
+        description: row.2,
+        created_at: row.3,
+        updated_at: row.4,
+    }
+}
+
+const REGISTER_MAP_COLUMNS: &str =
+    "id, name, description, CAST(created_at AS TEXT), CAST(updated_at AS TEXT)";
+
+// 列出全部寄存器映射
+pub async fn db_register_map_list(pool: &SqlitePool) -> Result<Vec<RegisterMapRecord>, String> {
+    let rows: Vec<RegisterMapRow> =
+        sqlx::query_as(&format!("SELECT {} FROM register_maps ORDER BY name", REGISTER_MAP_COLUMNS))
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("查询寄存器映射失败: {}", e))?;
+    Ok(rows.into_iter().map(to_record).collect())
+}
+
+// 读取映射模型 JSON
+pub async fn db_register_map_get_model(pool: &SqlitePool, id: i64) -> Result<Option<String>, String> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT model FROM register_maps WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("读取寄存器映射失败: {}", e))?;
+    Ok(row.map(|r| r.0))
+}
+
+// 新建（id 为空）或更新映射，返回记录 id
+pub async fn db_register_map_save(
+    pool: &SqlitePool,
+    id: Option<i64>,
+    name: &str,
+    description: Option<&str>,
+    model: &str,
+) -> Result<i64, String> {
+    let result = match id {
+        Some(id) => sqlx::query(
+            "UPDATE register_maps SET name = ?, description = ?, model = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(name)
+        .bind(description)
+        .bind(model)
+        .bind(id)
+        .execute(pool)
+        .await
+        .map(|r| (r.rows_affected(), id)),
+        None => sqlx::query("INSERT INTO register_maps (name, description, model) VALUES (?, ?, ?)")
+            .bind(name)
+            .bind(description)
+            .bind(model)
+            .execute(pool)
+            .await
+            .map(|r| (r.rows_affected(), r.last_insert_rowid())),
+    };
+    match result {
+        Ok((0, id)) => Err(format!("寄存器映射 {} 不存在", id)),
+        Ok((_, id)) => Ok(id),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => Err(format!("寄存器映射名称已存在: {}", name)),
+        Err(e) => Err(format!("保存寄存器映射失败: {}", e)),
+    }
+}
+
+// 删除映射
+pub async fn db_register_map_delete(pool: &SqlitePool, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM register_maps WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("删除寄存器映射失败: {}", e))?;
+    Ok(())
+}
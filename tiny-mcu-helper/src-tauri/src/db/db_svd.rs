@@ -0,0 +1,117 @@
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+// 解析后的 SVD 器件模型以 JSON 缓存，file_hash 变化时重新解析
+pub const SVD_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS svd_devices (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    file_path TEXT NOT NULL UNIQUE,
+    file_hash TEXT NOT NULL,
+    peripheral_count INTEGER NOT NULL,
+    register_count INTEGER NOT NULL,
+    model TEXT NOT NULL,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)";
+
+/// 缓存记录（不含模型 JSON）
+#[derive(Debug, Clone, Serialize)]
+pub struct SvdDeviceRecord {
+    pub id: i64,
+    pub name: String,
+    pub file_path: String,
+    pub file_hash: String,
+    pub peripheral_count: i64,
+    pub register_count: i64,
+    pub updated_at: String,
+}
+
+type SvdDeviceRow = (i64, String, String, String, i64, i64, String);
+
+fn to_record(row: SvdDeviceRow) -> SvdDeviceRecord {
+    SvdDeviceRecord {
+        id: row.0,
+        name: row.1,
+        file_path: row.2,
+        file_hash: row.3,
+        peripheral_count: row.4,
+        register_count: row.5,
+        updated_at: row.6,
+    }
+}
+
+const SVD_RECORD_COLUMNS: &str =
+    "id, name, file_path, file_hash, peripheral_count, register_count, CAST(updated_at AS TEXT)";
+
+// 列出全部缓存的器件
+pub async fn db_svd_list(pool: &SqlitePool) -> Result<Vec<SvdDeviceRecord>, String> {
+    let rows: Vec<SvdDeviceRow> = sqlx::query_as(&format!("SELECT {} FROM svd_devices ORDER BY name", SVD_RECORD_COLUMNS))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("查询 SVD 缓存失败: {}", e))?;
+    Ok(rows.into_iter().map(to_record).collect())
+}
+
+// 按文件路径查找缓存记录
+pub async fn db_svd_find_by_path(pool: &SqlitePool, file_path: &str) -> Result<Option<SvdDeviceRecord>, String> {
+    let row: Option<SvdDeviceRow> = sqlx::query_as(&format!("SELECT {} FROM svd_devices WHERE file_path = ?", SVD_RECORD_COLUMNS))
+        .bind(file_path)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("查询 SVD 缓存失败: {}", e))?;
+    Ok(row.map(to_record))
+}
+
+// 读取缓存的模型 JSON
+pub async fn db_svd_get_model(pool: &SqlitePool, id: i64) -> Result<Option<String>, String> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT model FROM svd_devices WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("读取 SVD 缓存失败: {}", e))?;
+    Ok(row.map(|r| r.0))
+}
+
+// 写入或更新缓存，返回记录
+pub async fn db_svd_save(
+    pool: &SqlitePool,
+    name: &str,
+    file_path: &str,
+    file_hash: &str,
+    peripheral_count: i64,
+    register_count: i64,
+    model: &str,
+) -> Result<SvdDeviceRecord, String> {
+    sqlx::query(
+        "INSERT INTO svd_devices (name, file_path, file_hash, peripheral_count, register_count, model)
+         VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT(file_path) DO UPDATE SET
+            name = excluded.name,
+            file_hash = excluded.file_hash,
+            peripheral_count = excluded.peripheral_count,
+            register_count = excluded.register_count,
+            model = excluded.model,
+            updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(name)
+    .bind(file_path)
+    .bind(file_hash)
+    .bind(peripheral_count)
+    .bind(register_count)
+    .bind(model)
+    .execute(pool)
+    .await
+    .map_err(|e| format!("写入 SVD 缓存失败: {}", e))?;
+    db_svd_find_by_path(pool, file_path)
+        .await?
+        .ok_or("写入 SVD 缓存失败".to_string())
+}
+
+// 删除缓存记录
+pub async fn db_svd_delete(pool: &SqlitePool, id: i64) -> Result<(), String> {
+    sqlx::query("DELETE FROM svd_devices WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| format!("删除 SVD 缓存失败: {}", e))?;
+    Ok(())
+}
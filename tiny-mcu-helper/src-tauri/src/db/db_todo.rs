@@ -1,3 +1,5 @@
+use serde::Serialize;
+use sqlx::SqlitePool;
 use tauri::{App, AppHandle};
 use tauri_plugin_sql::{Migration, MigrationKind};
 
@@ -15,3 +17,35 @@ pub fn create_todo_migrations() -> Vec<Migration> {
         kind: MigrationKind::Up,
     }]
 }
+
+/// 待办事项（表由前端 tauri-plugin-sql 创建，这里只读取）
+#[derive(Debug, Clone, Serialize)]
+pub struct TodoItem {
+    pub id: i64,
+    pub content: String,
+    pub completed: bool,
+}
+
+// 读取全部待办事项；待办页面从未打开过时表不存在，返回空列表
+pub async fn db_todo_list(pool: &SqlitePool) -> Result<Vec<TodoItem>, String> {
+    let exists: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'todos'")
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("查询待办事项失败: {}", e))?;
+    if exists.is_none() {
+        return Ok(Vec::new());
+    }
+    let rows: Vec<(i64, String, bool)> = sqlx::query_as("SELECT id, content, completed FROM todos ORDER BY id")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("查询待办事项失败: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .map(|r| TodoItem {
+            id: r.0,
+            content: r.1,
+            completed: r.2,
+        })
+        .collect())
+}
@@ -1,2 +1,14 @@
 pub mod db_todo;
-pub use db_todo::*;
\ No newline at end of file
+pub use db_todo::*;
+pub mod db_pool;
+pub use db_pool::*;
+pub mod db_svd;
+pub use db_svd::*;
+pub mod db_register_map;
+pub use db_register_map::*;
+pub mod db_memory_layout;
+pub use db_memory_layout::*;
+pub mod db_job;
+pub use db_job::*;
+pub mod db_doc_template;
+pub use db_doc_template::*;
\ No newline at end of file
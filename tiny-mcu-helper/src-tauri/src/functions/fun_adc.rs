@@ -0,0 +1,435 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{util_format_c_array, util_format_c_value, util_format_csv, util_write_generate_file, TableFormat};
+
+/// ADC 换算参数
+/// 换算链：原始码 -> 校准（(raw - offset) * gain）-> ADC 引脚电压 -> 分压前输入电压 -> 工程值
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdcScaleConfig {
+    pub resolution_bits: u8,
+    pub vref: f64,
+    pub divider_top_ohm: Option<f64>,    // 分压上臂（信号到 ADC 引脚），不填表示无分压
+    pub divider_bottom_ohm: Option<f64>, // 分压下臂（ADC 引脚到地）
+    pub offset_lsb: Option<f64>,         // 偏移校准，默认 0
+    pub gain: Option<f64>,               // 增益校准，默认 1
+    pub eng_scale: Option<f64>,          // 工程值 = 输入电压 * eng_scale + eng_offset
+    pub eng_offset: Option<f64>,
+    pub unit: Option<String>,
+}
+
+impl AdcScaleConfig {
+    fn validate(&self) -> Result<(), String> {
+        if !(1..=24).contains(&self.resolution_bits) {
+            return Err(format!("不支持的 ADC 分辨率: {} 位", self.resolution_bits));
+        }
+        if self.vref <= 0.0 {
+            return Err("参考电压必须大于 0".to_string());
+        }
+        if self.gain.unwrap_or(1.0) == 0.0 || self.eng_scale.unwrap_or(1.0) == 0.0 {
+            return Err("增益和工程值系数不能为 0".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn max_code(&self) -> u32 {
+        (1u32 << self.resolution_bits) - 1
+    }
+
+    // 分压比：输入电压 / ADC 引脚电压
+    fn divider_ratio(&self) -> Result<f64, String> {
+        match (self.divider_top_ohm, self.divider_bottom_ohm) {
+            (Some(top), Some(bottom)) if bottom > 0.0 && top >= 0.0 => Ok((top + bottom) / bottom),
+            (None, None) => Ok(1.0),
+            _ => Err("分压电阻需同时填写且下臂电阻大于 0".to_string()),
+        }
+    }
+}
+
+/// 各级换算结果
+#[derive(Debug, Clone, Serialize)]
+pub struct AdcConversion {
+    pub raw: f64,
+    pub code: u32, // 四舍五入并限制在量程内的原始码
+    pub corrected: f64,
+    pub adc_voltage: f64,
+    pub input_voltage: f64,
+    pub engineering: f64,
+    pub clipped: bool, // 输入超出 ADC 量程
+}
+
+/// 换算起点
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdcValueKind {
+    Raw,
+    AdcVoltage,
+    InputVoltage,
+    Engineering,
+}
+
+/// 从任意一级换算出全部结果
+pub fn adc_convert_value(config: &AdcScaleConfig, value: f64, from: AdcValueKind) -> Result<AdcConversion, String> {
+    config.validate()?;
+    let ratio = config.divider_ratio()?;
+    let full_scale = config.max_code() as f64;
+    let offset = config.offset_lsb.unwrap_or(0.0);
+    let gain = config.gain.unwrap_or(1.0);
+    let eng_scale = config.eng_scale.unwrap_or(1.0);
+    let eng_offset = config.eng_offset.unwrap_or(0.0);
+
+    // 统一先求出校准后的码值
+    let corrected = match from {
+        AdcValueKind::Raw => (value - offset) * gain,
+        AdcValueKind::AdcVoltage => value / config.vref * full_scale,
+        AdcValueKind::InputVoltage => value / ratio / config.vref * full_scale,
+        AdcValueKind::Engineering => (value - eng_offset) / eng_scale / ratio / config.vref * full_scale,
+    };
+    let raw = match from {
+        AdcValueKind::Raw => value,
+        _ => corrected / gain + offset,
+    };
+    let adc_voltage = corrected / full_scale * config.vref;
+    let input_voltage = adc_voltage * ratio;
+
+    Ok(AdcConversion {
+        raw,
+        code: raw.round().clamp(0.0, full_scale) as u32,
+        corrected,
+        adc_voltage,
+        input_voltage,
+        engineering: input_voltage * eng_scale + eng_offset,
+        clipped: raw.round() < 0.0 || raw.round() > full_scale,
+    })
+}
+
+/// 查找表参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdcTableConfig {
+    pub scale: AdcScaleConfig,
+    pub format: TableFormat,
+    pub step: Option<u32>,         // 原始码步进，默认 1
+    pub name: Option<String>,      // C 数组名，默认 adc_table
+    pub c_type: Option<String>,    // float / double / int16_t 等，默认 float
+    pub decimals: Option<usize>,   // 小数位数，默认 4
+    pub file_name: Option<String>, // 填写时同时保存到 generate 目录
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AdcTableResult {
+    pub content: String,
+    pub entries: usize,
+    pub output_path: Option<String>,
+}
+
+/// 生成原始码 -> 工程值查找表
+pub fn adc_build_table(config: &AdcTableConfig) -> Result<AdcTableResult, String> {
+    config.scale.validate()?;
+    let step = config.step.unwrap_or(1).max(1);
+    let decimals = config.decimals.unwrap_or(4);
+    let codes: Vec<u32> = (0..=config.scale.max_code()).step_by(step as usize).collect();
+    let conversions = codes
+        .iter()
+        .map(|code| adc_convert_value(&config.scale, *code as f64, AdcValueKind::Raw))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let content = match config.format {
+        TableFormat::Csv => {
+            let rows: Vec<Vec<String>> = conversions
+                .iter()
+                .map(|c| {
+                    vec![
+                        format!("{}", c.code),
+                        format!("{:.*}", decimals, c.adc_voltage),
+                        format!("{:.*}", decimals, c.input_voltage),
+                        format!("{:.*}", decimals, c.engineering),
+                    ]
+                })
+                .collect();
+            util_format_csv(&["code", "adc_voltage", "input_voltage", "engineering"], &rows)
+        }
+        TableFormat::C => {
+            let c_type = config.c_type.as_deref().unwrap_or("float");
+            let values: Vec<String> = conversions
+                .iter()
+                .map(|c| util_format_c_value(c.engineering, c_type, decimals))
+                .collect();
+            let comment = format!(
+                "ADC 查找表：{} 位，Vref = {} V，原始码步进 {}\n索引 i 对应原始码 i * {}，单位: {}",
+                config.scale.resolution_bits,
+                config.scale.vref,
+                step,
+                step,
+                config.scale.unit.as_deref().unwrap_or("V")
+            );
+            util_format_c_array(config.name.as_deref().unwrap_or("adc_table"), c_type, &values, 8, &comment)
+        }
+    };
+
+    let output_path = match &config.file_name {
+        Some(file_name) => Some(util_write_generate_file(file_name, config.format.extension(), &content)?),
+        None => None,
+    };
+    Ok(AdcTableResult {
+        content,
+        entries: codes.len(),
+        output_path,
+    })
+}
+
+// ==================== 采样时间 ====================
+
+/// SAR ADC 采样模型
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AdcSamplingFamily {
+    pub name: String,
+    pub r_adc_ohm: f64,           // 采样开关电阻
+    pub c_adc_f: f64,             // 采样电容（含引脚寄生）
+    pub sampling_cycles: Vec<f64>, // 可选采样时间（ADC 时钟周期）
+    pub conversion_cycles: f64,    // 逐次逼近转换周期（按最高分辨率）
+    pub max_adc_clock_hz: f64,
+}
+
+/// 内置 ADC 采样参数（取自各系列数据手册的 R_ADC / C_ADC 最大值）
+pub fn adc_sampling_families() -> Vec<AdcSamplingFamily> {
+    vec![
+        AdcSamplingFamily {
+            name: "STM32F1".to_string(),
+            r_adc_ohm: 1000.0,
+            c_adc_f: 8e-12,
+            sampling_cycles: vec![1.5, 7.5, 13.5, 28.5, 41.5, 55.5, 71.5, 239.5],
+            conversion_cycles: 12.5,
+            max_adc_clock_hz: 14e6,
+        },
+        AdcSamplingFamily {
+            name: "STM32F4".to_string(),
+            r_adc_ohm: 6000.0,
+            c_adc_f: 7e-12,
+            sampling_cycles: vec![3.0, 15.0, 28.0, 56.0, 84.0, 112.0, 144.0, 480.0],
+            conversion_cycles: 12.0,
+            max_adc_clock_hz: 36e6,
+        },
+        AdcSamplingFamily {
+            name: "STM32G0".to_string(),
+            r_adc_ohm: 1000.0,
+            c_adc_f: 5e-12,
+            sampling_cycles: vec![1.5, 3.5, 7.5, 12.5, 19.5, 39.5, 79.5, 160.5],
+            conversion_cycles: 12.5,
+            max_adc_clock_hz: 35e6,
+        },
+        AdcSamplingFamily {
+            name: "STM32H7".to_string(),
+            r_adc_ohm: 1000.0,
+            c_adc_f: 4e-12,
+            sampling_cycles: vec![1.5, 2.5, 8.5, 16.5, 32.5, 64.5, 387.5, 810.5],
+            conversion_cycles: 8.5,
+            max_adc_clock_hz: 50e6,
+        },
+    ]
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdcSamplingConfig {
+    pub family: Option<String>,
+    pub custom_family: Option<AdcSamplingFamily>,
+    pub adc_clock_hz: f64,
+    pub resolution_bits: u8,
+    pub source_impedance_ohm: f64,
+    pub c_adc_f: Option<f64>,   // 覆盖系列默认值
+    pub r_adc_ohm: Option<f64>, // 覆盖系列默认值
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AdcSamplingResult {
+    pub required_time_ns: f64,
+    pub required_cycles: f64,
+    pub selected_cycles: Option<f64>, // 满足要求的最短采样时间，无则为 None
+    pub sampling_time_ns: Option<f64>,
+    pub conversion_time_ns: Option<f64>,
+    pub max_sample_rate_hz: Option<f64>,
+    pub max_source_impedance_ohm: Option<f64>, // 所选采样时间下允许的最大源阻抗
+}
+
+/// 求最短采样时间：电容需在采样期间充电到 1/4 LSB 以内，
+/// t_s >= (R_AIN + R_ADC) * C_ADC * ln(2^(N+2))
+pub fn adc_sampling_solve(config: &AdcSamplingConfig) -> Result<AdcSamplingResult, String> {
+    let family = match (&config.custom_family, &config.family) {
+        (Some(family), _) => family.clone(),
+        (None, Some(name)) => adc_sampling_families()
+            .into_iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+            .ok_or(format!("未知的 ADC 系列: {}", name))?,
+        (None, None) => return Err("请指定 ADC 系列".to_string()),
+    };
+    if config.adc_clock_hz <= 0.0 || config.adc_clock_hz > family.max_adc_clock_hz {
+        return Err(format!("ADC 时钟超出范围: 最高 {} Hz", family.max_adc_clock_hz));
+    }
+    if !(6..=16).contains(&config.resolution_bits) {
+        return Err(format!("不支持的 ADC 分辨率: {} 位", config.resolution_bits));
+    }
+
+    let r_adc = config.r_adc_ohm.unwrap_or(family.r_adc_ohm);
+    let c_adc = config.c_adc_f.unwrap_or(family.c_adc_f);
+    let ln_term = ((config.resolution_bits + 2) as f64) * std::f64::consts::LN_2;
+    let required_time_s = (config.source_impedance_ohm + r_adc) * c_adc * ln_term;
+    let required_cycles = required_time_s * config.adc_clock_hz;
+
+    let mut options = family.sampling_cycles.clone();
+    options.sort_by(|a, b| a.total_cmp(b));
+    let selected_cycles = options.into_iter().find(|c| *c >= required_cycles);
+
+    let cycle_ns = 1e9 / config.adc_clock_hz;
+    // 转换周期按分辨率缩减（每少 2 位约少 2 个周期）
+    let conversion_cycles = family.conversion_cycles - (12.0 - config.resolution_bits as f64).max(0.0);
+    Ok(AdcSamplingResult {
+        required_time_ns: required_time_s * 1e9,
+        required_cycles,
+        selected_cycles,
+        sampling_time_ns: selected_cycles.map(|c| c * cycle_ns),
+        conversion_time_ns: selected_cycles.map(|c| (c + conversion_cycles) * cycle_ns),
+        max_sample_rate_hz: selected_cycles.map(|c| config.adc_clock_hz / (c + conversion_cycles)),
+        max_source_impedance_ohm: selected_cycles
+            .map(|c| (c / config.adc_clock_hz / (c_adc * ln_term) - r_adc).max(0.0)),
+    })
+}
+
+// ADC 数值换算
+#[tauri::command]
+pub fn adc_convert(config: AdcScaleConfig, value: f64, from: AdcValueKind) -> Result<AdcConversion, String> {
+    adc_convert_value(&config, value, from)
+}
+
+// 生成 ADC 查找表（CSV 或 C 数组）
+#[tauri::command]
+pub fn adc_lookup_table(config: AdcTableConfig) -> Result<AdcTableResult, String> {
+    adc_build_table(&config)
+}
+
+// 列出内置 ADC 采样参数
+#[tauri::command]
+pub fn adc_list_sampling_families() -> Vec<AdcSamplingFamily> {
+    adc_sampling_families()
+}
+
+// 求解最短采样时间
+#[tauri::command]
+pub fn adc_sampling_time_solve(config: AdcSamplingConfig) -> Result<AdcSamplingResult, String> {
+    adc_sampling_solve(&config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scale() -> AdcScaleConfig {
+        AdcScaleConfig {
+            resolution_bits: 12,
+            vref: 3.3,
+            divider_top_ohm: Some(20_000.0),
+            divider_bottom_ohm: Some(10_000.0),
+            offset_lsb: Some(2.0),
+            gain: Some(1.01),
+            eng_scale: Some(10.0),
+            eng_offset: Some(-5.0),
+            unit: None,
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn convert_round_trips() {
+        let config = scale();
+        let base = adc_convert_value(&config, 2000.0, AdcValueKind::Raw).unwrap();
+        assert!(close(base.corrected, (2000.0 - 2.0) * 1.01));
+        assert!(close(base.adc_voltage, base.corrected / 4095.0 * 3.3));
+        assert!(close(base.input_voltage, base.adc_voltage * 3.0));
+        assert!(close(base.engineering, base.input_voltage * 10.0 - 5.0));
+        assert_eq!((base.code, base.clipped), (2000, false));
+
+        // 从任一级换算回来，各级结果一致
+        for (kind, value) in [
+            (AdcValueKind::Raw, base.raw),
+            (AdcValueKind::AdcVoltage, base.adc_voltage),
+            (AdcValueKind::InputVoltage, base.input_voltage),
+            (AdcValueKind::Engineering, base.engineering),
+        ] {
+            let c = adc_convert_value(&config, value, kind).unwrap();
+            assert!(close(c.raw, base.raw), "{:?} {:?}", kind, c);
+            assert!(close(c.corrected, base.corrected), "{:?} {:?}", kind, c);
+            assert!(close(c.adc_voltage, base.adc_voltage), "{:?} {:?}", kind, c);
+            assert!(close(c.input_voltage, base.input_voltage), "{:?} {:?}", kind, c);
+            assert!(close(c.engineering, base.engineering), "{:?} {:?}", kind, c);
+            assert_eq!((c.code, c.clipped), (2000, false));
+        }
+    }
+
+    #[test]
+    fn convert_clips_and_validates() {
+        let mut config = scale();
+        (config.offset_lsb, config.gain) = (None, None);
+        let full = adc_convert_value(&config, 4095.0, AdcValueKind::Raw).unwrap();
+        assert!(close(full.adc_voltage, 3.3) && close(full.input_voltage, 9.9));
+
+        // 输入 12V 经 1/3 分压后为 4V，超出 3.3V 量程
+        let over = adc_convert_value(&config, 12.0, AdcValueKind::InputVoltage).unwrap();
+        assert_eq!((over.code, over.clipped), (4095, true));
+        let under = adc_convert_value(&config, -0.1, AdcValueKind::AdcVoltage).unwrap();
+        assert_eq!((under.code, under.clipped), (0, true));
+
+        config.divider_bottom_ohm = None;
+        assert!(adc_convert_value(&config, 0.0, AdcValueKind::Raw).is_err());
+        config.divider_top_ohm = None;
+        config.gain = Some(0.0);
+        assert!(adc_convert_value(&config, 0.0, AdcValueKind::Raw).is_err());
+    }
+
+    fn sampling_config(source_impedance_ohm: f64) -> AdcSamplingConfig {
+        AdcSamplingConfig {
+            family: Some("stm32f1".to_string()),
+            custom_family: None,
+            adc_clock_hz: 14e6,
+            resolution_bits: 12,
+            source_impedance_ohm,
+            c_adc_f: None,
+            r_adc_ohm: None,
+        }
+    }
+
+    // STM32F103 数据手册表 "R_AIN max for f_ADC = 14 MHz"
+    #[test]
+    fn sampling_matches_datasheet() {
+        for (source, cycles, r_ain_max_kohm) in [
+            (0.0, 1.5, 0.4),
+            (1_000.0, 7.5, 5.9),
+            (10_000.0, 13.5, 11.4),
+            (20_000.0, 28.5, 25.2),
+            (30_000.0, 41.5, 37.2),
+            (45_000.0, 55.5, 50.0),
+        ] {
+            let result = adc_sampling_solve(&sampling_config(source)).unwrap();
+            assert_eq!(result.selected_cycles, Some(cycles), "{}", source);
+            let r_ain_max = result.max_source_impedance_ohm.unwrap();
+            assert!((r_ain_max / 1000.0 - r_ain_max_kohm).abs() < 0.1, "{} {}", cycles, r_ain_max);
+            assert!(r_ain_max >= source);
+        }
+
+        // 1.5 + 12.5 周期 @ 14 MHz = 1 µs
+        let fastest = adc_sampling_solve(&sampling_config(0.0)).unwrap();
+        assert!(close(fastest.conversion_time_ns.unwrap(), 1000.0));
+        assert!(close(fastest.max_sample_rate_hz.unwrap(), 1e6));
+        // t_s = (0 + 1kΩ) * 8pF * ln(2^14)
+        assert!(close(fastest.required_time_ns, 8.0 * 14.0 * std::f64::consts::LN_2));
+
+        // 源阻抗过大时没有可用的采样时间
+        let slow = adc_sampling_solve(&sampling_config(500_000.0)).unwrap();
+        assert_eq!((slow.selected_cycles, slow.max_sample_rate_hz), (None, None));
+
+        let mut invalid = sampling_config(0.0);
+        invalid.adc_clock_hz = 28e6;
+        assert!(adc_sampling_solve(&invalid).is_err());
+        invalid.family = Some("unknown".to_string());
+        assert!(adc_sampling_solve(&invalid).is_err());
+    }
+}
@@ -0,0 +1,456 @@
+use serde::{Deserialize, Serialize};
+
+/// I2C 速率模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum I2cSpeedMode {
+    Standard, // Sm 100kHz
+    Fast,     // Fm 400kHz
+    FastPlus, // Fm+ 1MHz
+}
+
+// I2C 规范时序（单位 ns）
+struct I2cSpec {
+    rate_hz: f64,
+    hddat_min: f64,
+    vddat_max: f64,
+    sudat_min: f64,
+    l_min: f64,
+    h_min: f64,
+}
+
+impl I2cSpeedMode {
+    fn spec(&self) -> I2cSpec {
+        match self {
+            Self::Standard => I2cSpec {
+                rate_hz: 100_000.0,
+                hddat_min: 0.0,
+                vddat_max: 3450.0,
+                sudat_min: 250.0,
+                l_min: 4700.0,
+                h_min: 4000.0,
+            },
+            Self::Fast => I2cSpec {
+                rate_hz: 400_000.0,
+                hddat_min: 0.0,
+                vddat_max: 900.0,
+                sudat_min: 100.0,
+                l_min: 1300.0,
+                h_min: 600.0,
+            },
+            Self::FastPlus => I2cSpec {
+                rate_hz: 1_000_000.0,
+                hddat_min: 0.0,
+                vddat_max: 450.0,
+                sudat_min: 50.0,
+                l_min: 500.0,
+                h_min: 260.0,
+            },
+        }
+    }
+}
+
+// 模拟滤波器延时范围（ns）
+const I2C_ANALOG_FILTER_DELAY_MIN: f64 = 50.0;
+const I2C_ANALOG_FILTER_DELAY_MAX: f64 = 260.0;
+
+/// TIMINGR 计算参数（STM32F0/F3/F7/G0/G4/H7/L4 等新版 I2C）
+#[derive(Debug, Clone, Deserialize)]
+pub struct I2cTimingConfig {
+    pub clock_hz: u64, // I2C 内核时钟
+    pub mode: I2cSpeedMode,
+    pub speed_hz: Option<u64>,     // 默认取模式的标称速率
+    pub rise_time_ns: Option<f64>, // 默认 0，与 CubeMX 一致
+    pub fall_time_ns: Option<f64>,
+    pub analog_filter: Option<bool>, // 默认开启
+    pub digital_filter: Option<u8>,  // DNF 0 ~ 15，默认 0
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct I2cTimingResult {
+    pub timingr: u32,
+    pub hex: String,
+    pub presc: u8,
+    pub scldel: u8,
+    pub sdadel: u8,
+    pub sclh: u8,
+    pub scll: u8,
+    pub low_ns: f64,
+    pub high_ns: f64,
+    pub actual_speed_hz: f64,
+    pub error_percent: f64,
+}
+
+// SCL 时序搜索的候选结果
+struct SclTiming {
+    error: f64,
+    presc: u32,
+    scldel: u32,
+    sdadel: u32,
+    sclh: u32,
+    scll: u32,
+    low_ns: f64,
+    high_ns: f64,
+}
+
+/// 计算 TIMINGR：先枚举满足数据保持 / 建立时间的 PRESC / SCLDEL / SDADEL，
+/// 再为每个 PRESC 取满足最小高电平时间的最小 SCLH，搜索使速率最接近目标的 SCLL
+/// （速率允许偏差 ±20%）。误差相同时保留 PRESC 较小的一组，与 CubeMX 的结果一致
+pub fn i2c_timing_calculate(config: &I2cTimingConfig) -> Result<I2cTimingResult, String> {
+    if config.clock_hz == 0 {
+        return Err("I2C 时钟必须大于 0".to_string());
+    }
+    let spec = config.mode.spec();
+    let speed_hz = config.speed_hz.map(|s| s as f64).unwrap_or(spec.rate_hz);
+    if speed_hz <= 0.0 || speed_hz > spec.rate_hz {
+        return Err(format!("速率 {} Hz 超出该模式上限 {} Hz", speed_hz, spec.rate_hz));
+    }
+    let dnf = config.digital_filter.unwrap_or(0);
+    if dnf > 15 {
+        return Err(format!("数字滤波器 DNF 只能为 0 ~ 15: {}", dnf));
+    }
+    let rise = config.rise_time_ns.unwrap_or(0.0);
+    let fall = config.fall_time_ns.unwrap_or(0.0);
+    let analog = config.analog_filter.unwrap_or(true);
+
+    let i2cclk = 1e9 / config.clock_hz as f64;
+    let i2cbus = 1e9 / speed_hz;
+    let (af_min, af_max) = if analog {
+        (I2C_ANALOG_FILTER_DELAY_MIN, I2C_ANALOG_FILTER_DELAY_MAX)
+    } else {
+        (0.0, 0.0)
+    };
+    let dnf_delay = dnf as f64 * i2cclk;
+
+    let sdadel_min = (spec.hddat_min + fall - af_min - (dnf as f64 + 3.0) * i2cclk).max(0.0);
+    let sdadel_max = (spec.vddat_max - rise - af_max - (dnf as f64 + 4.0) * i2cclk).max(0.0);
+    let scldel_min = rise + spec.sudat_min;
+
+    // 每个 PRESC 只保留第一组可行的 SCLDEL / SDADEL
+    let mut candidates = Vec::new();
+    for presc in 0..16u32 {
+        'scldel: for scldel in 0..16u32 {
+            if ((scldel + 1) * (presc + 1)) as f64 * i2cclk < scldel_min {
+                continue;
+            }
+            for sdadel in 0..16u32 {
+                let delay = (sdadel * (presc + 1) + 1) as f64 * i2cclk;
+                if delay >= sdadel_min && delay <= sdadel_max {
+                    candidates.push((presc, scldel, sdadel));
+                    break 'scldel;
+                }
+            }
+        }
+    }
+    if candidates.is_empty() {
+        return Err("找不到满足数据建立 / 保持时间的 PRESC / SCLDEL / SDADEL，请检查时钟和上升下降时间".to_string());
+    }
+
+    let clk_min = 1e9 / (speed_hz * 1.2);
+    let clk_max = 1e9 / (speed_hz * 0.8);
+    let tsync = af_min + dnf_delay + 2.0 * i2cclk;
+
+    let mut best: Option<SclTiming> = None;
+    for (presc, scldel, sdadel) in candidates {
+        let prescaler = (presc + 1) as f64 * i2cclk;
+        // 高电平取满足规范的最短时间，其余时间分给低电平
+        let Some(sclh) = (0..256u32).find(|sclh| {
+            let high = (sclh + 1) as f64 * prescaler + tsync;
+            high >= spec.h_min && i2cclk < high
+        }) else {
+            continue;
+        };
+        let high = (sclh + 1) as f64 * prescaler + tsync;
+        for scll in 0..256u32 {
+            let low = (scll + 1) as f64 * prescaler + tsync;
+            if low < spec.l_min || i2cclk >= (low - af_min - dnf_delay) / 4.0 {
+                continue;
+            }
+            let period = low + high + rise + fall;
+            if period < clk_min || period > clk_max {
+                continue;
+            }
+            let error = (period - i2cbus).abs();
+            if best.as_ref().is_none_or(|b| error < b.error - 1e-6) {
+                best = Some(SclTiming {
+                    error,
+                    presc,
+                    scldel,
+                    sdadel,
+                    sclh,
+                    scll,
+                    low_ns: low,
+                    high_ns: high,
+                });
+            }
+        }
+    }
+
+    let best = best.ok_or("找不到满足 SCL 高低电平时间的 SCLH / SCLL，请降低速率或提高 I2C 时钟")?;
+    let timingr = (best.presc << 28) | (best.scldel << 20) | (best.sdadel << 16) | (best.sclh << 8) | best.scll;
+    let actual_speed_hz = 1e9 / (best.low_ns + best.high_ns + rise + fall);
+    Ok(I2cTimingResult {
+        timingr,
+        hex: format!("0x{:08X}", timingr),
+        presc: best.presc as u8,
+        scldel: best.scldel as u8,
+        sdadel: best.sdadel as u8,
+        sclh: best.sclh as u8,
+        scll: best.scll as u8,
+        low_ns: best.low_ns,
+        high_ns: best.high_ns,
+        actual_speed_hz,
+        error_percent: (actual_speed_hz - speed_hz) / speed_hz * 100.0,
+    })
+}
+
+/// 旧版 I2C（STM32F1/F2/F4/L1）快速模式占空比
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum I2cFastDuty {
+    #[default]
+    Duty2,    // Tlow/Thigh = 2
+    Duty16_9, // Tlow/Thigh = 16/9
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct I2cLegacyConfig {
+    pub pclk1_hz: u64,
+    pub mode: I2cSpeedMode, // 仅支持 standard / fast
+    pub speed_hz: Option<u64>,
+    pub fast_duty: Option<I2cFastDuty>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct I2cLegacyResult {
+    pub freq: u32,     // CR2.FREQ（PCLK1 MHz）
+    pub ccr: u32,      // CCR[11:0]
+    pub ccr_reg: u32,  // 含 F/S 与 DUTY 位的完整 CCR 寄存器值
+    pub ccr_hex: String,
+    pub trise: u32,
+    pub actual_speed_hz: f64,
+    pub error_percent: f64,
+}
+
+/// 计算旧版 I2C 的 CCR / TRISE（CCR 向上取整，保证速率不超过目标）
+pub fn i2c_legacy_calculate(config: &I2cLegacyConfig) -> Result<I2cLegacyResult, String> {
+    let pclk = config.pclk1_hz as f64;
+    let freq = (config.pclk1_hz / 1_000_000) as u32;
+    if !(2..=50).contains(&freq) {
+        return Err(format!("PCLK1 {} MHz 超出范围 2 ~ 50 MHz", freq));
+    }
+    let spec = config.mode.spec();
+    let speed_hz = config.speed_hz.map(|s| s as f64).unwrap_or(spec.rate_hz);
+    if speed_hz <= 0.0 || speed_hz > spec.rate_hz {
+        return Err(format!("速率 {} Hz 超出该模式上限 {} Hz", speed_hz, spec.rate_hz));
+    }
+
+    let (ccr, ccr_reg, period_factor, trise) = match config.mode {
+        I2cSpeedMode::Standard => {
+            // Thigh = Tlow = CCR * Tpclk，最大上升时间 1000ns
+            let ccr = ((pclk / (2.0 * speed_hz)).ceil() as u32).max(4);
+            (ccr, ccr, 2.0, freq + 1)
+        }
+        I2cSpeedMode::Fast => {
+            // 最大上升时间 300ns
+            let trise = freq * 300 / 1000 + 1;
+            match config.fast_duty.unwrap_or_default() {
+                I2cFastDuty::Duty2 => {
+                    let ccr = ((pclk / (3.0 * speed_hz)).ceil() as u32).max(1);
+                    (ccr, 0x8000 | ccr, 3.0, trise)
+                }
+                I2cFastDuty::Duty16_9 => {
+                    let ccr = ((pclk / (25.0 * speed_hz)).ceil() as u32).max(1);
+                    (ccr, 0xC000 | ccr, 25.0, trise)
+                }
+            }
+        }
+        I2cSpeedMode::FastPlus => return Err("旧版 I2C 不支持 Fm+ 模式".to_string()),
+    };
+    if ccr > 0xFFF {
+        return Err(format!("CCR {} 超出 12 位范围，请提高速率或降低 PCLK1", ccr));
+    }
+
+    let actual_speed_hz = pclk / (period_factor * ccr as f64);
+    Ok(I2cLegacyResult {
+        freq,
+        ccr,
+        ccr_reg,
+        ccr_hex: format!("0x{:04X}", ccr_reg),
+        trise,
+        actual_speed_hz,
+        error_percent: (actual_speed_hz - speed_hz) / speed_hz * 100.0,
+    })
+}
+
+/// SPI 分频选项
+#[derive(Debug, Clone, Serialize)]
+pub struct SpiPrescalerOption {
+    pub br: u8,       // CR1.BR[2:0]
+    pub divider: u32, // 2 ~ 256
+    pub speed_hz: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpiPrescalerResult {
+    pub selected: Option<SpiPrescalerOption>, // 不超过目标速率的最高档
+    pub options: Vec<SpiPrescalerOption>,
+}
+
+/// 选择 SPI 波特率分频
+pub fn spi_prescaler_calculate(pclk_hz: u64, max_speed_hz: u64) -> Result<SpiPrescalerResult, String> {
+    if pclk_hz == 0 || max_speed_hz == 0 {
+        return Err("时钟和目标速率必须大于 0".to_string());
+    }
+    let options: Vec<SpiPrescalerOption> = (0..8u8)
+        .map(|br| {
+            let divider = 2u32 << br;
+            SpiPrescalerOption {
+                br,
+                divider,
+                speed_hz: pclk_hz as f64 / divider as f64,
+            }
+        })
+        .collect();
+    let selected = options.iter().find(|o| o.speed_hz <= max_speed_hz as f64).cloned();
+    Ok(SpiPrescalerResult { selected, options })
+}
+
+// 计算 I2C TIMINGR
+#[tauri::command]
+pub fn i2c_timingr_calc(config: I2cTimingConfig) -> Result<I2cTimingResult, String> {
+    i2c_timing_calculate(&config)
+}
+
+// 计算旧版 I2C CCR / TRISE
+#[tauri::command]
+pub fn i2c_legacy_calc(config: I2cLegacyConfig) -> Result<I2cLegacyResult, String> {
+    i2c_legacy_calculate(&config)
+}
+
+// 选择 SPI 波特率分频
+#[tauri::command]
+pub fn spi_prescaler_calc(pclk_hz: u64, max_speed_hz: u64) -> Result<SpiPrescalerResult, String> {
+    spi_prescaler_calculate(pclk_hz, max_speed_hz)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing_config(clock_hz: u64, mode: I2cSpeedMode) -> I2cTimingConfig {
+        I2cTimingConfig {
+            clock_hz,
+            mode,
+            speed_hz: None,
+            rise_time_ns: None,
+            fall_time_ns: None,
+            analog_filter: None,
+            digital_filter: None,
+        }
+    }
+
+    // CubeMX 默认参数（模拟滤波开启、DNF = 0、上升 / 下降时间 0）生成的 TIMINGR
+    #[test]
+    fn timingr_matches_cubemx() {
+        for (clock_hz, mode, timingr) in [
+            (32_000_000, I2cSpeedMode::Standard, 0x0070_7CBB),
+            (48_000_000, I2cSpeedMode::Standard, 0x2030_3E5D),
+            (64_000_000, I2cSpeedMode::Standard, 0x1070_7DBC),
+            (80_000_000, I2cSpeedMode::Standard, 0x1090_9CEC),
+            (8_000_000, I2cSpeedMode::Fast, 0x0000_020B),
+            (64_000_000, I2cSpeedMode::Fast, 0x0060_2173),
+            (80_000_000, I2cSpeedMode::Fast, 0x0070_2991),
+        ] {
+            let result = i2c_timing_calculate(&timing_config(clock_hz, mode)).unwrap();
+            assert_eq!(result.hex, format!("0x{:08X}", timingr), "{} Hz {:?}", clock_hz, mode);
+        }
+
+        let result = i2c_timing_calculate(&timing_config(80_000_000, I2cSpeedMode::Standard)).unwrap();
+        assert_eq!(
+            (result.presc, result.scldel, result.sdadel, result.sclh, result.scll),
+            (1, 9, 0, 156, 236)
+        );
+        assert_eq!((result.low_ns, result.high_ns, result.actual_speed_hz), (6000.0, 4000.0, 100_000.0));
+    }
+
+    #[test]
+    fn timingr_respects_spec() {
+        for mode in [I2cSpeedMode::Standard, I2cSpeedMode::Fast, I2cSpeedMode::FastPlus] {
+            let spec = mode.spec();
+            for clock_hz in [48_000_000, 80_000_000, 170_000_000] {
+                let mut config = timing_config(clock_hz, mode);
+                (config.rise_time_ns, config.fall_time_ns, config.digital_filter) = (Some(50.0), Some(10.0), Some(1));
+                let result = i2c_timing_calculate(&config).unwrap();
+                assert!(result.low_ns >= spec.l_min && result.high_ns >= spec.h_min, "{:?}", result);
+                assert!(result.error_percent.abs() <= 20.0, "{:?}", result);
+            }
+        }
+
+        let mut invalid = timing_config(48_000_000, I2cSpeedMode::Fast);
+        invalid.digital_filter = Some(16);
+        assert!(i2c_timing_calculate(&invalid).is_err());
+        invalid.digital_filter = None;
+        invalid.speed_hz = Some(500_000);
+        assert!(i2c_timing_calculate(&invalid).is_err());
+        assert!(i2c_timing_calculate(&timing_config(0, I2cSpeedMode::Fast)).is_err());
+    }
+
+    fn legacy(pclk1_hz: u64, mode: I2cSpeedMode, fast_duty: Option<I2cFastDuty>) -> Result<I2cLegacyResult, String> {
+        i2c_legacy_calculate(&I2cLegacyConfig {
+            pclk1_hz,
+            mode,
+            speed_hz: None,
+            fast_duty,
+        })
+    }
+
+    // RM0008：PCLK1 = 36 MHz 时标准模式 CCR = 180、TRISE = 37
+    #[test]
+    fn legacy_ccr_trise() {
+        let result = legacy(36_000_000, I2cSpeedMode::Standard, None).unwrap();
+        assert_eq!((result.freq, result.ccr, result.ccr_hex.as_str(), result.trise), (36, 180, "0x00B4", 37));
+        assert_eq!(result.actual_speed_hz, 100_000.0);
+
+        // 快速模式 DUTY = 0：Tlow / Thigh = 2，CCR = 36M / (3 * 400k) = 30，TRISE = 36 * 300 / 1000 + 1
+        let result = legacy(36_000_000, I2cSpeedMode::Fast, None).unwrap();
+        assert_eq!((result.ccr, result.ccr_reg, result.trise), (30, 0x801E, 11));
+        assert_eq!(result.actual_speed_hz, 400_000.0);
+
+        // DUTY = 1：CCR = 3.6 向上取整为 4，速率不超过目标
+        let result = legacy(36_000_000, I2cSpeedMode::Fast, Some(I2cFastDuty::Duty16_9)).unwrap();
+        assert_eq!((result.ccr, result.ccr_hex.as_str()), (4, "0xC004"));
+        assert_eq!((result.actual_speed_hz, result.error_percent), (360_000.0, -10.0));
+
+        // STM32F4 PCLK1 = 42 MHz
+        let result = legacy(42_000_000, I2cSpeedMode::Standard, None).unwrap();
+        assert_eq!((result.ccr, result.trise), (210, 43));
+
+        assert!(legacy(1_000_000, I2cSpeedMode::Standard, None).is_err());
+        assert!(legacy(36_000_000, I2cSpeedMode::FastPlus, None).is_err());
+        let slow = I2cLegacyConfig {
+            pclk1_hz: 50_000_000,
+            mode: I2cSpeedMode::Standard,
+            speed_hz: Some(5_000),
+            fast_duty: None,
+        };
+        assert!(i2c_legacy_calculate(&slow).is_err());
+    }
+
+    #[test]
+    fn spi_prescaler() {
+        let result = spi_prescaler_calculate(72_000_000, 18_000_000).unwrap();
+        let selected = result.selected.unwrap();
+        assert_eq!((selected.br, selected.divider, selected.speed_hz), (1, 4, 18e6));
+        assert_eq!(result.options.len(), 8);
+        assert_eq!((result.options[7].br, result.options[7].divider), (7, 256));
+
+        let selected = spi_prescaler_calculate(72_000_000, 20_000_000).unwrap().selected.unwrap();
+        assert_eq!(selected.br, 1);
+        let selected = spi_prescaler_calculate(72_000_000, 40_000_000).unwrap().selected.unwrap();
+        assert_eq!((selected.br, selected.speed_hz), (0, 36e6));
+        // 最大分频仍超过目标速率
+        assert!(spi_prescaler_calculate(72_000_000, 100_000).unwrap().selected.is_none());
+        assert!(spi_prescaler_calculate(0, 1).is_err());
+    }
+}
@@ -0,0 +1,397 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{util_de_u64_hex, util_parse_input_bytes, util_ser_u64_hex};
+
+/// CRC 参数模型（与 CRC RevEng 目录中的参数定义一致）
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct CrcParams {
+    pub width: u8, // 位宽（1 ~ 64）
+    #[serde(serialize_with = "util_ser_u64_hex", deserialize_with = "util_de_u64_hex")]
+    pub poly: u64, // 生成多项式（正序表示，不含最高位）
+    #[serde(serialize_with = "util_ser_u64_hex", deserialize_with = "util_de_u64_hex")]
+    pub init: u64, // 寄存器初始值
+    pub refin: bool,  // 输入字节是否按位反转
+    pub refout: bool, // 输出结果是否按位反转
+    #[serde(serialize_with = "util_ser_u64_hex", deserialize_with = "util_de_u64_hex")]
+    pub xorout: u64, // 结果异或值
+}
+
+/// 目录中的具名预设
+#[derive(Debug, Clone, Serialize)]
+pub struct CrcPreset {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    #[serde(flatten)]
+    pub params: CrcParams,
+    #[serde(serialize_with = "util_ser_u64_hex")]
+    pub check: u64, // 对 ASCII "123456789" 的计算结果
+}
+
+/// 返回给前端的计算结果
+#[derive(Debug, Serialize)]
+pub struct CrcResult {
+    pub name: String,
+    pub params: CrcParams,
+    pub value: String, // 十进制字符串，避免 64 位结果在 JS 中丢失精度
+    pub hex: String,   // 按位宽补零的十六进制（大写）
+}
+
+// 目录校验字符串
+const CRC_CHECK_INPUT: &[u8] = b"123456789";
+
+macro_rules! crc_preset {
+    ($name:expr, [$($alias:expr),*], $width:expr, $poly:expr, $init:expr, $refin:expr, $refout:expr, $xorout:expr, $check:expr) => {
+        CrcPreset {
+            name: $name,
+            aliases: &[$($alias),*],
+            params: CrcParams {
+                width: $width,
+                poly: $poly,
+                init: $init,
+                refin: $refin,
+                refout: $refout,
+                xorout: $xorout,
+            },
+            check: $check,
+        }
+    };
+}
+
+// CRC 预设目录（参数及 check 值取自 CRC RevEng catalogue）
+pub const CRC_PRESETS: &[CrcPreset] = &[
+    // ---------- CRC-8 ----------
+    crc_preset!("CRC-8/AUTOSAR", [], 8, 0x2F, 0xFF, false, false, 0xFF, 0xDF),
+    crc_preset!("CRC-8/BLUETOOTH", [], 8, 0xA7, 0x00, true, true, 0x00, 0x26),
+    crc_preset!("CRC-8/CDMA2000", [], 8, 0x9B, 0xFF, false, false, 0x00, 0xDA),
+    crc_preset!("CRC-8/DARC", [], 8, 0x39, 0x00, true, true, 0x00, 0x15),
+    crc_preset!("CRC-8/DVB-S2", [], 8, 0xD5, 0x00, false, false, 0x00, 0xBC),
+    crc_preset!("CRC-8/GSM-A", [], 8, 0x1D, 0x00, false, false, 0x00, 0x37),
+    crc_preset!("CRC-8/GSM-B", [], 8, 0x49, 0x00, false, false, 0xFF, 0x94),
+    crc_preset!("CRC-8/HITAG", [], 8, 0x1D, 0xFF, false, false, 0x00, 0xB4),
+    crc_preset!("CRC-8/I-432-1", ["CRC-8/ITU"], 8, 0x07, 0x00, false, false, 0x55, 0xA1),
+    crc_preset!("CRC-8/I-CODE", [], 8, 0x1D, 0xFD, false, false, 0x00, 0x7E),
+    crc_preset!("CRC-8/LTE", [], 8, 0x9B, 0x00, false, false, 0x00, 0xEA),
+    crc_preset!("CRC-8/MAXIM-DOW", ["CRC-8/MAXIM", "DOW-CRC"], 8, 0x31, 0x00, true, true, 0x00, 0xA1),
+    crc_preset!("CRC-8/MIFARE-MAD", [], 8, 0x1D, 0xC7, false, false, 0x00, 0x99),
+    crc_preset!("CRC-8/NRSC-5", [], 8, 0x31, 0xFF, false, false, 0x00, 0xF7),
+    crc_preset!("CRC-8/OPENSAFETY", [], 8, 0x2F, 0x00, false, false, 0x00, 0x3E),
+    crc_preset!("CRC-8/ROHC", [], 8, 0x07, 0xFF, true, true, 0x00, 0xD0),
+    crc_preset!("CRC-8/SAE-J1850", [], 8, 0x1D, 0xFF, false, false, 0xFF, 0x4B),
+    crc_preset!("CRC-8/SMBUS", ["CRC-8"], 8, 0x07, 0x00, false, false, 0x00, 0xF4),
+    crc_preset!("CRC-8/TECH-3250", ["CRC-8/AES", "CRC-8/EBU"], 8, 0x1D, 0xFF, true, true, 0x00, 0x97),
+    crc_preset!("CRC-8/WCDMA", [], 8, 0x9B, 0x00, true, true, 0x00, 0x25),
+    // ---------- CRC-16 ----------
+    crc_preset!("CRC-16/ARC", ["ARC", "CRC-16", "CRC-16/LHA", "CRC-IBM"], 16, 0x8005, 0x0000, true, true, 0x0000, 0xBB3D),
+    crc_preset!("CRC-16/CDMA2000", [], 16, 0xC867, 0xFFFF, false, false, 0x0000, 0x4C06),
+    crc_preset!("CRC-16/CMS", [], 16, 0x8005, 0xFFFF, false, false, 0x0000, 0xAEE7),
+    crc_preset!("CRC-16/DDS-110", [], 16, 0x8005, 0x800D, false, false, 0x0000, 0x9ECF),
+    crc_preset!("CRC-16/DECT-R", ["R-CRC-16"], 16, 0x0589, 0x0000, false, false, 0x0001, 0x007E),
+    crc_preset!("CRC-16/DECT-X", ["X-CRC-16"], 16, 0x0589, 0x0000, false, false, 0x0000, 0x007F),
+    crc_preset!("CRC-16/DNP", [], 16, 0x3D65, 0x0000, true, true, 0xFFFF, 0xEA82),
+    crc_preset!("CRC-16/EN-13757", [], 16, 0x3D65, 0x0000, false, false, 0xFFFF, 0xC2B7),
+    crc_preset!("CRC-16/GENIBUS", ["CRC-16/DARC", "CRC-16/EPC", "CRC-16/I-CODE"], 16, 0x1021, 0xFFFF, false, false, 0xFFFF, 0xD64E),
+    crc_preset!("CRC-16/GSM", [], 16, 0x1021, 0x0000, false, false, 0xFFFF, 0xCE3C),
+    crc_preset!("CRC-16/IBM-3740", ["CRC-16/CCITT-FALSE", "CRC-16/AUTOSAR"], 16, 0x1021, 0xFFFF, false, false, 0x0000, 0x29B1),
+    crc_preset!("CRC-16/IBM-SDLC", ["CRC-16/X-25", "CRC-16/ISO-HDLC", "CRC-B"], 16, 0x1021, 0xFFFF, true, true, 0xFFFF, 0x906E),
+    crc_preset!("CRC-16/ISO-IEC-14443-3-A", ["CRC-A"], 16, 0x1021, 0xC6C6, true, true, 0x0000, 0xBF05),
+    crc_preset!("CRC-16/KERMIT", ["CRC-16/CCITT", "CRC-16/CCITT-TRUE", "CRC-CCITT"], 16, 0x1021, 0x0000, true, true, 0x0000, 0x2189),
+    crc_preset!("CRC-16/LJ1200", [], 16, 0x6F63, 0x0000, false, false, 0x0000, 0xBDF4),
+    crc_preset!("CRC-16/M17", [], 16, 0x5935, 0xFFFF, false, false, 0x0000, 0x772B),
+    crc_preset!("CRC-16/MAXIM-DOW", ["CRC-16/MAXIM"], 16, 0x8005, 0x0000, true, true, 0xFFFF, 0x44C2),
+    crc_preset!("CRC-16/MCRF4XX", [], 16, 0x1021, 0xFFFF, true, true, 0x0000, 0x6F91),
+    crc_preset!("CRC-16/MODBUS", ["MODBUS"], 16, 0x8005, 0xFFFF, true, true, 0x0000, 0x4B37),
+    crc_preset!("CRC-16/NRSC-5", [], 16, 0x080B, 0xFFFF, true, true, 0x0000, 0xA066),
+    crc_preset!("CRC-16/OPENSAFETY-A", [], 16, 0x5935, 0x0000, false, false, 0x0000, 0x5D38),
+    crc_preset!("CRC-16/OPENSAFETY-B", [], 16, 0x755B, 0x0000, false, false, 0x0000, 0x20FE),
+    crc_preset!("CRC-16/PROFIBUS", ["CRC-16/IEC-61158-2"], 16, 0x1DCF, 0xFFFF, false, false, 0xFFFF, 0xA819),
+    crc_preset!("CRC-16/RIELLO", [], 16, 0x1021, 0xB2AA, true, true, 0x0000, 0x63D0),
+    crc_preset!("CRC-16/SPI-FUJITSU", ["CRC-16/AUG-CCITT"], 16, 0x1021, 0x1D0F, false, false, 0x0000, 0xE5CC),
+    crc_preset!("CRC-16/T10-DIF", [], 16, 0x8BB7, 0x0000, false, false, 0x0000, 0xD0DB),
+    crc_preset!("CRC-16/TELEDISK", [], 16, 0xA097, 0x0000, false, false, 0x0000, 0x0FB3),
+    crc_preset!("CRC-16/TMS37157", [], 16, 0x1021, 0x89EC, true, true, 0x0000, 0x26B1),
+    crc_preset!("CRC-16/UMTS", ["CRC-16/BUYPASS", "CRC-16/VERIFONE"], 16, 0x8005, 0x0000, false, false, 0x0000, 0xFEE8),
+    crc_preset!("CRC-16/USB", [], 16, 0x8005, 0xFFFF, true, true, 0xFFFF, 0xB4C8),
+    crc_preset!("CRC-16/XMODEM", ["CRC-16/ACORN", "CRC-16/LTE", "CRC-16/V-41-MSB", "ZMODEM"], 16, 0x1021, 0x0000, false, false, 0x0000, 0x31C3),
+    // ---------- CRC-32 ----------
+    crc_preset!("CRC-32/AIXM", ["CRC-32Q"], 32, 0x814141AB, 0x00000000, false, false, 0x00000000, 0x3010BF7F),
+    crc_preset!("CRC-32/AUTOSAR", [], 32, 0xF4ACFB13, 0xFFFFFFFF, true, true, 0xFFFFFFFF, 0x1697D06A),
+    crc_preset!("CRC-32/BASE91-D", ["CRC-32D"], 32, 0xA833982B, 0xFFFFFFFF, true, true, 0xFFFFFFFF, 0x87315576),
+    crc_preset!("CRC-32/BZIP2", ["CRC-32/AAL5", "CRC-32/DECT-B", "B-CRC-32"], 32, 0x04C11DB7, 0xFFFFFFFF, false, false, 0xFFFFFFFF, 0xFC891918),
+    crc_preset!("CRC-32/CD-ROM-EDC", [], 32, 0x8001801B, 0x00000000, true, true, 0x00000000, 0x6EC2EDC4),
+    crc_preset!("CRC-32/CKSUM", ["CKSUM", "CRC-32/POSIX"], 32, 0x04C11DB7, 0x00000000, false, false, 0xFFFFFFFF, 0x765E7680),
+    crc_preset!("CRC-32/ISCSI", ["CRC-32C", "CRC-32/BASE91-C", "CRC-32/CASTAGNOLI", "CRC-32/INTERLAKEN"], 32, 0x1EDC6F41, 0xFFFFFFFF, true, true, 0xFFFFFFFF, 0xE3069283),
+    crc_preset!("CRC-32/ISO-HDLC", ["CRC-32", "CRC-32/ADCCP", "CRC-32/V-42", "CRC-32/XZ", "PKZIP"], 32, 0x04C11DB7, 0xFFFFFFFF, true, true, 0xFFFFFFFF, 0xCBF43926),
+    crc_preset!("CRC-32/JAMCRC", ["JAMCRC"], 32, 0x04C11DB7, 0xFFFFFFFF, true, true, 0x00000000, 0x340BC6D9),
+    crc_preset!("CRC-32/MEF", [], 32, 0x741B8CD7, 0xFFFFFFFF, true, true, 0x00000000, 0xD2C22F51),
+    crc_preset!("CRC-32/MPEG-2", ["CRC-32/STM32"], 32, 0x04C11DB7, 0xFFFFFFFF, false, false, 0x00000000, 0x0376E6E7),
+    crc_preset!("CRC-32/XFER", ["XFER"], 32, 0x000000AF, 0x00000000, false, false, 0x00000000, 0xBD0BE338),
+    // ---------- CRC-64 ----------
+    crc_preset!("CRC-64/ECMA-182", [], 64, 0x42F0E1EBA9EA3693, 0x0000000000000000, false, false, 0x0000000000000000, 0x6C40DF5F0B497347),
+    crc_preset!("CRC-64/GO-ISO", [], 64, 0x000000000000001B, 0xFFFFFFFFFFFFFFFF, true, true, 0xFFFFFFFFFFFFFFFF, 0xB90956C775A41001),
+    crc_preset!("CRC-64/MS", [], 64, 0x259C84CBA6426349, 0xFFFFFFFFFFFFFFFF, true, true, 0x0000000000000000, 0x75D4B74F024ECEEA),
+    crc_preset!("CRC-64/NVME", [], 64, 0xAD93D23594C93659, 0xFFFFFFFFFFFFFFFF, true, true, 0xFFFFFFFFFFFFFFFF, 0xAE8B14860A799888),
+    crc_preset!("CRC-64/REDIS", [], 64, 0xAD93D23594C935A9, 0x0000000000000000, true, true, 0x0000000000000000, 0xE9C6D914C4B8D9CA),
+    crc_preset!("CRC-64/WE", [], 64, 0x42F0E1EBA9EA3693, 0xFFFFFFFFFFFFFFFF, false, false, 0xFFFFFFFFFFFFFFFF, 0x62EC59E3F1A4F00A),
+    crc_preset!("CRC-64/XZ", ["CRC-64/GO-ECMA"], 64, 0x42F0E1EBA9EA3693, 0xFFFFFFFFFFFFFFFF, true, true, 0xFFFFFFFFFFFFFFFF, 0x995DC9BBDF1939FA),
+];
+
+/// 通用 CRC 计算器，支持流式输入（任意位宽 1 ~ 64）
+pub struct CrcEngine {
+    params: CrcParams,
+    mask: u64,
+    table: Option<Box<[u64; 256]>>, // 位宽 >= 8 时使用查表法，否则逐位计算
+    crc: u64,
+}
+
+impl CrcEngine {
+    pub fn new(params: CrcParams) -> Result<Self, String> {
+        if params.width == 0 || params.width > 64 {
+            return Err(format!("CRC 位宽必须在 1 ~ 64 之间: {}", params.width));
+        }
+        let mask = width_mask(params.width);
+        if params.poly & !mask != 0 || params.init & !mask != 0 || params.xorout & !mask != 0 {
+            return Err(format!("CRC 参数超出 {} 位范围", params.width));
+        }
+
+        let table = if params.width >= 8 {
+            Some(build_table(params.poly, params.width, mask))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            params,
+            mask,
+            table,
+            // 寄存器内部统一按正序计算，输入反转在 update 中处理
+            crc: params.init,
+        })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let width = self.params.width as u32;
+        match &self.table {
+            Some(table) => {
+                for &byte in data {
+                    let byte = if self.params.refin { byte.reverse_bits() } else { byte };
+                    let index = ((self.crc >> (width - 8)) as u8 ^ byte) as usize;
+                    self.crc = ((self.crc << 8) & self.mask) ^ table[index];
+                }
+            }
+            None => {
+                for &byte in data {
+                    let byte = if self.params.refin { byte.reverse_bits() } else { byte };
+                    for bit in (0..8).rev() {
+                        let top = ((self.crc >> (width - 1)) & 1) ^ ((byte >> bit) & 1) as u64;
+                        self.crc = (self.crc << 1) & self.mask;
+                        if top != 0 {
+                            self.crc ^= self.params.poly;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn finalize(&self) -> u64 {
+        let crc = if self.params.refout {
+            self.crc.reverse_bits() >> (64 - self.params.width as u32)
+        } else {
+            self.crc
+        };
+        (crc ^ self.params.xorout) & self.mask
+    }
+}
+
+fn width_mask(width: u8) -> u64 {
+    if width == 64 {
+        u64::MAX
+    } else {
+        (1u64 << width) - 1
+    }
+}
+
+// 构建正序查表（仅用于位宽 >= 8）
+fn build_table(poly: u64, width: u8, mask: u64) -> Box<[u64; 256]> {
+    let top_bit = 1u64 << (width - 1);
+    let mut table = Box::new([0u64; 256]);
+    for (i, entry) in table.iter_mut().enumerate() {
+        let mut crc = (i as u64) << (width - 8);
+        for _ in 0..8 {
+            crc = if crc & top_bit != 0 {
+                ((crc << 1) & mask) ^ poly
+            } else {
+                (crc << 1) & mask
+            };
+        }
+        *entry = crc;
+    }
+    table
+}
+
+/// 一次性计算 CRC
+pub fn crc_compute(params: CrcParams, data: &[u8]) -> Result<u64, String> {
+    let mut engine = CrcEngine::new(params)?;
+    engine.update(data);
+    Ok(engine.finalize())
+}
+
+/// 按名称或别名查找预设（忽略大小写，"_" 与 "-" 等价）
+pub fn crc_find_preset(name: &str) -> Option<&'static CrcPreset> {
+    let normalize = |s: &str| s.trim().to_uppercase().replace('_', "-");
+    let target = normalize(name);
+    CRC_PRESETS.iter().find(|preset| {
+        normalize(preset.name) == target || preset.aliases.iter().any(|a| normalize(a) == target)
+    })
+}
+
+// 将结果格式化为按位宽补零的十六进制
+fn format_crc_hex(value: u64, width: u8) -> String {
+    let digits = (width as usize).div_ceil(4);
+    format!("{:0digits$X}", value, digits = digits)
+}
+
+// 列出全部 CRC 预设
+#[tauri::command]
+pub fn crc_list_presets() -> Vec<CrcPreset> {
+    CRC_PRESETS.to_vec()
+}
+
+// 计算 CRC：preset 与 params 二选一，preset 优先
+#[tauri::command]
+pub fn crc_calculate(
+    data: &str,
+    input_type: &str,
+    preset: Option<String>,
+    params: Option<CrcParams>,
+) -> Result<CrcResult, String> {
+    let bytes = util_parse_input_bytes(data, input_type)?;
+
+    let (name, params) = match (preset, params) {
+        (Some(preset), _) => {
+            let found = crc_find_preset(&preset).ok_or(format!("未知的 CRC 预设: {}", preset))?;
+            (found.name.to_string(), found.params)
+        }
+        (None, Some(params)) => ("CUSTOM".to_string(), params),
+        (None, None) => return Err("请指定 CRC 预设或自定义参数".to_string()),
+    };
+
+    let value = crc_compute(params, &bytes)?;
+
+    Ok(CrcResult {
+        name,
+        params,
+        value: value.to_string(),
+        hex: format_crc_hex(value, params.width),
+    })
+}
+
+// 校验预设目录：对 "123456789" 计算并与 check 值比对，返回不一致的预设名
+#[tauri::command]
+pub fn crc_verify_presets() -> Result<Vec<String>, String> {
+    let mut mismatched = Vec::new();
+    for preset in CRC_PRESETS {
+        if crc_compute(preset.params, CRC_CHECK_INPUT)? != preset.check {
+            mismatched.push(preset.name.to_string());
+        }
+    }
+    Ok(mismatched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(width: u8, poly: u64, init: u64, refin: bool, refout: bool, xorout: u64) -> CrcParams {
+        CrcParams {
+            width,
+            poly,
+            init,
+            refin,
+            refout,
+            xorout,
+        }
+    }
+
+    #[test]
+    fn presets_match_catalogue_check() {
+        for preset in CRC_PRESETS {
+            assert_eq!(
+                crc_compute(preset.params, CRC_CHECK_INPUT).unwrap(),
+                preset.check,
+                "{}",
+                preset.name
+            );
+        }
+        assert!(crc_verify_presets().unwrap().is_empty());
+    }
+
+    // 目录中不在预设表里的模型，覆盖 8 位以下与非字节对齐的位宽
+    #[test]
+    fn odd_widths_match_catalogue_check() {
+        let cases = [
+            ("CRC-3/GSM", params(3, 0x3, 0x0, false, false, 0x7), 0x4),
+            ("CRC-3/ROHC", params(3, 0x3, 0x7, true, true, 0x0), 0x6),
+            ("CRC-4/G-704", params(4, 0x3, 0x0, true, true, 0x0), 0x7),
+            ("CRC-4/INTERLAKEN", params(4, 0x3, 0xF, false, false, 0xF), 0xB),
+            ("CRC-5/EPC-C1G2", params(5, 0x09, 0x09, false, false, 0x00), 0x00),
+            ("CRC-5/USB", params(5, 0x05, 0x1F, true, true, 0x1F), 0x19),
+            ("CRC-6/CDMA2000-A", params(6, 0x27, 0x3F, false, false, 0x00), 0x0D),
+            ("CRC-6/G-704", params(6, 0x03, 0x00, true, true, 0x00), 0x06),
+            ("CRC-7/MMC", params(7, 0x09, 0x00, false, false, 0x00), 0x75),
+            ("CRC-7/ROHC", params(7, 0x4F, 0x7F, true, true, 0x00), 0x53),
+            ("CRC-10/ATM", params(10, 0x233, 0x000, false, false, 0x000), 0x199),
+            ("CRC-11/FLEXRAY", params(11, 0x385, 0x01A, false, false, 0x000), 0x5A3),
+            ("CRC-12/UMTS", params(12, 0x80F, 0x000, false, true, 0x000), 0xDAF),
+            ("CRC-15/CAN", params(15, 0x4599, 0x0000, false, false, 0x0000), 0x059E),
+            ("CRC-17/CAN-FD", params(17, 0x1685B, 0x00000, false, false, 0x00000), 0x04F03),
+            ("CRC-21/CAN-FD", params(21, 0x102899, 0x000000, false, false, 0x000000), 0x0ED841),
+            ("CRC-24/BLE", params(24, 0x00065B, 0x555555, true, true, 0x000000), 0xC25A56),
+            ("CRC-24/OPENPGP", params(24, 0x864CFB, 0xB704CE, false, false, 0x000000), 0x21CF02),
+            ("CRC-31/PHILIPS", params(31, 0x04C11DB7, 0x7FFFFFFF, false, false, 0x7FFFFFFF), 0x0CE9E46C),
+            ("CRC-40/GSM", params(40, 0x0004820009, 0x0000000000, false, false, 0xFFFFFFFFFF), 0xD4164FC646),
+        ];
+        for (name, params, check) in cases {
+            assert_eq!(crc_compute(params, CRC_CHECK_INPUT).unwrap(), check, "{}", name);
+        }
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        for preset in CRC_PRESETS {
+            let mut engine = CrcEngine::new(preset.params).unwrap();
+            data.chunks(7).for_each(|c| engine.update(c));
+            assert_eq!(engine.finalize(), crc_compute(preset.params, &data).unwrap(), "{}", preset.name);
+        }
+        let usb = params(5, 0x05, 0x1F, true, true, 0x1F);
+        let mut engine = CrcEngine::new(usb).unwrap();
+        CRC_CHECK_INPUT.chunks(2).for_each(|c| engine.update(c));
+        assert_eq!(engine.finalize(), 0x19);
+    }
+
+    #[test]
+    fn find_preset_by_alias() {
+        assert_eq!(crc_find_preset("crc_32").unwrap().name, "CRC-32/ISO-HDLC");
+        assert_eq!(crc_find_preset(" modbus ").unwrap().name, "CRC-16/MODBUS");
+        assert!(crc_find_preset("CRC-99").is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_params() {
+        assert!(CrcEngine::new(params(0, 0, 0, false, false, 0)).is_err());
+        assert!(CrcEngine::new(params(65, 0, 0, false, false, 0)).is_err());
+        assert!(CrcEngine::new(params(5, 0x25, 0, false, false, 0)).is_err());
+        assert!(CrcEngine::new(params(64, u64::MAX, u64::MAX, true, true, u64::MAX)).is_ok());
+    }
+
+    #[test]
+    fn calculate_formats_hex_by_width() {
+        let result = crc_calculate("123456789", "text", Some("CRC-16/XMODEM".to_string()), None).unwrap();
+        assert_eq!(result.hex, "31C3");
+        let custom = params(5, 0x05, 0x1F, true, true, 0x1F);
+        let result = crc_calculate("31 32 33 34 35 36 37 38 39", "hex", None, Some(custom)).unwrap();
+        assert_eq!((result.name.as_str(), result.hex.as_str(), result.value.as_str()), ("CUSTOM", "19", "25"));
+        assert!(crc_calculate("", "text", None, None).is_err());
+    }
+}
@@ -0,0 +1,807 @@
+use serde::{Deserialize, Serialize};
+
+/// 分频 / 倍频系数取值集合
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum FactorSet {
+    Range {
+        min: u32,
+        max: u32,
+        #[serde(default = "default_step")]
+        step: u32,
+    },
+    List(Vec<f64>), // 可包含 1.5、6.5 等非整数系数
+}
+
+fn default_step() -> u32 {
+    1
+}
+
+impl FactorSet {
+    pub fn values(&self) -> Vec<f64> {
+        match self {
+            FactorSet::Range { min, max, step } => (*min..=*max).step_by((*step).max(1) as usize).map(|v| v as f64).collect(),
+            FactorSet::List(values) => values.clone(),
+        }
+    }
+}
+
+/// 时钟源（HSI / HSE / CSI 等）
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClockSourceDesc {
+    pub name: String,
+    pub min_hz: f64, // 内部振荡器 min_hz == max_hz
+    pub max_hz: f64,
+    pub pll_m: FactorSet, // 进入 PLL 前的分频（F1 的 HSI/2 记为固定 2）
+}
+
+/// APB 总线
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApbBusDesc {
+    pub name: String,
+    pub max_hz: f64,
+    pub has_timers: bool, // 分频不为 1 时定时器时钟为 PCLK 的 2 倍
+}
+
+/// C 初始化代码风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockSnippetStyle {
+    Stm32F1Hal,
+    Stm32F4Hal,
+    Stm32G0Hal,
+    Stm32H7Hal,
+    Gd32F30x,
+    Ch32V30x,
+}
+
+/// 芯片系列时钟树描述
+/// PLL 模型：pll_in = 源 / M，vco = pll_in * N，SYSCLK = vco / P，
+/// USB = vco / Q（pll_q）或 SYSCLK 路径上的 PLL 输出 / usb_div（F1 类芯片）
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClockFamily {
+    pub name: String,
+    pub description: String,
+    pub sources: Vec<ClockSourceDesc>,
+    pub pll_in_min_hz: f64,
+    pub pll_in_max_hz: f64,
+    pub pll_n: FactorSet,
+    pub vco_min_hz: f64, // 无独立 VCO 的芯片填 PLL 输出范围
+    pub vco_max_hz: f64,
+    pub pll_p: FactorSet,
+    pub pll_p_name: String, // 输出到 SYSCLK 的分频器名称（P 或 R）
+    pub pll_q: Option<FactorSet>,
+    pub pll_q_max_hz: Option<f64>, // Q 输出允许的最高频率，不填表示不限制
+    pub usb_div: Option<FactorSet>,
+    pub sysclk_max_hz: f64,
+    pub hclk_max_hz: f64,
+    pub ahb_div: FactorSet,
+    pub apb_div: FactorSet,
+    pub apb_buses: Vec<ApbBusDesc>,
+    pub flash_latency: Vec<f64>, // 第 i 项为 i 个等待周期允许的最高 HCLK，空表示无需配置
+    pub snippet: Option<ClockSnippetStyle>,
+}
+
+fn range(min: u32, max: u32) -> FactorSet {
+    FactorSet::Range { min, max, step: 1 }
+}
+
+fn list(values: &[f64]) -> FactorSet {
+    FactorSet::List(values.to_vec())
+}
+
+fn source(name: &str, min_mhz: f64, max_mhz: f64, pll_m: FactorSet) -> ClockSourceDesc {
+    ClockSourceDesc {
+        name: name.to_string(),
+        min_hz: min_mhz * 1e6,
+        max_hz: max_mhz * 1e6,
+        pll_m,
+    }
+}
+
+fn apb(name: &str, max_mhz: f64, has_timers: bool) -> ApbBusDesc {
+    ApbBusDesc {
+        name: name.to_string(),
+        max_hz: max_mhz * 1e6,
+        has_timers,
+    }
+}
+
+fn mhz(values: &[f64]) -> Vec<f64> {
+    values.iter().map(|v| v * 1e6).collect()
+}
+
+/// 内置芯片系列
+pub fn clock_families() -> Vec<ClockFamily> {
+    let ahb_div = list(&[1.0, 2.0, 4.0, 8.0, 16.0, 64.0, 128.0, 256.0, 512.0]);
+    let apb_div = list(&[1.0, 2.0, 4.0, 8.0, 16.0]);
+
+    vec![
+        ClockFamily {
+            name: "STM32F1".to_string(),
+            description: "STM32F101/102/103（非互联型）".to_string(),
+            sources: vec![
+                source("HSI", 8.0, 8.0, list(&[2.0])),
+                source("HSE", 4.0, 16.0, list(&[1.0, 2.0])),
+            ],
+            pll_in_min_hz: 1e6,
+            pll_in_max_hz: 25e6,
+            pll_n: range(2, 16),
+            vco_min_hz: 16e6,
+            vco_max_hz: 72e6,
+            pll_p: list(&[1.0]),
+            pll_p_name: "P".to_string(),
+            pll_q: None,
+            pll_q_max_hz: None,
+            usb_div: Some(list(&[1.0, 1.5])),
+            sysclk_max_hz: 72e6,
+            hclk_max_hz: 72e6,
+            ahb_div: ahb_div.clone(),
+            apb_div: apb_div.clone(),
+            apb_buses: vec![apb("APB1", 36.0, true), apb("APB2", 72.0, true)],
+            flash_latency: mhz(&[24.0, 48.0, 72.0]),
+            snippet: Some(ClockSnippetStyle::Stm32F1Hal),
+        },
+        ClockFamily {
+            name: "STM32F4".to_string(),
+            description: "STM32F405/407/415/417".to_string(),
+            sources: vec![source("HSI", 16.0, 16.0, range(2, 63)), source("HSE", 4.0, 26.0, range(2, 63))],
+            pll_in_min_hz: 0.95e6,
+            pll_in_max_hz: 2.1e6,
+            pll_n: range(50, 432),
+            vco_min_hz: 100e6,
+            vco_max_hz: 432e6,
+            pll_p: list(&[2.0, 4.0, 6.0, 8.0]),
+            pll_p_name: "P".to_string(),
+            pll_q: Some(range(2, 15)),
+            pll_q_max_hz: Some(48e6),
+            usb_div: None,
+            sysclk_max_hz: 168e6,
+            hclk_max_hz: 168e6,
+            ahb_div: ahb_div.clone(),
+            apb_div: apb_div.clone(),
+            apb_buses: vec![apb("APB1", 42.0, true), apb("APB2", 84.0, true)],
+            flash_latency: mhz(&[30.0, 60.0, 90.0, 120.0, 150.0, 180.0]),
+            snippet: Some(ClockSnippetStyle::Stm32F4Hal),
+        },
+        ClockFamily {
+            name: "STM32G0".to_string(),
+            description: "STM32G07x/G0Bx（Range 1）".to_string(),
+            sources: vec![source("HSI", 16.0, 16.0, range(1, 8)), source("HSE", 4.0, 48.0, range(1, 8))],
+            pll_in_min_hz: 2.66e6,
+            pll_in_max_hz: 16e6,
+            pll_n: range(8, 86),
+            vco_min_hz: 64e6,
+            vco_max_hz: 344e6,
+            pll_p: range(2, 8),
+            pll_p_name: "R".to_string(),
+            pll_q: Some(range(2, 8)),
+            pll_q_max_hz: Some(128e6),
+            usb_div: None,
+            sysclk_max_hz: 64e6,
+            hclk_max_hz: 64e6,
+            ahb_div: ahb_div.clone(),
+            apb_div: apb_div.clone(),
+            apb_buses: vec![apb("APB1", 64.0, true)],
+            flash_latency: mhz(&[24.0, 48.0, 64.0]),
+            snippet: Some(ClockSnippetStyle::Stm32G0Hal),
+        },
+        ClockFamily {
+            name: "STM32H7".to_string(),
+            description: "STM32H743/753 rev V（VOS0，PLL1 宽 VCO 范围）".to_string(),
+            sources: vec![
+                source("HSI", 64.0, 64.0, range(1, 63)),
+                source("CSI", 4.0, 4.0, range(1, 63)),
+                source("HSE", 4.0, 48.0, range(1, 63)),
+            ],
+            pll_in_min_hz: 2e6,
+            pll_in_max_hz: 16e6,
+            pll_n: range(4, 512),
+            vco_min_hz: 192e6,
+            vco_max_hz: 960e6,
+            pll_p: FactorSet::Range { min: 2, max: 128, step: 2 },
+            pll_p_name: "P".to_string(),
+            pll_q: Some(range(1, 128)),
+            pll_q_max_hz: Some(400e6),
+            usb_div: None,
+            sysclk_max_hz: 480e6,
+            hclk_max_hz: 240e6,
+            ahb_div: ahb_div.clone(),
+            apb_div: apb_div.clone(),
+            apb_buses: vec![
+                apb("APB1", 120.0, true),
+                apb("APB2", 120.0, true),
+                apb("APB3", 120.0, false),
+                apb("APB4", 120.0, false),
+            ],
+            flash_latency: mhz(&[70.0, 140.0, 210.0, 225.0, 240.0]),
+            snippet: Some(ClockSnippetStyle::Stm32H7Hal),
+        },
+        ClockFamily {
+            name: "GD32F30x".to_string(),
+            description: "GD32F303/305/307（非互联型）".to_string(),
+            sources: vec![
+                source("HSI", 8.0, 8.0, list(&[2.0])),
+                source("HSE", 4.0, 32.0, list(&[1.0, 2.0])),
+            ],
+            pll_in_min_hz: 1e6,
+            pll_in_max_hz: 25e6,
+            pll_n: range(2, 63),
+            vco_min_hz: 16e6,
+            vco_max_hz: 120e6,
+            pll_p: list(&[1.0]),
+            pll_p_name: "P".to_string(),
+            pll_q: None,
+            pll_q_max_hz: None,
+            usb_div: Some(list(&[1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0])),
+            sysclk_max_hz: 120e6,
+            hclk_max_hz: 120e6,
+            ahb_div: ahb_div.clone(),
+            apb_div: apb_div.clone(),
+            apb_buses: vec![apb("APB1", 60.0, true), apb("APB2", 120.0, true)],
+            flash_latency: vec![],
+            snippet: Some(ClockSnippetStyle::Gd32F30x),
+        },
+        ClockFamily {
+            name: "CH32V30x".to_string(),
+            description: "CH32V303/305/307".to_string(),
+            sources: vec![source("HSI", 8.0, 8.0, list(&[2.0])), source("HSE", 3.0, 25.0, range(1, 16))],
+            pll_in_min_hz: 1e6,
+            pll_in_max_hz: 25e6,
+            pll_n: list(&[3.0, 4.0, 5.0, 6.0, 6.5, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 18.0]),
+            vco_min_hz: 18e6,
+            vco_max_hz: 144e6,
+            pll_p: list(&[1.0]),
+            pll_p_name: "P".to_string(),
+            pll_q: None,
+            pll_q_max_hz: None,
+            usb_div: Some(list(&[1.0, 2.0, 3.0])),
+            sysclk_max_hz: 144e6,
+            hclk_max_hz: 144e6,
+            ahb_div,
+            apb_div,
+            apb_buses: vec![apb("APB1", 144.0, true), apb("APB2", 144.0, true)],
+            flash_latency: vec![],
+            snippet: Some(ClockSnippetStyle::Ch32V30x),
+        },
+    ]
+}
+
+/// 求解参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClockSolveConfig {
+    pub family: Option<String>,               // 内置系列名
+    pub custom_family: Option<ClockFamily>,   // 自定义系列描述（优先）
+    pub source: String,                       // HSI / HSE / CSI
+    pub source_hz: Option<f64>,               // 外部晶振频率，内部振荡器可不填
+    pub target_sysclk_hz: f64,
+    pub sysclk_tolerance_hz: Option<f64>,     // 默认 0（精确匹配）
+    pub usb_48mhz: Option<bool>,              // 是否要求 USB 48MHz（允许 ±0.25%）
+    pub limit: Option<usize>,                 // 默认 10
+}
+
+/// APB 总线时钟
+#[derive(Debug, Clone, Serialize)]
+pub struct ApbClock {
+    pub name: String,
+    pub div: f64,
+    pub pclk_hz: f64,
+    pub timer_hz: Option<f64>,
+}
+
+/// 一组时钟配置
+#[derive(Debug, Clone, Serialize)]
+pub struct ClockSolution {
+    pub source: String,
+    pub source_hz: f64,
+    pub pll_m: f64,
+    pub pll_n: f64,
+    pub pll_p: f64,
+    pub pll_q: Option<f64>,
+    pub usb_div: Option<f64>,
+    pub pll_in_hz: f64,
+    pub vco_hz: f64,
+    pub sysclk_hz: f64,
+    pub sysclk_error_hz: f64,
+    pub usb_hz: Option<f64>,
+    pub ahb_div: f64,
+    pub hclk_hz: f64,
+    pub apb: Vec<ApbClock>,
+    pub flash_latency: Option<usize>,
+    pub c_snippet: Option<String>,
+}
+
+const USB_HZ: f64 = 48e6;
+const USB_TOLERANCE: f64 = 0.0025;
+
+// 在集合中选取使 input / div 最接近 target 且满足容差的分频
+fn pick_usb_div(set: &FactorSet, input_hz: f64) -> Option<(f64, f64)> {
+    set.values()
+        .into_iter()
+        .map(|div| (div, input_hz / div))
+        .filter(|(_, hz)| ((hz - USB_HZ) / USB_HZ).abs() <= USB_TOLERANCE)
+        .min_by(|a, b| (a.1 - USB_HZ).abs().total_cmp(&(b.1 - USB_HZ).abs()))
+}
+
+// 选取满足上限的最小分频
+fn pick_bus_div(set: &FactorSet, input_hz: f64, max_hz: f64) -> Option<f64> {
+    let mut values = set.values();
+    values.sort_by(|a, b| a.total_cmp(b));
+    values.into_iter().find(|div| input_hz / div <= max_hz + 0.5)
+}
+
+/// 搜索满足目标 SYSCLK（及 USB 48MHz）的 PLL 配置，按误差和 PLL 输入频率排序
+pub fn clock_solve(family: &ClockFamily, config: &ClockSolveConfig) -> Result<Vec<ClockSolution>, String> {
+    let source = family
+        .sources
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case(&config.source))
+        .ok_or(format!("{} 不支持时钟源 {}", family.name, config.source))?;
+    let source_hz = match config.source_hz {
+        Some(hz) => hz,
+        None if source.min_hz == source.max_hz => source.min_hz,
+        None => return Err(format!("请填写 {} 频率", source.name)),
+    };
+    if source_hz < source.min_hz || source_hz > source.max_hz {
+        return Err(format!(
+            "{} 频率 {} Hz 超出范围 {} ~ {} Hz",
+            source.name, source_hz, source.min_hz, source.max_hz
+        ));
+    }
+    if config.target_sysclk_hz <= 0.0 || config.target_sysclk_hz > family.sysclk_max_hz {
+        return Err(format!("目标 SYSCLK 超出范围: 最高 {} Hz", family.sysclk_max_hz));
+    }
+    let need_usb = config.usb_48mhz.unwrap_or(false);
+    if need_usb && family.pll_q.is_none() && family.usb_div.is_none() {
+        return Err(format!("{} 没有可由 PLL 产生的 USB 时钟", family.name));
+    }
+    let tolerance = config.sysclk_tolerance_hz.unwrap_or(0.0).abs() + 0.5;
+
+    let n_values = family.pll_n.values();
+    let p_values = family.pll_p.values();
+    let mut solutions = Vec::new();
+
+    for m in source.pll_m.values() {
+        let pll_in_hz = source_hz / m;
+        if pll_in_hz < family.pll_in_min_hz || pll_in_hz > family.pll_in_max_hz {
+            continue;
+        }
+        for n in &n_values {
+            let vco_hz = pll_in_hz * n;
+            if vco_hz < family.vco_min_hz || vco_hz > family.vco_max_hz {
+                continue;
+            }
+            for p in &p_values {
+                let sysclk_hz = vco_hz / p;
+                let sysclk_error_hz = sysclk_hz - config.target_sysclk_hz;
+                if sysclk_error_hz.abs() > tolerance || sysclk_hz > family.sysclk_max_hz {
+                    continue;
+                }
+
+                // USB 时钟：优先使用 Q 输出，F1 类芯片由 PLL 输出再分频
+                let (mut pll_q, mut usb_div, mut usb_hz) = (None, None, None);
+                if let Some(q_set) = &family.pll_q {
+                    if let Some((q, hz)) = pick_usb_div(q_set, vco_hz) {
+                        (pll_q, usb_hz) = (Some(q), Some(hz));
+                    } else if !need_usb {
+                        // 不需要 USB 时取使 Q 输出不超过上限的最小分频，没有则该组合不可用
+                        let q_max_hz = family.pll_q_max_hz.unwrap_or(f64::INFINITY);
+                        pll_q = q_set.values().into_iter().filter(|q| vco_hz / q <= q_max_hz + 0.5).reduce(f64::min);
+                        if pll_q.is_none() {
+                            continue;
+                        }
+                    }
+                } else if let Some(div_set) = &family.usb_div {
+                    if let Some((div, hz)) = pick_usb_div(div_set, sysclk_hz) {
+                        (usb_div, usb_hz) = (Some(div), Some(hz));
+                    }
+                }
+                if need_usb && usb_hz.is_none() {
+                    continue;
+                }
+
+                let Some(ahb_div) = pick_bus_div(&family.ahb_div, sysclk_hz, family.hclk_max_hz) else {
+                    continue;
+                };
+                let hclk_hz = sysclk_hz / ahb_div;
+                // 任一 APB 总线无法分频到上限以内时该组合不可用
+                let apb: Option<Vec<ApbClock>> = family
+                    .apb_buses
+                    .iter()
+                    .map(|bus| {
+                        let div = pick_bus_div(&family.apb_div, hclk_hz, bus.max_hz)?;
+                        let pclk_hz = hclk_hz / div;
+                        Some(ApbClock {
+                            name: bus.name.clone(),
+                            div,
+                            pclk_hz,
+                            timer_hz: bus.has_timers.then_some(if div == 1.0 { pclk_hz } else { pclk_hz * 2.0 }),
+                        })
+                    })
+                    .collect();
+                let Some(apb) = apb else {
+                    continue;
+                };
+                let flash_latency = if family.flash_latency.is_empty() {
+                    None
+                } else {
+                    // HCLK 超出等待周期表时无法配置 Flash，跳过
+                    let Some(latency) = family.flash_latency.iter().position(|max| hclk_hz <= max + 0.5) else {
+                        continue;
+                    };
+                    Some(latency)
+                };
+
+                solutions.push(ClockSolution {
+                    source: source.name.clone(),
+                    source_hz,
+                    pll_m: m,
+                    pll_n: *n,
+                    pll_p: *p,
+                    pll_q,
+                    usb_div,
+                    pll_in_hz,
+                    vco_hz,
+                    sysclk_hz,
+                    sysclk_error_hz,
+                    usb_hz,
+                    ahb_div,
+                    hclk_hz,
+                    apb,
+                    flash_latency,
+                    c_snippet: None,
+                });
+            }
+        }
+    }
+
+    // 误差优先；其次 PLL 输入频率越高抖动越小；再次 VCO 越低功耗越小
+    solutions.sort_by(|a, b| {
+        a.sysclk_error_hz
+            .abs()
+            .total_cmp(&b.sysclk_error_hz.abs())
+            .then(b.pll_in_hz.total_cmp(&a.pll_in_hz))
+            .then(a.vco_hz.total_cmp(&b.vco_hz))
+    });
+    solutions.truncate(config.limit.unwrap_or(10));
+
+    if let Some(style) = family.snippet {
+        for solution in solutions.iter_mut() {
+            solution.c_snippet = Some(clock_c_snippet(style, solution)?);
+        }
+    }
+    Ok(solutions)
+}
+
+// ==================== C 初始化代码 ====================
+
+// 系数转宏名片段：9 -> "9"，1.5 -> "1_5"
+fn factor_name(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as u64)
+    } else {
+        format!("{}", value).replace('.', "_")
+    }
+}
+
+fn apb_div(solution: &ClockSolution, name: &str) -> f64 {
+    solution.apb.iter().find(|a| a.name == name).map(|a| a.div).unwrap_or(1.0)
+}
+
+// HAL 的 RCC_OscInitStruct 振荡器部分
+fn hal_osc_lines(solution: &ClockSolution, out: &mut Vec<String>) {
+    let name = solution.source.as_str();
+    out.push(format!("RCC_OscInitStruct.OscillatorType = RCC_OSCILLATORTYPE_{};", name));
+    match name {
+        "HSE" => out.push("RCC_OscInitStruct.HSEState = RCC_HSE_ON;".to_string()),
+        _ => {
+            out.push(format!("RCC_OscInitStruct.{}State = RCC_{}_ON;", name, name));
+            if name == "HSI" {
+                out.push("RCC_OscInitStruct.HSICalibrationValue = RCC_HSICALIBRATION_DEFAULT;".to_string());
+            }
+        }
+    }
+    out.push("RCC_OscInitStruct.PLL.PLLState = RCC_PLL_ON;".to_string());
+}
+
+fn hal_snippet(style: ClockSnippetStyle, solution: &ClockSolution) -> Result<String, String> {
+    // HAL_RCC_ClockConfig 必须给出等待周期，不能默认 0
+    let latency = solution
+        .flash_latency
+        .ok_or("未配置 Flash 等待周期表，无法生成 HAL 初始化代码".to_string())?;
+    let mut lines = vec![
+        "RCC_OscInitTypeDef RCC_OscInitStruct = {0};".to_string(),
+        "RCC_ClkInitTypeDef RCC_ClkInitStruct = {0};".to_string(),
+        String::new(),
+    ];
+    match style {
+        ClockSnippetStyle::Stm32F4Hal => {
+            lines.push("__HAL_RCC_PWR_CLK_ENABLE();".to_string());
+            lines.push("__HAL_PWR_VOLTAGESCALING_CONFIG(PWR_REGULATOR_VOLTAGE_SCALE1);".to_string());
+        }
+        ClockSnippetStyle::Stm32G0Hal => {
+            lines.push("HAL_PWREx_ControlVoltageScaling(PWR_REGULATOR_VOLTAGE_SCALE1);".to_string());
+        }
+        ClockSnippetStyle::Stm32H7Hal => {
+            lines.push("HAL_PWREx_ConfigSupply(PWR_LDO_SUPPLY);".to_string());
+            lines.push("__HAL_PWR_VOLTAGESCALING_CONFIG(PWR_REGULATOR_VOLTAGE_SCALE0);".to_string());
+            lines.push("while (!__HAL_PWR_GET_FLAG(PWR_FLAG_VOSRDY)) {}".to_string());
+        }
+        _ => {}
+    }
+    if lines.len() > 3 {
+        lines.push(String::new());
+    }
+
+    hal_osc_lines(solution, &mut lines);
+    let pll_source = match (style, solution.source.as_str()) {
+        (ClockSnippetStyle::Stm32F1Hal, "HSI") => "RCC_PLLSOURCE_HSI_DIV2".to_string(),
+        (_, name) => format!("RCC_PLLSOURCE_{}", name),
+    };
+    lines.push(format!("RCC_OscInitStruct.PLL.PLLSource = {};", pll_source));
+
+    let (m, n, p) = (solution.pll_m as u32, solution.pll_n as u32, solution.pll_p as u32);
+    let q = solution.pll_q.unwrap_or(2.0) as u32;
+    match style {
+        ClockSnippetStyle::Stm32F1Hal => {
+            if solution.source == "HSE" {
+                lines.push(format!("RCC_OscInitStruct.HSEPredivValue = RCC_HSE_PREDIV_DIV{};", m));
+            }
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLMUL = RCC_PLL_MUL{};", n));
+        }
+        ClockSnippetStyle::Stm32F4Hal => {
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLM = {};", m));
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLN = {};", n));
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLP = RCC_PLLP_DIV{};", p));
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLQ = {};", q));
+        }
+        ClockSnippetStyle::Stm32G0Hal => {
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLM = RCC_PLLM_DIV{};", m));
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLN = {};", n));
+            lines.push("RCC_OscInitStruct.PLL.PLLP = RCC_PLLP_DIV2;".to_string());
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLQ = RCC_PLLQ_DIV{};", q));
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLR = RCC_PLLR_DIV{};", p));
+        }
+        ClockSnippetStyle::Stm32H7Hal => {
+            let range = match solution.pll_in_hz {
+                hz if hz < 2e6 => 0,
+                hz if hz < 4e6 => 1,
+                hz if hz < 8e6 => 2,
+                _ => 3,
+            };
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLM = {};", m));
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLN = {};", n));
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLP = {};", p));
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLQ = {};", q));
+            lines.push("RCC_OscInitStruct.PLL.PLLR = 2;".to_string());
+            lines.push(format!("RCC_OscInitStruct.PLL.PLLRGE = RCC_PLL1VCIRANGE_{};", range));
+            lines.push("RCC_OscInitStruct.PLL.PLLVCOSEL = RCC_PLL1VCOWIDE;".to_string());
+            lines.push("RCC_OscInitStruct.PLL.PLLFRACN = 0;".to_string());
+        }
+        _ => {}
+    }
+    lines.push("if (HAL_RCC_OscConfig(&RCC_OscInitStruct) != HAL_OK)".to_string());
+    lines.push("{\n    Error_Handler();\n}".to_string());
+    lines.push(String::new());
+
+    let ahb = solution.ahb_div as u32;
+    match style {
+        ClockSnippetStyle::Stm32H7Hal => {
+            lines.push("RCC_ClkInitStruct.ClockType = RCC_CLOCKTYPE_HCLK | RCC_CLOCKTYPE_SYSCLK | RCC_CLOCKTYPE_PCLK1 | RCC_CLOCKTYPE_PCLK2 | RCC_CLOCKTYPE_D3PCLK1 | RCC_CLOCKTYPE_D1PCLK1;".to_string());
+            lines.push("RCC_ClkInitStruct.SYSCLKSource = RCC_SYSCLKSOURCE_PLLCLK;".to_string());
+            lines.push("RCC_ClkInitStruct.SYSCLKDivider = RCC_SYSCLK_DIV1;".to_string());
+            lines.push(format!("RCC_ClkInitStruct.AHBCLKDivider = RCC_HCLK_DIV{};", ahb));
+            for (field, bus) in [("APB3", "APB3"), ("APB1", "APB1"), ("APB2", "APB2"), ("APB4", "APB4")] {
+                lines.push(format!(
+                    "RCC_ClkInitStruct.{}CLKDivider = RCC_{}_DIV{};",
+                    field,
+                    bus,
+                    apb_div(solution, bus) as u32
+                ));
+            }
+        }
+        _ => {
+            let clock_type = if solution.apb.len() > 1 {
+                "RCC_CLOCKTYPE_HCLK | RCC_CLOCKTYPE_SYSCLK | RCC_CLOCKTYPE_PCLK1 | RCC_CLOCKTYPE_PCLK2"
+            } else {
+                "RCC_CLOCKTYPE_HCLK | RCC_CLOCKTYPE_SYSCLK | RCC_CLOCKTYPE_PCLK1"
+            };
+            lines.push(format!("RCC_ClkInitStruct.ClockType = {};", clock_type));
+            lines.push("RCC_ClkInitStruct.SYSCLKSource = RCC_SYSCLKSOURCE_PLLCLK;".to_string());
+            lines.push(format!("RCC_ClkInitStruct.AHBCLKDivider = RCC_SYSCLK_DIV{};", ahb));
+            for bus in &solution.apb {
+                lines.push(format!(
+                    "RCC_ClkInitStruct.{}CLKDivider = RCC_HCLK_DIV{};",
+                    bus.name, bus.div as u32
+                ));
+            }
+        }
+    }
+    lines.push(format!(
+        "if (HAL_RCC_ClockConfig(&RCC_ClkInitStruct, FLASH_LATENCY_{}) != HAL_OK)",
+        latency
+    ));
+    lines.push("{\n    Error_Handler();\n}".to_string());
+
+    if let (ClockSnippetStyle::Stm32F1Hal, Some(div)) = (style, solution.usb_div) {
+        let usb = if div == 1.0 {
+            "RCC_USBCLKSOURCE_PLL".to_string()
+        } else {
+            format!("RCC_USBCLKSOURCE_PLL_DIV{}", factor_name(div))
+        };
+        lines.push(String::new());
+        lines.push("RCC_PeriphCLKInitTypeDef PeriphClkInit = {0};".to_string());
+        lines.push("PeriphClkInit.PeriphClockSelection = RCC_PERIPHCLK_USB;".to_string());
+        lines.push(format!("PeriphClkInit.UsbClockSelection = {};", usb));
+        lines.push("if (HAL_RCCEx_PeriphCLKConfig(&PeriphClkInit) != HAL_OK)".to_string());
+        lines.push("{\n    Error_Handler();\n}".to_string());
+    }
+    Ok(lines.join("\n"))
+}
+
+fn gd32_snippet(solution: &ClockSolution) -> String {
+    let hse = solution.source == "HSE";
+    let mut lines = Vec::new();
+    if hse {
+        lines.push("rcu_osci_on(RCU_HXTAL);".to_string());
+        lines.push("while (ERROR == rcu_osci_stab_wait(RCU_HXTAL)) {}".to_string());
+    }
+    if solution.sysclk_hz > 108e6 {
+        // 120MHz 需要开启高驱动模式
+        lines.push("rcu_periph_clock_enable(RCU_PMU);".to_string());
+        lines.push("pmu_highdriver_mode_enable();".to_string());
+        lines.push("while (0U == pmu_flag_get(PMU_FLAG_HDRF)) {}".to_string());
+        lines.push("pmu_highdriver_switch_select(PMU_HIGHDR_SWITCH_EN);".to_string());
+        lines.push("while (0U == pmu_flag_get(PMU_FLAG_HDSRF)) {}".to_string());
+    }
+    lines.push(format!("rcu_ahb_clock_config(RCU_AHB_CKSYS_DIV{});", solution.ahb_div as u32));
+    lines.push(format!("rcu_apb2_clock_config(RCU_APB2_CKAHB_DIV{});", apb_div(solution, "APB2") as u32));
+    lines.push(format!("rcu_apb1_clock_config(RCU_APB1_CKAHB_DIV{});", apb_div(solution, "APB1") as u32));
+    if hse {
+        lines.push(format!("rcu_predv0_config(RCU_PREDV0_DIV{});", solution.pll_m as u32));
+    }
+    lines.push(format!(
+        "rcu_pll_config({}, RCU_PLL_MUL{});",
+        if hse { "RCU_PLLSRC_HXTAL" } else { "RCU_PLLSRC_IRC8M_DIV2" },
+        solution.pll_n as u32
+    ));
+    lines.push("rcu_osci_on(RCU_PLL_CK);".to_string());
+    lines.push("while (0U == rcu_flag_get(RCU_FLAG_PLLSTB)) {}".to_string());
+    lines.push("rcu_system_clock_source_config(RCU_CKSYSSRC_PLL);".to_string());
+    lines.push("while (RCU_SCSS_PLL != rcu_system_clock_source_get()) {}".to_string());
+    if let Some(div) = solution.usb_div {
+        lines.push(format!("rcu_usb_clock_config(RCU_CKUSB_CKPLL_DIV{});", factor_name(div)));
+    }
+    lines.join("\n")
+}
+
+fn ch32_snippet(solution: &ClockSolution) -> String {
+    let hse = solution.source == "HSE";
+    let mut lines = Vec::new();
+    if hse {
+        lines.push("RCC_HSEConfig(RCC_HSE_ON);".to_string());
+        lines.push("if (RCC_WaitForHSEStartUp() != SUCCESS)".to_string());
+        lines.push("{\n    return;\n}".to_string());
+    }
+    lines.push(format!("RCC_HCLKConfig(RCC_SYSCLK_Div{});", solution.ahb_div as u32));
+    lines.push(format!("RCC_PCLK2Config(RCC_HCLK_Div{});", apb_div(solution, "APB2") as u32));
+    lines.push(format!("RCC_PCLK1Config(RCC_HCLK_Div{});", apb_div(solution, "APB1") as u32));
+    if hse {
+        lines.push(format!(
+            "RCC_PREDIV1Config(RCC_PREDIV1_Source_HSE, RCC_PREDIV1_Div{});",
+            solution.pll_m as u32
+        ));
+    }
+    lines.push(format!(
+        "RCC_PLLConfig({}, RCC_PLLMul_{});",
+        if hse { "RCC_PLLSource_PREDIV1" } else { "RCC_PLLSource_HSI_Div2" },
+        factor_name(solution.pll_n)
+    ));
+    lines.push("RCC_PLLCmd(ENABLE);".to_string());
+    lines.push("while (RCC_GetFlagStatus(RCC_FLAG_PLLRDY) == RESET) {}".to_string());
+    lines.push("RCC_SYSCLKConfig(RCC_SYSCLKSource_PLLCLK);".to_string());
+    lines.push("while (RCC_GetSYSCLKSource() != 0x08) {}".to_string());
+    if let Some(div) = solution.usb_div {
+        lines.push(format!("RCC_OTGFSCLKConfig(RCC_OTGFSCLKSource_PLLCLK_Div{});", factor_name(div)));
+    }
+    lines.join("\n")
+}
+
+/// 生成时钟初始化 C 代码
+pub fn clock_c_snippet(style: ClockSnippetStyle, solution: &ClockSolution) -> Result<String, String> {
+    match style {
+        ClockSnippetStyle::Gd32F30x => Ok(gd32_snippet(solution)),
+        ClockSnippetStyle::Ch32V30x => Ok(ch32_snippet(solution)),
+        _ => hal_snippet(style, solution),
+    }
+}
+
+// 列出内置芯片系列
+#[tauri::command]
+pub fn clock_list_families() -> Vec<ClockFamily> {
+    clock_families()
+}
+
+// 求解时钟树配置
+#[tauri::command]
+pub fn clock_tree_solve(config: ClockSolveConfig) -> Result<Vec<ClockSolution>, String> {
+    let family = match (&config.custom_family, &config.family) {
+        (Some(family), _) => family.clone(),
+        (None, Some(name)) => clock_families()
+            .into_iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+            .ok_or(format!("未知的芯片系列: {}", name))?,
+        (None, None) => return Err("请指定芯片系列".to_string()),
+    };
+    clock_solve(&family, &config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn family(name: &str) -> ClockFamily {
+        clock_families().into_iter().find(|f| f.name == name).unwrap()
+    }
+
+    fn solve_config(family: &str, source: &str, source_hz: f64, target_sysclk_hz: f64) -> ClockSolveConfig {
+        ClockSolveConfig {
+            family: Some(family.to_string()),
+            custom_family: None,
+            source: source.to_string(),
+            source_hz: Some(source_hz),
+            target_sysclk_hz,
+            sysclk_tolerance_hz: None,
+            usb_48mhz: Some(false),
+            limit: Some(usize::MAX),
+        }
+    }
+
+    #[test]
+    fn pll_q_respects_output_limit() {
+        let f4 = family("STM32F4");
+        let solutions = clock_solve(&f4, &solve_config("STM32F4", "HSE", 8e6, 168e6)).unwrap();
+        assert!(!solutions.is_empty());
+        for s in &solutions {
+            let q = s.pll_q.unwrap();
+            assert!(s.vco_hz / q <= 48e6 + 0.5, "{:?}", s);
+            // 取满足上限的最小 Q
+            assert!(q == 2.0 || s.vco_hz / (q - 1.0) > 48e6 + 0.5, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn apb_respects_bus_limit() {
+        let f1 = family("STM32F1");
+        let solutions = clock_solve(&f1, &solve_config("STM32F1", "HSE", 8e6, 72e6)).unwrap();
+        let apb: Vec<(&str, f64, f64)> =
+            solutions[0].apb.iter().map(|a| (a.name.as_str(), a.div, a.pclk_hz)).collect();
+        assert_eq!(apb, [("APB1", 2.0, 36e6), ("APB2", 1.0, 72e6)]);
+
+        // APB 分频最大为 1 时 APB1 无法降到 36 MHz 以下，不能给出超频的组合
+        let mut custom = f1.clone();
+        custom.apb_div = list(&[1.0]);
+        let mut config = solve_config("STM32F1", "HSE", 8e6, 72e6);
+        config.custom_family = Some(custom.clone());
+        assert!(clock_solve(&custom, &config).unwrap().is_empty());
+        config.target_sysclk_hz = 36e6;
+        assert!(!clock_solve(&custom, &config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn hal_snippet_uses_flash_latency() {
+        let f4 = family("STM32F4");
+        let solutions = clock_solve(&f4, &solve_config("STM32F4", "HSE", 8e6, 168e6)).unwrap();
+        assert_eq!(solutions[0].flash_latency, Some(5));
+        assert!(solutions[0].c_snippet.as_ref().unwrap().contains("FLASH_LATENCY_5"));
+    }
+
+    #[test]
+    fn hal_snippet_requires_flash_latency() {
+        let mut custom = family("STM32F1");
+        custom.flash_latency = vec![];
+        let mut config = solve_config("STM32F1", "HSE", 8e6, 72e6);
+        config.custom_family = Some(custom.clone());
+        assert!(clock_solve(&custom, &config).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use handlebars::{no_escape, Handlebars, Template};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use crate::functions::fun_checksum::{crc_compute, crc_find_preset};
+use crate::functions::fun_file_convert::MarkdownExportFormat;
+use crate::functions::fun_firmware::{FirmwareInfo, FirmwareInput};
+use crate::functions::fun_memory_usage::{memory_analyze, MemoryInput};
+use crate::utils::util_date_today;
+
+// 固件镜像超过该大小时不计算整镜像 CRC（地址空洞过大时避免分配巨大缓冲区）
+const IMAGE_CRC_MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+/// 模板数据来源：除手工填写的 JSON 外，可自动读取固件、内存占用与待办事项
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DocDataSources {
+    pub firmware: Option<FirmwareInput>,
+    pub memory: Option<MemoryInput>,
+    pub top_symbols: Option<usize>, // 内存报告中的大符号数量，默认 10
+    pub todos: bool,                // 是否读取 todo.db 中的待办事项
+}
+
+/// 文档输出格式：文本走 create_txt_file，其余走 Markdown 导出
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocOutputFormat {
+    Text,
+    Pdf,
+    Html,
+    Docx,
+}
+
+impl DocOutputFormat {
+    pub fn markdown_format(&self) -> Option<MarkdownExportFormat> {
+        match self {
+            Self::Text => None,
+            Self::Pdf => Some(MarkdownExportFormat::Pdf),
+            Self::Html => Some(MarkdownExportFormat::Html),
+            Self::Docx => Some(MarkdownExportFormat::Docx),
+        }
+    }
+}
+
+/// 输出到 generate 目录的参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct DocOutput {
+    pub format: DocOutputFormat,
+    pub file_name: String,
+    pub style: Option<String>, // Markdown 导出样式名，为空时使用默认样式
+}
+
+/// 渲染请求：content 不为空时渲染未保存的正文，否则读取 template_id 对应版本
+#[derive(Debug, Clone, Deserialize)]
+pub struct DocRenderRequest {
+    pub template_id: Option<i64>,
+    pub version: Option<i64>,
+    pub content: Option<String>,
+    #[serde(default)]
+    pub data: Value,
+    #[serde(default)]
+    pub sources: DocDataSources,
+    #[serde(default)]
+    pub strict: bool,
+    pub output: Option<DocOutput>, // 为空时仅预览
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocRenderResult {
+    pub markdown: String,
+    pub data: Value, // 实际传给模板的数据，便于检查字段名
+    pub output_path: Option<String>,
+}
+
+/// 内置模板，用于新建模板时的起点
+#[derive(Debug, Clone, Serialize)]
+pub struct DocTemplatePreset {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub content: &'static str,
+}
+
+const RELEASE_NOTES_TEMPLATE: &str = r#"# {{default project "项目"}} {{version}} 发布说明
+
+- 发布日期：{{date}}
+- 硬件平台：{{default board "-"}}
+
+{{#if firmware}}
+## 固件信息
+
+| 项目 | 值 |
+| --- | --- |
+| 文件 | {{firmware.file_name}} |
+| 文件大小 | {{size firmware.file_size}} |
+| 地址范围 | {{hex firmware.min_address}} - {{hex firmware.max_address}} |
+| CRC-32 | {{firmware.crc32}} |
+| SHA-256 | `{{firmware.sha256}}` |
+{{/if}}
+
+## 变更内容
+
+{{#each changes}}
+- {{this}}
+{{else}}
+- 无
+{{/each}}
+
+{{#if todos}}
+## 已知问题
+
+{{#each todos.open}}
+- {{content}}
+{{else}}
+- 无
+{{/each}}
+{{/if}}
+"#;
+
+const TEST_REPORT_TEMPLATE: &str = r#"# {{default project "项目"}} 测试报告
+
+| 项目 | 内容 |
+| --- | --- |
+| 固件版本 | {{version}} |
+| 测试人员 | {{default tester "-"}} |
+| 测试日期 | {{date}} |
+
+{{#if memory}}
+## 资源占用
+
+| 类型 | 占用 |
+| --- | --- |
+| Flash | {{size memory.flash_total}} |
+| RAM | {{size memory.ram_total}} |
+
+{{#if memory.regions}}
+| 区域 | 起始地址 | 容量 | 已用 | 占比 |
+| --- | --- | --- | --- | --- |
+{{#each memory.regions}}
+| {{name}} | {{hex origin}} | {{size length}} | {{size used}} | {{percent used_percent}} |
+{{/each}}
+{{/if}}
+{{/if}}
+
+## 测试用例
+
+| 用例 | 结果 | 备注 |
+| --- | --- | --- |
+{{#each cases}}
+| {{name}} | {{#if passed}}通过{{else}}**未通过**{{/if}} | {{default note ""}} |
+{{/each}}
+
+{{#if todos}}
+## 遗留事项
+
+{{#each todos.all}}
+- {{checkbox completed}} {{content}}
+{{/each}}
+{{/if}}
+"#;
+
+pub const DOC_TEMPLATE_PRESETS: &[DocTemplatePreset] = &[
+    DocTemplatePreset {
+        name: "发布说明",
+        description: "版本号、固件校验值、变更列表与未完成的待办",
+        content: RELEASE_NOTES_TEMPLATE,
+    },
+    DocTemplatePreset {
+        name: "测试报告",
+        description: "测试用例结果、Flash/RAM 占用与遗留事项",
+        content: TEST_REPORT_TEMPLATE,
+    },
+];
+
+// handlebars_helper! 生成的是公开的单元结构体，放在私有模块中避免被 functions::* 重新导出
+mod helpers {
+    use handlebars::handlebars_helper;
+    use serde_json::Value;
+
+    // 数值格式化为十六进制地址，{{hex value width=8}}
+    handlebars_helper!(hex: |value: u64, {width: u64 = 8}| format!("0x{:0width$X}", value, width = width as usize));
+
+    // 字节数格式化为 B / KB / MB
+    handlebars_helper!(size: |value: u64| {
+        if value >= 1024 * 1024 {
+            format!("{:.2} MB", value as f64 / 1024.0 / 1024.0)
+        } else if value >= 1024 {
+            format!("{:.1} KB", value as f64 / 1024.0)
+        } else {
+            format!("{} B", value)
+        }
+    });
+
+    // 百分比，{{percent value digits=1}}
+    handlebars_helper!(percent: |value: f64, {digits: u64 = 1}| format!("{:.*}%", digits as usize, value));
+
+    // 值为空时使用默认值
+    handlebars_helper!(default: |value: Json, fallback: Json| {
+        match value {
+            Value::Null => fallback.clone(),
+            Value::String(s) if s.is_empty() => fallback.clone(),
+            _ => value.clone(),
+        }
+    });
+
+    // Markdown 任务列表复选框
+    handlebars_helper!(checkbox: |checked: Json| if checked.as_bool().unwrap_or(false) || checked.as_i64().unwrap_or(0) != 0 {
+        "[x]"
+    } else {
+        "[ ]"
+    });
+
+    handlebars_helper!(upper: |value: str| value.to_uppercase());
+    handlebars_helper!(lower: |value: str| value.to_lowercase());
+}
+
+// 创建带有自定义 helper 的渲染器；输出为 Markdown，不做 HTML 转义
+fn doc_template_registry(strict: bool) -> Handlebars<'static> {
+    let mut registry = Handlebars::new();
+    registry.register_escape_fn(no_escape);
+    registry.set_strict_mode(strict);
+    registry.register_helper("hex", Box::new(helpers::hex));
+    registry.register_helper("size", Box::new(helpers::size));
+    registry.register_helper("percent", Box::new(helpers::percent));
+    registry.register_helper("default", Box::new(helpers::default));
+    registry.register_helper("checkbox", Box::new(helpers::checkbox));
+    registry.register_helper("upper", Box::new(helpers::upper));
+    registry.register_helper("lower", Box::new(helpers::lower));
+    registry
+}
+
+/// 检查模板语法
+pub fn doc_template_check(content: &str) -> Result<(), String> {
+    Template::compile(content)
+        .map(|_| ())
+        .map_err(|e| format!("模板语法错误: {}", e))
+}
+
+/// 渲染模板；strict 为 true 时引用不存在的字段会报错
+pub fn doc_template_fill(content: &str, data: &Value, strict: bool) -> Result<String, String> {
+    doc_template_registry(strict)
+        .render_template(content, data)
+        .map_err(|e| format!("模板渲染失败: {}", e))
+}
+
+// 读取固件文件，汇总地址范围与校验值
+fn firmware_data(input: &FirmwareInput) -> Result<Value, String> {
+    let raw = std::fs::read(&input.path).map_err(|e| format!("读取固件文件失败: {}", e))?;
+    let (format, map) = input.load()?;
+    let info = FirmwareInfo::from_map(Some(format), &map);
+    let crc32 = crc_find_preset("CRC-32").ok_or("缺少 CRC-32 预设")?.params;
+
+    // 整镜像 CRC：从最低地址到最高地址，空洞按 0xFF 填充
+    let image_crc32 = match (info.min_address, info.max_address) {
+        (Some(min), Some(max)) if max - (min as u64) <= IMAGE_CRC_MAX_SIZE => {
+            let image = map.read(min, (max - min as u64) as usize, 0xFF);
+            Some(format!("0x{:08X}", crc_compute(crc32, &image)?))
+        }
+        _ => None,
+    };
+
+    let mut value = serde_json::to_value(&info).map_err(|e| format!("序列化固件信息失败: {}", e))?;
+    let object = value.as_object_mut().ok_or("固件信息格式错误")?;
+    object.insert("path".to_string(), json!(input.path));
+    object.insert(
+        "file_name".to_string(),
+        json!(Path::new(&input.path).file_name().map(|n| n.to_string_lossy().to_string())),
+    );
+    object.insert("file_size".to_string(), json!(raw.len()));
+    object.insert("crc32".to_string(), json!(format!("0x{:08X}", crc_compute(crc32, &raw)?)));
+    object.insert("sha256".to_string(), json!(format!("{:x}", Sha256::digest(&raw))));
+    object.insert("image_crc32".to_string(), json!(image_crc32));
+    Ok(value)
+}
+
+/// 组装模板数据：手工数据为对象时作为顶层字段，自动读取的数据放在 firmware / memory 下
+pub fn doc_collect_data(data: Value, sources: &DocDataSources) -> Result<Map<String, Value>, String> {
+    let mut context = match data {
+        Value::Object(map) => map,
+        Value::Null => Map::new(),
+        other => {
+            let mut map = Map::new();
+            map.insert("data".to_string(), other);
+            map
+        }
+    };
+    context.entry("date").or_insert_with(|| json!(util_date_today()));
+    if let Some(firmware) = &sources.firmware {
+        context.insert("firmware".to_string(), firmware_data(firmware)?);
+    }
+    if let Some(memory) = &sources.memory {
+        let mut report = memory_analyze(memory, Some(sources.top_symbols.unwrap_or(10)))?;
+        // 模板只需要大符号列表，完整符号表可能有上万项
+        report.symbols.truncate(report.top_n);
+        context.insert(
+            "memory".to_string(),
+            serde_json::to_value(&report).map_err(|e| format!("序列化内存报告失败: {}", e))?,
+        );
+    }
+    Ok(context)
+}
@@ -1,8 +1,40 @@
 use markdown2pdf::{config::ConfigSource, parse_into_file};
-use crate::utils::util_get_app_path;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::functions::fun_markdown_export::{
+    markdown_parse_blocks, markdown_title, markdown_to_docx, markdown_to_html, MarkdownStyleProfile,
+};
+use crate::functions::fun_markdown_pdf::markdown_to_styled_pdf;
+use crate::utils::{util_get_app_path, util_get_generate_path};
+
+/// Markdown 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkdownExportFormat {
+    Pdf,
+    Html,
+    Docx,
+}
+
+impl MarkdownExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MarkdownExportFormat::Pdf => "pdf",
+            MarkdownExportFormat::Html => "html",
+            MarkdownExportFormat::Docx => "docx",
+        }
+    }
+}
 
 #[tauri::command]
 pub fn convert_markdown_to_pdf(markdown_content: &str, pdf_file_name: &str) -> Result<(), String> {
+    markdown_to_pdf_file(markdown_content, pdf_file_name)?;
+    Ok(())
+}
+
+/// 生成 PDF 到 generate 目录，返回文件路径（后台任务共用）
+pub fn markdown_to_pdf_file(markdown_content: &str, pdf_file_name: &str) -> Result<PathBuf, String> {
     // 获取当前目录
     let current_dir = util_get_app_path();
 
@@ -39,5 +71,39 @@ pub fn convert_markdown_to_pdf(markdown_content: &str, pdf_file_name: &str) -> R
         return Err("PDF文件未创建".to_string());
     }
 
-    Ok(())
+    Ok(pdf_path)
+}
+
+/// 按样式导出 Markdown 到 generate 目录，返回文件路径
+/// PDF 未指定样式时仍使用 markdown2pdf 默认样式；HTML / DOCX 未指定样式时使用默认样式
+/// base_dir 为 Markdown 文件所在目录，用于解析图片的相对路径
+pub fn markdown_export_file(
+    markdown_content: &str,
+    file_name: &str,
+    format: MarkdownExportFormat,
+    profile: Option<&MarkdownStyleProfile>,
+    base_dir: Option<&Path>,
+) -> Result<PathBuf, String> {
+    if format == MarkdownExportFormat::Pdf && profile.is_none() {
+        return markdown_to_pdf_file(markdown_content, file_name);
+    }
+    let default_profile = MarkdownStyleProfile::default();
+    let profile = profile.unwrap_or(&default_profile);
+    let title = markdown_title(&markdown_parse_blocks(markdown_content)).unwrap_or(file_name.to_string());
+    let path = util_get_generate_path()?.join(format!("{}.{}", file_name, format.extension()));
+
+    match format {
+        MarkdownExportFormat::Pdf => {
+            markdown_to_styled_pdf(markdown_content, &title, profile, base_dir, &path)?;
+        }
+        MarkdownExportFormat::Html => {
+            let html = markdown_to_html(markdown_content, &title, profile, base_dir)?;
+            std::fs::write(&path, html).map_err(|e| format!("写入 HTML 文件失败: {}", e))?;
+        }
+        MarkdownExportFormat::Docx => {
+            let docx = markdown_to_docx(markdown_content, &title, profile, base_dir)?;
+            std::fs::write(&path, docx).map_err(|e| format!("写入 DOCX 文件失败: {}", e))?;
+        }
+    }
+    Ok(path)
 }
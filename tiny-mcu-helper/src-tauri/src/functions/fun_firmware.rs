@@ -0,0 +1,829 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::utils::util_get_generate_path;
+
+// 填充空洞 / 输出 bin 时允许展开的最大区间，避免稀疏镜像（如 0x00000000 与 0xFFFF0000 各有数据）占满内存
+const FIRMWARE_FILL_LIMIT: u64 = 64 * 1024 * 1024;
+
+/// 固件文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FirmwareFormat {
+    Hex,  // Intel HEX
+    Srec, // Motorola S-record（S19/S28/S37）
+    Bin,  // 原始二进制
+}
+
+impl FirmwareFormat {
+    // 根据扩展名推断格式
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "hex" | "ihx" | "ihex" | "h86" => Some(Self::Hex),
+            "s19" | "s28" | "s37" | "srec" | "mot" | "mhx" | "s" => Some(Self::Srec),
+            "bin" | "raw" => Some(Self::Bin),
+            _ => None,
+        }
+    }
+
+    // 根据内容推断格式（文本首字符为 ':' 或 'S'）
+    pub fn from_content(content: &[u8]) -> Self {
+        let first = content.iter().find(|b| !b.is_ascii_whitespace());
+        let is_text = content.iter().all(|b| b.is_ascii());
+        match first {
+            Some(b':') if is_text => Self::Hex,
+            Some(b'S') if is_text => Self::Srec,
+            _ => Self::Bin,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::Srec => "srec",
+            Self::Bin => "bin",
+        }
+    }
+}
+
+/// 程序入口地址（Intel HEX 03/05 记录，S-record S7/S8/S9 记录）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum EntryPoint {
+    Linear { address: u32 },
+    Segment { cs: u16, ip: u16 },
+}
+
+impl EntryPoint {
+    pub fn linear_address(&self) -> u32 {
+        match *self {
+            Self::Linear { address } => address,
+            Self::Segment { cs, ip } => ((cs as u32) << 4) + ip as u32,
+        }
+    }
+
+    /// 平移入口地址；段地址形式优先保持 IP 不变，无法表示时改为线性地址
+    pub fn offset(&self, offset: i64) -> Result<Self, String> {
+        let address = self.linear_address() as i64 + offset;
+        if !(0..=u32::MAX as i64).contains(&address) {
+            return Err(format!("入口地址平移后超出 32 位地址空间: 0x{:08X}", self.linear_address()));
+        }
+        let address = address as u32;
+        Ok(match *self {
+            Self::Linear { .. } => Self::Linear { address },
+            Self::Segment { ip, .. } => {
+                let base = address.wrapping_sub(ip as u32);
+                if address >= ip as u32 && base.is_multiple_of(16) && base >> 4 <= 0xFFFF {
+                    Self::Segment { cs: (base >> 4) as u16, ip }
+                } else if address <= 0xF_FFFF {
+                    Self::Segment {
+                        cs: (address >> 4) as u16,
+                        ip: (address & 0xF) as u16,
+                    }
+                } else {
+                    Self::Linear { address }
+                }
+            }
+        })
+    }
+}
+
+/// 稀疏内存映射：起始地址 -> 连续数据块（块之间互不重叠、互不相邻）
+#[derive(Debug, Clone, Default)]
+pub struct MemoryMap {
+    segments: BTreeMap<u32, Vec<u8>>,
+    pub entry: Option<EntryPoint>,
+    pub header: Option<Vec<u8>>, // S-record S0 头部内容
+}
+
+/// 数据块信息
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentInfo {
+    pub start: u32,
+    pub end: u32, // 不包含
+    pub size: usize,
+}
+
+impl MemoryMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = (u32, &[u8])> {
+        self.segments.iter().map(|(addr, data)| (*addr, data.as_slice()))
+    }
+
+    pub fn segment_infos(&self) -> Vec<SegmentInfo> {
+        self.segments()
+            .map(|(start, data)| SegmentInfo {
+                start,
+                end: (start as u64 + data.len() as u64) as u32,
+                size: data.len(),
+            })
+            .collect()
+    }
+
+    // 有效数据总字节数
+    pub fn data_size(&self) -> usize {
+        self.segments.values().map(|d| d.len()).sum()
+    }
+
+    pub fn min_address(&self) -> Option<u32> {
+        self.segments.keys().next().copied()
+    }
+
+    // 最高地址（不包含）
+    pub fn max_address(&self) -> Option<u64> {
+        self.segments
+            .iter()
+            .next_back()
+            .map(|(addr, data)| *addr as u64 + data.len() as u64)
+    }
+
+    /// 写入数据；overwrite 为 false 时若与已有数据重叠则报错
+    pub fn write(&mut self, address: u32, data: &[u8], overwrite: bool) -> Result<(), String> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let end = address as u64 + data.len() as u64;
+        if end > u32::MAX as u64 + 1 {
+            return Err(format!("数据超出 32 位地址空间: 0x{:08X}", address));
+        }
+
+        // 找出所有与 [address, end] 重叠或相邻的数据块
+        let touched: Vec<u32> = self
+            .segments
+            .range(..=end.min(u32::MAX as u64) as u32)
+            .filter(|(start, seg)| **start as u64 + seg.len() as u64 >= address as u64)
+            .map(|(start, _)| *start)
+            .collect();
+
+        let mut merged_start = address as u64;
+        let mut merged_end = end;
+        for start in &touched {
+            let seg = &self.segments[start];
+            if !overwrite {
+                let overlap_start = (*start as u64).max(address as u64);
+                let overlap_end = (*start as u64 + seg.len() as u64).min(end);
+                if overlap_start < overlap_end {
+                    return Err(format!(
+                        "地址 0x{:08X} - 0x{:08X} 存在重叠数据",
+                        overlap_start,
+                        overlap_end - 1
+                    ));
+                }
+            }
+            merged_start = merged_start.min(*start as u64);
+            merged_end = merged_end.max(*start as u64 + seg.len() as u64);
+        }
+
+        let mut merged = vec![0u8; (merged_end - merged_start) as usize];
+        for start in touched {
+            let seg = self.segments.remove(&start).unwrap();
+            let offset = (start as u64 - merged_start) as usize;
+            merged[offset..offset + seg.len()].copy_from_slice(&seg);
+        }
+        let offset = (address as u64 - merged_start) as usize;
+        merged[offset..offset + data.len()].copy_from_slice(data);
+
+        self.segments.insert(merged_start as u32, merged);
+        Ok(())
+    }
+
+    /// 读取连续区间，区间内存在空洞时用 pad 填充
+    pub fn read(&self, start: u32, len: usize, pad: u8) -> Vec<u8> {
+        let mut out = vec![pad; len];
+        let end = start as u64 + len as u64;
+        for (seg_start, data) in self.segments() {
+            let seg_end = seg_start as u64 + data.len() as u64;
+            let from = (seg_start as u64).max(start as u64);
+            let to = seg_end.min(end);
+            if from < to {
+                let src = (from - seg_start as u64) as usize;
+                let dst = (from - start as u64) as usize;
+                let n = (to - from) as usize;
+                out[dst..dst + n].copy_from_slice(&data[src..src + n]);
+            }
+        }
+        out
+    }
+
+    /// 读取 [start, end)，区间超过 FIRMWARE_FILL_LIMIT 时报错
+    pub fn read_range(&self, start: u32, end: u64, pad: u8) -> Result<Vec<u8>, String> {
+        if end <= start as u64 {
+            return Ok(Vec::new());
+        }
+        if end - start as u64 > FIRMWARE_FILL_LIMIT {
+            return Err(format!(
+                "区间 0x{:08X} - 0x{:X} 超过 {} MiB，请先裁剪固件或缩小范围",
+                start,
+                end - 1,
+                FIRMWARE_FILL_LIMIT / 1024 / 1024
+            ));
+        }
+        Ok(self.read(start, (end - start as u64) as usize, pad))
+    }
+
+    /// 区间 [start, end) 是否全部有数据
+    pub fn is_range_filled(&self, start: u32, end: u64) -> bool {
+        self.segments().any(|(seg_start, data)| {
+            seg_start <= start && seg_start as u64 + data.len() as u64 >= end
+        })
+    }
+
+    /// 合并另一个内存映射
+    pub fn merge(&mut self, other: &MemoryMap, overwrite: bool) -> Result<(), String> {
+        for (addr, data) in other.segments() {
+            self.write(addr, data, overwrite)?;
+        }
+        if self.entry.is_none() {
+            self.entry = other.entry;
+        }
+        if self.header.is_none() {
+            self.header = other.header.clone();
+        }
+        Ok(())
+    }
+
+    /// 裁剪到 [start, end)
+    pub fn crop(&mut self, start: u32, end: u64) {
+        let mut cropped = BTreeMap::new();
+        for (seg_start, data) in std::mem::take(&mut self.segments) {
+            let seg_end = seg_start as u64 + data.len() as u64;
+            let from = (seg_start as u64).max(start as u64);
+            let to = seg_end.min(end);
+            if from < to {
+                let src = (from - seg_start as u64) as usize;
+                cropped.insert(from as u32, data[src..src + (to - from) as usize].to_vec());
+            }
+        }
+        self.segments = cropped;
+    }
+
+    /// 用 pad 填充 [start, end) 中的所有空洞
+    pub fn fill(&mut self, start: u32, end: u64, pad: u8) -> Result<(), String> {
+        if end <= start as u64 {
+            return Ok(());
+        }
+        let filled = self.read_range(start, end, pad)?;
+        // 已有数据在 read 时已被保留，覆盖写入即可
+        self.write(start, &filled, true)
+    }
+
+    /// 整体平移地址（offset 可为负）
+    pub fn offset(&mut self, offset: i64) -> Result<(), String> {
+        // 先检查全部地址，出错时不修改原映射
+        let entry = self.entry.map(|e| e.offset(offset)).transpose()?;
+        for (start, data) in &self.segments {
+            let new_start = *start as i64 + offset;
+            if new_start < 0 || new_start as u64 + data.len() as u64 > u32::MAX as u64 + 1 {
+                return Err(format!("地址平移后超出 32 位地址空间: 0x{:08X}", start));
+            }
+        }
+        self.segments = std::mem::take(&mut self.segments)
+            .into_iter()
+            .map(|(start, data)| ((start as i64 + offset) as u32, data))
+            .collect();
+        self.entry = entry;
+        Ok(())
+    }
+}
+
+// ==================== Intel HEX ====================
+
+fn parse_hex_record_bytes(line: &str, line_no: usize) -> Result<Vec<u8>, String> {
+    let body = &line[1..];
+    if !body.is_ascii() || !body.len().is_multiple_of(2) {
+        return Err(format!("第 {} 行: 记录长度无效", line_no));
+    }
+    (0..body.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&body[i..i + 2], 16)
+                .map_err(|_| format!("第 {} 行: 无效的十六进制字符", line_no))
+        })
+        .collect()
+}
+
+/// 解析 Intel HEX（支持 00 ~ 05 记录）
+pub fn parse_intel_hex(content: &str) -> Result<MemoryMap, String> {
+    let mut map = MemoryMap::new();
+    let mut base: u32 = 0;
+    let mut eof = false;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if eof {
+            return Err(format!("第 {} 行: EOF 记录之后仍有数据", line_no));
+        }
+        if !line.starts_with(':') {
+            return Err(format!("第 {} 行: 记录必须以 ':' 开头", line_no));
+        }
+
+        let bytes = parse_hex_record_bytes(line, line_no)?;
+        if bytes.len() < 5 || bytes.len() != bytes[0] as usize + 5 {
+            return Err(format!("第 {} 行: 数据长度与记录长度不符", line_no));
+        }
+        let sum = bytes.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+        if sum != 0 {
+            return Err(format!("第 {} 行: 校验和错误", line_no));
+        }
+
+        let len = bytes[0] as usize;
+        let offset = u16::from_be_bytes([bytes[1], bytes[2]]) as u32;
+        let record_type = bytes[3];
+        let data = &bytes[4..4 + len];
+
+        match record_type {
+            0x00 => map.write(base.wrapping_add(offset), data, false)?,
+            0x01 => eof = true,
+            0x02 if len == 2 => base = (u16::from_be_bytes([data[0], data[1]]) as u32) << 4,
+            0x03 if len == 4 => {
+                map.entry = Some(EntryPoint::Segment {
+                    cs: u16::from_be_bytes([data[0], data[1]]),
+                    ip: u16::from_be_bytes([data[2], data[3]]),
+                })
+            }
+            0x04 if len == 2 => base = (u16::from_be_bytes([data[0], data[1]]) as u32) << 16,
+            0x05 if len == 4 => {
+                map.entry = Some(EntryPoint::Linear {
+                    address: u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+                })
+            }
+            0x02..=0x05 => return Err(format!("第 {} 行: 记录类型 {:02X} 长度无效", line_no, record_type)),
+            _ => return Err(format!("第 {} 行: 不支持的记录类型 {:02X}", line_no, record_type)),
+        }
+    }
+
+    if !eof {
+        return Err("缺少 EOF 记录（:00000001FF）".to_string());
+    }
+    Ok(map)
+}
+
+fn format_hex_record(record_type: u8, offset: u16, data: &[u8]) -> String {
+    let mut bytes = vec![data.len() as u8];
+    bytes.extend_from_slice(&offset.to_be_bytes());
+    bytes.push(record_type);
+    bytes.extend_from_slice(data);
+    let checksum = bytes.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)).wrapping_neg();
+    bytes.push(checksum);
+
+    let mut line = String::with_capacity(bytes.len() * 2 + 1);
+    line.push(':');
+    for b in bytes {
+        line.push_str(&format!("{:02X}", b));
+    }
+    line
+}
+
+/// 生成 Intel HEX（超过 64K 时使用 04 扩展线性地址记录）
+pub fn write_intel_hex(map: &MemoryMap, record_length: usize) -> String {
+    let record_length = record_length.clamp(1, 255);
+    let mut lines = Vec::new();
+    let mut current_upper: u32 = 0;
+
+    for (start, data) in map.segments() {
+        let mut pos = 0usize;
+        while pos < data.len() {
+            let address = start + pos as u32;
+            let upper = address >> 16;
+            if upper != current_upper {
+                lines.push(format_hex_record(0x04, 0, &(upper as u16).to_be_bytes()));
+                current_upper = upper;
+            }
+            // 单条记录不跨越 64K 边界
+            let to_boundary = 0x10000 - (address & 0xFFFF) as usize;
+            let n = record_length.min(data.len() - pos).min(to_boundary);
+            lines.push(format_hex_record(0x00, address as u16, &data[pos..pos + n]));
+            pos += n;
+        }
+    }
+
+    match map.entry {
+        Some(EntryPoint::Linear { address }) => {
+            lines.push(format_hex_record(0x05, 0, &address.to_be_bytes()))
+        }
+        Some(EntryPoint::Segment { cs, ip }) => {
+            let mut data = cs.to_be_bytes().to_vec();
+            data.extend_from_slice(&ip.to_be_bytes());
+            lines.push(format_hex_record(0x03, 0, &data))
+        }
+        None => {}
+    }
+    lines.push(":00000001FF".to_string());
+    lines.join("\n") + "\n"
+}
+
+// ==================== Motorola S-record ====================
+
+/// S-record 地址宽度（S19 / S28 / S37）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SrecType {
+    S19,
+    S28,
+    S37,
+}
+
+impl SrecType {
+    fn address_len(&self) -> usize {
+        match self {
+            Self::S19 => 2,
+            Self::S28 => 3,
+            Self::S37 => 4,
+        }
+    }
+
+    // 根据最高地址自动选择
+    fn for_max_address(max_address: u64) -> Self {
+        if max_address <= 0x1_0000 {
+            Self::S19
+        } else if max_address <= 0x100_0000 {
+            Self::S28
+        } else {
+            Self::S37
+        }
+    }
+}
+
+/// 解析 Motorola S-record
+pub fn parse_srec(content: &str) -> Result<MemoryMap, String> {
+    let mut map = MemoryMap::new();
+    let mut data_records: u32 = 0;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.len() < 4 || !line.starts_with('S') || !line.is_ascii() {
+            return Err(format!("第 {} 行: 无效的 S-record 记录", line_no));
+        }
+
+        let record_type = line.as_bytes()[1];
+        let bytes = parse_hex_record_bytes(&line[1..], line_no)?;
+        if bytes.is_empty() || bytes.len() != bytes[0] as usize + 1 {
+            return Err(format!("第 {} 行: 数据长度与记录长度不符", line_no));
+        }
+        let sum = bytes[..bytes.len() - 1]
+            .iter()
+            .fold(0u8, |acc, b| acc.wrapping_add(*b));
+        if !sum != bytes[bytes.len() - 1] {
+            return Err(format!("第 {} 行: 校验和错误", line_no));
+        }
+
+        let address_len = match record_type {
+            b'0' | b'1' | b'5' | b'9' => 2,
+            b'2' | b'6' | b'8' => 3,
+            b'3' | b'7' => 4,
+            _ => return Err(format!("第 {} 行: 不支持的记录类型 S{}", line_no, record_type as char)),
+        };
+        if bytes.len() < address_len + 2 {
+            return Err(format!("第 {} 行: 记录长度过短", line_no));
+        }
+        let address = bytes[1..1 + address_len]
+            .iter()
+            .fold(0u32, |acc, b| (acc << 8) | *b as u32);
+        let data = &bytes[1 + address_len..bytes.len() - 1];
+
+        match record_type {
+            b'0' => map.header = Some(data.to_vec()),
+            b'1' | b'2' | b'3' => {
+                map.write(address, data, false)?;
+                data_records += 1;
+            }
+            b'5' | b'6' => {
+                if address != data_records {
+                    return Err(format!(
+                        "第 {} 行: 记录计数不符（声明 {}，实际 {}）",
+                        line_no, address, data_records
+                    ));
+                }
+            }
+            _ => map.entry = Some(EntryPoint::Linear { address }),
+        }
+    }
+
+    Ok(map)
+}
+
+fn format_srec_record(record_type: u8, address: u32, address_len: usize, data: &[u8]) -> String {
+    let mut bytes = vec![(address_len + data.len() + 1) as u8];
+    bytes.extend_from_slice(&address.to_be_bytes()[4 - address_len..]);
+    bytes.extend_from_slice(data);
+    let checksum = !bytes.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+    bytes.push(checksum);
+
+    let mut line = format!("S{}", record_type);
+    for b in bytes {
+        line.push_str(&format!("{:02X}", b));
+    }
+    line
+}
+
+/// 生成 Motorola S-record；srec_type 为空时按最高地址自动选择
+pub fn write_srec(map: &MemoryMap, record_length: usize, srec_type: Option<SrecType>) -> Result<String, String> {
+    let max_address = map.max_address().unwrap_or(0);
+    let entry_address = map.entry.map(|e| e.linear_address()).unwrap_or(0) as u64;
+    let srec_type =
+        srec_type.unwrap_or_else(|| SrecType::for_max_address(max_address.max(entry_address + 1)));
+    let address_len = srec_type.address_len();
+    if max_address > 1u64 << (address_len * 8) {
+        return Err(format!("最高地址 0x{:X} 超出 {:?} 的地址范围", max_address - 1, srec_type));
+    }
+    if entry_address >= 1u64 << (address_len * 8) {
+        return Err(format!("入口地址 0x{:X} 超出 {:?} 的地址范围", entry_address, srec_type));
+    }
+
+    let record_length = record_length.clamp(1, 255 - address_len - 1);
+    let (data_type, count_type_end) = match srec_type {
+        SrecType::S19 => (1, 9),
+        SrecType::S28 => (2, 8),
+        SrecType::S37 => (3, 7),
+    };
+
+    let mut lines = Vec::new();
+    let header = map.header.clone().unwrap_or_else(|| b"HDR".to_vec());
+    lines.push(format_srec_record(0, 0, 2, &header[..header.len().min(252)]));
+
+    let mut count: u32 = 0;
+    for (start, data) in map.segments() {
+        for (i, chunk) in data.chunks(record_length).enumerate() {
+            let address = start + (i * record_length) as u32;
+            lines.push(format_srec_record(data_type, address, address_len, chunk));
+            count += 1;
+        }
+    }
+
+    // S5（16 位计数）/ S6（24 位计数）
+    if count <= 0xFFFF {
+        lines.push(format_srec_record(5, count, 2, &[]));
+    } else if count <= 0xFF_FFFF {
+        lines.push(format_srec_record(6, count, 3, &[]));
+    }
+    lines.push(format_srec_record(count_type_end, entry_address as u32, address_len, &[]));
+    Ok(lines.join("\n") + "\n")
+}
+
+// ==================== 加载 / 保存 ====================
+
+/// 从文件加载固件，format 为空时按扩展名或内容推断；bin 文件从 base_address 开始放置
+pub fn load_firmware(
+    path: &Path,
+    format: Option<FirmwareFormat>,
+    base_address: u32,
+) -> Result<(FirmwareFormat, MemoryMap), String> {
+    let content =
+        std::fs::read(path).map_err(|e| format!("读取固件文件失败 {:?}: {}", path, e))?;
+    let format = format
+        .or_else(|| FirmwareFormat::from_path(path))
+        .unwrap_or_else(|| FirmwareFormat::from_content(&content));
+
+    let map = match format {
+        FirmwareFormat::Hex => parse_intel_hex(&String::from_utf8_lossy(&content))?,
+        FirmwareFormat::Srec => parse_srec(&String::from_utf8_lossy(&content))?,
+        FirmwareFormat::Bin => {
+            let mut map = MemoryMap::new();
+            map.write(base_address, &content, false)?;
+            map
+        }
+    };
+    Ok((format, map))
+}
+
+/// 将内存映射序列化为指定格式
+pub fn serialize_firmware(map: &MemoryMap, output: &FirmwareOutput) -> Result<Vec<u8>, String> {
+    let record_length = output.record_length.unwrap_or(16);
+    match output.format {
+        FirmwareFormat::Hex => Ok(write_intel_hex(map, record_length).into_bytes()),
+        FirmwareFormat::Srec => Ok(write_srec(map, record_length, output.srec_type)?.into_bytes()),
+        FirmwareFormat::Bin => {
+            let start = map.min_address().ok_or("固件内容为空")?;
+            let end = map.max_address().unwrap_or(start as u64);
+            map.read_range(start, end, output.pad_byte.unwrap_or(0xFF))
+        }
+    }
+}
+
+/// 保存到 generate 目录，返回完整路径
+pub fn save_firmware(map: &MemoryMap, output: &FirmwareOutput) -> Result<String, String> {
+    let bytes = serialize_firmware(map, output)?;
+    let file_name = if Path::new(&output.file_name).extension().is_some() {
+        output.file_name.clone()
+    } else {
+        format!("{}.{}", output.file_name, output.format.extension())
+    };
+    let file_path = util_get_generate_path()?.join(file_name);
+    std::fs::write(&file_path, bytes).map_err(|e| format!("写入文件失败: {}", e))?;
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+// ==================== Tauri 命令 ====================
+
+/// 固件输入文件
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirmwareInput {
+    pub path: String,
+    pub format: Option<FirmwareFormat>,
+    pub base_address: Option<u32>, // 仅 bin 文件使用，默认 0
+}
+
+impl FirmwareInput {
+    pub fn load(&self) -> Result<(FirmwareFormat, MemoryMap), String> {
+        load_firmware(Path::new(&self.path), self.format, self.base_address.unwrap_or(0))
+    }
+}
+
+/// 输出参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirmwareOutput {
+    pub format: FirmwareFormat,
+    pub file_name: String,
+    pub record_length: Option<usize>, // HEX / S-record 每条记录的数据字节数，默认 16
+    pub srec_type: Option<SrecType>,  // 为空时自动选择
+    pub pad_byte: Option<u8>,         // bin 输出填充空洞的字节，默认 0xFF
+}
+
+/// 处理步骤，按顺序执行
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FirmwareOperation {
+    Crop { start: u32, end: u64 },
+    Fill { start: Option<u32>, end: Option<u64>, pad: u8 },
+    Offset { offset: i64 },
+    Relocate { address: u32 }, // 把最低地址移动到 address
+}
+
+/// 固件概要信息
+#[derive(Debug, Clone, Serialize)]
+pub struct FirmwareInfo {
+    pub format: Option<FirmwareFormat>,
+    pub segments: Vec<SegmentInfo>,
+    pub min_address: Option<u32>,
+    pub max_address: Option<u64>, // 不包含
+    pub data_size: usize,
+    pub entry: Option<EntryPoint>,
+}
+
+impl FirmwareInfo {
+    pub fn from_map(format: Option<FirmwareFormat>, map: &MemoryMap) -> Self {
+        Self {
+            format,
+            segments: map.segment_infos(),
+            min_address: map.min_address(),
+            max_address: map.max_address(),
+            data_size: map.data_size(),
+            entry: map.entry,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FirmwareProcessResult {
+    pub output_path: String,
+    pub info: FirmwareInfo,
+}
+
+/// 执行单个处理步骤
+pub fn apply_firmware_operation(map: &mut MemoryMap, operation: &FirmwareOperation) -> Result<(), String> {
+    match *operation {
+        FirmwareOperation::Crop { start, end } => map.crop(start, end),
+        FirmwareOperation::Fill { start, end, pad } => {
+            let start = match start.or(map.min_address()) {
+                Some(start) => start,
+                None => return Ok(()),
+            };
+            let end = end.or(map.max_address()).unwrap_or(start as u64);
+            map.fill(start, end, pad)?;
+        }
+        FirmwareOperation::Offset { offset } => map.offset(offset)?,
+        FirmwareOperation::Relocate { address } => {
+            if let Some(min) = map.min_address() {
+                map.offset(address as i64 - min as i64)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// 加载并合并多个输入文件
+pub fn load_firmware_inputs(inputs: &[FirmwareInput], overwrite: bool) -> Result<MemoryMap, String> {
+    if inputs.is_empty() {
+        return Err("请至少选择一个固件文件".to_string());
+    }
+    let mut map = MemoryMap::new();
+    for input in inputs {
+        let (_, loaded) = input.load()?;
+        map.merge(&loaded, overwrite)
+            .map_err(|e| format!("合并 {} 失败: {}", input.path, e))?;
+    }
+    Ok(map)
+}
+
+// 查看固件文件的数据块分布
+#[tauri::command]
+pub fn firmware_inspect(input: FirmwareInput) -> Result<FirmwareInfo, String> {
+    let (format, map) = input.load()?;
+    Ok(FirmwareInfo::from_map(Some(format), &map))
+}
+
+// 合并 -> 按顺序执行处理步骤 -> 输出到 generate 目录
+#[tauri::command]
+pub fn firmware_process(
+    inputs: Vec<FirmwareInput>,
+    operations: Vec<FirmwareOperation>,
+    output: FirmwareOutput,
+    overwrite: Option<bool>,
+) -> Result<FirmwareProcessResult, String> {
+    let mut map = load_firmware_inputs(&inputs, overwrite.unwrap_or(false))?;
+    for operation in &operations {
+        apply_firmware_operation(&mut map, operation)?;
+    }
+    if map.is_empty() {
+        return Err("处理后固件内容为空".to_string());
+    }
+
+    let output_path = save_firmware(&map, &output)?;
+    Ok(FirmwareProcessResult {
+        output_path,
+        info: FirmwareInfo::from_map(Some(output.format), &map),
+    })
+}
+
+// 格式转换（单文件，无额外处理）
+#[tauri::command]
+pub fn firmware_convert(input: FirmwareInput, output: FirmwareOutput) -> Result<FirmwareProcessResult, String> {
+    firmware_process(vec![input], vec![], output, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_rejects_sparse_span() {
+        let mut map = MemoryMap::new();
+        map.write(0x0000_0000, &[1, 2], false).unwrap();
+        map.write(0xFFFF_0000, &[3, 4], false).unwrap();
+        assert!(map.fill(0, map.max_address().unwrap(), 0xFF).is_err());
+        assert_eq!(map.data_size(), 4);
+
+        let output = FirmwareOutput {
+            format: FirmwareFormat::Bin,
+            file_name: "sparse".to_string(),
+            record_length: None,
+            srec_type: None,
+            pad_byte: None,
+        };
+        assert!(serialize_firmware(&map, &output).is_err());
+
+        map.crop(0, 0x10);
+        map.fill(0, 4, 0xFF).unwrap();
+        assert_eq!(map.read(0, 4, 0), vec![1, 2, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn offset_moves_entry_point() {
+        let mut map = MemoryMap::new();
+        map.write(0x1000, &[0xAA], false).unwrap();
+        map.entry = Some(EntryPoint::Segment { cs: 0x0100, ip: 0x0004 });
+        map.offset(0x20).unwrap();
+        assert_eq!(map.entry, Some(EntryPoint::Segment { cs: 0x0102, ip: 0x0004 }));
+        map.offset(0x3).unwrap();
+        assert_eq!(map.entry.unwrap().linear_address(), 0x1027);
+        map.offset(0x0800_0000).unwrap();
+        assert_eq!(map.entry, Some(EntryPoint::Linear { address: 0x0800_1027 }));
+        assert_eq!(map.min_address(), Some(0x0800_1023));
+
+        // 越界时保持原映射不变
+        assert!(map.offset(-0x1000_0000).is_err());
+        assert_eq!(map.min_address(), Some(0x0800_1023));
+        assert_eq!(map.entry, Some(EntryPoint::Linear { address: 0x0800_1027 }));
+    }
+
+    #[test]
+    fn srec_entry_selects_address_width() {
+        let mut map = MemoryMap::new();
+        map.write(0x0100, &[1, 2, 3, 4], false).unwrap();
+        map.entry = Some(EntryPoint::Linear { address: 0x0002_0000 });
+
+        let auto = write_srec(&map, 16, None).unwrap();
+        assert!(auto.lines().any(|l| l.starts_with("S804020000")));
+        assert_eq!(parse_srec(&auto).unwrap().entry, map.entry);
+        assert!(write_srec(&map, 16, Some(SrecType::S19)).is_err());
+
+        map.entry = Some(EntryPoint::Linear { address: 0x0100 });
+        assert!(write_srec(&map, 16, Some(SrecType::S19)).unwrap().contains("\nS9030100FB\n"));
+    }
+}
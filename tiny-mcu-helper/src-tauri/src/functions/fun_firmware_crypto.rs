@@ -0,0 +1,523 @@
+use aes::cipher::block_padding::NoPadding;
+use aes::cipher::{BlockDecryptMut, BlockEncrypt, BlockEncryptMut, KeyInit, KeyIvInit, StreamCipher};
+use aes::{Aes128, Aes256};
+use serde::{Deserialize, Serialize, Serializer};
+use std::path::Path;
+use zeroize::Zeroizing;
+
+use crate::functions::fun_checksum::{crc_compute, crc_find_preset};
+use crate::functions::fun_firmware::{save_firmware, FirmwareInfo, FirmwareInput, FirmwareOutput, MemoryMap};
+use crate::utils::{util_get_generate_path, util_parse_hex_str, util_random_bytes, util_to_hex_str};
+
+// 加密镜像格式（版本 1），头部 48 字节，多字节字段均为小端，其后紧跟密文：
+// | 偏移 | 长度 | 内容                                             |
+// | 0    | 4    | 魔数 "TENC"                                      |
+// | 4    | 1    | 版本 1                                           |
+// | 5    | 1    | 密钥长度（字节）：16 = AES-128，32 = AES-256     |
+// | 6    | 1    | 模式：1 = CTR，2 = CBC                           |
+// | 7    | 1    | 填充：0 = 无，1 = PKCS7，2 = 补 0                |
+// | 8    | 4    | 镜像加载地址                                     |
+// | 12   | 4    | 明文长度（不含填充）                             |
+// | 16   | 4    | 明文 CRC-32/ISO-HDLC（不含填充）                 |
+// | 20   | 4    | 密文 CRC-32/ISO-HDLC                             |
+// | 24   | 16   | IV（CTR 模式为初始计数块，128 位大端递增）       |
+// | 40   | 4    | 保留，填 0                                       |
+// | 44   | 4    | 头部 0..44 字节的 CRC-32/ISO-HDLC                |
+// Bootloader 可先校验头部与密文 CRC 再解密，解密后用明文 CRC 确认密钥正确
+const ENCRYPT_MAGIC: &[u8; 4] = b"TENC";
+const ENCRYPT_VERSION: u8 = 1;
+pub const ENCRYPT_HEADER_LEN: usize = 48;
+const ENCRYPT_HEADER_CRC_OFFSET: usize = 44;
+const AES_BLOCK_LEN: usize = 16;
+
+/// 分组模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FirmwareCipherMode {
+    Ctr,
+    Cbc,
+}
+
+/// 填充方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FirmwarePadding {
+    #[default]
+    None, // 不填充，CBC 模式要求长度为 16 的整数倍
+    Pkcs7,
+    Zero, // 补 0 到 16 的整数倍，解密时按头部记录的长度截断
+}
+
+impl FirmwareCipherMode {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Ctr => 1,
+            Self::Cbc => 2,
+        }
+    }
+
+    fn from_byte(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::Ctr),
+            2 => Some(Self::Cbc),
+            _ => None,
+        }
+    }
+}
+
+impl FirmwarePadding {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Pkcs7 => 1,
+            Self::Zero => 2,
+        }
+    }
+
+    fn from_byte(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Pkcs7),
+            2 => Some(Self::Zero),
+            _ => None,
+        }
+    }
+}
+
+fn ser_hex<S: Serializer>(value: &[u8; AES_BLOCK_LEN], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&util_to_hex_str(value))
+}
+
+/// 加密镜像头部
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FirmwareEncryptHeader {
+    pub key_bits: u16,
+    pub mode: FirmwareCipherMode,
+    pub padding: FirmwarePadding,
+    pub address: u32,
+    pub length: u32,
+    pub crc32: u32,
+    pub payload_crc32: u32,
+    #[serde(serialize_with = "ser_hex")]
+    pub iv: [u8; AES_BLOCK_LEN],
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let params = crc_find_preset("CRC-32/ISO-HDLC").unwrap().params;
+    crc_compute(params, data).unwrap_or(0) as u32
+}
+
+impl FirmwareEncryptHeader {
+    pub fn to_bytes(&self) -> [u8; ENCRYPT_HEADER_LEN] {
+        let mut out = [0u8; ENCRYPT_HEADER_LEN];
+        out[0..4].copy_from_slice(ENCRYPT_MAGIC);
+        out[4] = ENCRYPT_VERSION;
+        out[5] = (self.key_bits / 8) as u8;
+        out[6] = self.mode.to_byte();
+        out[7] = self.padding.to_byte();
+        out[8..12].copy_from_slice(&self.address.to_le_bytes());
+        out[12..16].copy_from_slice(&self.length.to_le_bytes());
+        out[16..20].copy_from_slice(&self.crc32.to_le_bytes());
+        out[20..24].copy_from_slice(&self.payload_crc32.to_le_bytes());
+        out[24..40].copy_from_slice(&self.iv);
+        let crc = crc32(&out[..ENCRYPT_HEADER_CRC_OFFSET]);
+        out[ENCRYPT_HEADER_CRC_OFFSET..].copy_from_slice(&crc.to_le_bytes());
+        out
+    }
+
+    /// 解析头部，校验魔数、版本与头部 CRC
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < ENCRYPT_HEADER_LEN || !bytes.starts_with(ENCRYPT_MAGIC) {
+            return Err("不是有效的加密固件".to_string());
+        }
+        if bytes[4] != ENCRYPT_VERSION {
+            return Err(format!("不支持的加密固件版本: {}", bytes[4]));
+        }
+        let u32_at = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        if u32_at(ENCRYPT_HEADER_CRC_OFFSET) != crc32(&bytes[..ENCRYPT_HEADER_CRC_OFFSET]) {
+            return Err("加密固件头部 CRC 校验失败".to_string());
+        }
+        let key_bits = match bytes[5] {
+            16 | 32 => bytes[5] as u16 * 8,
+            other => return Err(format!("不支持的密钥长度: {} 字节", other)),
+        };
+        Ok(Self {
+            key_bits,
+            mode: FirmwareCipherMode::from_byte(bytes[6]).ok_or(format!("未知的加密模式: {}", bytes[6]))?,
+            padding: FirmwarePadding::from_byte(bytes[7]).ok_or(format!("未知的填充方式: {}", bytes[7]))?,
+            address: u32_at(8),
+            length: u32_at(12),
+            crc32: u32_at(16),
+            payload_crc32: u32_at(20),
+            iv: bytes[24..40].try_into().unwrap(),
+        })
+    }
+}
+
+// ==================== AES ====================
+
+// 校验 AES 密钥长度
+fn check_key(key: &[u8]) -> Result<(), String> {
+    match key.len() {
+        16 | 32 => Ok(()),
+        len => Err(format!("AES 密钥长度应为 16 或 32 字节，当前为 {} 字节", len)),
+    }
+}
+
+// 原地加密 / 解密，buf 长度在 CBC 模式下必须为 16 的整数倍
+fn apply_cipher(
+    key: &[u8],
+    iv: &[u8; AES_BLOCK_LEN],
+    mode: FirmwareCipherMode,
+    encrypt: bool,
+    buf: &mut [u8],
+) -> Result<(), String> {
+    check_key(key)?;
+    let len = buf.len();
+    let result = match (mode, key.len(), encrypt) {
+        (FirmwareCipherMode::Ctr, 16, _) => {
+            ctr::Ctr128BE::<Aes128>::new(key.into(), iv.into()).apply_keystream(buf);
+            Ok(())
+        }
+        (FirmwareCipherMode::Ctr, _, _) => {
+            ctr::Ctr128BE::<Aes256>::new(key.into(), iv.into()).apply_keystream(buf);
+            Ok(())
+        }
+        (FirmwareCipherMode::Cbc, 16, true) => cbc::Encryptor::<Aes128>::new(key.into(), iv.into())
+            .encrypt_padded_mut::<NoPadding>(buf, len)
+            .map(|_| ())
+            .map_err(|_| ()),
+        (FirmwareCipherMode::Cbc, _, true) => cbc::Encryptor::<Aes256>::new(key.into(), iv.into())
+            .encrypt_padded_mut::<NoPadding>(buf, len)
+            .map(|_| ())
+            .map_err(|_| ()),
+        (FirmwareCipherMode::Cbc, 16, false) => cbc::Decryptor::<Aes128>::new(key.into(), iv.into())
+            .decrypt_padded_mut::<NoPadding>(buf)
+            .map(|_| ())
+            .map_err(|_| ()),
+        (FirmwareCipherMode::Cbc, _, false) => cbc::Decryptor::<Aes256>::new(key.into(), iv.into())
+            .decrypt_padded_mut::<NoPadding>(buf)
+            .map(|_| ())
+            .map_err(|_| ()),
+    };
+    result.map_err(|_| "CBC 模式数据长度需为 16 的整数倍".to_string())
+}
+
+/// 密钥校验值（KCV）：AES 加密全 0 块后取前 3 字节，用于核对两端密钥一致而不泄露密钥
+pub fn firmware_key_check_value(key: &[u8]) -> Result<String, String> {
+    check_key(key)?;
+    let mut block = [0u8; AES_BLOCK_LEN].into();
+    if key.len() == 16 {
+        Aes128::new(key.into()).encrypt_block(&mut block);
+    } else {
+        Aes256::new(key.into()).encrypt_block(&mut block);
+    }
+    Ok(util_to_hex_str(&block[..3]))
+}
+
+/// 生成随机 AES 密钥（128 或 256 位）
+pub fn firmware_key_generate_bytes(key_bits: u16) -> Result<Zeroizing<Vec<u8>>, String> {
+    if key_bits != 128 && key_bits != 256 {
+        return Err(format!("不支持的密钥长度: {} 位", key_bits));
+    }
+    let mut key = Zeroizing::new(vec![0u8; key_bits as usize / 8]);
+    util_random_bytes(&mut key).map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// 加密镜像，返回头部 + 密文
+pub fn firmware_encrypt_image(
+    key: &[u8],
+    mode: FirmwareCipherMode,
+    padding: FirmwarePadding,
+    iv: [u8; AES_BLOCK_LEN],
+    address: u32,
+    plain: &[u8],
+) -> Result<(FirmwareEncryptHeader, Vec<u8>), String> {
+    check_key(key)?;
+    let length = u32::try_from(plain.len()).map_err(|_| "固件超过 4 GiB".to_string())?;
+    let mut payload = plain.to_vec();
+    match padding {
+        FirmwarePadding::None => {
+            if mode == FirmwareCipherMode::Cbc && !payload.len().is_multiple_of(AES_BLOCK_LEN) {
+                return Err("CBC 模式数据长度需为 16 的整数倍，请选择填充方式".to_string());
+            }
+        }
+        FirmwarePadding::Pkcs7 => {
+            let pad = AES_BLOCK_LEN - payload.len() % AES_BLOCK_LEN;
+            payload.resize(payload.len() + pad, pad as u8);
+        }
+        FirmwarePadding::Zero => payload.resize(payload.len().next_multiple_of(AES_BLOCK_LEN), 0),
+    }
+    apply_cipher(key, &iv, mode, true, &mut payload)?;
+
+    let header = FirmwareEncryptHeader {
+        key_bits: key.len() as u16 * 8,
+        mode,
+        padding,
+        address,
+        length,
+        crc32: crc32(plain),
+        payload_crc32: crc32(&payload),
+        iv,
+    };
+    let mut out = Vec::with_capacity(ENCRYPT_HEADER_LEN + payload.len());
+    out.extend_from_slice(&header.to_bytes());
+    out.extend_from_slice(&payload);
+    Ok((header, out))
+}
+
+/// 解密镜像并校验 CRC，返回头部与明文
+pub fn firmware_decrypt_image(key: &[u8], data: &[u8]) -> Result<(FirmwareEncryptHeader, Vec<u8>), String> {
+    let header = FirmwareEncryptHeader::parse(data)?;
+    if key.len() * 8 != header.key_bits as usize {
+        return Err(format!("固件使用 AES-{} 加密，密钥长度不匹配", header.key_bits));
+    }
+    let payload = &data[ENCRYPT_HEADER_LEN..];
+    if crc32(payload) != header.payload_crc32 {
+        return Err("密文 CRC 校验失败，文件不完整或已损坏".to_string());
+    }
+
+    let mut plain = payload.to_vec();
+    apply_cipher(key, &header.iv, header.mode, false, &mut plain)?;
+    if header.padding == FirmwarePadding::Pkcs7 {
+        let pad = plain.last().copied().unwrap_or(0) as usize;
+        let valid = (1..=AES_BLOCK_LEN).contains(&pad)
+            && pad <= plain.len()
+            && plain[plain.len() - pad..].iter().all(|b| *b as usize == pad);
+        if !valid {
+            return Err("PKCS7 填充无效，密钥可能不正确".to_string());
+        }
+        plain.truncate(plain.len() - pad);
+    }
+    if plain.len() < header.length as usize {
+        return Err("密文长度小于头部记录的明文长度".to_string());
+    }
+    plain.truncate(header.length as usize);
+    if crc32(&plain) != header.crc32 {
+        return Err("解密后 CRC 校验失败，密钥可能不正确".to_string());
+    }
+    Ok((header, plain))
+}
+
+// ==================== 固件加密 / 解密 ====================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirmwareEncryptRequest {
+    pub input: FirmwareInput,
+    pub range_start: Option<u32>, // 默认固件最低地址
+    pub range_end: Option<u64>,   // 默认固件最高地址（不包含）
+    pub pad_byte: Option<u8>,     // 范围内空洞的填充值，默认 0xFF
+    pub mode: FirmwareCipherMode,
+    #[serde(default)]
+    pub padding: FirmwarePadding,
+    pub iv: Option<String>, // 十六进制 16 字节，为空时随机生成
+    pub file_name: String,  // 未带扩展名时补 .bin
+}
+
+#[derive(Debug, Serialize)]
+pub struct FirmwareEncryptResult {
+    pub output_path: String,
+    pub header: FirmwareEncryptHeader,
+    pub output_size: usize,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirmwareDecryptRequest {
+    pub path: String,
+    pub output: FirmwareOutput,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FirmwareDecryptResult {
+    pub output_path: String,
+    pub header: FirmwareEncryptHeader,
+    pub info: FirmwareInfo,
+}
+
+// 指定 IV 时的提示：同一密钥下重复使用 IV，CTR 会产生相同密钥流（两份密文异或即得明文异或），
+// CBC 会使相同的明文前缀得到相同的密文
+fn fixed_iv_warning(mode: FirmwareCipherMode) -> &'static str {
+    match mode {
+        FirmwareCipherMode::Ctr => "CTR 模式使用了指定的 IV：同一密钥下 IV 绝不能重复，否则可由两份密文推出明文，建议留空随机生成",
+        FirmwareCipherMode::Cbc => "CBC 模式使用了指定的 IV：同一密钥下重复使用 IV 时，相同的明文前缀会得到相同的密文，建议留空随机生成",
+    }
+}
+
+/// 加密固件并输出到 generate 目录
+pub fn firmware_encrypt_with(key: &[u8], request: &FirmwareEncryptRequest) -> Result<FirmwareEncryptResult, String> {
+    let mut warnings = Vec::new();
+    let iv: [u8; AES_BLOCK_LEN] = match request.iv.as_deref().filter(|iv| !iv.trim().is_empty()) {
+        Some(iv) => util_parse_hex_str(iv)?
+            .try_into()
+            .map_err(|_| "IV 长度应为 16 字节".to_string())?,
+        None => {
+            let mut iv = [0u8; AES_BLOCK_LEN];
+            util_random_bytes(&mut iv).map_err(|e| e.to_string())?;
+            iv
+        }
+    };
+    let (_, map) = request.input.load()?;
+    let start = request.range_start.or(map.min_address()).ok_or("固件内容为空")?;
+    let end = request.range_end.or(map.max_address()).unwrap_or(start as u64);
+    if end <= start as u64 {
+        return Err(format!("加密范围无效: 0x{:08X} - 0x{:08X}", start, end));
+    }
+    let plain = map.read_range(start, end, request.pad_byte.unwrap_or(0xFF))?;
+    let (header, bytes) = firmware_encrypt_image(key, request.mode, request.padding, iv, start, &plain)?;
+    if request.iv.as_deref().is_some_and(|iv| !iv.trim().is_empty()) {
+        warnings.push(fixed_iv_warning(request.mode).to_string());
+    }
+
+    let file_name = if Path::new(&request.file_name).extension().is_some() {
+        request.file_name.clone()
+    } else {
+        format!("{}.bin", request.file_name)
+    };
+    let file_path = util_get_generate_path()?.join(file_name);
+    std::fs::write(&file_path, &bytes).map_err(|e| format!("写入文件失败: {}", e))?;
+    Ok(FirmwareEncryptResult {
+        output_path: file_path.to_string_lossy().to_string(),
+        header,
+        output_size: bytes.len(),
+        warnings,
+    })
+}
+
+/// 解密固件，按头部记录的加载地址输出到 generate 目录
+pub fn firmware_decrypt_with(key: &[u8], request: &FirmwareDecryptRequest) -> Result<FirmwareDecryptResult, String> {
+    let data = std::fs::read(Path::new(&request.path)).map_err(|e| format!("读取文件失败 {}: {}", request.path, e))?;
+    let (header, plain) = firmware_decrypt_image(key, &data)?;
+    let mut map = MemoryMap::new();
+    map.write(header.address, &plain, false)?;
+    let output_path = save_firmware(&map, &request.output)?;
+    Ok(FirmwareDecryptResult {
+        output_path,
+        header,
+        info: FirmwareInfo::from_map(Some(request.output.format), &map),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        util_parse_hex_str(s).unwrap()
+    }
+
+    const NIST_KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+    const NIST_PLAIN: &str = "6bc1bee22e409f96e93d7e117393172a";
+
+    // NIST SP 800-38A F.5.1 / F.2.1 第一个分组
+    #[test]
+    fn aes_known_answers() {
+        let key = hex(NIST_KEY);
+        let mut buf = hex(NIST_PLAIN);
+        let iv: [u8; 16] = hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").try_into().unwrap();
+        apply_cipher(&key, &iv, FirmwareCipherMode::Ctr, true, &mut buf).unwrap();
+        assert_eq!(buf, hex("874d6191b620e3261bef6864990db6ce"));
+
+        let mut buf = hex(NIST_PLAIN);
+        let iv: [u8; 16] = hex("000102030405060708090a0b0c0d0e0f").try_into().unwrap();
+        apply_cipher(&key, &iv, FirmwareCipherMode::Cbc, true, &mut buf).unwrap();
+        assert_eq!(buf, hex("7649abac8119b246cee98e9b12e9197d"));
+
+        // AES-128 全 0 密钥加密全 0 块为 66E94BD4...
+        assert_eq!(firmware_key_check_value(&[0u8; 16]).unwrap(), "66 E9 4B");
+    }
+
+    #[test]
+    fn header_layout() {
+        let header = FirmwareEncryptHeader {
+            key_bits: 256,
+            mode: FirmwareCipherMode::Cbc,
+            padding: FirmwarePadding::Pkcs7,
+            address: 0x0800_8000,
+            length: 0x0102_0304,
+            crc32: 0x1122_3344,
+            payload_crc32: 0x5566_7788,
+            iv: [0xA5; 16],
+        };
+        let bytes = header.to_bytes();
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(&bytes[0..8], b"TENC\x01\x20\x02\x01");
+        assert_eq!(&bytes[8..24], &hex("00800008 04030201 44332211 88776655")[..]);
+        assert_eq!(&bytes[24..40], &[0xA5; 16]);
+        assert_eq!(&bytes[40..44], &[0; 4]);
+        assert_eq!(bytes[44..48], crc32(&bytes[..44]).to_le_bytes());
+        assert_eq!(FirmwareEncryptHeader::parse(&bytes).unwrap(), header);
+
+        let mut tampered = bytes;
+        tampered[12] ^= 1;
+        assert_eq!(FirmwareEncryptHeader::parse(&tampered), Err("加密固件头部 CRC 校验失败".to_string()));
+        assert!(FirmwareEncryptHeader::parse(&bytes[..47]).is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let plain: Vec<u8> = (0..61u8).collect();
+        let block_plain: Vec<u8> = (0..64u8).collect();
+        let iv = [0x3C; 16];
+        for key_len in [16, 32] {
+            let key = vec![0x42; key_len];
+            for mode in [FirmwareCipherMode::Ctr, FirmwareCipherMode::Cbc] {
+                for padding in [FirmwarePadding::None, FirmwarePadding::Pkcs7, FirmwarePadding::Zero] {
+                    let case = format!("AES-{} {:?} {:?}", key_len * 8, mode, padding);
+                    let plain = if mode == FirmwareCipherMode::Cbc && padding == FirmwarePadding::None {
+                        assert!(firmware_encrypt_image(&key, mode, padding, iv, 0, &plain).is_err(), "{}", case);
+                        &block_plain
+                    } else {
+                        &plain
+                    };
+                    let (header, image) = firmware_encrypt_image(&key, mode, padding, iv, 0x0800_0000, plain).unwrap();
+                    let payload_len = match (mode, padding) {
+                        (_, FirmwarePadding::Pkcs7) => 64,
+                        (FirmwareCipherMode::Ctr, FirmwarePadding::None) => plain.len(),
+                        _ => plain.len().next_multiple_of(16),
+                    };
+                    assert_eq!(image.len(), ENCRYPT_HEADER_LEN + payload_len, "{}", case);
+                    assert_ne!(&image[ENCRYPT_HEADER_LEN..][..16], &plain[..16], "{}", case);
+                    assert_eq!(header.length as usize, plain.len());
+
+                    let (decoded, decrypted) = firmware_decrypt_image(&key, &image).unwrap();
+                    assert_eq!(decoded, header, "{}", case);
+                    assert_eq!(&decrypted, plain, "{}", case);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_tampered_or_wrong_key() {
+        let key = [0x42; 16];
+        let plain: Vec<u8> = (0..100u8).collect();
+        for (mode, padding) in [
+            (FirmwareCipherMode::Ctr, FirmwarePadding::None),
+            (FirmwareCipherMode::Cbc, FirmwarePadding::Pkcs7),
+            (FirmwareCipherMode::Cbc, FirmwarePadding::Zero),
+        ] {
+            let (_, image) = firmware_encrypt_image(&key, mode, padding, [0; 16], 0, &plain).unwrap();
+
+            let mut tampered = image.clone();
+            tampered[8] ^= 1;
+            assert_eq!(firmware_decrypt_image(&key, &tampered).unwrap_err(), "加密固件头部 CRC 校验失败");
+
+            let mut tampered = image.clone();
+            tampered[ENCRYPT_HEADER_LEN + 5] ^= 1;
+            assert!(firmware_decrypt_image(&key, &tampered).unwrap_err().starts_with("密文 CRC 校验失败"));
+
+            let error = firmware_decrypt_image(&[0x43; 16], &image).unwrap_err();
+            assert!(error.contains("密钥可能不正确"), "{:?}: {}", mode, error);
+            assert!(firmware_decrypt_image(&[0x42; 32], &image).unwrap_err().contains("密钥长度不匹配"));
+        }
+        assert!(firmware_encrypt_image(&[0; 24], FirmwareCipherMode::Ctr, FirmwarePadding::None, [0; 16], 0, &plain).is_err());
+    }
+
+    #[test]
+    fn fixed_iv_warns_in_both_modes() {
+        assert!(fixed_iv_warning(FirmwareCipherMode::Ctr).starts_with("CTR"));
+        assert!(fixed_iv_warning(FirmwareCipherMode::Cbc).starts_with("CBC"));
+    }
+}
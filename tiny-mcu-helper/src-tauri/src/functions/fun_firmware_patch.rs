@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+
+use crate::functions::fun_checksum::{crc_compute, crc_find_preset, CrcParams};
+use crate::functions::fun_firmware::{
+    load_firmware_inputs, save_firmware, FirmwareInfo, FirmwareInput, FirmwareOutput, MemoryMap,
+};
+
+/// 字节序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Endian {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Endian {
+    // 将 value 的低 size 个字节按字节序输出
+    pub fn encode(&self, value: u64, size: usize) -> Vec<u8> {
+        let bytes = value.to_le_bytes();
+        let mut out = bytes[..size.min(8)].to_vec();
+        if *self == Endian::Big {
+            out.reverse();
+        }
+        out
+    }
+}
+
+/// 校验算法
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    Crc {
+        preset: Option<String>, // 默认 CRC-32/ISO-HDLC
+        params: Option<CrcParams>,
+    },
+    Sum { width: u8 }, // 8 / 16 / 32 位累加和（逐字节累加）
+    Xor,               // 8 位异或和
+}
+
+/// 校验值写入配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChecksumPatch {
+    pub algorithm: ChecksumAlgorithm,
+    pub range_start: Option<u32>, // 默认固件最低地址（含长度、版本字段）
+    pub range_end: Option<u64>,   // 默认固件最高地址（不包含，含长度、版本字段）
+    pub location: Option<u32>,    // 默认紧跟在 range_end 之后
+    pub pad_byte: Option<u8>,     // 计算范围内空洞的填充值，默认 0xFF（Flash 擦除值）
+}
+
+/// 镜像长度写入配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct LengthPatch {
+    pub location: u32,
+    pub size: Option<usize>, // 2 或 4 字节，默认 4
+    pub value: Option<u32>,  // 默认使用校验范围长度（未配置校验时为整个镜像长度）
+}
+
+/// 版本结构体字段
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VersionField {
+    pub name: String, // 例如 major / minor / patch / build
+    pub value: u32,
+    pub size: usize, // 1 / 2 / 4 字节
+}
+
+/// 版本结构体写入配置（各字段按顺序紧密排列）
+#[derive(Debug, Clone, Deserialize)]
+pub struct VersionPatch {
+    pub location: u32,
+    pub fields: Vec<VersionField>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirmwarePatchConfig {
+    pub checksum: Option<ChecksumPatch>,
+    pub length: Option<LengthPatch>,
+    pub version: Option<VersionPatch>,
+    #[serde(default)]
+    pub endian: Endian,
+    #[serde(default)]
+    pub overwrite: bool, // 写入位置已有数据时是否允许覆盖
+}
+
+/// 补丁执行摘要
+#[derive(Debug, Serialize)]
+pub struct FirmwarePatchSummary {
+    pub checksum: Option<String>, // 按校验宽度补零的十六进制
+    pub checksum_location: Option<u32>,
+    pub range_start: u32,
+    pub range_end: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FirmwarePatchResult {
+    pub output_path: String,
+    #[serde(flatten)]
+    pub summary: FirmwarePatchSummary,
+    pub info: FirmwareInfo,
+}
+
+/// 计算校验值，返回 (值, 字节数)
+pub fn compute_checksum(algorithm: &ChecksumAlgorithm, data: &[u8]) -> Result<(u64, usize), String> {
+    match algorithm {
+        ChecksumAlgorithm::Crc { preset, params } => {
+            let params = match (preset, params) {
+                (Some(name), _) => crc_find_preset(name).ok_or(format!("未知的 CRC 预设: {}", name))?.params,
+                (None, Some(params)) => *params,
+                (None, None) => crc_find_preset("CRC-32/ISO-HDLC").unwrap().params,
+            };
+            let value = crc_compute(params, data)?;
+            Ok((value, (params.width as usize).div_ceil(8)))
+        }
+        ChecksumAlgorithm::Sum { width } => {
+            let sum = data.iter().fold(0u64, |acc, b| acc.wrapping_add(*b as u64));
+            match width {
+                8 => Ok((sum & 0xFF, 1)),
+                16 => Ok((sum & 0xFFFF, 2)),
+                32 => Ok((sum & 0xFFFF_FFFF, 4)),
+                _ => Err(format!("不支持的累加和位宽: {}", width)),
+            }
+        }
+        ChecksumAlgorithm::Xor => Ok((data.iter().fold(0u8, |acc, b| acc ^ b) as u64, 1)),
+    }
+}
+
+// 写入补丁字段，默认不允许覆盖已有数据
+fn write_patch(map: &mut MemoryMap, location: u32, bytes: &[u8], overwrite: bool, what: &str) -> Result<(), String> {
+    map.write(location, bytes, overwrite)
+        .map_err(|e| format!("写入{}失败: {}", what, e))
+}
+
+/// 对内存映射执行补丁：先写长度与版本，再计算并写入校验值；
+/// 默认校验范围覆盖镜像及长度、版本字段，使校验覆盖前两者
+pub fn apply_firmware_patch(
+    map: &mut MemoryMap,
+    config: &FirmwarePatchConfig,
+) -> Result<FirmwarePatchSummary, String> {
+    let image_start = map.min_address().ok_or("固件内容为空")?;
+    let image_end = map.max_address().unwrap_or(image_start as u64);
+
+    let length_size = match &config.length {
+        Some(length) => {
+            let size = length.size.unwrap_or(4);
+            if size != 2 && size != 4 {
+                return Err(format!("长度字段只能为 2 或 4 字节: {}", size));
+            }
+            size
+        }
+        None => 0,
+    };
+    let mut version_bytes = Vec::new();
+    if let Some(version) = &config.version {
+        for field in &version.fields {
+            if ![1, 2, 4].contains(&field.size) {
+                return Err(format!("版本字段 {} 的长度只能为 1/2/4 字节", field.name));
+            }
+            if field.size < 4 && field.value as u64 >= 1u64 << (field.size * 8) {
+                return Err(format!("版本字段 {} 的值 {} 超出范围", field.name, field.value));
+            }
+            version_bytes.extend(config.endian.encode(field.value as u64, field.size));
+        }
+    }
+
+    let (range_start, range_end) = match &config.checksum {
+        Some(patch) => {
+            // 长度、版本字段写入后固件的地址范围
+            let mut start = image_start as u64;
+            let mut end = image_end;
+            let fields = [
+                config.length.as_ref().map(|l| (l.location, length_size)),
+                config.version.as_ref().map(|v| (v.location, version_bytes.len())),
+            ];
+            for (location, size) in fields.into_iter().flatten().filter(|(_, size)| *size > 0) {
+                start = start.min(location as u64);
+                end = end.max(location as u64 + size as u64);
+            }
+            (patch.range_start.unwrap_or(start as u32), patch.range_end.unwrap_or(end))
+        }
+        None => (image_start, image_end),
+    };
+    if range_end <= range_start as u64 {
+        return Err(format!(
+            "校验范围无效: 0x{:08X} - 0x{:08X}",
+            range_start, range_end
+        ));
+    }
+
+    if let Some(length) = &config.length {
+        let value = length
+            .value
+            .map(|v| v as u64)
+            .unwrap_or(range_end - range_start as u64);
+        if length_size == 2 && value > 0xFFFF {
+            return Err(format!("镜像长度 {} 超出 16 位长度字段", value));
+        }
+        let bytes = config.endian.encode(value, length_size);
+        write_patch(map, length.location, &bytes, config.overwrite, "长度字段")?;
+    }
+
+    if let Some(version) = &config.version {
+        write_patch(map, version.location, &version_bytes, config.overwrite, "版本信息")?;
+    }
+
+    let mut checksum = None;
+    let mut checksum_location = None;
+    if let Some(patch) = &config.checksum {
+        let data = map.read_range(range_start, range_end, patch.pad_byte.unwrap_or(0xFF))?;
+        let (value, size) = compute_checksum(&patch.algorithm, &data)?;
+
+        let location = match patch.location {
+            Some(location) => location,
+            None if range_end <= u32::MAX as u64 => range_end as u32,
+            None => return Err("校验范围已到地址空间末尾，请指定写入位置".to_string()),
+        };
+        let location_end = location as u64 + size as u64;
+        if (location as u64) < range_end && location_end > range_start as u64 {
+            return Err(format!("校验值写入位置 0x{:08X} 位于校验范围内", location));
+        }
+
+        let bytes = config.endian.encode(value, size);
+        write_patch(map, location, &bytes, config.overwrite, "校验值")?;
+        checksum = Some(format!("{:0width$X}", value, width = size * 2));
+        checksum_location = Some(location);
+    }
+
+    Ok(FirmwarePatchSummary {
+        checksum,
+        checksum_location,
+        range_start,
+        range_end,
+    })
+}
+
+// 向固件写入校验值 / 镜像长度 / 版本信息，并输出到 generate 目录
+#[tauri::command]
+pub fn firmware_patch(
+    inputs: Vec<FirmwareInput>,
+    config: FirmwarePatchConfig,
+    output: FirmwareOutput,
+) -> Result<FirmwarePatchResult, String> {
+    let mut map = load_firmware_inputs(&inputs, false)?;
+    let summary = apply_firmware_patch(&mut map, &config)?;
+    let output_path = save_firmware(&map, &output)?;
+
+    Ok(FirmwarePatchResult {
+        output_path,
+        summary,
+        info: FirmwareInfo::from_map(Some(output.format), &map),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> MemoryMap {
+        let mut map = MemoryMap::new();
+        map.write(0x0800_0000, b"123456789", false).unwrap();
+        map
+    }
+
+    fn config(json: &str) -> FirmwarePatchConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn checksum_algorithms() {
+        // "123456789" 的 CRC-32 校验值 0xCBF43926，累加和 0x1DD，异或和 0x31
+        let cases = [
+            (r#"{"type":"crc"}"#, "little", "CBF43926", vec![0x26, 0x39, 0xF4, 0xCB]),
+            (r#"{"type":"crc"}"#, "big", "CBF43926", vec![0xCB, 0xF4, 0x39, 0x26]),
+            (r#"{"type":"crc","preset":"CRC-16/XMODEM"}"#, "big", "31C3", vec![0x31, 0xC3]),
+            (r#"{"type":"sum","width":8}"#, "little", "DD", vec![0xDD]),
+            (r#"{"type":"sum","width":16}"#, "little", "01DD", vec![0xDD, 0x01]),
+            (r#"{"type":"sum","width":32}"#, "big", "000001DD", vec![0x00, 0x00, 0x01, 0xDD]),
+            (r#"{"type":"xor"}"#, "little", "31", vec![0x31]),
+        ];
+        for (algorithm, endian, checksum, bytes) in cases {
+            let mut map = image();
+            let config = config(&format!(r#"{{"checksum":{{"algorithm":{}}},"endian":"{}"}}"#, algorithm, endian));
+            let summary = apply_firmware_patch(&mut map, &config).unwrap();
+            assert_eq!(summary.checksum.as_deref(), Some(checksum), "{}", algorithm);
+            assert_eq!(summary.checksum_location, Some(0x0800_0009));
+            assert_eq!(map.read(0x0800_0009, bytes.len(), 0), bytes, "{} {}", algorithm, endian);
+        }
+        assert!(apply_firmware_patch(&mut image(), &config(r#"{"checksum":{"algorithm":{"type":"sum","width":24}}}"#)).is_err());
+    }
+
+    #[test]
+    fn checksum_covers_header_fields() {
+        // 长度字段位于镜像末尾，默认校验范围包含长度与版本字段，校验值写在其后
+        let mut map = image();
+        let config = config(
+            r#"{"checksum":{"algorithm":{"type":"sum","width":8}},"length":{"location":134217737,"size":2},
+                "version":{"location":134217739,"fields":[{"name":"major","value":1,"size":1},{"name":"minor","value":2,"size":1}]},
+                "endian":"big"}"#,
+        );
+        let summary = apply_firmware_patch(&mut map, &config).unwrap();
+        assert_eq!((summary.range_start, summary.range_end), (0x0800_0000, 0x0800_000D));
+        assert_eq!(map.read(0x0800_0009, 4, 0), vec![0x00, 0x0D, 0x01, 0x02]);
+        let sum = map.read(0x0800_0000, 13, 0).iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+        assert_eq!(summary.checksum_location, Some(0x0800_000D));
+        assert_eq!(map.read(0x0800_000D, 1, 0), vec![sum]);
+    }
+
+    #[test]
+    fn rejects_location_inside_range() {
+        let config = config(r#"{"checksum":{"algorithm":{"type":"crc"},"location":134217734}}"#);
+        let error = apply_firmware_patch(&mut image(), &config).unwrap_err();
+        assert!(error.contains("位于校验范围内"), "{}", error);
+        // 紧贴范围起点之前写入会跨入范围
+        let config = self::config(r#"{"checksum":{"algorithm":{"type":"crc"},"location":134217726}}"#);
+        assert!(apply_firmware_patch(&mut image(), &config).is_err());
+        let config = self::config(r#"{"checksum":{"algorithm":{"type":"crc"},"location":134217724}}"#);
+        assert!(apply_firmware_patch(&mut image(), &config).is_ok());
+    }
+
+    #[test]
+    fn length_field_overflow() {
+        let mut map = MemoryMap::new();
+        map.write(0x0800_0000, &vec![0xAA; 0x10000], false).unwrap();
+        let two_bytes = config(r#"{"length":{"location":134283264,"size":2}}"#);
+        assert!(apply_firmware_patch(&mut map.clone(), &two_bytes).unwrap_err().contains("16 位"));
+        let explicit = config(r#"{"length":{"location":134283264,"size":2,"value":65535}}"#);
+        assert!(apply_firmware_patch(&mut map.clone(), &explicit).is_ok());
+        let four_bytes = config(r#"{"length":{"location":134283264}}"#);
+        apply_firmware_patch(&mut map, &four_bytes).unwrap();
+        assert_eq!(map.read(0x0801_0000, 4, 0), vec![0x00, 0x00, 0x01, 0x00]);
+        assert!(apply_firmware_patch(&mut image(), &config(r#"{"length":{"location":0,"size":3}}"#)).is_err());
+    }
+}
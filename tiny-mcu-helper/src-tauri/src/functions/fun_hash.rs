@@ -0,0 +1,335 @@
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::digest::Digest;
+use hmac::{Mac, SimpleHmac};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+use crate::utils::util_parse_input_bytes;
+
+// 读取文件的分块大小
+const HASH_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 摘要算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Blake2b512,
+    Blake2s256,
+    Blake3,
+}
+
+/// 算法说明（供前端列出）
+#[derive(Debug, Clone, Serialize)]
+pub struct HashAlgorithmInfo {
+    pub algorithm: HashAlgorithm,
+    pub name: &'static str,
+    pub output_bits: usize,
+    pub hmac: bool, // 是否支持 HMAC
+}
+
+const HASH_ALGORITHMS: &[HashAlgorithm] = &[
+    HashAlgorithm::Md5,
+    HashAlgorithm::Sha1,
+    HashAlgorithm::Sha224,
+    HashAlgorithm::Sha256,
+    HashAlgorithm::Sha384,
+    HashAlgorithm::Sha512,
+    HashAlgorithm::Sha3_224,
+    HashAlgorithm::Sha3_256,
+    HashAlgorithm::Sha3_384,
+    HashAlgorithm::Sha3_512,
+    HashAlgorithm::Blake2b512,
+    HashAlgorithm::Blake2s256,
+    HashAlgorithm::Blake3,
+];
+
+impl HashAlgorithm {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Sha1 => "SHA-1",
+            Self::Sha224 => "SHA-224",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512",
+            Self::Sha3_224 => "SHA3-224",
+            Self::Sha3_256 => "SHA3-256",
+            Self::Sha3_384 => "SHA3-384",
+            Self::Sha3_512 => "SHA3-512",
+            Self::Blake2b512 => "BLAKE2b-512",
+            Self::Blake2s256 => "BLAKE2s-256",
+            Self::Blake3 => "BLAKE3",
+        }
+    }
+
+    pub fn output_bits(&self) -> usize {
+        match self {
+            Self::Md5 => 128,
+            Self::Sha1 => 160,
+            Self::Sha224 | Self::Sha3_224 => 224,
+            Self::Sha256 | Self::Sha3_256 | Self::Blake2s256 | Self::Blake3 => 256,
+            Self::Sha384 | Self::Sha3_384 => 384,
+            Self::Sha512 | Self::Sha3_512 | Self::Blake2b512 => 512,
+        }
+    }
+
+    // BLAKE3 没有标准 HMAC 构造（自带密钥模式，但要求 32 字节密钥），不提供
+    fn supports_hmac(&self) -> bool {
+        *self != Self::Blake3
+    }
+}
+
+// 流式摘要 / HMAC 的统一接口
+trait StreamHash: Send {
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self: Box<Self>) -> Vec<u8>;
+}
+
+struct DigestHash<D>(D);
+
+impl<D: Digest + Send> StreamHash for DigestHash<D> {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(&mut self.0, data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        self.0.finalize().to_vec()
+    }
+}
+
+impl<D: Digest + BlockSizeUser + Send> StreamHash for SimpleHmac<D> {
+    fn update(&mut self, data: &[u8]) {
+        Mac::update(self, data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        Mac::finalize(*self).into_bytes().to_vec()
+    }
+}
+
+impl StreamHash for blake3::Hasher {
+    fn update(&mut self, data: &[u8]) {
+        blake3::Hasher::update(self, data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        blake3::Hasher::finalize(&self).as_bytes().to_vec()
+    }
+}
+
+fn new_hash<D: Digest + BlockSizeUser + Send + 'static>(key: Option<&[u8]>) -> Box<dyn StreamHash> {
+    match key {
+        // SimpleHmac 接受任意长度的密钥
+        Some(key) => Box::new(<SimpleHmac<D> as Mac>::new_from_slice(key).unwrap()),
+        None => Box::new(DigestHash(D::new())),
+    }
+}
+
+/// 流式摘要计算器，key 不为空时计算 HMAC
+pub struct HashEngine {
+    algorithm: HashAlgorithm,
+    hmac: bool,
+    inner: Box<dyn StreamHash>,
+}
+
+impl HashEngine {
+    pub fn new(algorithm: HashAlgorithm, key: Option<&[u8]>) -> Result<Self, String> {
+        if key.is_some() && !algorithm.supports_hmac() {
+            return Err(format!("{} 不支持 HMAC", algorithm.name()));
+        }
+        let inner = match algorithm {
+            HashAlgorithm::Md5 => new_hash::<md5::Md5>(key),
+            HashAlgorithm::Sha1 => new_hash::<sha1::Sha1>(key),
+            HashAlgorithm::Sha224 => new_hash::<sha2::Sha224>(key),
+            HashAlgorithm::Sha256 => new_hash::<sha2::Sha256>(key),
+            HashAlgorithm::Sha384 => new_hash::<sha2::Sha384>(key),
+            HashAlgorithm::Sha512 => new_hash::<sha2::Sha512>(key),
+            HashAlgorithm::Sha3_224 => new_hash::<sha3::Sha3_224>(key),
+            HashAlgorithm::Sha3_256 => new_hash::<sha3::Sha3_256>(key),
+            HashAlgorithm::Sha3_384 => new_hash::<sha3::Sha3_384>(key),
+            HashAlgorithm::Sha3_512 => new_hash::<sha3::Sha3_512>(key),
+            HashAlgorithm::Blake2b512 => new_hash::<blake2::Blake2b512>(key),
+            HashAlgorithm::Blake2s256 => new_hash::<blake2::Blake2s256>(key),
+            HashAlgorithm::Blake3 => Box::new(blake3::Hasher::new()),
+        };
+        Ok(Self {
+            algorithm,
+            hmac: key.is_some(),
+            inner,
+        })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> HashResult {
+        let digest = self.inner.finalize();
+        HashResult {
+            algorithm: self.algorithm,
+            name: match self.hmac {
+                true => format!("HMAC-{}", self.algorithm.name()),
+                false => self.algorithm.name().to_string(),
+            },
+            hex: digest.iter().map(|b| format!("{:02x}", b)).collect(),
+        }
+    }
+}
+
+/// 计算结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HashResult {
+    pub algorithm: HashAlgorithm,
+    pub name: String, // 例如 SHA-256 / HMAC-SHA-256
+    pub hex: String,  // 小写连续十六进制，与 sha256sum 等工具一致
+}
+
+// 同时创建多个算法的计算器
+fn new_engines(algorithms: &[HashAlgorithm], key: Option<&[u8]>) -> Result<Vec<HashEngine>, String> {
+    if algorithms.is_empty() {
+        return Err("请至少选择一种算法".to_string());
+    }
+    algorithms.iter().map(|a| HashEngine::new(*a, key)).collect()
+}
+
+/// 一次性计算多个算法的摘要
+pub fn hash_compute(algorithms: &[HashAlgorithm], key: Option<&[u8]>, data: &[u8]) -> Result<Vec<HashResult>, String> {
+    let mut engines = new_engines(algorithms, key)?;
+    engines.iter_mut().for_each(|e| e.update(data));
+    Ok(engines.into_iter().map(|e| e.finalize()).collect())
+}
+
+/// 分块读取文件并计算摘要，每读完一块调用 progress(已处理字节, 总字节)，返回 false 时中止
+pub fn hash_compute_file(
+    path: &Path,
+    algorithms: &[HashAlgorithm],
+    key: Option<&[u8]>,
+    mut progress: impl FnMut(u64, u64) -> bool,
+) -> Result<Vec<HashResult>, String> {
+    let mut engines = new_engines(algorithms, key)?;
+    let mut file = std::fs::File::open(path).map_err(|e| format!("打开文件失败 {:?}: {}", path, e))?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut buf = vec![0u8; HASH_CHUNK_SIZE];
+    let mut processed = 0u64;
+    loop {
+        let n = match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("读取文件失败 {:?}: {}", path, e)),
+        };
+        engines.iter_mut().for_each(|e| e.update(&buf[..n]));
+        processed += n as u64;
+        if !progress(processed, total) {
+            return Err("已取消".to_string());
+        }
+    }
+    Ok(engines.into_iter().map(|e| e.finalize()).collect())
+}
+
+/// 解析 HMAC 密钥，为空表示不使用 HMAC
+pub fn hash_parse_key(key: Option<&str>, key_type: Option<&str>) -> Result<Option<Zeroizing<Vec<u8>>>, String> {
+    match key.filter(|k| !k.is_empty()) {
+        Some(key) => Ok(Some(Zeroizing::new(util_parse_input_bytes(key, key_type.unwrap_or("text"))?))),
+        None => Ok(None),
+    }
+}
+
+// 列出支持的摘要算法
+#[tauri::command]
+pub fn hash_list_algorithms() -> Vec<HashAlgorithmInfo> {
+    HASH_ALGORITHMS
+        .iter()
+        .map(|a| HashAlgorithmInfo {
+            algorithm: *a,
+            name: a.name(),
+            output_bits: a.output_bits(),
+            hmac: a.supports_hmac(),
+        })
+        .collect()
+}
+
+// 计算文本或十六进制数据的摘要；hmac_key 不为空时计算 HMAC（key_type 为 "text" 或 "hex"）
+#[tauri::command]
+pub fn hash_calculate(
+    data: &str,
+    input_type: &str,
+    algorithms: Vec<HashAlgorithm>,
+    hmac_key: Option<String>,
+    key_type: Option<String>,
+) -> Result<Vec<HashResult>, String> {
+    let bytes = util_parse_input_bytes(data, input_type)?;
+    let key = hash_parse_key(hmac_key.as_deref(), key_type.as_deref())?;
+    hash_compute(&algorithms, key.as_deref().map(|k| k.as_slice()), &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_of(algorithm: HashAlgorithm, key: Option<&[u8]>, data: &[u8]) -> String {
+        hash_compute(&[algorithm], key, data).unwrap().remove(0).hex
+    }
+
+    #[test]
+    fn known_answers() {
+        let cases = [
+            (HashAlgorithm::Md5, "900150983cd24fb0d6963f7d28e17f72"),
+            (HashAlgorithm::Sha1, "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (HashAlgorithm::Sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            (HashAlgorithm::Sha3_256, "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+        ];
+        for (algorithm, expected) in cases {
+            assert_eq!(hex_of(algorithm, None, b"abc"), expected, "{}", algorithm.name());
+        }
+        assert_eq!(
+            hex_of(HashAlgorithm::Blake3, None, b""),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+    }
+
+    // RFC 4231 测试用例 2 与 6（密钥长于分组长度）
+    #[test]
+    fn hmac_sha256_rfc4231() {
+        let result = hash_compute(&[HashAlgorithm::Sha256], Some(b"Jefe"), b"what do ya want for nothing?").unwrap();
+        assert_eq!(result[0].name, "HMAC-SHA-256");
+        assert_eq!(result[0].hex, "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        assert_eq!(
+            hex_of(HashAlgorithm::Sha256, Some(&[0xAA; 131]), b"Test Using Larger Than Block-Size Key - Hash Key First"),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+        assert!(hash_compute(&[HashAlgorithm::Blake3], Some(b"k"), b"").is_err());
+        assert!(hash_compute(&[], None, b"").is_err());
+    }
+
+    #[test]
+    fn file_matches_memory_and_cancels() {
+        let data: Vec<u8> = (0..HASH_CHUNK_SIZE * 2 + 123).map(|i| (i * 7) as u8).collect();
+        let path = std::env::temp_dir().join(format!("tmh-hash-{}.bin", std::process::id()));
+        std::fs::write(&path, &data).unwrap();
+        let algorithms = [HashAlgorithm::Sha256, HashAlgorithm::Blake3];
+        let mut calls = Vec::new();
+        let results = hash_compute_file(&path, &algorithms, None, |processed, total| {
+            calls.push((processed, total));
+            true
+        })
+        .unwrap();
+        assert_eq!(results, hash_compute(&algorithms, None, &data).unwrap());
+        assert!(calls.len() >= 3);
+        assert_eq!(calls.last(), Some(&(data.len() as u64, data.len() as u64)));
+        assert_eq!(hash_compute_file(&path, &algorithms, None, |_, _| false), Err("已取消".to_string()));
+        std::fs::remove_file(path).unwrap();
+    }
+}
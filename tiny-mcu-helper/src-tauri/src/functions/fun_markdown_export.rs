@@ -0,0 +1,1294 @@
+use base64::Engine;
+use pulldown_cmark::{html, Alignment, Event, Options, Parser, Tag};
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::utils::util_date_today;
+
+// 标题字号相对正文的倍数（h1 ~ h6）
+pub const HEADING_SCALE: [f32; 6] = [1.8, 1.5, 1.3, 1.15, 1.05, 1.0];
+
+/// 纸张尺寸
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkdownPageSize {
+    A4,
+    A5,
+    Letter,
+    Custom, // 使用 page_width_mm / page_height_mm
+}
+
+/// Markdown 导出样式（保存在 plugin_store 中，PDF / HTML / DOCX 共用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkdownStyleProfile {
+    pub name: String,
+    pub font_family: String,            // HTML / DOCX 西文字体名
+    pub cjk_font_family: String,        // HTML / DOCX 中文字体名
+    pub mono_font_family: String,       // HTML / DOCX 代码字体名
+    pub font_path: Option<String>,      // PDF 西文字体文件，为空时全部使用中文字体
+    pub cjk_font_path: Option<String>,  // PDF 中文字体文件，为空时查找系统字体
+    pub bold_font_path: Option<String>, // PDF 粗体字体文件，为空时描边加粗
+    pub mono_font_path: Option<String>, // PDF 代码字体文件，为空时使用正文字体
+    pub font_size: f32,                 // 正文字号（pt）
+    pub code_font_size: f32,            // 代码字号（pt）
+    pub line_height: f32,               // 行高倍数
+    pub page_size: MarkdownPageSize,
+    pub page_width_mm: f32,
+    pub page_height_mm: f32,
+    pub landscape: bool,
+    pub margin_top_mm: f32,
+    pub margin_bottom_mm: f32,
+    pub margin_left_mm: f32,
+    pub margin_right_mm: f32,
+    pub header: Option<String>, // 页眉模板 "左|中|右"，只有一段时居中；可用 {title} {page} {pages} {date}
+    pub footer: Option<String>, // 页脚模板，格式同页眉
+    pub accent_color: String,   // 标题、链接与表头颜色，如 "#1f4e79"
+    pub embed_fonts: bool,      // HTML 内嵌字体文件，未安装对应字体的电脑上显示一致
+}
+
+impl Default for MarkdownStyleProfile {
+    fn default() -> Self {
+        // 默认按国内公文常用的 A4 版心与五号字
+        Self {
+            name: "默认".to_string(),
+            font_family: "Arial".to_string(),
+            cjk_font_family: "Microsoft YaHei".to_string(),
+            mono_font_family: "Consolas".to_string(),
+            font_path: None,
+            cjk_font_path: None,
+            bold_font_path: None,
+            mono_font_path: None,
+            font_size: 10.5,
+            code_font_size: 9.0,
+            line_height: 1.5,
+            page_size: MarkdownPageSize::A4,
+            page_width_mm: 210.0,
+            page_height_mm: 297.0,
+            landscape: false,
+            margin_top_mm: 25.4,
+            margin_bottom_mm: 25.4,
+            margin_left_mm: 31.8,
+            margin_right_mm: 31.8,
+            header: None,
+            footer: Some("第 {page} 页 / 共 {pages} 页".to_string()),
+            accent_color: "#1f4e79".to_string(),
+            embed_fonts: false,
+        }
+    }
+}
+
+impl MarkdownStyleProfile {
+    /// 纸张宽高（mm），已考虑横向
+    pub fn page_size_mm(&self) -> (f32, f32) {
+        let (w, h) = match self.page_size {
+            MarkdownPageSize::A4 => (210.0, 297.0),
+            MarkdownPageSize::A5 => (148.0, 210.0),
+            MarkdownPageSize::Letter => (215.9, 279.4),
+            MarkdownPageSize::Custom => (self.page_width_mm, self.page_height_mm),
+        };
+        if self.landscape {
+            (h, w)
+        } else {
+            (w, h)
+        }
+    }
+
+    /// 版心宽高（mm）
+    pub fn content_size_mm(&self) -> (f32, f32) {
+        let (w, h) = self.page_size_mm();
+        (
+            w - self.margin_left_mm - self.margin_right_mm,
+            h - self.margin_top_mm - self.margin_bottom_mm,
+        )
+    }
+
+    /// 强调色 RGB
+    pub fn accent_rgb(&self) -> (u8, u8, u8) {
+        parse_hex_color(&self.accent_color).unwrap_or((0x1f, 0x4e, 0x79))
+    }
+}
+
+/// 检查样式配置是否可用
+pub fn markdown_style_check(profile: &MarkdownStyleProfile) -> Result<(), String> {
+    if profile.name.trim().is_empty() {
+        return Err("样式名称不能为空".to_string());
+    }
+    if !(4.0..=72.0).contains(&profile.font_size) || !(4.0..=72.0).contains(&profile.code_font_size) {
+        return Err("字号需在 4 ~ 72 pt 之间".to_string());
+    }
+    if !(1.0..=3.0).contains(&profile.line_height) {
+        return Err("行高倍数需在 1.0 ~ 3.0 之间".to_string());
+    }
+    let margins = [
+        profile.margin_top_mm,
+        profile.margin_bottom_mm,
+        profile.margin_left_mm,
+        profile.margin_right_mm,
+    ];
+    if margins.iter().any(|m| !m.is_finite() || *m < 0.0) {
+        return Err("页边距不能为负数".to_string());
+    }
+    let (page_w, page_h) = profile.page_size_mm();
+    if !(50.0..=2000.0).contains(&page_w) || !(50.0..=2000.0).contains(&page_h) {
+        return Err(format!("纸张尺寸 {}×{} mm 超出范围", page_w, page_h));
+    }
+    let (content_w, content_h) = profile.content_size_mm();
+    if content_w < 40.0 || content_h < 40.0 {
+        return Err(format!("页边距过大，版心只剩 {:.1}×{:.1} mm", content_w, content_h));
+    }
+    if parse_hex_color(&profile.accent_color).is_none() {
+        return Err(format!("无法识别的颜色: {}", profile.accent_color));
+    }
+    Ok(())
+}
+
+// 解析 "#RRGGBB" / "RRGGBB"
+fn parse_hex_color(text: &str) -> Option<(u8, u8, u8)> {
+    let hex = text.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    Some(((value >> 16) as u8, (value >> 8) as u8, value as u8))
+}
+
+// ---------------------------------------------------------------------------
+// Markdown 解析为简单的块结构，PDF 与 DOCX 共用
+// ---------------------------------------------------------------------------
+
+/// 行内样式
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub strike: bool,
+    pub code: bool,
+    pub link: Option<String>,
+}
+
+/// 行内元素
+#[derive(Debug, Clone)]
+pub enum MdSpan {
+    Text(String, SpanStyle),
+    Break,
+    Image { src: String, alt: String },
+}
+
+/// 块元素
+#[derive(Debug, Clone)]
+pub enum MdBlock {
+    Heading(usize, Vec<MdSpan>),
+    Paragraph(Vec<MdSpan>),
+    Plain(Vec<MdSpan>), // 紧凑列表项中的文字（没有段落间距）
+    Code(String),
+    Quote(Vec<MdBlock>),
+    List(Option<u64>, Vec<Vec<MdBlock>>),
+    Table(Vec<Alignment>, Vec<Vec<Vec<MdSpan>>>), // 第一行为表头
+    Rule,
+}
+
+// 行内临时元素：软换行在前后都是中文时不应变成空格，收尾时再处理
+enum RawSpan {
+    Span(MdSpan),
+    Soft,
+}
+
+fn markdown_options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS | Options::ENABLE_FOOTNOTES
+}
+
+/// 解析 Markdown 为块列表
+pub fn markdown_parse_blocks(markdown: &str) -> Vec<MdBlock> {
+    let mut events = Parser::new_ext(markdown, markdown_options());
+    read_blocks(&mut events)
+}
+
+/// 取第一个一级标题的文字作为文档标题
+pub fn markdown_title(blocks: &[MdBlock]) -> Option<String> {
+    blocks.iter().find_map(|b| match b {
+        MdBlock::Heading(1, spans) => Some(spans_plain_text(spans)).filter(|t| !t.trim().is_empty()),
+        _ => None,
+    })
+}
+
+/// 行内元素的纯文本
+pub fn spans_plain_text(spans: &[MdSpan]) -> String {
+    spans
+        .iter()
+        .map(|s| match s {
+            MdSpan::Text(text, _) => text.as_str(),
+            MdSpan::Break => " ",
+            MdSpan::Image { alt, .. } => alt.as_str(),
+        })
+        .collect()
+}
+
+// 读取块元素，直到遇到所在容器的结束事件
+fn read_blocks<'a>(events: &mut impl Iterator<Item = Event<'a>>) -> Vec<MdBlock> {
+    let mut blocks = Vec::new();
+    let mut loose = Vec::new(); // 紧凑列表项中直接出现的行内元素
+    while let Some(event) = events.next() {
+        let tag = match event {
+            Event::End(_) => break,
+            Event::Rule => {
+                flush_plain(&mut blocks, &mut loose);
+                blocks.push(MdBlock::Rule);
+                continue;
+            }
+            Event::Start(tag) => tag,
+            other => {
+                read_inline(other, events, &SpanStyle::default(), &mut loose);
+                continue;
+            }
+        };
+        match tag {
+            Tag::Paragraph => {
+                flush_plain(&mut blocks, &mut loose);
+                blocks.push(MdBlock::Paragraph(read_spans(events)));
+            }
+            Tag::Heading { level, .. } => {
+                flush_plain(&mut blocks, &mut loose);
+                blocks.push(MdBlock::Heading(level as usize, read_spans(events)));
+            }
+            Tag::BlockQuote(_) => {
+                flush_plain(&mut blocks, &mut loose);
+                blocks.push(MdBlock::Quote(read_blocks(events)));
+            }
+            Tag::CodeBlock(_) => {
+                flush_plain(&mut blocks, &mut loose);
+                let code = read_text(events);
+                blocks.push(MdBlock::Code(code.strip_suffix('\n').unwrap_or(&code).to_string()));
+            }
+            Tag::List(start) => {
+                flush_plain(&mut blocks, &mut loose);
+                let mut items = Vec::new();
+                while let Some(Event::Start(Tag::Item)) = events.next() {
+                    items.push(read_blocks(events));
+                }
+                blocks.push(MdBlock::List(start, items));
+            }
+            Tag::Table(aligns) => {
+                flush_plain(&mut blocks, &mut loose);
+                let mut rows = Vec::new();
+                while let Some(Event::Start(Tag::TableHead | Tag::TableRow)) = events.next() {
+                    let mut cells = Vec::new();
+                    while let Some(Event::Start(Tag::TableCell)) = events.next() {
+                        cells.push(read_spans(events));
+                    }
+                    rows.push(cells);
+                }
+                blocks.push(MdBlock::Table(aligns, rows));
+            }
+            // 原始 HTML 与 front matter 不导出
+            Tag::HtmlBlock | Tag::MetadataBlock(_) => {
+                read_text(events);
+            }
+            Tag::FootnoteDefinition(name) => {
+                flush_plain(&mut blocks, &mut loose);
+                let mut inner = read_blocks(events);
+                if let Some(MdBlock::Paragraph(spans)) = inner.first_mut() {
+                    spans.insert(0, MdSpan::Text(format!("[{}] ", name), SpanStyle::default()));
+                }
+                blocks.extend(inner);
+            }
+            Tag::DefinitionList | Tag::DefinitionListTitle | Tag::DefinitionListDefinition | Tag::Item => {
+                flush_plain(&mut blocks, &mut loose);
+                blocks.extend(read_blocks(events));
+            }
+            inline => read_inline(Event::Start(inline), events, &SpanStyle::default(), &mut loose),
+        }
+    }
+    flush_plain(&mut blocks, &mut loose);
+    blocks
+}
+
+fn flush_plain(blocks: &mut Vec<MdBlock>, loose: &mut Vec<RawSpan>) {
+    if !loose.is_empty() {
+        blocks.push(MdBlock::Plain(finish_spans(std::mem::take(loose))));
+    }
+}
+
+// 读取行内元素直到结束事件
+fn read_spans<'a>(events: &mut impl Iterator<Item = Event<'a>>) -> Vec<MdSpan> {
+    let mut spans = Vec::new();
+    while let Some(event) = events.next() {
+        if matches!(event, Event::End(_)) {
+            break;
+        }
+        read_inline(event, events, &SpanStyle::default(), &mut spans);
+    }
+    finish_spans(spans)
+}
+
+// 读取纯文本直到结束事件（代码块、图片说明等）
+fn read_text<'a>(events: &mut impl Iterator<Item = Event<'a>>) -> String {
+    let mut text = String::new();
+    let mut depth = 0;
+    for event in events.by_ref() {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) if depth == 0 => break,
+            Event::End(_) => depth -= 1,
+            Event::Text(t) | Event::Code(t) | Event::Html(t) | Event::InlineHtml(t) => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+// 处理一个行内事件，遇到行内标签时一直读到对应的结束事件
+fn read_inline<'a>(
+    event: Event<'a>,
+    events: &mut impl Iterator<Item = Event<'a>>,
+    style: &SpanStyle,
+    spans: &mut Vec<RawSpan>,
+) {
+    let push = |spans: &mut Vec<RawSpan>, text: &str, style: SpanStyle| {
+        spans.push(RawSpan::Span(MdSpan::Text(text.to_string(), style)))
+    };
+    match event {
+        Event::Text(text) => push(spans, &text, style.clone()),
+        Event::Code(text) | Event::InlineMath(text) | Event::DisplayMath(text) => push(
+            spans,
+            &text,
+            SpanStyle {
+                code: true,
+                ..style.clone()
+            },
+        ),
+        Event::SoftBreak => spans.push(RawSpan::Soft),
+        Event::HardBreak => spans.push(RawSpan::Span(MdSpan::Break)),
+        Event::TaskListMarker(done) => push(spans, if done { "☑ " } else { "☐ " }, style.clone()),
+        Event::FootnoteReference(name) => push(spans, &format!("[{}]", name), style.clone()),
+        Event::InlineHtml(html) if html.trim_start().to_ascii_lowercase().starts_with("<br") => {
+            spans.push(RawSpan::Span(MdSpan::Break))
+        }
+        Event::Start(Tag::Image { dest_url, .. }) => {
+            let alt = read_text(events);
+            spans.push(RawSpan::Span(MdSpan::Image {
+                src: dest_url.to_string(),
+                alt,
+            }));
+        }
+        Event::Start(tag) => {
+            let mut inner = style.clone();
+            match tag {
+                Tag::Emphasis => inner.italic = true,
+                Tag::Strong => inner.bold = true,
+                Tag::Strikethrough => inner.strike = true,
+                Tag::Link { dest_url, .. } => inner.link = Some(dest_url.to_string()),
+                _ => {}
+            }
+            while let Some(event) = events.next() {
+                if matches!(event, Event::End(_)) {
+                    break;
+                }
+                read_inline(event, events, &inner, spans);
+            }
+        }
+        _ => {}
+    }
+}
+
+// 软换行：前后任一侧是中文时直接去掉，否则变成空格
+fn finish_spans(raw: Vec<RawSpan>) -> Vec<MdSpan> {
+    let mut spans: Vec<MdSpan> = Vec::with_capacity(raw.len());
+    let mut pending_soft = false;
+    for item in raw {
+        match item {
+            RawSpan::Soft => pending_soft = true,
+            RawSpan::Span(span) => {
+                if pending_soft {
+                    pending_soft = false;
+                    let prev = match spans.last() {
+                        Some(MdSpan::Text(text, style)) => text.chars().last().map(|c| (c, style.clone())),
+                        _ => None,
+                    };
+                    let next = match &span {
+                        MdSpan::Text(text, _) => text.chars().next(),
+                        _ => None,
+                    };
+                    if let (Some((prev, style)), Some(next)) = (prev, next) {
+                        if !is_cjk(prev) && !is_cjk(next) {
+                            spans.push(MdSpan::Text(" ".to_string(), SpanStyle { link: None, ..style }));
+                        }
+                    }
+                }
+                spans.push(span);
+            }
+        }
+    }
+    spans
+}
+
+/// 是否为中日韩文字或全角标点
+pub fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x2E80..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFFEF | 0x20000..=0x2FA1F)
+}
+
+/// 图片数据
+pub struct MarkdownImage {
+    pub data: Vec<u8>,
+    pub extension: &'static str,
+    pub mime: &'static str,
+    pub width: u32,  // 像素
+    pub height: u32, // 像素
+}
+
+/// 读取本地图片（相对路径基于 Markdown 文件所在目录），网络图片与 data URI 返回 None
+pub fn markdown_load_image(src: &str, base_dir: Option<&Path>) -> Option<MarkdownImage> {
+    let src = src.trim();
+    if src.contains("://") && !src.starts_with("file://") || src.starts_with("data:") {
+        return None;
+    }
+    let path = Path::new(src.trim_start_matches("file://"));
+    let path = match base_dir {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_path_buf(),
+    };
+    let data = std::fs::read(&path).ok()?;
+    let (extension, mime, (width, height)) = if data.starts_with(b"\x89PNG\r\n\x1a\n") && data.len() >= 24 {
+        let width = u32::from_be_bytes([data[16], data[17], data[18], data[19]]);
+        let height = u32::from_be_bytes([data[20], data[21], data[22], data[23]]);
+        ("png", "image/png", (width, height))
+    } else if data.starts_with(&[0xFF, 0xD8]) {
+        ("jpeg", "image/jpeg", jpeg_size(&data)?)
+    } else if data.starts_with(b"GIF8") && data.len() >= 10 {
+        let width = u16::from_le_bytes([data[6], data[7]]) as u32;
+        let height = u16::from_le_bytes([data[8], data[9]]) as u32;
+        ("gif", "image/gif", (width, height))
+    } else {
+        return None;
+    };
+    if width == 0 || height == 0 {
+        return None;
+    }
+    Some(MarkdownImage {
+        data,
+        extension,
+        mime,
+        width,
+        height,
+    })
+}
+
+/// JPEG 像素尺寸（读取 SOF 段）
+pub fn jpeg_size(data: &[u8]) -> Option<(u32, u32)> {
+    jpeg_frame(data).map(|(w, h, _)| (w, h))
+}
+
+/// JPEG 宽、高与颜色分量数
+pub fn jpeg_frame(data: &[u8]) -> Option<(u32, u32, u8)> {
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF {
+            return None;
+        }
+        let marker = data[pos + 1];
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        let len = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        // SOF0 ~ SOF15，排除 DHT(C4) / JPG(C8) / DAC(CC)
+        if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) && pos + 10 <= data.len() {
+            let height = u16::from_be_bytes([data[pos + 5], data[pos + 6]]) as u32;
+            let width = u16::from_be_bytes([data[pos + 7], data[pos + 8]]) as u32;
+            return Some((width, height, data[pos + 9]));
+        }
+        pos += 2 + len;
+    }
+    None
+}
+
+// 页眉页脚模板中的片段
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePiece {
+    Text(String),
+    Page,
+    Pages,
+}
+
+/// 拆分页眉页脚模板为 左 / 中 / 右 三段，{title} 与 {date} 直接替换
+pub fn markdown_template_parts(template: &str, title: &str) -> [Vec<TemplatePiece>; 3] {
+    let text = template.replace("{title}", title).replace("{date}", &util_date_today());
+    let parts: Vec<&str> = text.split('|').collect();
+    let (left, center, right) = match parts.as_slice() {
+        [center] => ("", *center, ""),
+        [left, right] => (*left, "", *right),
+        [left, center, right, ..] => (*left, *center, *right),
+        [] => ("", "", ""),
+    };
+    [left, center, right].map(|part| {
+        let mut pieces = Vec::new();
+        let mut rest = part;
+        while let Some(pos) = rest.find("{page") {
+            let (field, len) = if rest[pos..].starts_with("{pages}") {
+                (TemplatePiece::Pages, 7)
+            } else if rest[pos..].starts_with("{page}") {
+                (TemplatePiece::Page, 6)
+            } else {
+                pieces.push(TemplatePiece::Text(rest[..pos + 5].to_string()));
+                rest = &rest[pos + 5..];
+                continue;
+            };
+            if pos > 0 {
+                pieces.push(TemplatePiece::Text(rest[..pos].to_string()));
+            }
+            pieces.push(field);
+            rest = &rest[pos + len..];
+        }
+        if !rest.is_empty() {
+            pieces.push(TemplatePiece::Text(rest.to_string()));
+        }
+        pieces
+    })
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// CSS 字体族列表
+fn css_font_list(families: &[&str], fallback: &str) -> String {
+    let mut list: Vec<String> = families
+        .iter()
+        .filter(|f| !f.trim().is_empty())
+        .map(|f| format!("\"{}\"", f.trim().replace('"', "")))
+        .collect();
+    list.push(fallback.to_string());
+    list.join(", ")
+}
+
+// 内嵌字体的 @font-face
+fn css_font_face(family: &str, path: &str) -> Result<String, String> {
+    let data = std::fs::read(path).map_err(|e| format!("读取字体文件失败 {}: {}", path, e))?;
+    let format = if data.starts_with(b"OTTO") { "opentype" } else { "truetype" };
+    Ok(format!(
+        "@font-face {{ font-family: \"{}\"; src: url(data:font/{};base64,{}) format(\"{}\"); }}\n",
+        family,
+        if format == "opentype" { "otf" } else { "ttf" },
+        base64::engine::general_purpose::STANDARD.encode(data),
+        format
+    ))
+}
+
+/// 导出为单文件 HTML：样式写在 <style> 中，本地图片与（可选）字体以 data URI 内嵌
+pub fn markdown_to_html(
+    markdown: &str,
+    title: &str,
+    profile: &MarkdownStyleProfile,
+    base_dir: Option<&Path>,
+) -> Result<String, String> {
+    let events = Parser::new_ext(markdown, markdown_options()).map(|event| match event {
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => {
+            let dest_url = match markdown_load_image(&dest_url, base_dir) {
+                Some(image) => format!(
+                    "data:{};base64,{}",
+                    image.mime,
+                    base64::engine::general_purpose::STANDARD.encode(&image.data)
+                )
+                .into(),
+                None => dest_url,
+            };
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            })
+        }
+        other => other,
+    });
+    let mut body = String::new();
+    html::push_html(&mut body, events);
+
+    let mut font_faces = String::new();
+    let mut body_families = vec![profile.font_family.as_str(), profile.cjk_font_family.as_str()];
+    let mut mono_families = vec![profile.mono_font_family.as_str(), profile.cjk_font_family.as_str()];
+    if profile.embed_fonts {
+        for (family, path) in [
+            ("md-body", &profile.font_path),
+            ("md-cjk", &profile.cjk_font_path),
+            ("md-mono", &profile.mono_font_path),
+        ] {
+            if let Some(path) = path.as_deref().filter(|p| !p.trim().is_empty()) {
+                font_faces.push_str(&css_font_face(family, path)?);
+            }
+        }
+        // 内嵌字体优先，系统字体作为后备
+        if profile.cjk_font_path.is_some() {
+            body_families.insert(0, "md-cjk");
+            mono_families.push("md-cjk");
+        }
+        if profile.font_path.is_some() {
+            body_families.insert(0, "md-body");
+        }
+        if profile.mono_font_path.is_some() {
+            mono_families.insert(0, "md-mono");
+        }
+    }
+
+    let (r, g, b) = profile.accent_rgb();
+    let accent = format!("#{:02x}{:02x}{:02x}", r, g, b);
+    let (page_w, page_h) = profile.page_size_mm();
+    let (content_w, _) = profile.content_size_mm();
+    let headings: String = HEADING_SCALE
+        .iter()
+        .enumerate()
+        .map(|(i, scale)| format!("h{} {{ font-size: {:.1}pt; }}\n", i + 1, profile.font_size * scale))
+        .collect();
+    let style = format!(
+        r#"{font_faces}@page {{ size: {page_w}mm {page_h}mm; margin: {mt}mm {mr}mm {mb}mm {ml}mm; }}
+body {{ font-family: {body_font}; font-size: {size}pt; line-height: {line}; color: #222; margin: 0; }}
+main {{ max-width: {content_w}mm; margin: 0 auto; padding: 12mm 0; }}
+@media print {{ main {{ max-width: none; padding: 0; }} }}
+h1, h2, h3, h4, h5, h6 {{ color: {accent}; line-height: 1.3; margin: 1.2em 0 0.5em; page-break-after: avoid; }}
+h1, h2 {{ border-bottom: 1px solid {accent}; padding-bottom: 0.2em; }}
+{headings}a {{ color: {accent}; }}
+code, pre {{ font-family: {mono_font}; font-size: {code_size}pt; }}
+code {{ background: #f3f3f3; padding: 0 0.25em; border-radius: 3px; }}
+pre {{ background: #f5f5f5; padding: 0.8em 1em; border-radius: 4px; white-space: pre-wrap; word-break: break-all; }}
+pre code {{ background: none; padding: 0; }}
+blockquote {{ margin: 0.8em 0; padding: 0 1em; color: #555; border-left: 3px solid #ccc; }}
+table {{ border-collapse: collapse; margin: 0.8em 0; width: 100%; }}
+th, td {{ border: 1px solid #bbb; padding: 0.3em 0.6em; }}
+th {{ background: rgba({r}, {g}, {b}, 0.12); }}
+tr {{ page-break-inside: avoid; }}
+img {{ max-width: 100%; }}
+hr {{ border: none; border-top: 1px solid #ccc; margin: 1.2em 0; }}
+"#,
+        font_faces = font_faces,
+        page_w = page_w,
+        page_h = page_h,
+        mt = profile.margin_top_mm,
+        mr = profile.margin_right_mm,
+        mb = profile.margin_bottom_mm,
+        ml = profile.margin_left_mm,
+        body_font = css_font_list(&body_families, "sans-serif"),
+        size = profile.font_size,
+        line = profile.line_height,
+        content_w = content_w,
+        accent = accent,
+        headings = headings,
+        mono_font = css_font_list(&mono_families, "monospace"),
+        code_size = profile.code_font_size,
+        r = r,
+        g = g,
+        b = b,
+    );
+    Ok(format!(
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n<main>\n{}</main>\n</body>\n</html>\n",
+        html_escape(title),
+        style,
+        body
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// DOCX
+// ---------------------------------------------------------------------------
+
+const W_NS: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+const R_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const REL_NS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+const REL_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+// 1 mm = 56.69 twip；1 mm = 36000 EMU
+fn mm_to_twip(mm: f32) -> i64 {
+    (mm * 1440.0 / 25.4).round() as i64
+}
+
+fn xml_escape(text: &str) -> String {
+    // XML 1.0 不允许大部分控制字符
+    text.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// 文档中用到的外部资源（超链接、图片），生成 document.xml.rels
+struct DocxContext<'a> {
+    profile: &'a MarkdownStyleProfile,
+    base_dir: Option<&'a Path>,
+    relationships: Vec<String>,
+    media: Vec<(String, Vec<u8>)>,
+    content_width_emu: i64,
+    drawing_id: usize,
+}
+
+impl DocxContext<'_> {
+    fn add_relationship(&mut self, rel_type: &str, target: &str, external: bool) -> String {
+        let id = format!("rId{}", self.relationships.len() + 10);
+        self.relationships.push(format!(
+            r#"<Relationship Id="{}" Type="{}/{}" Target="{}"{}/>"#,
+            id,
+            REL_TYPE,
+            rel_type,
+            xml_escape(target),
+            if external { r#" TargetMode="External""# } else { "" }
+        ));
+        id
+    }
+
+    // 行内元素转为 w:r
+    fn runs(&mut self, spans: &[MdSpan]) -> String {
+        let mut xml = String::new();
+        for span in spans {
+            match span {
+                MdSpan::Text(text, style) => {
+                    let run = docx_run(text, style);
+                    match style.link.as_deref().filter(|l| !l.starts_with('#')) {
+                        Some(link) => {
+                            let id = self.add_relationship("hyperlink", link, true);
+                            xml.push_str(&format!(r#"<w:hyperlink r:id="{}">{}</w:hyperlink>"#, id, run));
+                        }
+                        None => xml.push_str(&run),
+                    }
+                }
+                MdSpan::Break => xml.push_str("<w:r><w:br/></w:r>"),
+                MdSpan::Image { src, alt } => xml.push_str(&self.image(src, alt)),
+            }
+        }
+        xml
+    }
+
+    // 内嵌图片，宽度超出版心时等比缩小；无法读取时以文字代替
+    fn image(&mut self, src: &str, alt: &str) -> String {
+        let image = match markdown_load_image(src, self.base_dir) {
+            Some(image) => image,
+            None => return docx_run(&format!("[图片: {}]", if alt.is_empty() { src } else { alt }), &SpanStyle::default()),
+        };
+        self.drawing_id += 1;
+        let name = format!("image{}.{}", self.drawing_id, image.extension);
+        let rel_id = self.add_relationship("image", &format!("media/{}", name), false);
+        // 按 96 DPI 换算
+        let mut cx = image.width as i64 * 9525;
+        let mut cy = image.height as i64 * 9525;
+        if cx > self.content_width_emu {
+            cy = cy * self.content_width_emu / cx;
+            cx = self.content_width_emu;
+        }
+        self.media.push((name.clone(), image.data));
+        format!(
+            concat!(
+                r#"<w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0"><wp:extent cx="{cx}" cy="{cy}"/>"#,
+                r#"<wp:docPr id="{id}" name="{name}" descr="{alt}"/><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture">"#,
+                r#"<pic:pic><pic:nvPicPr><pic:cNvPr id="{id}" name="{name}"/><pic:cNvPicPr/></pic:nvPicPr>"#,
+                r#"<pic:blipFill><a:blip r:embed="{rel}"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill>"#,
+                r#"<pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr>"#,
+                r#"</pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"#
+            ),
+            cx = cx,
+            cy = cy,
+            id = self.drawing_id,
+            name = name,
+            alt = xml_escape(alt),
+            rel = rel_id
+        )
+    }
+
+    // 块元素转为 w:p / w:tbl，indent 为左缩进（twip）
+    fn blocks(&mut self, blocks: &[MdBlock], indent: i64, style: Option<&str>) -> String {
+        let mut xml = String::new();
+        for block in blocks {
+            match block {
+                MdBlock::Heading(level, spans) => {
+                    let runs = self.runs(spans);
+                    xml.push_str(&docx_paragraph(Some(&format!("Heading{}", level)), indent, "", &runs));
+                }
+                MdBlock::Paragraph(spans) | MdBlock::Plain(spans) => {
+                    let runs = self.runs(spans);
+                    xml.push_str(&docx_paragraph(style, indent, "", &runs));
+                }
+                MdBlock::Code(code) => {
+                    let runs = code
+                        .split('\n')
+                        .map(|line| docx_run(line, &SpanStyle::default()))
+                        .collect::<Vec<_>>()
+                        .join("<w:r><w:br/></w:r>");
+                    xml.push_str(&docx_paragraph(Some("CodeBlock"), indent, "", &runs));
+                }
+                MdBlock::Quote(inner) => xml.push_str(&self.blocks(inner, indent + 360, Some("Quote"))),
+                MdBlock::List(start, items) => {
+                    let hanging = 360;
+                    for (index, item) in items.iter().enumerate() {
+                        let marker = match start {
+                            Some(start) => format!("{}.", start + index as u64),
+                            None => "•".to_string(),
+                        };
+                        let mut item_xml = self.blocks(item, indent + hanging, style);
+                        // 编号写在第一段开头，用悬挂缩进对齐
+                        let marker_run = format!(
+                            r#"<w:r><w:t xml:space="preserve">{}</w:t></w:r><w:r><w:tab/></w:r>"#,
+                            marker
+                        );
+                        match item_xml.find("</w:pPr>") {
+                            Some(pos) if item_xml.starts_with("<w:p>") => {
+                                item_xml.insert_str(pos + "</w:pPr>".len(), &marker_run);
+                                let ind = format!(r#"<w:ind w:left="{}""#, indent + hanging);
+                                item_xml = item_xml.replacen(
+                                    &format!("{}/>", ind),
+                                    &format!(r#"{} w:hanging="{}"/>"#, ind, hanging),
+                                    1,
+                                );
+                            }
+                            _ => item_xml.insert_str(0, &docx_paragraph(style, indent, "", &marker_run)),
+                        }
+                        xml.push_str(&item_xml);
+                    }
+                }
+                MdBlock::Table(aligns, rows) => xml.push_str(&self.table(aligns, rows, indent)),
+                MdBlock::Rule => xml.push_str(&docx_paragraph(
+                    style,
+                    indent,
+                    r#"<w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="BBBBBB"/></w:pBdr>"#,
+                    "",
+                )),
+            }
+        }
+        xml
+    }
+
+    fn table(&mut self, aligns: &[Alignment], rows: &[Vec<Vec<MdSpan>>], indent: i64) -> String {
+        let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0).max(1);
+        let table_width = self.content_width_emu / 635 - indent; // EMU -> twip
+        let column_width = table_width / columns as i64;
+        let (r, g, b) = self.profile.accent_rgb();
+        // 表头底色取强调色的浅色
+        let tint = |c: u8| (255.0 - (255.0 - c as f32) * 0.12).round() as u8;
+        let header_fill = format!("{:02X}{:02X}{:02X}", tint(r), tint(g), tint(b));
+
+        let mut xml = format!(
+            concat!(
+                r#"<w:tbl><w:tblPr><w:tblW w:w="{}" w:type="dxa"/><w:tblInd w:w="{}" w:type="dxa"/><w:tblBorders>"#,
+                r#"<w:top w:val="single" w:sz="4" w:color="BBBBBB"/><w:left w:val="single" w:sz="4" w:color="BBBBBB"/>"#,
+                r#"<w:bottom w:val="single" w:sz="4" w:color="BBBBBB"/><w:right w:val="single" w:sz="4" w:color="BBBBBB"/>"#,
+                r#"<w:insideH w:val="single" w:sz="4" w:color="BBBBBB"/><w:insideV w:val="single" w:sz="4" w:color="BBBBBB"/>"#,
+                r#"</w:tblBorders><w:tblLayout w:type="fixed"/><w:tblCellMar><w:left w:w="100" w:type="dxa"/><w:right w:w="100" w:type="dxa"/></w:tblCellMar></w:tblPr><w:tblGrid>"#
+            ),
+            table_width, indent
+        );
+        for _ in 0..columns {
+            xml.push_str(&format!(r#"<w:gridCol w:w="{}"/>"#, column_width));
+        }
+        xml.push_str("</w:tblGrid>");
+        for (row_index, row) in rows.iter().enumerate() {
+            let header = row_index == 0;
+            xml.push_str(if header {
+                "<w:tr><w:trPr><w:tblHeader/><w:cantSplit/></w:trPr>"
+            } else {
+                "<w:tr><w:trPr><w:cantSplit/></w:trPr>"
+            });
+            for column in 0..columns {
+                let empty = Vec::new();
+                let cell = row.get(column).unwrap_or(&empty);
+                let cell: Vec<MdSpan> = if header {
+                    cell.iter()
+                        .map(|s| match s {
+                            MdSpan::Text(text, style) => MdSpan::Text(
+                                text.clone(),
+                                SpanStyle {
+                                    bold: true,
+                                    ..style.clone()
+                                },
+                            ),
+                            other => other.clone(),
+                        })
+                        .collect()
+                } else {
+                    cell.clone()
+                };
+                let jc = match aligns.get(column) {
+                    Some(Alignment::Center) => r#"<w:jc w:val="center"/>"#,
+                    Some(Alignment::Right) => r#"<w:jc w:val="right"/>"#,
+                    _ => "",
+                };
+                let shading = if header {
+                    format!(r#"<w:shd w:val="clear" w:color="auto" w:fill="{}"/>"#, header_fill)
+                } else {
+                    String::new()
+                };
+                let runs = self.runs(&cell);
+                xml.push_str(&format!(
+                    r#"<w:tc><w:tcPr><w:tcW w:w="{}" w:type="dxa"/>{}</w:tcPr><w:p><w:pPr><w:spacing w:before="40" w:after="40"/>{}</w:pPr>{}</w:p></w:tc>"#,
+                    column_width, shading, jc, runs
+                ));
+            }
+            xml.push_str("</w:tr>");
+        }
+        xml.push_str("</w:tbl>");
+        // 表格后补一个空段落，避免相邻表格被合并
+        xml.push_str("<w:p/>");
+        xml
+    }
+
+    // 页眉 / 页脚：三段用居中与右对齐制表位分隔，{page} {pages} 转为域
+    fn header_footer(&self, tag: &str, template: &str, title: &str) -> String {
+        let content_width = self.content_width_emu / 635;
+        let parts = markdown_template_parts(template, title);
+        let mut runs = String::new();
+        for (index, part) in parts.iter().enumerate() {
+            if index > 0 {
+                runs.push_str("<w:r><w:tab/></w:r>");
+            }
+            for piece in part {
+                runs.push_str(&match piece {
+                    TemplatePiece::Text(text) => docx_run(text, &SpanStyle::default()),
+                    TemplatePiece::Page => r#"<w:fldSimple w:instr=" PAGE "><w:r><w:t>1</w:t></w:r></w:fldSimple>"#.to_string(),
+                    TemplatePiece::Pages => {
+                        r#"<w:fldSimple w:instr=" NUMPAGES "><w:r><w:t>1</w:t></w:r></w:fldSimple>"#.to_string()
+                    }
+                });
+            }
+        }
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:{tag} xmlns:w="{w}" xmlns:r="{r}"><w:p><w:pPr><w:pStyle w:val="HeaderFooter"/><w:tabs><w:tab w:val="center" w:pos="{center}"/><w:tab w:val="right" w:pos="{right}"/></w:tabs></w:pPr>{runs}</w:p></w:{tag}>"#,
+            tag = tag,
+            w = W_NS,
+            r = R_NS,
+            center = content_width / 2,
+            right = content_width,
+            runs = runs
+        )
+    }
+}
+
+fn docx_run(text: &str, style: &SpanStyle) -> String {
+    let mut props = String::new();
+    if style.code {
+        props.push_str(r#"<w:rStyle w:val="CodeChar"/>"#);
+    } else if style.link.is_some() {
+        props.push_str(r#"<w:rStyle w:val="Hyperlink"/>"#);
+    }
+    if style.bold {
+        props.push_str("<w:b/>");
+    }
+    if style.italic {
+        props.push_str("<w:i/>");
+    }
+    if style.strike {
+        props.push_str("<w:strike/>");
+    }
+    let props = if props.is_empty() { props } else { format!("<w:rPr>{}</w:rPr>", props) };
+    // 制表符需要单独的 w:tab
+    text.split('\t')
+        .map(|part| format!(r#"<w:r>{}<w:t xml:space="preserve">{}</w:t></w:r>"#, props, xml_escape(part)))
+        .collect::<Vec<_>>()
+        .join(&format!("<w:r>{}<w:tab/></w:r>", props))
+}
+
+fn docx_paragraph(style: Option<&str>, indent: i64, extra_props: &str, runs: &str) -> String {
+    let mut props = String::new();
+    if let Some(style) = style {
+        props.push_str(&format!(r#"<w:pStyle w:val="{}"/>"#, style));
+    }
+    props.push_str(extra_props);
+    if indent > 0 {
+        props.push_str(&format!(r#"<w:ind w:left="{}"/>"#, indent));
+    }
+    format!("<w:p><w:pPr>{}</w:pPr>{}</w:p>", props, runs)
+}
+
+fn docx_styles(profile: &MarkdownStyleProfile) -> String {
+    let (r, g, b) = profile.accent_rgb();
+    let accent = format!("{:02X}{:02X}{:02X}", r, g, b);
+    let half_points = |pt: f32| (pt * 2.0).round() as i64;
+    let line = (240.0 * profile.line_height).round() as i64;
+    let fonts = format!(
+        r#"<w:rFonts w:ascii="{0}" w:hAnsi="{0}" w:eastAsia="{1}" w:cs="{0}"/>"#,
+        xml_escape(&profile.font_family),
+        xml_escape(&profile.cjk_font_family)
+    );
+    let mono_fonts = format!(
+        r#"<w:rFonts w:ascii="{0}" w:hAnsi="{0}" w:eastAsia="{1}" w:cs="{0}"/>"#,
+        xml_escape(&profile.mono_font_family),
+        xml_escape(&profile.cjk_font_family)
+    );
+    let mut styles = format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            "\n",
+            r#"<w:styles xmlns:w="{w}"><w:docDefaults><w:rPrDefault><w:rPr>{fonts}<w:color w:val="222222"/><w:sz w:val="{size}"/><w:szCs w:val="{size}"/>"#,
+            r#"<w:lang w:val="en-US" w:eastAsia="zh-CN"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="120" w:line="{line}" w:lineRule="auto"/>"#,
+            r#"</w:pPr></w:pPrDefault></w:docDefaults>"#,
+            r#"<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:qFormat/></w:style>"#,
+            r#"<w:style w:type="paragraph" w:styleId="CodeBlock"><w:name w:val="Code Block"/><w:basedOn w:val="Normal"/>"#,
+            r#"<w:pPr><w:shd w:val="clear" w:color="auto" w:fill="F5F5F5"/><w:spacing w:after="120" w:line="240" w:lineRule="auto"/></w:pPr>"#,
+            r#"<w:rPr>{mono}<w:sz w:val="{code}"/><w:szCs w:val="{code}"/></w:rPr></w:style>"#,
+            r#"<w:style w:type="paragraph" w:styleId="Quote"><w:name w:val="Quote"/><w:basedOn w:val="Normal"/>"#,
+            r#"<w:pPr><w:pBdr><w:left w:val="single" w:sz="18" w:space="8" w:color="CCCCCC"/></w:pBdr></w:pPr><w:rPr><w:color w:val="555555"/></w:rPr></w:style>"#,
+            r#"<w:style w:type="paragraph" w:styleId="HeaderFooter"><w:name w:val="Header Footer"/><w:basedOn w:val="Normal"/>"#,
+            r#"<w:pPr><w:spacing w:after="0"/></w:pPr><w:rPr><w:color w:val="777777"/><w:sz w:val="18"/><w:szCs w:val="18"/></w:rPr></w:style>"#,
+            r#"<w:style w:type="character" w:styleId="CodeChar"><w:name w:val="Code Char"/><w:rPr>{mono}<w:sz w:val="{code}"/><w:szCs w:val="{code}"/>"#,
+            r#"<w:shd w:val="clear" w:color="auto" w:fill="F3F3F3"/></w:rPr></w:style>"#,
+            r#"<w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:rPr><w:color w:val="{accent}"/><w:u w:val="single"/></w:rPr></w:style>"#
+        ),
+        w = W_NS,
+        fonts = fonts,
+        mono = mono_fonts,
+        size = half_points(profile.font_size),
+        code = half_points(profile.code_font_size),
+        line = line,
+        accent = accent
+    );
+    for (index, scale) in HEADING_SCALE.iter().enumerate() {
+        let border = if index < 2 {
+            format!(r#"<w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="{}"/></w:pBdr>"#, accent)
+        } else {
+            String::new()
+        };
+        styles.push_str(&format!(
+            concat!(
+                r#"<w:style w:type="paragraph" w:styleId="Heading{level}"><w:name w:val="heading {level}"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:qFormat/>"#,
+                r#"<w:pPr><w:keepNext/><w:keepLines/>{border}<w:spacing w:before="240" w:after="120" w:line="240" w:lineRule="auto"/><w:outlineLvl w:val="{outline}"/></w:pPr>"#,
+                r#"<w:rPr><w:b/><w:color w:val="{accent}"/><w:sz w:val="{size}"/><w:szCs w:val="{size}"/></w:rPr></w:style>"#
+            ),
+            level = index + 1,
+            border = border,
+            outline = index,
+            accent = accent,
+            size = half_points(profile.font_size * scale)
+        ));
+    }
+    styles.push_str("</w:styles>");
+    styles
+}
+
+/// 导出为 DOCX（WordprocessingML 直接打包为 zip），返回文件内容
+pub fn markdown_to_docx(
+    markdown: &str,
+    title: &str,
+    profile: &MarkdownStyleProfile,
+    base_dir: Option<&Path>,
+) -> Result<Vec<u8>, String> {
+    let blocks = markdown_parse_blocks(markdown);
+    let (page_w, page_h) = profile.page_size_mm();
+    let (content_w, _) = profile.content_size_mm();
+    let mut ctx = DocxContext {
+        profile,
+        base_dir,
+        relationships: Vec::new(),
+        media: Vec::new(),
+        content_width_emu: (content_w * 36000.0) as i64,
+        drawing_id: 0,
+    };
+    let body = ctx.blocks(&blocks, 0, None);
+
+    let header = profile.header.as_deref().filter(|h| !h.trim().is_empty());
+    let footer = profile.footer.as_deref().filter(|f| !f.trim().is_empty());
+    let mut section = String::new();
+    if header.is_some() {
+        section.push_str(r#"<w:headerReference w:type="default" r:id="rIdHeader"/>"#);
+    }
+    if footer.is_some() {
+        section.push_str(r#"<w:footerReference w:type="default" r:id="rIdFooter"/>"#);
+    }
+    section.push_str(&format!(
+        r#"<w:pgSz w:w="{}" w:h="{}"{}/><w:pgMar w:top="{}" w:right="{}" w:bottom="{}" w:left="{}" w:header="{}" w:footer="{}" w:gutter="0"/>"#,
+        mm_to_twip(page_w),
+        mm_to_twip(page_h),
+        if profile.landscape { r#" w:orient="landscape""# } else { "" },
+        mm_to_twip(profile.margin_top_mm),
+        mm_to_twip(profile.margin_right_mm),
+        mm_to_twip(profile.margin_bottom_mm),
+        mm_to_twip(profile.margin_left_mm),
+        mm_to_twip(profile.margin_top_mm / 2.0),
+        mm_to_twip(profile.margin_bottom_mm / 2.0)
+    ));
+    let document = format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            "\n",
+            r#"<w:document xmlns:w="{}" xmlns:r="{}" "#,
+            r#"xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing" "#,
+            r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" "#,
+            r#"xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture">"#,
+            r#"<w:body>{}<w:sectPr>{}</w:sectPr></w:body></w:document>"#
+        ),
+        W_NS, R_NS, body, section
+    );
+
+    let mut document_rels = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="{}"><Relationship Id="rIdStyles" Type="{}/styles" Target="styles.xml"/>"#,
+        REL_NS, REL_TYPE
+    );
+    if header.is_some() {
+        document_rels.push_str(&format!(r#"<Relationship Id="rIdHeader" Type="{}/header" Target="header1.xml"/>"#, REL_TYPE));
+    }
+    if footer.is_some() {
+        document_rels.push_str(&format!(r#"<Relationship Id="rIdFooter" Type="{}/footer" Target="footer1.xml"/>"#, REL_TYPE));
+    }
+    for rel in &ctx.relationships {
+        document_rels.push_str(rel);
+    }
+    document_rels.push_str("</Relationships>");
+
+    let mut overrides = String::from(concat!(
+        r#"<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>"#,
+        r#"<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>"#,
+        r#"<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>"#
+    ));
+    if header.is_some() {
+        overrides.push_str(r#"<Override PartName="/word/header1.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.header+xml"/>"#);
+    }
+    if footer.is_some() {
+        overrides.push_str(r#"<Override PartName="/word/footer1.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.footer+xml"/>"#);
+    }
+    let content_types = format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            "\n",
+            r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+            r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+            r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+            r#"<Default Extension="png" ContentType="image/png"/><Default Extension="jpeg" ContentType="image/jpeg"/>"#,
+            r#"<Default Extension="gif" ContentType="image/gif"/>{}</Types>"#
+        ),
+        overrides
+    );
+    let package_rels = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="{}"><Relationship Id="rId1" Type="{}/officeDocument" Target="word/document.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#,
+        REL_NS, REL_TYPE
+    );
+    let core = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>{}</dc:title></cp:coreProperties>"#,
+        xml_escape(title)
+    );
+
+    let mut files: Vec<(String, Vec<u8>)> = vec![
+        ("[Content_Types].xml".to_string(), content_types.into_bytes()),
+        ("_rels/.rels".to_string(), package_rels.into_bytes()),
+        ("docProps/core.xml".to_string(), core.into_bytes()),
+        ("word/document.xml".to_string(), document.into_bytes()),
+        ("word/styles.xml".to_string(), docx_styles(profile).into_bytes()),
+        ("word/_rels/document.xml.rels".to_string(), document_rels.into_bytes()),
+    ];
+    if let Some(header) = header {
+        files.push(("word/header1.xml".to_string(), ctx.header_footer("hdr", header, title).into_bytes()));
+    }
+    if let Some(footer) = footer {
+        files.push(("word/footer1.xml".to_string(), ctx.header_footer("ftr", footer, title).into_bytes()));
+    }
+    for (name, data) in std::mem::take(&mut ctx.media) {
+        files.push((format!("word/media/{}", name), data));
+    }
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    for (name, data) in files {
+        zip.start_file(name, options).map_err(|e| format!("写入 DOCX 失败: {}", e))?;
+        zip.write_all(&data).map_err(|e| format!("写入 DOCX 失败: {}", e))?;
+    }
+    let cursor = zip.finish().map_err(|e| format!("写入 DOCX 失败: {}", e))?;
+    Ok(cursor.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    const MARKDOWN: &str = "# 标题 <1>\n\n正文 a < b & \"c\"\u{1}\tend [链接](https://example.com/?a=1&b=2)\n\n\
+                            | 名称 | 值 |\n|:--|--:|\n| x & y | 1 |\n\n```\nint a = b < c;\n```\n";
+
+    fn docx_parts(data: &[u8]) -> Vec<(String, String)> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(data)).unwrap();
+        (0..archive.len())
+            .map(|i| {
+                let mut file = archive.by_index(i).unwrap();
+                let mut content = String::new();
+                file.read_to_string(&mut content).unwrap_or_default();
+                (file.name().to_string(), content)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(html_escape(r#"<a href="x">&</a>"#), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+        assert_eq!(xml_escape("a\u{1}\u{1F}<&>\"\t\n"), "a&lt;&amp;&gt;&quot;\t\n");
+    }
+
+    #[test]
+    fn html_is_self_contained_and_escaped() {
+        let html = markdown_to_html(MARKDOWN, "</title><script>", &MarkdownStyleProfile::default(), None).unwrap();
+        assert!(html.contains("<title>&lt;/title&gt;&lt;script&gt;</title>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("a &lt; b &amp;"));
+        assert!(html.contains("int a = b &lt; c;"));
+        assert!(html.contains("<style>"));
+    }
+
+    #[test]
+    fn docx_package_is_well_formed() {
+        let profile = MarkdownStyleProfile {
+            font_family: "A&B \"Sans\"".to_string(),
+            header: Some("{title}|<内部>|第 {page} 页".to_string()),
+            footer: Some("共 {pages} 页".to_string()),
+            ..MarkdownStyleProfile::default()
+        };
+        let data = markdown_to_docx(MARKDOWN, "报告 & <v1>", &profile, None).unwrap();
+        let parts = docx_parts(&data);
+        let names: Vec<&str> = parts.iter().map(|(name, _)| name.as_str()).collect();
+        for name in [
+            "[Content_Types].xml",
+            "_rels/.rels",
+            "docProps/core.xml",
+            "word/document.xml",
+            "word/styles.xml",
+            "word/_rels/document.xml.rels",
+            "word/header1.xml",
+            "word/footer1.xml",
+        ] {
+            assert!(names.contains(&name), "缺少 {}", name);
+        }
+        for (name, content) in &parts {
+            if let Err(e) = roxmltree::Document::parse(content) {
+                panic!("{} 不是有效的 XML: {}", name, e);
+            }
+        }
+
+        let xml = &parts.iter().find(|(name, _)| name == "word/document.xml").unwrap().1;
+        let doc = roxmltree::Document::parse(xml).unwrap();
+        let text: String = doc.descendants().filter(|n| n.has_tag_name((W_NS, "t"))).filter_map(|n| n.text()).collect();
+        assert!(text.contains("标题 <1>"));
+        assert!(text.contains("正文 a < b & \"c\""));
+        assert!(text.contains("x & y"));
+        assert!(text.contains("int a = b < c;"));
+        assert_eq!(doc.descendants().filter(|n| n.has_tag_name((W_NS, "tbl"))).count(), 1);
+        assert!(doc.descendants().any(|n| n.has_tag_name((W_NS, "headerReference"))));
+
+        let rels = &parts.iter().find(|(name, _)| name == "word/_rels/document.xml.rels").unwrap().1;
+        assert!(rels.contains("https://example.com/?a=1&amp;b=2"));
+        let header = &parts.iter().find(|(name, _)| name == "word/header1.xml").unwrap().1;
+        assert!(header.contains(" PAGE ") && header.contains("&lt;内部&gt;"));
+        let core = &parts.iter().find(|(name, _)| name == "docProps/core.xml").unwrap().1;
+        assert!(core.contains("报告 &amp; &lt;v1&gt;"));
+    }
+}
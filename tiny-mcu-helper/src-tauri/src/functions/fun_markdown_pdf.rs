@@ -0,0 +1,1194 @@
+use printpdf::path::PaintMode;
+use printpdf::{
+    Color, ColorBits, ColorSpace, Image, ImageFilter, ImageTransform, ImageXObject, IndirectFontRef, Line, Mm,
+    PdfDocument, PdfLayerReference, Point, Px, Rect, Rgb, TextRenderingMode,
+};
+use pulldown_cmark::Alignment;
+use std::io::{BufWriter, Cursor};
+use std::path::Path;
+use ttf_parser::{Face, GlyphId};
+
+use crate::functions::fun_markdown_export::{
+    is_cjk, jpeg_frame, markdown_load_image, markdown_parse_blocks, markdown_template_parts, MarkdownImage, MdBlock,
+    MdSpan, MarkdownStyleProfile, SpanStyle, TemplatePiece, HEADING_SCALE,
+};
+
+const PT_PER_MM: f32 = 72.0 / 25.4;
+
+// 字体槽位
+const FONT_BODY: usize = 0;
+const FONT_CJK: usize = 1;
+const FONT_BOLD: usize = 2;
+const FONT_MONO: usize = 3;
+
+// 未指定中文字体时依次查找的系统字体（TTC 取第一个字体）
+const SYSTEM_CJK_FONTS: &[&str] = &[
+    "C:\\Windows\\Fonts\\simhei.ttf",
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\simsun.ttc",
+    "C:\\Windows\\Fonts\\Deng.ttf",
+    "/System/Library/Fonts/STHeiti Medium.ttc",
+    "/System/Library/Fonts/Supplemental/Arial Unicode.ttf",
+    "/Library/Fonts/Arial Unicode.ttf",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-zenhei.ttc",
+    "/usr/share/fonts/wqy-microhei/wqy-microhei.ttc",
+    "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+    "/usr/share/fonts/truetype/arphic/uming.ttc",
+];
+
+type Rgbf = (f32, f32, f32);
+
+const TEXT_COLOR: Rgbf = (0.13, 0.13, 0.13);
+const QUOTE_COLOR: Rgbf = (0.33, 0.33, 0.33);
+const MUTED_COLOR: Rgbf = (0.47, 0.47, 0.47);
+const BORDER_COLOR: Rgbf = (0.73, 0.73, 0.73);
+const CODE_BACKGROUND: Rgbf = (0.96, 0.96, 0.96);
+
+/// 读取字体文件：TTC 取第一个字体拆成独立 TTF；PDF 以 TrueType 方式嵌入，不支持 CFF 轮廓
+pub fn pdf_load_font(path: &str) -> Result<Vec<u8>, String> {
+    let data = std::fs::read(path).map_err(|e| format!("读取字体文件失败 {}: {}", path, e))?;
+    let data = if data.starts_with(b"ttcf") {
+        font_from_collection(&data, 0).ok_or(format!("无法解析字体集合 {}", path))?
+    } else {
+        data
+    };
+    if data.starts_with(b"OTTO") {
+        return Err(format!("{} 为 CFF 轮廓的 OpenType 字体，PDF 导出请使用 TrueType 字体（.ttf / .ttc）", path));
+    }
+    Face::parse(&data, 0).map_err(|e| format!("无法解析字体 {}: {}", path, e))?;
+    Ok(data)
+}
+
+// 从 TTC 中取出第 index 个字体，重新排列表数据为独立的 sfnt 文件
+fn font_from_collection(data: &[u8], index: usize) -> Option<Vec<u8>> {
+    let u16_at = |pos: usize| data.get(pos..pos + 2).map(|b| u16::from_be_bytes([b[0], b[1]]));
+    let u32_at = |pos: usize| data.get(pos..pos + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+    if index >= u32_at(8)? as usize {
+        return None;
+    }
+    let offset = u32_at(12 + index * 4)? as usize;
+    let num_tables = u16_at(offset + 4)? as usize;
+    let header_len = 12 + num_tables * 16;
+    let mut out = data.get(offset..offset + header_len)?.to_vec();
+    for table in 0..num_tables {
+        let record = offset + 12 + table * 16;
+        let table_offset = u32_at(record + 8)? as usize;
+        let table_len = u32_at(record + 12)? as usize;
+        let new_offset = out.len() as u32;
+        out[12 + table * 16 + 8..12 + table * 16 + 12].copy_from_slice(&new_offset.to_be_bytes());
+        out.extend_from_slice(data.get(table_offset..table_offset + table_len)?);
+        while out.len() % 4 != 0 {
+            out.push(0);
+        }
+    }
+    Some(out)
+}
+
+// 查找可用的系统中文字体
+fn pdf_system_cjk_font() -> Option<Vec<u8>> {
+    SYSTEM_CJK_FONTS
+        .iter()
+        .filter(|p| Path::new(p).exists())
+        .find_map(|p| pdf_load_font(p).ok())
+}
+
+// 按样式载入字体数据：[正文, 中文, 粗体, 代码]
+fn pdf_font_data(profile: &MarkdownStyleProfile) -> Result<[Option<Vec<u8>>; 4], String> {
+    let load = |path: &Option<String>| -> Result<Option<Vec<u8>>, String> {
+        match path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+            Some(path) => pdf_load_font(path).map(Some),
+            None => Ok(None),
+        }
+    };
+    let cjk = match load(&profile.cjk_font_path)? {
+        Some(data) => data,
+        None => pdf_system_cjk_font().ok_or("未找到可用的中文字体，请在样式中设置中文字体文件路径")?,
+    };
+    Ok([
+        load(&profile.font_path)?,
+        Some(cjk),
+        load(&profile.bold_font_path)?,
+        load(&profile.mono_font_path)?,
+    ])
+}
+
+// 字体度量
+struct PdfFonts<'a> {
+    faces: [Option<Face<'a>>; 4],
+}
+
+impl PdfFonts<'_> {
+    // 选择字形所用字体：优先按样式选择，缺字时退回中文字体；返回 (字体, 是否需要描边加粗)
+    fn pick(&self, ch: char, bold: bool, mono: bool) -> (usize, bool) {
+        let preferred: &[usize] = if mono {
+            &[FONT_MONO, FONT_BODY, FONT_CJK]
+        } else if bold {
+            &[FONT_BOLD, FONT_BODY, FONT_CJK]
+        } else {
+            &[FONT_BODY, FONT_CJK]
+        };
+        let font = preferred
+            .iter()
+            .copied()
+            .find(|&i| {
+                self.faces[i]
+                    .as_ref()
+                    .map(|f| ch.is_whitespace() || f.glyph_index(ch).is_some())
+                    .unwrap_or(false)
+            })
+            .unwrap_or(FONT_CJK);
+        (font, bold && font != FONT_BOLD)
+    }
+
+    // 字形宽度（pt），字体中没有的字符不会输出，宽度为 0
+    fn advance(&self, font: usize, ch: char, size: f32) -> f32 {
+        let face = match self.faces[font].as_ref() {
+            Some(face) => face,
+            None => return 0.0,
+        };
+        let ch = if ch == '\t' { ' ' } else { ch };
+        // 缺字时按 .notdef 的宽度计算，与阅读器显示的方框一致
+        let id = face.glyph_index(ch).unwrap_or(GlyphId(0));
+        face.glyph_hor_advance(id)
+            .map(|adv| adv as f32 / face.units_per_em() as f32 * size)
+            .unwrap_or(0.0)
+    }
+}
+
+// 排版用的文字样式
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TextStyle {
+    size: f32,
+    bold: bool,
+    mono: bool,
+    code_background: bool, // 行内代码底色
+    underline: bool,
+    strike: bool,
+    color: Rgbf,
+}
+
+// 排版后的单个字符
+#[derive(Debug, Clone)]
+struct Glyph {
+    ch: char,
+    font: usize,
+    faux_bold: bool,
+    style: TextStyle,
+    width: f32,
+}
+
+enum DrawOp {
+    Text {
+        x: f32,
+        y: f32,
+        size: f32,
+        font: usize,
+        faux_bold: bool,
+        color: Rgbf,
+        text: String,
+    },
+    Fill {
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        color: Rgbf,
+    },
+    Line {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+        width: f32,
+        color: Rgbf,
+    },
+    Image {
+        index: usize,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+    },
+}
+
+fn is_closing_punct(c: char) -> bool {
+    "，。、；：？！）》」』】〕…—,.;:?!)]}%".contains(c)
+}
+
+fn is_opening_punct(c: char) -> bool {
+    "（《「『【〔([{".contains(c)
+}
+
+// 是否允许在第 i 个字符前换行：空格之后、中文字符前后（避头尾标点除外）
+fn can_break_before(glyphs: &[Glyph], i: usize) -> bool {
+    let prev = glyphs[i - 1].ch;
+    let cur = glyphs[i].ch;
+    if cur == ' ' {
+        return false;
+    }
+    if prev == ' ' {
+        return true;
+    }
+    if is_closing_punct(cur) || is_opening_punct(prev) {
+        return false;
+    }
+    is_cjk(cur) || is_cjk(prev)
+}
+
+// 贪心断行，返回每行的字符区间；'\n' 强制换行
+fn break_lines(glyphs: &[Glyph], max_width: f32) -> Vec<(usize, usize)> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut width = 0.0;
+    let mut last_break = None;
+    let mut i = 0;
+    while i < glyphs.len() {
+        let glyph = &glyphs[i];
+        if glyph.ch == '\n' {
+            lines.push((start, i));
+            start = i + 1;
+            width = 0.0;
+            last_break = None;
+            i += 1;
+            continue;
+        }
+        if i > start && can_break_before(glyphs, i) {
+            last_break = Some(i);
+        }
+        if width + glyph.width > max_width && i > start {
+            let end = last_break.filter(|&b| b > start).unwrap_or(i);
+            lines.push((start, end));
+            start = end;
+            while start < glyphs.len() && glyphs[start].ch == ' ' {
+                start += 1;
+            }
+            last_break = None;
+            if start > i {
+                i = start;
+                width = 0.0;
+            } else {
+                width = glyphs[start..i].iter().map(|g| g.width).sum();
+            }
+            continue;
+        }
+        width += glyph.width;
+        i += 1;
+    }
+    if start < glyphs.len() || lines.is_empty() {
+        lines.push((start, glyphs.len()));
+    }
+    lines
+}
+
+struct PdfLayout<'a> {
+    fonts: &'a PdfFonts<'a>,
+    profile: &'a MarkdownStyleProfile,
+    base_dir: Option<&'a Path>,
+    accent: Rgbf,
+    top: f32,
+    bottom: f32,
+    pages: Vec<Vec<DrawOp>>,
+    page: usize, // 当前输出的页
+    y: f32, // 当前页剩余区域的上沿（pt，自页面底部起）
+    images: Vec<Option<ImageXObject>>,
+    marker: Option<(String, f32, TextStyle)>, // 待输出的列表符号，画在下一行文字的基线上
+}
+
+impl PdfLayout<'_> {
+    fn new_page(&mut self) {
+        self.pages.push(Vec::new());
+        self.page = self.pages.len() - 1;
+        self.y = self.top;
+    }
+
+    fn ops(&mut self) -> &mut Vec<DrawOp> {
+        &mut self.pages[self.page]
+    }
+
+    fn at_page_top(&self) -> bool {
+        self.y >= self.top - 0.01
+    }
+
+    // 剩余高度不足时换页
+    fn ensure(&mut self, height: f32) {
+        if self.y - height < self.bottom && !self.at_page_top() {
+            self.new_page();
+        }
+    }
+
+    // 块间距，页首不留空
+    fn space(&mut self, height: f32) {
+        if !self.at_page_top() {
+            self.y = (self.y - height).max(self.bottom);
+        }
+    }
+
+    fn base_style(&self, color: Rgbf) -> TextStyle {
+        TextStyle {
+            size: self.profile.font_size,
+            bold: false,
+            mono: false,
+            code_background: false,
+            underline: false,
+            strike: false,
+            color,
+        }
+    }
+
+    fn shape_text(&self, text: &str, style: TextStyle, out: &mut Vec<Glyph>) {
+        for ch in text.chars() {
+            let ch = if ch == '\r' { continue } else if ch == '\t' { ' ' } else { ch };
+            let (font, faux_bold) = self.fonts.pick(ch, style.bold, style.mono);
+            let width = if ch == '\n' { 0.0 } else { self.fonts.advance(font, ch, style.size) };
+            out.push(Glyph {
+                ch,
+                font,
+                faux_bold,
+                style,
+                width,
+            });
+        }
+    }
+
+    // 行内元素转为字符序列（图片在段落级别单独处理）
+    fn shape_spans(&self, spans: &[MdSpan], base: TextStyle) -> Vec<Glyph> {
+        let mut glyphs = Vec::new();
+        for span in spans {
+            match span {
+                MdSpan::Text(text, style) => {
+                    let style = self.span_style(style, base);
+                    self.shape_text(text, style, &mut glyphs);
+                }
+                MdSpan::Break => self.shape_text("\n", base, &mut glyphs),
+                MdSpan::Image { .. } => {}
+            }
+        }
+        glyphs
+    }
+
+    fn span_style(&self, span: &SpanStyle, base: TextStyle) -> TextStyle {
+        let mut style = base;
+        style.bold |= span.bold;
+        style.strike |= span.strike;
+        if span.code {
+            style.mono = true;
+            style.code_background = true;
+            style.size = self.profile.code_font_size.min(base.size);
+        }
+        if span.link.is_some() {
+            style.underline = true;
+            style.color = self.accent;
+        }
+        style
+    }
+
+    // 输出一行文字，行尾空格不计入对齐宽度
+    fn emit_line(&mut self, glyphs: &[Glyph], x_left: f32, x_right: f32, baseline: f32, align: Alignment) {
+        let end = glyphs.iter().rposition(|g| g.ch != ' ').map(|p| p + 1).unwrap_or(0);
+        let glyphs = &glyphs[..end];
+        let width: f32 = glyphs.iter().map(|g| g.width).sum();
+        let x = match align {
+            Alignment::Center => x_left + (x_right - x_left - width) / 2.0,
+            Alignment::Right => x_right - width,
+            _ => x_left,
+        };
+        if let Some((marker, marker_x, style)) = self.marker.take() {
+            let mut marker_glyphs = Vec::new();
+            self.shape_text(&marker, style, &mut marker_glyphs);
+            self.push_runs(&marker_glyphs, marker_x, baseline);
+        }
+        // 行内代码底色先画
+        let mut bg_x = x;
+        let mut backgrounds = Vec::new();
+        for glyph in glyphs {
+            if glyph.style.code_background {
+                let size = glyph.style.size;
+                backgrounds.push(DrawOp::Fill {
+                    x: bg_x,
+                    y: baseline - size * 0.28,
+                    w: glyph.width,
+                    h: size * 1.2,
+                    color: CODE_BACKGROUND,
+                });
+            }
+            bg_x += glyph.width;
+        }
+        self.ops().extend(backgrounds);
+        self.push_runs(glyphs, x, baseline);
+    }
+
+    // 按字体与样式分段输出文字及下划线、删除线，返回结束位置
+    fn push_runs(&mut self, glyphs: &[Glyph], mut x: f32, baseline: f32) -> f32 {
+        let mut start = 0;
+        while start < glyphs.len() {
+            let first = &glyphs[start];
+            let mut end = start + 1;
+            while end < glyphs.len()
+                && glyphs[end].font == first.font
+                && glyphs[end].faux_bold == first.faux_bold
+                && glyphs[end].style == first.style
+            {
+                end += 1;
+            }
+            let text: String = glyphs[start..end].iter().map(|g| g.ch).collect();
+            let width: f32 = glyphs[start..end].iter().map(|g| g.width).sum();
+            let style = first.style;
+            let op = DrawOp::Text {
+                x,
+                y: baseline,
+                size: style.size,
+                font: first.font,
+                faux_bold: first.faux_bold,
+                color: style.color,
+                text,
+            };
+            self.ops().push(op);
+            for (flag, offset) in [(style.underline, -0.12), (style.strike, 0.3)] {
+                if flag {
+                    let y = baseline + style.size * offset;
+                    self.ops().push(DrawOp::Line {
+                        x1: x,
+                        y1: y,
+                        x2: x + width,
+                        y2: y,
+                        width: style.size * 0.05,
+                        color: style.color,
+                    });
+                }
+            }
+            x += width;
+            start = end;
+        }
+        x
+    }
+
+    // 排版一段文字
+    fn lay_text(&mut self, glyphs: &[Glyph], x_left: f32, x_right: f32, align: Alignment, line_height: f32) {
+        for (start, end) in break_lines(glyphs, x_right - x_left) {
+            let line = &glyphs[start..end];
+            let size = line
+                .iter()
+                .map(|g| g.style.size)
+                .fold(0.0, f32::max)
+                .max(glyphs.first().map(|g| g.style.size).unwrap_or(self.profile.font_size));
+            let height = size * line_height;
+            self.ensure(height);
+            let baseline = self.y - (height - size) / 2.0 - size * 0.8;
+            self.emit_line(line, x_left, x_right, baseline, align);
+            self.y -= height;
+        }
+    }
+
+    // 段落：文字与图片依次排列
+    fn lay_paragraph(&mut self, spans: &[MdSpan], base: TextStyle, x_left: f32, x_right: f32) {
+        let mut pending: Vec<MdSpan> = Vec::new();
+        for span in spans {
+            if let MdSpan::Image { src, alt } = span {
+                if !pending.is_empty() {
+                    let glyphs = self.shape_spans(&pending, base);
+                    self.lay_text(&glyphs, x_left, x_right, Alignment::None, self.profile.line_height);
+                    pending.clear();
+                }
+                self.lay_image(src, alt, base, x_left, x_right);
+            } else {
+                pending.push(span.clone());
+            }
+        }
+        if !pending.is_empty() || spans.is_empty() {
+            let glyphs = self.shape_spans(&pending, base);
+            self.lay_text(&glyphs, x_left, x_right, Alignment::None, self.profile.line_height);
+        }
+    }
+
+    // 图片按 96 DPI 换算，超出版心时等比缩小；无法读取时输出说明文字
+    fn lay_image(&mut self, src: &str, alt: &str, base: TextStyle, x_left: f32, x_right: f32) {
+        let image = markdown_load_image(src, self.base_dir).and_then(|image| pdf_image(&image).map(|x| (image, x)));
+        let (image, xobject) = match image {
+            Some(image) => image,
+            None => {
+                let label = format!("[图片: {}]", if alt.is_empty() { src } else { alt });
+                let mut glyphs = Vec::new();
+                self.shape_text(&label, TextStyle { color: MUTED_COLOR, ..base }, &mut glyphs);
+                self.lay_text(&glyphs, x_left, x_right, Alignment::None, self.profile.line_height);
+                return;
+            }
+        };
+        let mut w = image.width as f32 * 0.75;
+        let mut h = image.height as f32 * 0.75;
+        let max_w = x_right - x_left;
+        let max_h = (self.top - self.bottom) * 0.9;
+        let scale = (max_w / w).min(max_h / h).min(1.0);
+        w *= scale;
+        h *= scale;
+        self.ensure(h);
+        self.images.push(Some(xobject));
+        let index = self.images.len() - 1;
+        let y = self.y - h;
+        self.ops().push(DrawOp::Image { index, x: x_left, y, w, h });
+        self.y = y;
+    }
+
+    fn lay_blocks(&mut self, blocks: &[MdBlock], x_left: f32, x_right: f32, color: Rgbf) {
+        let font_size = self.profile.font_size;
+        let line_height = self.profile.line_height;
+        for block in blocks {
+            match block {
+                MdBlock::Heading(level, spans) => {
+                    let size = font_size * HEADING_SCALE[(*level).clamp(1, 6) - 1];
+                    self.space(size * 0.8);
+                    // 标题与下文至少两行放在同一页
+                    self.ensure(size * 1.3 + font_size * line_height * 2.0);
+                    let style = TextStyle {
+                        size,
+                        bold: true,
+                        color: self.accent,
+                        ..self.base_style(color)
+                    };
+                    let glyphs = self.shape_spans(spans, style);
+                    self.lay_text(&glyphs, x_left, x_right, Alignment::None, 1.3);
+                    if *level <= 2 {
+                        let y = self.y - 2.0;
+                        let accent = self.accent;
+                        self.ops().push(DrawOp::Line {
+                            x1: x_left,
+                            y1: y,
+                            x2: x_right,
+                            y2: y,
+                            width: 0.6,
+                            color: accent,
+                        });
+                        self.y -= 4.0;
+                    }
+                    self.space(size * 0.3);
+                }
+                MdBlock::Paragraph(spans) => {
+                    self.lay_paragraph(spans, self.base_style(color), x_left, x_right);
+                    self.space(font_size * 0.6);
+                }
+                MdBlock::Plain(spans) => {
+                    self.lay_paragraph(spans, self.base_style(color), x_left, x_right);
+                    self.space(font_size * 0.2);
+                }
+                MdBlock::Code(code) => {
+                    self.lay_code(code, x_left, x_right);
+                    self.space(font_size * 0.6);
+                }
+                MdBlock::Quote(inner) => {
+                    let start_page = self.pages.len() - 1;
+                    let start_y = self.y;
+                    self.lay_blocks(inner, x_left + 14.0, x_right, QUOTE_COLOR);
+                    // 左侧竖线可能跨页
+                    let end_page = self.pages.len() - 1;
+                    for page in start_page..=end_page {
+                        let y1 = if page == start_page { start_y } else { self.top };
+                        let y2 = if page == end_page { self.y } else { self.bottom };
+                        self.pages[page].push(DrawOp::Line {
+                            x1: x_left + 4.0,
+                            y1,
+                            x2: x_left + 4.0,
+                            y2,
+                            width: 2.5,
+                            color: BORDER_COLOR,
+                        });
+                    }
+                }
+                MdBlock::List(start, items) => {
+                    let indent = font_size * 1.8;
+                    for (index, item) in items.iter().enumerate() {
+                        let marker = match start {
+                            Some(start) => format!("{}.", start + index as u64),
+                            None => "•".to_string(),
+                        };
+                        self.marker = Some((marker, x_left + font_size * 0.3, self.base_style(color)));
+                        self.lay_blocks(item, x_left + indent, x_right, color);
+                        self.marker = None;
+                    }
+                    self.space(font_size * 0.4);
+                }
+                MdBlock::Table(aligns, rows) => {
+                    self.lay_table(aligns, rows, x_left, x_right, color);
+                    self.space(font_size * 0.6);
+                }
+                MdBlock::Rule => {
+                    self.space(font_size * 0.4);
+                    self.ensure(2.0);
+                    let y = self.y - 1.0;
+                    self.ops().push(DrawOp::Line {
+                        x1: x_left,
+                        y1: y,
+                        x2: x_right,
+                        y2: y,
+                        width: 0.5,
+                        color: BORDER_COLOR,
+                    });
+                    self.y -= 2.0;
+                    self.space(font_size * 0.8);
+                }
+            }
+        }
+    }
+
+    // 代码块：等宽字体、灰色底色，超长行折行
+    fn lay_code(&mut self, code: &str, x_left: f32, x_right: f32) {
+        let padding = 6.0;
+        let style = TextStyle {
+            size: self.profile.code_font_size,
+            mono: true,
+            ..self.base_style(TEXT_COLOR)
+        };
+        let mut glyphs = Vec::new();
+        self.shape_text(code, style, &mut glyphs);
+        let height = style.size * 1.4;
+        let lines = break_lines(&glyphs, x_right - x_left - padding * 2.0);
+        let count = lines.len();
+        for (index, (start, end)) in lines.into_iter().enumerate() {
+            let top_pad = if index == 0 { padding } else { 0.0 };
+            let bottom_pad = if index + 1 == count { padding } else { 0.0 };
+            self.ensure(height + top_pad + bottom_pad);
+            let top = if self.at_page_top() { 0.0 } else { top_pad };
+            let y = self.y - height - top - bottom_pad;
+            self.ops().push(DrawOp::Fill {
+                x: x_left,
+                y,
+                w: x_right - x_left,
+                h: height + top + bottom_pad,
+                color: CODE_BACKGROUND,
+            });
+            self.y -= top;
+            let baseline = self.y - (height - style.size) / 2.0 - style.size * 0.8;
+            self.emit_line(&glyphs[start..end], x_left + padding, x_right - padding, baseline, Alignment::None);
+            self.y -= height + bottom_pad;
+        }
+    }
+
+    // 表格：列宽按内容比例分配，跨页时重复表头
+    fn lay_table(&mut self, aligns: &[Alignment], rows: &[Vec<Vec<MdSpan>>], x_left: f32, x_right: f32, color: Rgbf) {
+        let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+        let padding = 4.0;
+        let base = self.base_style(color);
+        let header_style = TextStyle { bold: true, ..base };
+        let shaped: Vec<Vec<Vec<Glyph>>> = rows
+            .iter()
+            .enumerate()
+            .map(|(row_index, row)| {
+                (0..columns)
+                    .map(|c| {
+                        let style = if row_index == 0 { header_style } else { base };
+                        row.get(c).map(|cell| self.shape_spans(cell, style)).unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // 列宽：内容自然宽度，最小 36pt，按比例缩放到版心宽度
+        let available = x_right - x_left;
+        let natural: Vec<f32> = (0..columns)
+            .map(|c| {
+                shaped
+                    .iter()
+                    .map(|row| row[c].iter().map(|g| g.width).sum::<f32>())
+                    .fold(0.0, f32::max)
+                    .max(36.0 - padding * 2.0)
+                    + padding * 2.0
+            })
+            .collect();
+        let total: f32 = natural.iter().sum();
+        let widths: Vec<f32> = if total <= available {
+            let extra = (available - total) / columns as f32;
+            natural.iter().map(|w| w + extra).collect()
+        } else {
+            let scaled: Vec<f32> = natural.iter().map(|w| (w * available / total).max(36.0)).collect();
+            let sum: f32 = scaled.iter().sum();
+            scaled.iter().map(|w| w * available / sum).collect()
+        };
+        let line_height = self.profile.line_height.min(1.4);
+        let header_fill = {
+            let (r, g, b) = self.accent;
+            let tint = |c: f32| 1.0 - (1.0 - c) * 0.12;
+            (tint(r), tint(g), tint(b))
+        };
+
+        for (row_index, row) in shaped.iter().enumerate() {
+            self.lay_table_row(row, row_index == 0, aligns, &widths, x_left, padding, line_height, header_fill, Some(&shaped[0]));
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn lay_table_row(
+        &mut self,
+        row: &[Vec<Glyph>],
+        header: bool,
+        aligns: &[Alignment],
+        widths: &[f32],
+        x_left: f32,
+        padding: f32,
+        line_height: f32,
+        header_fill: Rgbf,
+        header_row: Option<&Vec<Vec<Glyph>>>,
+    ) {
+        let layouts: Vec<Vec<(usize, usize)>> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, w)| break_lines(cell, w - padding * 2.0))
+            .collect();
+        let line = self.profile.font_size * line_height;
+        let height = layouts.iter().map(|l| l.len()).max().unwrap_or(1) as f32 * line + padding * 2.0;
+        if self.y - height < self.bottom && !self.at_page_top() {
+            self.new_page();
+            if !header {
+                if let Some(header_row) = header_row {
+                    self.lay_table_row(header_row, true, aligns, widths, x_left, padding, line_height, header_fill, None);
+                }
+            }
+        }
+        let top = self.y;
+        let total_width: f32 = widths.iter().sum();
+        if header {
+            self.ops().push(DrawOp::Fill {
+                x: x_left,
+                y: top - height,
+                w: total_width,
+                h: height,
+                color: header_fill,
+            });
+        }
+        let mut x = x_left;
+        for (column, (cell, lines)) in row.iter().zip(&layouts).enumerate() {
+            let align = aligns.get(column).copied().unwrap_or(Alignment::None);
+            for (index, (start, end)) in lines.iter().enumerate() {
+                let size = self.profile.font_size;
+                let baseline = top - padding - index as f32 * line - (line - size) / 2.0 - size * 0.8;
+                self.emit_line(&cell[*start..*end], x + padding, x + widths[column] - padding, baseline, align);
+            }
+            x += widths[column];
+        }
+        // 边框
+        let bottom = top - height;
+        let mut lines = vec![(x_left, top, x_left + total_width, top), (x_left, bottom, x_left + total_width, bottom)];
+        let mut x = x_left;
+        lines.push((x, top, x, bottom));
+        for w in widths {
+            x += w;
+            lines.push((x, top, x, bottom));
+        }
+        for (x1, y1, x2, y2) in lines {
+            self.ops().push(DrawOp::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                width: 0.5,
+                color: BORDER_COLOR,
+            });
+        }
+        self.y = bottom;
+    }
+}
+
+// 图片转为 PDF 图像对象：JPEG 直接嵌入，PNG 解码后按白底合成透明度
+fn pdf_image(image: &MarkdownImage) -> Option<ImageXObject> {
+    let (width, height, color_space, data, filter) = match image.extension {
+        "jpeg" => {
+            let (width, height, components) = jpeg_frame(&image.data)?;
+            let color_space = match components {
+                1 => ColorSpace::Greyscale,
+                4 => ColorSpace::Cmyk,
+                _ => ColorSpace::Rgb,
+            };
+            (width, height, color_space, image.data.clone(), Some(ImageFilter::DCT))
+        }
+        "png" => {
+            let mut decoder = png::Decoder::new(Cursor::new(&image.data));
+            decoder.set_transformations(png::Transformations::normalize_to_color8());
+            let mut reader = decoder.read_info().ok()?;
+            let mut buf = vec![0; reader.output_buffer_size()];
+            let info = reader.next_frame(&mut buf).ok()?;
+            buf.truncate(info.buffer_size());
+            let blend = |c: u8, a: u8| ((c as u32 * a as u32 + 255 * (255 - a as u32)) / 255) as u8;
+            let (color_space, data) = match info.color_type {
+                png::ColorType::Grayscale => (ColorSpace::Greyscale, buf),
+                png::ColorType::GrayscaleAlpha => {
+                    (ColorSpace::Greyscale, buf.chunks_exact(2).map(|p| blend(p[0], p[1])).collect())
+                }
+                png::ColorType::Rgba => (
+                    ColorSpace::Rgb,
+                    buf.chunks_exact(4)
+                        .flat_map(|p| [blend(p[0], p[3]), blend(p[1], p[3]), blend(p[2], p[3])])
+                        .collect(),
+                ),
+                _ => (ColorSpace::Rgb, buf),
+            };
+            (info.width, info.height, color_space, data, None)
+        }
+        _ => return None,
+    };
+    Some(ImageXObject {
+        width: Px(width as usize),
+        height: Px(height as usize),
+        color_space,
+        bits_per_component: ColorBits::Bit8,
+        interpolate: true,
+        image_data: data,
+        image_filter: filter,
+        smask: None,
+        clipping_bbox: None,
+    })
+}
+
+fn pdf_color((r, g, b): Rgbf) -> Color {
+    Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+fn mm(pt: f32) -> Mm {
+    Mm(pt / PT_PER_MM)
+}
+
+fn draw_op(layer: &PdfLayerReference, op: DrawOp, fonts: &[Option<IndirectFontRef>], images: &mut [Option<ImageXObject>]) {
+    match op {
+        DrawOp::Text {
+            x,
+            y,
+            size,
+            font,
+            faux_bold,
+            color,
+            text,
+        } => {
+            let font = match &fonts[font] {
+                Some(font) => font,
+                None => return,
+            };
+            layer.set_fill_color(pdf_color(color));
+            if faux_bold {
+                layer.set_text_rendering_mode(TextRenderingMode::FillStroke);
+                layer.set_outline_color(pdf_color(color));
+                layer.set_outline_thickness(size * 0.04);
+            }
+            layer.use_text(text, size, mm(x), mm(y), font);
+            if faux_bold {
+                layer.set_text_rendering_mode(TextRenderingMode::Fill);
+            }
+        }
+        DrawOp::Fill { x, y, w, h, color } => {
+            layer.set_fill_color(pdf_color(color));
+            layer.add_rect(Rect::new(mm(x), mm(y), mm(x + w), mm(y + h)).with_mode(PaintMode::Fill));
+        }
+        DrawOp::Line {
+            x1,
+            y1,
+            x2,
+            y2,
+            width,
+            color,
+        } => {
+            layer.set_outline_color(pdf_color(color));
+            layer.set_outline_thickness(width);
+            layer.add_line(Line {
+                points: vec![(Point::new(mm(x1), mm(y1)), false), (Point::new(mm(x2), mm(y2)), false)],
+                is_closed: false,
+            });
+        }
+        DrawOp::Image { index, x, y, w, h } => {
+            if let Some(xobject) = images[index].take() {
+                let (px_w, px_h) = (xobject.width.0 as f32, xobject.height.0 as f32);
+                // dpi 取 72 时 1 像素 = 1 pt，再按目标尺寸缩放
+                Image::from(xobject).add_to_layer(
+                    layer.clone(),
+                    ImageTransform {
+                        translate_x: Some(mm(x)),
+                        translate_y: Some(mm(y)),
+                        scale_x: Some(w / px_w),
+                        scale_y: Some(h / px_h),
+                        dpi: Some(72.0),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// 按样式生成 PDF：先排版得到全部页面，再绘制页眉页脚（需要总页数）
+pub fn markdown_to_styled_pdf(
+    markdown: &str,
+    title: &str,
+    profile: &MarkdownStyleProfile,
+    base_dir: Option<&Path>,
+    pdf_path: &Path,
+) -> Result<usize, String> {
+    let font_data = pdf_font_data(profile)?;
+    let faces = [0, 1, 2, 3].map(|i| font_data[i].as_ref().and_then(|d| Face::parse(d, 0).ok()));
+    let fonts = PdfFonts { faces };
+
+    let (page_w_mm, page_h_mm) = profile.page_size_mm();
+    let (page_w, page_h) = (page_w_mm * PT_PER_MM, page_h_mm * PT_PER_MM);
+    let (r, g, b) = profile.accent_rgb();
+    let mut layout = PdfLayout {
+        fonts: &fonts,
+        profile,
+        base_dir,
+        accent: (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0),
+        top: page_h - profile.margin_top_mm * PT_PER_MM,
+        bottom: profile.margin_bottom_mm * PT_PER_MM,
+        pages: Vec::new(),
+        page: 0,
+        y: 0.0,
+        images: Vec::new(),
+        marker: None,
+    };
+    layout.new_page();
+    let x_left = profile.margin_left_mm * PT_PER_MM;
+    let x_right = page_w - profile.margin_right_mm * PT_PER_MM;
+    layout.lay_blocks(&markdown_parse_blocks(markdown), x_left, x_right, TEXT_COLOR);
+
+    // 页眉页脚
+    let page_count = layout.pages.len();
+    let header_footer = [
+        (profile.header.as_deref(), page_h - profile.margin_top_mm * PT_PER_MM / 2.0),
+        (profile.footer.as_deref(), profile.margin_bottom_mm * PT_PER_MM / 2.0),
+    ];
+    for (template, baseline) in header_footer {
+        let template = match template.filter(|t| !t.trim().is_empty()) {
+            Some(template) => template,
+            None => continue,
+        };
+        let parts = markdown_template_parts(template, title);
+        let style = TextStyle {
+            size: 9.0,
+            color: MUTED_COLOR,
+            ..layout.base_style(MUTED_COLOR)
+        };
+        for page in 0..page_count {
+            layout.page = page;
+            for (part, align) in parts.iter().zip([Alignment::Left, Alignment::Center, Alignment::Right]) {
+                let text: String = part
+                    .iter()
+                    .map(|piece| match piece {
+                        TemplatePiece::Text(text) => text.clone(),
+                        TemplatePiece::Page => (page + 1).to_string(),
+                        TemplatePiece::Pages => page_count.to_string(),
+                    })
+                    .collect();
+                let mut glyphs = Vec::new();
+                layout.shape_text(&text, style, &mut glyphs);
+                layout.emit_line(&glyphs, x_left, x_right, baseline, align);
+            }
+        }
+    }
+
+    // 绘制
+    let (doc, first_page, first_layer) = PdfDocument::new(title, Mm(page_w_mm), Mm(page_h_mm), "正文");
+    let font_refs: Vec<Option<IndirectFontRef>> = font_data
+        .iter()
+        .map(|data| match data {
+            Some(data) => doc
+                .add_external_font(Cursor::new(data))
+                .map(Some)
+                .map_err(|e| format!("嵌入字体失败: {}", e)),
+            None => Ok(None),
+        })
+        .collect::<Result<_, _>>()?;
+    let mut images = std::mem::take(&mut layout.images);
+    for (index, ops) in std::mem::take(&mut layout.pages).into_iter().enumerate() {
+        let (page, layer) = if index == 0 {
+            (first_page, first_layer)
+        } else {
+            doc.add_page(Mm(page_w_mm), Mm(page_h_mm), "正文")
+        };
+        let layer = doc.get_page(page).get_layer(layer);
+        for op in ops {
+            draw_op(&layer, op, &font_refs, &mut images);
+        }
+    }
+    let file = std::fs::File::create(pdf_path).map_err(|e| format!("创建 PDF 文件失败: {}", e))?;
+    doc.save(&mut BufWriter::new(file)).map_err(|e| format!("PDF 生成失败: {}", e))?;
+    Ok(page_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::fun_markdown_export::MarkdownStyleProfile;
+
+    fn table_directory(tables: &[(&[u8; 4], Vec<u8>)], data_offset: usize) -> (Vec<u8>, Vec<u8>) {
+        let mut directory = Vec::new();
+        let mut data = Vec::new();
+        for (tag, table) in tables {
+            directory.extend_from_slice(*tag);
+            directory.extend_from_slice(&0u32.to_be_bytes());
+            directory.extend_from_slice(&((data_offset + data.len()) as u32).to_be_bytes());
+            directory.extend_from_slice(&(table.len() as u32).to_be_bytes());
+            data.extend_from_slice(table);
+            data.resize(data.len().next_multiple_of(4), 0);
+        }
+        (directory, data)
+    }
+
+    // 构造最小的 TrueType 字体：chars 中的字符依次映射到字形 1..，全部为空轮廓，宽度 advance
+    fn test_font_tables(chars: &[char], advance: u16) -> Vec<(&'static [u8; 4], Vec<u8>)> {
+        let glyphs = chars.len() as u16 + 1;
+        let mut head = vec![0u8; 54];
+        head[0..4].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+        head[12..16].copy_from_slice(&0x5F0F_3CF5u32.to_be_bytes());
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+        let mut hhea = vec![0u8; 36];
+        hhea[0..4].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+        hhea[4..6].copy_from_slice(&800i16.to_be_bytes());
+        hhea[6..8].copy_from_slice(&(-200i16).to_be_bytes());
+        hhea[34..36].copy_from_slice(&glyphs.to_be_bytes());
+        let mut maxp = 0x0000_5000u32.to_be_bytes().to_vec();
+        maxp.extend_from_slice(&glyphs.to_be_bytes());
+        let hmtx: Vec<u8> = (0..glyphs).flat_map(|_| [advance.to_be_bytes(), [0, 0]].concat()).collect();
+        let loca = vec![0u8; (glyphs as usize + 1) * 2];
+
+        // cmap 格式 4：每个字符一段，最后是 0xFFFF 结束段
+        let mut codes: Vec<(u16, u16)> = chars.iter().enumerate().map(|(i, c)| (*c as u16, i as u16 + 1)).collect();
+        codes.sort();
+        codes.push((0xFFFF, 0));
+        let seg_count = codes.len() as u16;
+        let mut subtable = Vec::new();
+        for value in [4, 16 + 8 * seg_count, 0, seg_count * 2, 0, 0, 0] {
+            subtable.extend_from_slice(&value.to_be_bytes());
+        }
+        codes.iter().for_each(|(code, _)| subtable.extend_from_slice(&code.to_be_bytes()));
+        subtable.extend_from_slice(&[0, 0]);
+        codes.iter().for_each(|(code, _)| subtable.extend_from_slice(&code.to_be_bytes()));
+        codes.iter().for_each(|(code, glyph)| {
+            let delta = if *code == 0xFFFF { 1 } else { glyph.wrapping_sub(*code) };
+            subtable.extend_from_slice(&delta.to_be_bytes());
+        });
+        codes.iter().for_each(|_| subtable.extend_from_slice(&[0, 0]));
+        let mut cmap = vec![0, 0, 0, 1, 0, 3, 0, 1, 0, 0, 0, 12];
+        cmap.extend_from_slice(&subtable);
+
+        vec![
+            (b"cmap", cmap),
+            (b"glyf", Vec::new()),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"loca", loca),
+            (b"maxp", maxp),
+        ]
+    }
+
+    fn test_font(chars: &[char], advance: u16) -> Vec<u8> {
+        let tables = test_font_tables(chars, advance);
+        let (directory, data) = table_directory(&tables, 12 + tables.len() * 16);
+        let mut font = vec![0, 1, 0, 0];
+        font.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+        font.extend_from_slice(&[0; 6]);
+        font.extend(directory);
+        font.extend(data);
+        font
+    }
+
+    // 两个字体共用一段表数据区的 TTC
+    fn test_collection(fonts: &[(&[char], u16)]) -> Vec<u8> {
+        let header_len = 12 + fonts.len() * 4;
+        let font_tables: Vec<_> = fonts.iter().map(|(chars, advance)| test_font_tables(chars, *advance)).collect();
+        let offset_tables_len: usize = font_tables.iter().map(|t| 12 + t.len() * 16).sum();
+        let mut out = b"ttcf".to_vec();
+        out.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+        out.extend_from_slice(&(fonts.len() as u32).to_be_bytes());
+        let mut directories = Vec::new();
+        let mut data = Vec::new();
+        for tables in &font_tables {
+            out.extend_from_slice(&((header_len + directories.len()) as u32).to_be_bytes());
+            let (directory, table_data) = table_directory(tables, header_len + offset_tables_len + data.len());
+            directories.extend_from_slice(&[0, 1, 0, 0]);
+            directories.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+            directories.extend_from_slice(&[0; 6]);
+            directories.extend(directory);
+            data.extend(table_data);
+        }
+        out.extend(directories);
+        out.extend(data);
+        out
+    }
+
+    fn temp_file(name: &str, data: &[u8]) -> String {
+        let path = std::env::temp_dir().join(format!("tmh-font-{}-{}", std::process::id(), name));
+        std::fs::write(&path, data).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn extracts_font_from_collection() {
+        let collection = test_collection(&[(&['A'], 500), (&['中', 'B'], 1000)]);
+        let second = font_from_collection(&collection, 1).unwrap();
+        let face = Face::parse(&second, 0).unwrap();
+        assert!(face.glyph_index('中').is_some() && face.glyph_index('B').is_some());
+        assert!(face.glyph_index('A').is_none());
+        assert_eq!(face.glyph_hor_advance(GlyphId(1)), Some(1000));
+        assert!(font_from_collection(&collection, 2).is_none());
+        assert!(font_from_collection(&collection[..40], 1).is_none());
+
+        // pdf_load_font 取集合中的第一个字体
+        let path = temp_file("collection.ttc", &collection);
+        let first = pdf_load_font(&path).unwrap();
+        assert!(Face::parse(&first, 0).unwrap().glyph_index('A').is_some());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_cff_and_missing_fonts() {
+        let mut cff = test_font(&['A'], 500);
+        cff[0..4].copy_from_slice(b"OTTO");
+        let path = temp_file("cff.otf", &cff);
+        assert!(pdf_load_font(&path).unwrap_err().contains("TrueType"));
+        std::fs::remove_file(path).unwrap();
+
+        let profile = MarkdownStyleProfile {
+            cjk_font_path: Some("/nonexistent/font.ttf".to_string()),
+            ..MarkdownStyleProfile::default()
+        };
+        assert!(pdf_font_data(&profile).unwrap_err().contains("读取字体文件失败"));
+    }
+
+    #[test]
+    fn falls_back_to_cjk_font() {
+        let latin = test_font(&['A', 'b'], 500);
+        let cjk = test_font(&['中', '文', 'A'], 1000);
+        let fonts = PdfFonts {
+            faces: [Face::parse(&latin, 0).ok(), Face::parse(&cjk, 0).ok(), None, None],
+        };
+        assert_eq!(fonts.pick('A', false, false), (FONT_BODY, false));
+        assert_eq!(fonts.pick('中', false, false), (FONT_CJK, false));
+        // 没有粗体、代码字体时退回正文字体，粗体改为描边
+        assert_eq!(fonts.pick('A', true, false), (FONT_BODY, true));
+        assert_eq!(fonts.pick('文', true, false), (FONT_CJK, true));
+        assert_eq!(fonts.pick('b', false, true), (FONT_BODY, false));
+        // 所有字体都缺字时用中文字体输出
+        assert_eq!(fonts.pick('Z', false, false), (FONT_CJK, false));
+        assert_eq!(fonts.advance(FONT_BODY, 'A', 10.0), 5.0);
+        assert_eq!(fonts.advance(FONT_CJK, '中', 10.0), 10.0);
+
+        // 只有中文字体时全部使用中文字体
+        let cjk_only = PdfFonts {
+            faces: [None, Face::parse(&cjk, 0).ok(), None, None],
+        };
+        assert_eq!(cjk_only.pick('A', false, false), (FONT_CJK, false));
+    }
+
+    #[test]
+    fn renders_with_profile_fonts() {
+        let path = temp_file("cjk.ttf", &test_font(&['中', '文', '第', '页', 'A', 'B', '1', '2', ' '], 1000));
+        let profile = MarkdownStyleProfile {
+            cjk_font_path: Some(path.clone()),
+            footer: Some("第 {page} 页".to_string()),
+            ..MarkdownStyleProfile::default()
+        };
+        let pdf_path = std::env::temp_dir().join(format!("tmh-styled-{}.pdf", std::process::id()));
+        let markdown = format!("# 中文\n\n{}\n\n| A | B |\n|--|--|\n| 1 | 2 |\n", "中文 AB ".repeat(400));
+        let pages = markdown_to_styled_pdf(&markdown, "中文", &profile, None, &pdf_path).unwrap();
+        assert!(pages > 1);
+        assert!(std::fs::read(&pdf_path).unwrap().starts_with(b"%PDF-"));
+        std::fs::remove_file(pdf_path).unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,797 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::utils::util_write_generate_file;
+
+/// 存储器类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutRegionKind {
+    Flash,
+    Ram,
+}
+
+/// 物理存储区域（如片内 Flash、SRAM、CCM）
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LayoutRegion {
+    pub name: String,
+    pub kind: LayoutRegionKind,
+    #[serde(deserialize_with = "de_size")]
+    pub origin: u64,
+    #[serde(deserialize_with = "de_size")]
+    pub length: u64,
+    #[serde(default, deserialize_with = "de_size_opt")]
+    pub sector_size: Option<u64>, // 均匀扇区大小（擦除粒度）
+    #[serde(default, deserialize_with = "de_size_vec")]
+    pub sectors: Option<Vec<u64>>, // 非均匀扇区（如 STM32F4），依次列出各扇区大小，优先于 sector_size
+    #[serde(default, deserialize_with = "de_size_opt")]
+    pub page_size: Option<u64>, // 编程粒度，分区大小须为其整数倍
+}
+
+/// 分区用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutPartitionRole {
+    Bootloader,
+    App,    // 应用（A/B 槽各建一个分区）
+    Config,
+    Eeprom, // EEPROM 模拟，至少需要两个扇区轮换
+    Data,
+    Shared, // 如 bootloader 与应用共享的 noinit RAM
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LayoutPartition {
+    pub name: String,
+    pub region: String,
+    pub role: LayoutPartitionRole,
+    #[serde(default, deserialize_with = "de_size_opt")]
+    pub offset: Option<u64>, // 相对区域起始的偏移，为空时紧接上一个分区
+    #[serde(default, deserialize_with = "de_size_opt")]
+    pub size: Option<u64>, // 为空时延伸到下一个固定偏移的分区或区域末尾
+    #[serde(default, deserialize_with = "de_size_opt")]
+    pub align: Option<u64>, // 额外的起始地址对齐，如向量表对齐
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemoryLayout {
+    pub name: String,
+    pub description: Option<String>,
+    pub regions: Vec<LayoutRegion>,
+    pub partitions: Vec<LayoutPartition>,
+}
+
+// ==================== 数值解析 ====================
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SizeValue {
+    Number(u64),
+    Text(String),
+}
+
+/// 解析地址/大小："0x08000000"、"0b1000"、"65536"、"64K"、"1M"（十六进制不可带单位后缀）
+pub fn layout_parse_size(text: &str) -> Result<u64, String> {
+    let text = text.trim().replace('_', "");
+    let upper = text.to_uppercase();
+    let (number, scale) = if let Some(n) = upper.strip_suffix("KB").or(upper.strip_suffix('K')) {
+        (n, 1024)
+    } else if let Some(n) = upper.strip_suffix("MB").or(upper.strip_suffix('M')) {
+        (n, 1024 * 1024)
+    } else {
+        (upper.as_str(), 1)
+    };
+    let value = if let Some(hex) = number.strip_prefix("0X") {
+        if scale != 1 {
+            return Err(format!("无效的大小: {}", text));
+        }
+        u64::from_str_radix(hex, 16)
+    } else if let Some(bin) = number.strip_prefix("0B") {
+        u64::from_str_radix(bin, 2)
+    } else {
+        number.trim().parse::<u64>()
+    }
+    .map_err(|_| format!("无效的大小: {}", text))?;
+    value.checked_mul(scale).ok_or(format!("数值溢出: {}", text))
+}
+
+fn size_value(value: SizeValue) -> Result<u64, String> {
+    match value {
+        SizeValue::Number(n) => Ok(n),
+        SizeValue::Text(text) => layout_parse_size(&text),
+    }
+}
+
+fn de_size<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    size_value(SizeValue::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+}
+
+fn de_size_opt<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    Option::<SizeValue>::deserialize(deserializer)?
+        .map(size_value)
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+fn de_size_vec<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u64>>, D::Error> {
+    Option::<Vec<SizeValue>>::deserialize(deserializer)?
+        .map(|values| values.into_iter().map(size_value).collect::<Result<Vec<_>, _>>())
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+/// 解析 "0x0000-0x4000" 形式的地址区间（结束地址不含），返回 (起始, 长度)
+pub fn layout_parse_range(text: &str) -> Result<(u64, u64), String> {
+    let (start, end) = text
+        .split_once('-')
+        .or_else(|| text.split_once('~'))
+        .ok_or(format!("无效的地址区间: {}", text))?;
+    let start = layout_parse_size(start)?;
+    let end = layout_parse_size(end)?;
+    if end <= start {
+        return Err(format!("地址区间结束地址须大于起始地址: {}", text));
+    }
+    Ok((start, end - start))
+}
+
+// ==================== 规划与校验 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutIssueLevel {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutIssue {
+    pub level: LayoutIssueLevel,
+    pub partition: Option<String>,
+    pub message: String,
+}
+
+/// 计算出绝对地址后的分区
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedPartition {
+    pub name: String,
+    pub region: String,
+    pub role: LayoutPartitionRole,
+    pub start: u64,
+    pub size: u64,
+    pub end: u64, // 不含
+    pub first_sector: Option<usize>,
+    pub sector_count: Option<usize>,
+}
+
+/// 区域中未分配的空间
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutFreeRange {
+    pub region: String,
+    pub start: u64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryLayoutPlan {
+    pub layout: MemoryLayout,
+    pub partitions: Vec<PlannedPartition>,
+    pub free: Vec<LayoutFreeRange>,
+    pub issues: Vec<LayoutIssue>,
+}
+
+impl MemoryLayoutPlan {
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.level == LayoutIssueLevel::Error)
+    }
+}
+
+impl LayoutRegion {
+    fn end(&self) -> u64 {
+        self.origin + self.length
+    }
+
+    // 各扇区的起始地址（含区域末尾），无扇区信息时为空
+    fn sector_bounds(&self) -> Vec<u64> {
+        let mut bounds = vec![self.origin];
+        match (&self.sectors, self.sector_size) {
+            (Some(sectors), _) if !sectors.is_empty() => {
+                for size in sectors {
+                    bounds.push(bounds.last().unwrap() + size);
+                }
+            }
+            (_, Some(size)) if size > 0 => {
+                let mut addr = self.origin + size;
+                while addr <= self.end() {
+                    bounds.push(addr);
+                    addr += size;
+                }
+            }
+            _ => return vec![],
+        }
+        bounds
+    }
+}
+
+// 不小于 addr 的第一个扇区边界
+fn next_bound(bounds: &[u64], addr: u64) -> u64 {
+    bounds.iter().copied().find(|b| *b >= addr).unwrap_or(addr)
+}
+
+fn align_up(value: u64, align: u64) -> u64 {
+    if align <= 1 {
+        value
+    } else {
+        value.div_ceil(align) * align
+    }
+}
+
+fn issue(issues: &mut Vec<LayoutIssue>, level: LayoutIssueLevel, partition: Option<&str>, message: String) {
+    issues.push(LayoutIssue {
+        level,
+        partition: partition.map(|p| p.to_string()),
+        message,
+    });
+}
+
+/// 计算分区地址并校验对齐、越界与重叠
+pub fn memory_layout_plan(layout: &MemoryLayout) -> MemoryLayoutPlan {
+    use LayoutIssueLevel::{Error, Warning};
+    let mut issues = Vec::new();
+
+    // 区域自身检查
+    for (i, region) in layout.regions.iter().enumerate() {
+        if region.length == 0 {
+            issue(&mut issues, Error, None, format!("区域 {} 长度为 0", region.name));
+        }
+        if layout.regions[..i].iter().any(|r| r.name.eq_ignore_ascii_case(&region.name)) {
+            issue(&mut issues, Error, None, format!("区域名称重复: {}", region.name));
+        }
+        for other in &layout.regions[..i] {
+            if region.origin < other.end() && other.origin < region.end() {
+                issue(
+                    &mut issues,
+                    Error,
+                    None,
+                    format!("区域 {} 与 {} 地址重叠", region.name, other.name),
+                );
+            }
+        }
+        if let Some(sectors) = &region.sectors {
+            let total: u64 = sectors.iter().sum();
+            if total != region.length {
+                issue(
+                    &mut issues,
+                    Error,
+                    None,
+                    format!("区域 {} 扇区合计 0x{:X} 与长度 0x{:X} 不一致", region.name, total, region.length),
+                );
+            }
+        } else if let Some(size) = region.sector_size {
+            if size == 0 || region.length % size != 0 {
+                issue(
+                    &mut issues,
+                    Error,
+                    None,
+                    format!("区域 {} 长度不是扇区大小 0x{:X} 的整数倍", region.name, size),
+                );
+            }
+        }
+        if region.kind == LayoutRegionKind::Flash && region.sector_bounds().is_empty() {
+            issue(
+                &mut issues,
+                Warning,
+                None,
+                format!("Flash 区域 {} 未设置扇区大小，无法检查擦除对齐", region.name),
+            );
+        }
+    }
+
+    // 依次放置分区
+    let mut partitions: Vec<PlannedPartition> = Vec::new();
+    for (i, part) in layout.partitions.iter().enumerate() {
+        let name = part.name.as_str();
+        if layout.partitions[..i].iter().any(|p| p.name.eq_ignore_ascii_case(name)) {
+            issue(&mut issues, Error, Some(name), format!("分区名称重复: {}", name));
+        }
+        let Some(region) = layout.regions.iter().find(|r| r.name.eq_ignore_ascii_case(&part.region)) else {
+            issue(&mut issues, Error, Some(name), format!("分区 {} 所属区域 {} 不存在", name, part.region));
+            continue;
+        };
+        let bounds = region.sector_bounds();
+        let is_flash = region.kind == LayoutRegionKind::Flash;
+
+        let start = match part.offset {
+            Some(offset) => region.origin + offset,
+            None => {
+                let cursor = partitions
+                    .iter()
+                    .filter(|p| p.region == region.name)
+                    .map(|p| p.end)
+                    .max()
+                    .unwrap_or(region.origin);
+                let cursor = if is_flash { next_bound(&bounds, cursor) } else { cursor };
+                region.origin + align_up(cursor - region.origin, part.align.unwrap_or(1))
+            }
+        };
+        let size = match part.size {
+            Some(size) => size,
+            None => {
+                // 延伸到后面第一个固定偏移分区或区域末尾
+                let limit = layout.partitions[i + 1..]
+                    .iter()
+                    .filter(|p| p.region.eq_ignore_ascii_case(&region.name))
+                    .filter_map(|p| p.offset.map(|o| region.origin + o))
+                    .filter(|addr| *addr > start)
+                    .min()
+                    .unwrap_or(region.end());
+                limit.saturating_sub(start)
+            }
+        };
+        let end = start + size;
+
+        if size == 0 {
+            issue(&mut issues, Error, Some(name), format!("分区 {} 大小为 0", name));
+        }
+        if start < region.origin || end > region.end() {
+            issue(
+                &mut issues,
+                Error,
+                Some(name),
+                format!(
+                    "分区 {} (0x{:08X}-0x{:08X}) 超出区域 {} (0x{:08X}-0x{:08X})",
+                    name,
+                    start,
+                    end,
+                    region.name,
+                    region.origin,
+                    region.end()
+                ),
+            );
+        }
+        if let Some(align) = part.align.filter(|a| *a > 1) {
+            if start % align != 0 {
+                issue(
+                    &mut issues,
+                    Error,
+                    Some(name),
+                    format!("分区 {} 起始地址 0x{:08X} 未按 0x{:X} 对齐", name, start, align),
+                );
+            }
+        }
+        if let Some(page) = region.page_size.filter(|p| *p > 1) {
+            if start.saturating_sub(region.origin) % page != 0 || size % page != 0 {
+                issue(
+                    &mut issues,
+                    Error,
+                    Some(name),
+                    format!("分区 {} 的起始地址或大小不是页大小 0x{:X} 的整数倍", name, page),
+                );
+            }
+        }
+
+        // Flash 分区必须按扇区边界划分，否则擦除一个分区会破坏相邻分区
+        let (mut first_sector, mut sector_count) = (None, None);
+        if is_flash && !bounds.is_empty() {
+            let first = bounds.iter().position(|b| *b == start);
+            let last = bounds.iter().position(|b| *b == end);
+            match (first, last) {
+                (Some(first), Some(last)) => {
+                    first_sector = Some(first);
+                    sector_count = Some(last - first);
+                }
+                _ => {
+                    let which = match (first, last) {
+                        (None, None) => "起始与结束地址",
+                        (None, _) => "起始地址",
+                        _ => "结束地址",
+                    };
+                    issue(
+                        &mut issues,
+                        Error,
+                        Some(name),
+                        format!("分区 {} 的{}不在扇区边界上，擦除时会影响相邻分区", name, which),
+                    );
+                }
+            }
+            if part.role == LayoutPartitionRole::Eeprom && sector_count.is_some_and(|c| c < 2) {
+                issue(
+                    &mut issues,
+                    Warning,
+                    Some(name),
+                    format!("EEPROM 模拟分区 {} 少于两个扇区，掉电时无法安全轮换", name),
+                );
+            }
+        }
+        if part.role == LayoutPartitionRole::Bootloader && is_flash && start != region.origin {
+            issue(
+                &mut issues,
+                Warning,
+                Some(name),
+                format!("Bootloader 分区 {} 不在区域 {} 起始地址，请确认启动地址", name, region.name),
+            );
+        }
+        if !is_flash && matches!(part.role, LayoutPartitionRole::Bootloader | LayoutPartitionRole::App) {
+            issue(
+                &mut issues,
+                Warning,
+                Some(name),
+                format!("分区 {} 为代码分区但位于 RAM 区域 {}", name, region.name),
+            );
+        }
+
+        partitions.push(PlannedPartition {
+            name: part.name.clone(),
+            region: region.name.clone(),
+            role: part.role,
+            start,
+            size,
+            end,
+            first_sector,
+            sector_count,
+        });
+    }
+
+    // 重叠检查
+    for (i, a) in partitions.iter().enumerate() {
+        for b in &partitions[i + 1..] {
+            if a.size > 0 && b.size > 0 && a.start < b.end && b.start < a.end {
+                issue(
+                    &mut issues,
+                    Error,
+                    Some(&b.name),
+                    format!(
+                        "分区 {} (0x{:08X}-0x{:08X}) 与 {} (0x{:08X}-0x{:08X}) 重叠",
+                        a.name, a.start, a.end, b.name, b.start, b.end
+                    ),
+                );
+            }
+        }
+    }
+
+    // 未分配空间
+    let mut free = Vec::new();
+    for region in &layout.regions {
+        let mut used: Vec<(u64, u64)> = partitions
+            .iter()
+            .filter(|p| p.region == region.name)
+            .map(|p| (p.start.max(region.origin), p.end.min(region.end())))
+            .collect();
+        used.sort();
+        let mut cursor = region.origin;
+        for (start, end) in used {
+            if start > cursor {
+                free.push(LayoutFreeRange {
+                    region: region.name.clone(),
+                    start: cursor,
+                    size: start - cursor,
+                });
+            }
+            cursor = cursor.max(end);
+        }
+        if cursor < region.end() {
+            free.push(LayoutFreeRange {
+                region: region.name.clone(),
+                start: cursor,
+                size: region.end() - cursor,
+            });
+        }
+    }
+
+    MemoryLayoutPlan {
+        layout: layout.clone(),
+        partitions,
+        free,
+        issues,
+    }
+}
+
+// ==================== 输出 ====================
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutOutputFormat {
+    LinkerMemory, // GNU ld MEMORY {} 块
+    CHeader,      // 分区地址宏
+    Markdown,
+}
+
+impl LayoutOutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LayoutOutputFormat::LinkerMemory => "ld",
+            LayoutOutputFormat::CHeader => "h",
+            LayoutOutputFormat::Markdown => "md",
+        }
+    }
+}
+
+fn macro_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+// 整 K/M 时使用 ld 的 K/M 后缀
+fn ld_size(size: u64) -> String {
+    if size >= 1024 * 1024 && size.is_multiple_of(1024 * 1024) {
+        format!("{}M", size / (1024 * 1024))
+    } else if size >= 1024 && size.is_multiple_of(1024) {
+        format!("{}K", size / 1024)
+    } else {
+        format!("0x{:X}", size)
+    }
+}
+
+fn human_size(size: u64) -> String {
+    if size >= 1024 * 1024 && size.is_multiple_of(1024 * 1024) {
+        format!("{} MB", size / (1024 * 1024))
+    } else if size >= 1024 && size.is_multiple_of(1024) {
+        format!("{} KB", size / 1024)
+    } else {
+        format!("{} B", size)
+    }
+}
+
+fn ld_attributes(region: &LayoutRegion, role: Option<LayoutPartitionRole>) -> &'static str {
+    match (region.kind, role) {
+        (LayoutRegionKind::Ram, _) => "rwx",
+        (LayoutRegionKind::Flash, Some(LayoutPartitionRole::Bootloader | LayoutPartitionRole::App)) => "rx",
+        (LayoutRegionKind::Flash, _) => "r",
+    }
+}
+
+/// 生成 GNU ld MEMORY 块。target 为正在链接的分区（如 app_a），该分区以所属区域名输出，
+/// 以便沿用厂商链接脚本中的 "> FLASH"；同区域的其他分区不输出。未指定 target 时列出全部 Flash 分区。
+/// RAM 区域扣除 Shared 分区后以区域名输出
+pub fn memory_layout_linker(plan: &MemoryLayoutPlan, target: Option<&str>) -> Result<String, String> {
+    let target = match target {
+        Some(name) => Some(
+            plan.partitions
+                .iter()
+                .find(|p| p.name.eq_ignore_ascii_case(name))
+                .ok_or(format!("分区 {} 不存在", name))?,
+        ),
+        None => None,
+    };
+    let mut out = format!("/* {} 内存布局，由 tiny-mcu-helper 生成 */\n", plan.layout.name);
+    if let Some(t) = target {
+        out.push_str(&format!("/* 链接目标: {} */\n", t.name));
+    }
+    out.push_str("MEMORY\n{\n");
+
+    let mut entries: Vec<(String, &'static str, u64, u64)> = Vec::new();
+    for region in &plan.layout.regions {
+        let parts: Vec<&PlannedPartition> = plan.partitions.iter().filter(|p| p.region == region.name).collect();
+        match target {
+            Some(t) if t.region == region.name => {
+                entries.push((macro_name(&region.name), ld_attributes(region, Some(t.role)), t.start, t.size));
+            }
+            _ if target.is_some() || region.kind == LayoutRegionKind::Ram => {
+                // RAM 及非目标区域：整块输出，Shared 分区单独列出并从整块中扣除（仅限位于两端的情况）
+                let mut start = region.origin;
+                let mut end = region.origin + region.length;
+                for p in parts.iter().filter(|p| p.role == LayoutPartitionRole::Shared) {
+                    if p.start == start {
+                        start = p.end;
+                    } else if p.end == end {
+                        end = p.start;
+                    }
+                    entries.push((macro_name(&p.name), ld_attributes(region, Some(p.role)), p.start, p.size));
+                }
+                entries.push((macro_name(&region.name), ld_attributes(region, None), start, end.saturating_sub(start)));
+            }
+            _ => {
+                if parts.is_empty() {
+                    entries.push((macro_name(&region.name), ld_attributes(region, None), region.origin, region.length));
+                }
+                for p in parts {
+                    entries.push((macro_name(&p.name), ld_attributes(region, Some(p.role)), p.start, p.size));
+                }
+            }
+        }
+    }
+    entries.sort_by_key(|e| e.2);
+    let width = entries.iter().map(|e| e.0.len() + e.1.len() + 3).max().unwrap_or(0);
+    for (name, attr, start, size) in entries {
+        let head = format!("{} ({})", name, attr);
+        out.push_str(&format!(
+            "    {:<width$} : ORIGIN = 0x{:08X}, LENGTH = {}\n",
+            head,
+            start,
+            ld_size(size),
+            width = width
+        ));
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
+
+/// 生成分区地址 C 头文件
+pub fn memory_layout_c_header(plan: &MemoryLayoutPlan) -> String {
+    let guard = format!("{}_MEMORY_LAYOUT_H", macro_name(&plan.layout.name));
+    let mut out = format!(
+        "/* {} 内存布局，由 tiny-mcu-helper 生成 */\n#ifndef {}\n#define {}\n\n",
+        plan.layout.name, guard, guard
+    );
+    let mut define = |name: String, value: String| {
+        out.push_str(&format!("#define {:<40} {}\n", name, value));
+    };
+    for region in &plan.layout.regions {
+        let prefix = format!("REGION_{}", macro_name(&region.name));
+        define(format!("{}_START", prefix), format!("0x{:08X}UL", region.origin));
+        define(format!("{}_SIZE", prefix), format!("0x{:08X}UL", region.length));
+        define(format!("{}_END", prefix), format!("0x{:08X}UL", region.end()));
+    }
+    for p in &plan.partitions {
+        let prefix = format!("PART_{}", macro_name(&p.name));
+        define(format!("{}_START", prefix), format!("0x{:08X}UL", p.start));
+        define(format!("{}_SIZE", prefix), format!("0x{:08X}UL", p.size));
+        define(format!("{}_END", prefix), format!("0x{:08X}UL", p.end));
+        if let (Some(first), Some(count)) = (p.first_sector, p.sector_count) {
+            define(format!("{}_FIRST_SECTOR", prefix), format!("{}U", first));
+            define(format!("{}_SECTOR_COUNT", prefix), format!("{}U", count));
+        }
+    }
+    out.push_str(&format!("\n#endif /* {} */\n", guard));
+    out
+}
+
+/// 生成 Markdown 分区表（含校验结果）
+pub fn memory_layout_markdown(plan: &MemoryLayoutPlan) -> String {
+    let mut out = format!("# {} 内存布局\n\n", plan.layout.name);
+    if let Some(desc) = plan.layout.description.as_deref().filter(|d| !d.is_empty()) {
+        out.push_str(&format!("{}\n\n", desc));
+    }
+    out.push_str("## 存储区域\n\n| 区域 | 类型 | 起始地址 | 结束地址 | 大小 | 扇区 |\n| --- | --- | --- | --- | ---: | --- |\n");
+    for r in &plan.layout.regions {
+        let sectors = match (&r.sectors, r.sector_size) {
+            (Some(s), _) if !s.is_empty() => format!("{} 个（非均匀）", s.len()),
+            (_, Some(size)) if size > 0 => format!("{} × {}", r.length / size, human_size(size)),
+            _ => "-".to_string(),
+        };
+        out.push_str(&format!(
+            "| {} | {} | 0x{:08X} | 0x{:08X} | {} | {} |\n",
+            r.name,
+            if r.kind == LayoutRegionKind::Flash { "Flash" } else { "RAM" },
+            r.origin,
+            r.end().saturating_sub(1),
+            human_size(r.length),
+            sectors
+        ));
+    }
+
+    out.push_str("\n## 分区\n\n| 分区 | 用途 | 区域 | 起始地址 | 结束地址 | 大小 | 扇区 |\n| --- | --- | --- | --- | --- | ---: | --- |\n");
+    for p in &plan.partitions {
+        let sectors = match (p.first_sector, p.sector_count) {
+            (Some(first), Some(count)) if count > 1 => format!("{}-{}", first, first + count - 1),
+            (Some(first), Some(_)) => first.to_string(),
+            _ => "-".to_string(),
+        };
+        let role = match p.role {
+            LayoutPartitionRole::Bootloader => "Bootloader",
+            LayoutPartitionRole::App => "应用",
+            LayoutPartitionRole::Config => "配置",
+            LayoutPartitionRole::Eeprom => "EEPROM 模拟",
+            LayoutPartitionRole::Data => "数据",
+            LayoutPartitionRole::Shared => "共享",
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | 0x{:08X} | 0x{:08X} | {} | {} |\n",
+            p.name,
+            role,
+            p.region,
+            p.start,
+            p.end.saturating_sub(1),
+            human_size(p.size),
+            sectors
+        ));
+    }
+
+    if !plan.free.is_empty() {
+        out.push_str("\n## 未分配空间\n\n| 区域 | 起始地址 | 大小 |\n| --- | --- | ---: |\n");
+        for f in &plan.free {
+            out.push_str(&format!("| {} | 0x{:08X} | {} |\n", f.region, f.start, human_size(f.size)));
+        }
+    }
+
+    out.push_str("\n## 校验结果\n\n");
+    if plan.issues.is_empty() {
+        out.push_str("未发现问题。\n");
+    }
+    for i in &plan.issues {
+        let level = if i.level == LayoutIssueLevel::Error { "错误" } else { "警告" };
+        out.push_str(&format!("- **{}**：{}\n", level, i.message));
+    }
+    out
+}
+
+// ==================== 命令 ====================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryLayoutOutput {
+    pub plan: MemoryLayoutPlan,
+    pub content: String,
+    pub output_path: Option<String>,
+}
+
+// 解析 "0x0000-0x4000" 地址区间，返回 [起始, 长度]
+#[tauri::command]
+pub fn memory_layout_parse_range(text: String) -> Result<(u64, u64), String> {
+    layout_parse_range(&text)
+}
+
+// 计算分区地址并校验
+#[tauri::command]
+pub fn memory_layout_validate(layout: MemoryLayout) -> Result<MemoryLayoutPlan, String> {
+    Ok(memory_layout_plan(&layout))
+}
+
+// 生成链接脚本片段 / C 头文件 / Markdown，存在错误时只允许生成 Markdown
+#[tauri::command]
+pub fn memory_layout_generate(
+    layout: MemoryLayout,
+    format: LayoutOutputFormat,
+    target: Option<String>,
+    file_name: Option<String>,
+) -> Result<MemoryLayoutOutput, String> {
+    let plan = memory_layout_plan(&layout);
+    let content = match format {
+        LayoutOutputFormat::Markdown => memory_layout_markdown(&plan),
+        _ if plan.has_errors() => {
+            let first = plan.issues.iter().find(|i| i.level == LayoutIssueLevel::Error).unwrap();
+            return Err(format!("内存布局存在错误，请先修正: {}", first.message));
+        }
+        LayoutOutputFormat::LinkerMemory => memory_layout_linker(&plan, target.as_deref())?,
+        LayoutOutputFormat::CHeader => memory_layout_c_header(&plan),
+    };
+    let output_path = match file_name {
+        Some(file_name) => Some(util_write_generate_file(&file_name, format.extension(), &content)?),
+        None => None,
+    };
+    Ok(MemoryLayoutOutput {
+        plan,
+        content,
+        output_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(name: &str, origin: u64, length: u64) -> LayoutRegion {
+        LayoutRegion {
+            name: name.to_string(),
+            kind: LayoutRegionKind::Flash,
+            origin,
+            length,
+            sector_size: Some(0x4000),
+            sectors: None,
+            page_size: None,
+        }
+    }
+
+    #[test]
+    fn parse_range_excludes_end() {
+        assert_eq!(layout_parse_range("0x0000-0x4000").unwrap(), (0, 0x4000));
+        assert_eq!(layout_parse_range("0x08000000 ~ 0x08010000").unwrap(), (0x0800_0000, 0x10000));
+        assert!(layout_parse_range("0x4000-0x0000").is_err());
+    }
+
+    #[test]
+    fn markdown_handles_empty_region_at_zero() {
+        let layout = MemoryLayout {
+            name: "T".to_string(),
+            description: None,
+            regions: vec![region("EMPTY", 0, 0), region("FLASH", 0x0800_0000, 0x10000)],
+            partitions: vec![],
+        };
+        let markdown = memory_layout_markdown(&memory_layout_plan(&layout));
+        assert!(markdown.contains("| EMPTY | Flash | 0x00000000 | 0x00000000 |"), "{}", markdown);
+    }
+}
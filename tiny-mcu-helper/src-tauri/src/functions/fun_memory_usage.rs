@@ -0,0 +1,1079 @@
+use object::{Object, ObjectSection, ObjectSymbol, SectionFlags, SectionKind, SymbolKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::utils::util_write_generate_file;
+
+// 默认列出的最大符号数
+const DEFAULT_TOP_N: usize = 20;
+
+/// 段的占用类别：代码/只读数据只占 Flash，已初始化数据同时占 Flash 与 RAM，零初始化只占 RAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryClass {
+    Flash,
+    FlashRam,
+    Ram,
+}
+
+impl MemoryClass {
+    fn flash(&self, size: u64) -> u64 {
+        if *self == MemoryClass::Ram {
+            0
+        } else {
+            size
+        }
+    }
+
+    fn ram(&self, size: u64) -> u64 {
+        if *self == MemoryClass::Flash {
+            0
+        } else {
+            size
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemorySection {
+    pub name: String,
+    pub address: u64,
+    pub load_address: Option<u64>, // 与运行地址不同时（如 .data）
+    pub size: u64,
+    pub class: MemoryClass,
+}
+
+/// 按目标文件（.o / 库成员）汇总
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemoryObject {
+    pub name: String,
+    pub flash: u64,
+    pub ram: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemorySymbol {
+    pub name: String,
+    pub demangled: Option<String>,
+    pub address: u64,
+    pub size: u64,
+    pub size_estimated: bool, // map 文件不含符号大小，按相邻符号地址推算
+    pub section: String,
+    pub object: Option<String>,
+    pub class: MemoryClass,
+}
+
+/// map 文件中的 MEMORY 区域
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemoryRegion {
+    pub name: String,
+    pub origin: u64,
+    pub length: u64,
+    pub attributes: String,
+    pub used: u64,
+    pub used_percent: f64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemoryReport {
+    pub elf_path: Option<String>,
+    pub map_path: Option<String>,
+    pub architecture: Option<String>,
+    pub flash_total: u64,
+    pub ram_total: u64,
+    pub regions: Vec<MemoryRegion>,
+    pub sections: Vec<MemorySection>,
+    pub objects: Vec<MemoryObject>, // 仅 map 文件可提供
+    pub symbols: Vec<MemorySymbol>, // 全部符号，按大小降序
+    pub top_n: usize,
+}
+
+impl MemoryReport {
+    pub fn top_symbols(&self) -> &[MemorySymbol] {
+        &self.symbols[..self.top_n.min(self.symbols.len())]
+    }
+}
+
+/// 分析输入：ELF 与 map 至少提供一个，同时提供时以 ELF 的段与符号为准
+#[derive(Debug, Clone, Deserialize)]
+pub struct MemoryInput {
+    pub elf_path: Option<String>,
+    pub map_path: Option<String>,
+}
+
+// 还原 Rust / C++ 符号名
+fn demangle(name: &str) -> Option<String> {
+    if let Ok(demangled) = rustc_demangle::try_demangle(name) {
+        return Some(format!("{:#}", demangled));
+    }
+    let symbol = cpp_demangle::Symbol::new(name).ok()?;
+    symbol.demangle(&cpp_demangle::DemangleOptions::default()).ok()
+}
+
+// ==================== ELF ====================
+
+struct ElfData {
+    architecture: String,
+    sections: Vec<MemorySection>,
+    symbols: Vec<MemorySymbol>,
+}
+
+fn elf_section_class(kind: SectionKind) -> Option<MemoryClass> {
+    match kind {
+        SectionKind::Text
+        | SectionKind::ReadOnlyData
+        | SectionKind::ReadOnlyDataWithRel
+        | SectionKind::ReadOnlyString => Some(MemoryClass::Flash),
+        SectionKind::Data | SectionKind::Tls => Some(MemoryClass::FlashRam),
+        SectionKind::UninitializedData | SectionKind::UninitializedTls | SectionKind::Common => {
+            Some(MemoryClass::Ram)
+        }
+        _ => None,
+    }
+}
+
+fn parse_elf(data: &[u8]) -> Result<ElfData, String> {
+    let file = object::File::parse(data).map_err(|e| format!("解析 ELF 失败: {}", e))?;
+    if file.format() != object::BinaryFormat::Elf {
+        return Err("不是 ELF 文件".to_string());
+    }
+
+    // 已初始化数据段的加载地址取自程序头
+    let segments: Vec<(u64, u64, u64)> = match &file {
+        object::File::Elf32(elf) => elf_segments(elf),
+        object::File::Elf64(elf) => elf_segments(elf),
+        _ => vec![],
+    };
+
+    let mut sections = Vec::new();
+    let mut section_classes = HashMap::new();
+    for section in file.sections() {
+        let alloc = match section.flags() {
+            SectionFlags::Elf { sh_flags } => sh_flags & object::elf::SHF_ALLOC as u64 != 0,
+            _ => false,
+        };
+        let Some(class) = elf_section_class(section.kind()).filter(|_| alloc && section.size() > 0) else {
+            continue;
+        };
+        let name = section.name().unwrap_or("").to_string();
+        let load_address = segments
+            .iter()
+            .find(|(vaddr, paddr, memsz)| {
+                section.address() >= *vaddr && section.address() < vaddr + memsz && vaddr != paddr
+            })
+            .map(|(vaddr, paddr, _)| paddr + (section.address() - vaddr))
+            .filter(|_| class == MemoryClass::FlashRam);
+        section_classes.insert(section.index(), (name.clone(), class));
+        sections.push(MemorySection {
+            name,
+            address: section.address(),
+            load_address,
+            size: section.size(),
+            class,
+        });
+    }
+
+    let mut symbols = Vec::new();
+    for symbol in file.symbols() {
+        if symbol.size() == 0 || !matches!(symbol.kind(), SymbolKind::Text | SymbolKind::Data | SymbolKind::Tls) {
+            continue;
+        }
+        let Some((section, class)) = symbol.section_index().and_then(|i| section_classes.get(&i)) else {
+            continue;
+        };
+        let name = symbol.name().unwrap_or("").to_string();
+        symbols.push(MemorySymbol {
+            demangled: demangle(&name),
+            name,
+            address: symbol.address(),
+            size: symbol.size(),
+            size_estimated: false,
+            section: section.clone(),
+            object: None,
+            class: *class,
+        });
+    }
+
+    Ok(ElfData {
+        architecture: format!("{:?}", file.architecture()),
+        sections,
+        symbols,
+    })
+}
+
+// (vaddr, paddr, memsz)
+fn elf_segments<Elf: object::read::elf::FileHeader>(file: &object::read::elf::ElfFile<Elf>) -> Vec<(u64, u64, u64)> {
+    use object::read::elf::ProgramHeader;
+    let endian = file.endian();
+    file.elf_program_headers()
+        .iter()
+        .filter(|ph| ph.p_type(endian) == object::elf::PT_LOAD)
+        .map(|ph| (ph.p_vaddr(endian).into(), ph.p_paddr(endian).into(), ph.p_memsz(endian).into()))
+        .collect()
+}
+
+// ==================== GNU ld map ====================
+
+struct MapData {
+    regions: Vec<MemoryRegion>,
+    sections: Vec<MemorySection>,
+    objects: Vec<MemoryObject>,
+    symbols: Vec<MemorySymbol>,
+}
+
+fn parse_hex(token: &str) -> Option<u64> {
+    u64::from_str_radix(token.strip_prefix("0x")?, 16).ok()
+}
+
+// 目标文件路径只保留文件名，库成员形如 libc.a(memcpy.o)
+fn object_name(path: &str) -> String {
+    let path = path.trim();
+    match path.find('(') {
+        Some(pos) => {
+            let archive = &path[..pos];
+            let archive = archive.rsplit(['/', '\\']).next().unwrap_or(archive);
+            format!("{}{}", archive, &path[pos..])
+        }
+        None => path.rsplit(['/', '\\']).next().unwrap_or(path).to_string(),
+    }
+}
+
+// 区域名或属性判断是否为 Flash：不可写，或名称含 FLASH/ROM
+fn region_is_flash(region: &MemoryRegion) -> bool {
+    let name = region.name.to_uppercase();
+    name.contains("FLASH") || name.contains("ROM") || !region.attributes.contains('w')
+}
+
+// map 文件不区分 NOBITS，零初始化段按常见命名判断
+fn map_section_uninitialized(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.starts_with(".bss")
+        || name.starts_with(".tbss")
+        || name.starts_with(".noinit")
+        || name.contains("heap")
+        || name.contains("stack")
+}
+
+struct MapInputSection {
+    output: String,
+    address: u64,
+    size: u64,
+    object: String,
+    symbols: Vec<(u64, String)>,
+}
+
+fn parse_map(content: &str) -> Result<MapData, String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut regions = Vec::new();
+
+    // Memory Configuration 表
+    let memory_start = lines.iter().position(|l| l.trim() == "Memory Configuration");
+    let map_start = lines
+        .iter()
+        .position(|l| l.trim() == "Linker script and memory map")
+        .ok_or("不是有效的 GNU ld map 文件：缺少 \"Linker script and memory map\"".to_string())?;
+    // 只解析位于内存映射之前的 Memory Configuration 表
+    if let Some(start) = memory_start.filter(|start| *start < map_start) {
+        for line in &lines[start + 1..map_start] {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            if tokens.len() >= 3 && tokens[0] != "*default*" {
+                if let (Some(origin), Some(length)) = (parse_hex(tokens[1]), parse_hex(tokens[2])) {
+                    regions.push(MemoryRegion {
+                        name: tokens[0].to_string(),
+                        origin,
+                        length,
+                        attributes: tokens.get(3).unwrap_or(&"").to_string(),
+                        used: 0,
+                        used_percent: 0.0,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut outputs: Vec<(String, u64, u64, Option<u64>)> = Vec::new(); // (名称, 地址, 大小, 加载地址)
+    let mut inputs: Vec<MapInputSection> = Vec::new();
+    let mut pending_output: Option<String> = None;
+    let mut pending_input: Option<String> = None;
+
+    for line in &lines[map_start + 1..] {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let indented = line.starts_with(' ');
+
+        if !indented {
+            // 输出段：".text 0x08000000 0x1234 [load address 0x...]"，段名过长时地址在下一行
+            pending_input = None;
+            if tokens.len() == 1 && !tokens[0].starts_with("0x") {
+                pending_output = Some(tokens[0].to_string());
+                continue;
+            }
+            pending_output = None;
+            if let (Some(address), Some(size)) = (tokens.get(1).and_then(|t| parse_hex(t)), tokens.get(2).and_then(|t| parse_hex(t))) {
+                let load = line.find("load address").and_then(|pos| line[pos..].split_whitespace().nth(2)).and_then(parse_hex);
+                outputs.push((tokens[0].to_string(), address, size, load));
+            }
+            continue;
+        }
+
+        if let Some(name) = pending_output.take() {
+            if let (Some(address), Some(size)) = (tokens.first().and_then(|t| parse_hex(t)), tokens.get(1).and_then(|t| parse_hex(t))) {
+                let load = line.find("load address").and_then(|pos| line[pos..].split_whitespace().nth(2)).and_then(parse_hex);
+                outputs.push((name, address, size, load));
+            }
+            continue;
+        }
+        let Some((output, _, _, _)) = outputs.last() else {
+            continue;
+        };
+
+        // 输入段续行：段名单独一行，下一行为 "地址 大小 目标文件"
+        let (name, rest) = if let Some(name) = pending_input.take() {
+            (Some(name), &tokens[..])
+        } else if !tokens[0].starts_with("0x") && !line.starts_with("  ") {
+            if tokens[0].starts_with('*') && tokens[0] != "*fill*" {
+                continue; // 链接脚本匹配规则，如 *(.text*)
+            }
+            if tokens.len() == 1 {
+                pending_input = Some(tokens[0].to_string());
+                continue;
+            }
+            (Some(tokens[0].to_string()), &tokens[1..])
+        } else {
+            (None, &tokens[..])
+        };
+
+        match name {
+            Some(name) => {
+                if let (Some(address), Some(size)) = (rest.first().and_then(|t| parse_hex(t)), rest.get(1).and_then(|t| parse_hex(t))) {
+                    // *fill* 为对齐填充，不属于任何目标文件
+                    let object = rest[2..].join(" ");
+                    if size > 0 && !object.is_empty() && name != "*fill*" {
+                        inputs.push(MapInputSection {
+                            output: output.clone(),
+                            address,
+                            size,
+                            object: object_name(&object),
+                            symbols: vec![],
+                        });
+                    }
+                }
+            }
+            None => {
+                // 符号行："0x08000100    main"，排除 ". = ALIGN (0x4)" 等赋值语句
+                if tokens.len() == 2 && !line.contains('=') {
+                    if let (Some(address), Some(input)) = (parse_hex(tokens[0]), inputs.last_mut()) {
+                        if address >= input.address && address < input.address + input.size {
+                            input.symbols.push((address, tokens[1].to_string()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let section_class = |name: &str, address: u64, load: Option<u64>| -> Option<MemoryClass> {
+        let in_flash = |addr: u64| {
+            regions
+                .iter()
+                .find(|r| addr >= r.origin && addr < r.origin + r.length)
+                .map(region_is_flash)
+        };
+        match (in_flash(address), load.and_then(in_flash)) {
+            (Some(true), _) => Some(MemoryClass::Flash),
+            // ld 对 .bss 也会输出 load address，需按段名排除
+            (Some(false), Some(true)) if !map_section_uninitialized(name) => Some(MemoryClass::FlashRam),
+            (Some(false), _) => Some(MemoryClass::Ram),
+            // 无 MEMORY 配置时按段名推断
+            (None, _) if regions.is_empty() => Some(
+                if map_section_uninitialized(name) {
+                    MemoryClass::Ram
+                } else if name.starts_with(".data") {
+                    MemoryClass::FlashRam
+                } else {
+                    MemoryClass::Flash
+                },
+            ),
+            (None, _) => None, // 不在任何区域内（如调试段）
+        }
+    };
+
+    let mut sections = Vec::new();
+    let mut classes = HashMap::new();
+    for (name, address, size, load) in &outputs {
+        if *size == 0 {
+            continue;
+        }
+        if let Some(class) = section_class(name, *address, *load) {
+            classes.insert(name.clone(), class);
+            sections.push(MemorySection {
+                name: name.clone(),
+                address: *address,
+                load_address: load.filter(|l| l != address && class == MemoryClass::FlashRam),
+                size: *size,
+                class,
+            });
+        }
+    }
+
+    // 区域使用量：运行地址与加载地址分别计入
+    for region in regions.iter_mut() {
+        let contains = |addr: u64| addr >= region.origin && addr < region.origin + region.length;
+        region.used = sections
+            .iter()
+            .map(|s| {
+                let mut used = if contains(s.address) { s.size } else { 0 };
+                if s.load_address.is_some_and(contains) {
+                    used += s.size;
+                }
+                used
+            })
+            .sum();
+        region.used_percent = if region.length > 0 {
+            region.used as f64 / region.length as f64 * 100.0
+        } else {
+            0.0
+        };
+    }
+
+    let mut objects: HashMap<String, MemoryObject> = HashMap::new();
+    let mut symbols = Vec::new();
+    for input in &inputs {
+        let Some(class) = classes.get(&input.output) else {
+            continue;
+        };
+        let entry = objects.entry(input.object.clone()).or_insert(MemoryObject {
+            name: input.object.clone(),
+            flash: 0,
+            ram: 0,
+        });
+        entry.flash += class.flash(input.size);
+        entry.ram += class.ram(input.size);
+
+        // 符号大小按相邻符号地址推算；-ffunction-sections 时通常一个输入段对应一个符号
+        let end = input.address + input.size;
+        for (i, (address, name)) in input.symbols.iter().enumerate() {
+            let next = input.symbols.get(i + 1).map(|s| s.0).unwrap_or(end);
+            if next > *address {
+                symbols.push(MemorySymbol {
+                    demangled: demangle(name),
+                    name: name.clone(),
+                    address: *address,
+                    size: next - address,
+                    size_estimated: true,
+                    section: input.output.clone(),
+                    object: Some(input.object.clone()),
+                    class: *class,
+                });
+            }
+        }
+    }
+    let mut objects: Vec<MemoryObject> = objects.into_values().collect();
+    objects.sort_by(|a, b| (b.flash + b.ram).cmp(&(a.flash + a.ram)).then(a.name.cmp(&b.name)));
+
+    Ok(MapData {
+        regions,
+        sections,
+        objects,
+        symbols,
+    })
+}
+
+/// 分析 ELF 和/或 map 文件
+pub fn memory_analyze(input: &MemoryInput, top_n: Option<usize>) -> Result<MemoryReport, String> {
+    if input.elf_path.is_none() && input.map_path.is_none() {
+        return Err("请至少提供 ELF 或 map 文件".to_string());
+    }
+    let elf = match &input.elf_path {
+        Some(path) => Some(parse_elf(&std::fs::read(path).map_err(|e| format!("读取 ELF 文件失败: {}", e))?)?),
+        None => None,
+    };
+    let map = match &input.map_path {
+        Some(path) => Some(parse_map(
+            &std::fs::read_to_string(path).map_err(|e| format!("读取 map 文件失败: {}", e))?,
+        )?),
+        None => None,
+    };
+
+    let (architecture, sections, mut symbols) = match elf {
+        Some(mut elf) => {
+            // ELF 符号补充 map 中的目标文件归属
+            if let Some(map) = &map {
+                let owners: HashMap<(&str, u64), &str> = map
+                    .symbols
+                    .iter()
+                    .filter_map(|s| s.object.as_deref().map(|o| ((s.name.as_str(), s.address), o)))
+                    .collect();
+                for symbol in elf.symbols.iter_mut() {
+                    let address = symbol.address & !1; // Thumb 函数地址最低位为 1
+                    symbol.object = owners
+                        .get(&(symbol.name.as_str(), address))
+                        .or_else(|| owners.get(&(symbol.name.as_str(), symbol.address)))
+                        .map(|o| o.to_string());
+                }
+            }
+            (Some(elf.architecture), elf.sections, elf.symbols)
+        }
+        None => {
+            let map = map.as_ref().unwrap();
+            (None, map.sections.clone(), map.symbols.clone())
+        }
+    };
+    symbols.sort_by(|a, b| b.size.cmp(&a.size).then(a.name.cmp(&b.name)));
+    let (regions, objects) = match map {
+        Some(map) => (map.regions, map.objects),
+        None => (vec![], vec![]),
+    };
+
+    Ok(MemoryReport {
+        elf_path: input.elf_path.clone(),
+        map_path: input.map_path.clone(),
+        architecture,
+        flash_total: sections.iter().map(|s| s.class.flash(s.size)).sum(),
+        ram_total: sections.iter().map(|s| s.class.ram(s.size)).sum(),
+        regions,
+        sections,
+        objects,
+        symbols,
+        top_n: top_n.unwrap_or(DEFAULT_TOP_N),
+    })
+}
+
+// ==================== 对比 ====================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryDiffEntry {
+    pub name: String,
+    pub base: u64,
+    pub current: u64,
+    pub delta: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryDiff {
+    pub flash_base: u64,
+    pub flash_current: u64,
+    pub flash_delta: i64,
+    pub ram_base: u64,
+    pub ram_current: u64,
+    pub ram_delta: i64,
+    pub sections: Vec<MemoryDiffEntry>,
+    pub objects: Vec<MemoryDiffEntry>, // 按 Flash + RAM 合计对比
+    pub symbols: Vec<MemoryDiffEntry>, // 只列出有变化的符号，按变化量绝对值降序，最多 top_n 个
+}
+
+fn diff_entries(base: HashMap<String, u64>, current: HashMap<String, u64>, changed_only: bool) -> Vec<MemoryDiffEntry> {
+    let mut names: Vec<&String> = base.keys().chain(current.keys()).collect();
+    names.sort();
+    names.dedup();
+    let mut entries: Vec<MemoryDiffEntry> = names
+        .into_iter()
+        .map(|name| {
+            let b = base.get(name).copied().unwrap_or(0);
+            let c = current.get(name).copied().unwrap_or(0);
+            MemoryDiffEntry {
+                name: name.clone(),
+                base: b,
+                current: c,
+                delta: c as i64 - b as i64,
+            }
+        })
+        .filter(|e| !changed_only || e.delta != 0)
+        .collect();
+    entries.sort_by(|a, b| b.delta.abs().cmp(&a.delta.abs()).then(a.name.cmp(&b.name)));
+    entries
+}
+
+/// 对比两次构建的内存占用
+pub fn memory_diff(base: &MemoryReport, current: &MemoryReport) -> MemoryDiff {
+    let sections = |r: &MemoryReport| {
+        let mut map = HashMap::new();
+        for s in &r.sections {
+            *map.entry(s.name.clone()).or_insert(0) += s.size;
+        }
+        map
+    };
+    let objects = |r: &MemoryReport| r.objects.iter().map(|o| (o.name.clone(), o.flash + o.ram)).collect();
+    // 同名静态符号可能有多个，合并统计
+    let symbols = |r: &MemoryReport| {
+        let mut map = HashMap::new();
+        for s in &r.symbols {
+            *map.entry(s.demangled.clone().unwrap_or(s.name.clone())).or_insert(0) += s.size;
+        }
+        map
+    };
+    let mut symbol_entries = diff_entries(symbols(base), symbols(current), true);
+    symbol_entries.truncate(current.top_n);
+
+    MemoryDiff {
+        flash_base: base.flash_total,
+        flash_current: current.flash_total,
+        flash_delta: current.flash_total as i64 - base.flash_total as i64,
+        ram_base: base.ram_total,
+        ram_current: current.ram_total,
+        ram_delta: current.ram_total as i64 - base.ram_total as i64,
+        sections: diff_entries(sections(base), sections(current), false),
+        objects: diff_entries(objects(base), objects(current), true),
+        symbols: symbol_entries,
+    }
+}
+
+// ==================== Markdown ====================
+
+fn class_label(class: MemoryClass) -> &'static str {
+    match class {
+        MemoryClass::Flash => "Flash",
+        MemoryClass::FlashRam => "Flash + RAM",
+        MemoryClass::Ram => "RAM",
+    }
+}
+
+fn signed(delta: i64) -> String {
+    if delta > 0 {
+        format!("+{}", delta)
+    } else {
+        delta.to_string()
+    }
+}
+
+// 表格单元格中的 | 需要转义
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// 生成内存占用报告（Markdown，可直接用于 convert_markdown_to_pdf）
+pub fn memory_report_markdown(report: &MemoryReport) -> String {
+    let mut out = String::from("# 内存占用报告\n\n");
+    if let Some(path) = &report.elf_path {
+        out.push_str(&format!("- ELF: `{}`\n", path));
+    }
+    if let Some(path) = &report.map_path {
+        out.push_str(&format!("- Map: `{}`\n", path));
+    }
+    if let Some(arch) = &report.architecture {
+        out.push_str(&format!("- 架构: {}\n", arch));
+    }
+    out.push_str(&format!(
+        "- Flash 合计: {} 字节\n- RAM 合计: {} 字节\n\n",
+        report.flash_total, report.ram_total
+    ));
+
+    if !report.regions.is_empty() {
+        out.push_str("## 存储区域\n\n| 区域 | 起始地址 | 容量 | 已用 | 使用率 |\n| --- | --- | ---: | ---: | ---: |\n");
+        for r in &report.regions {
+            out.push_str(&format!(
+                "| {} | 0x{:08X} | {} | {} | {:.1}% |\n",
+                r.name, r.origin, r.length, r.used, r.used_percent
+            ));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("## 段\n\n| 段 | 地址 | 大小 | 占用 |\n| --- | --- | ---: | --- |\n");
+    for s in &report.sections {
+        out.push_str(&format!("| {} | 0x{:08X} | {} | {} |\n", cell(&s.name), s.address, s.size, class_label(s.class)));
+    }
+    out.push('\n');
+
+    if !report.objects.is_empty() {
+        out.push_str(&format!(
+            "## 目标文件（前 {} 个）\n\n| 目标文件 | Flash | RAM |\n| --- | ---: | ---: |\n",
+            report.top_n.min(report.objects.len())
+        ));
+        for o in report.objects.iter().take(report.top_n) {
+            out.push_str(&format!("| {} | {} | {} |\n", cell(&o.name), o.flash, o.ram));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!(
+        "## 最大的 {} 个符号\n\n| 符号 | 大小 | 段 | 目标文件 |\n| --- | ---: | --- | --- |\n",
+        report.top_symbols().len()
+    ));
+    for s in report.top_symbols() {
+        out.push_str(&format!(
+            "| {} | {}{} | {} | {} |\n",
+            cell(s.demangled.as_deref().unwrap_or(&s.name)),
+            s.size,
+            if s.size_estimated { "*" } else { "" },
+            cell(&s.section),
+            cell(s.object.as_deref().unwrap_or("-"))
+        ));
+    }
+    if report.top_symbols().iter().any(|s| s.size_estimated) {
+        out.push_str("\n\\* 大小由 map 文件中相邻符号地址推算\n");
+    }
+    out
+}
+
+fn diff_table(title: &str, entries: &[MemoryDiffEntry], out: &mut String) {
+    if entries.is_empty() {
+        return;
+    }
+    out.push_str(&format!(
+        "## {}\n\n| 名称 | 基准 | 当前 | 变化 |\n| --- | ---: | ---: | ---: |\n",
+        title
+    ));
+    for e in entries {
+        out.push_str(&format!("| {} | {} | {} | {} |\n", cell(&e.name), e.base, e.current, signed(e.delta)));
+    }
+    out.push('\n');
+}
+
+/// 生成构建对比报告（Markdown）
+pub fn memory_diff_markdown(diff: &MemoryDiff) -> String {
+    let mut out = String::from("# 内存占用对比\n\n| | 基准 | 当前 | 变化 |\n| --- | ---: | ---: | ---: |\n");
+    out.push_str(&format!(
+        "| Flash | {} | {} | {} |\n| RAM | {} | {} | {} |\n\n",
+        diff.flash_base,
+        diff.flash_current,
+        signed(diff.flash_delta),
+        diff.ram_base,
+        diff.ram_current,
+        signed(diff.ram_delta)
+    ));
+    diff_table("段", &diff.sections, &mut out);
+    diff_table("目标文件", &diff.objects, &mut out);
+    diff_table("变化最大的符号", &diff.symbols, &mut out);
+    out
+}
+
+// ==================== 命令 ====================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryReportResult {
+    pub report: MemoryReport,
+    pub markdown: String,
+    pub output_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryDiffResult {
+    pub diff: MemoryDiff,
+    pub markdown: String,
+    pub output_path: Option<String>,
+}
+
+fn write_markdown(file_name: Option<String>, markdown: &str) -> Result<Option<String>, String> {
+    match file_name {
+        Some(file_name) => Ok(Some(util_write_generate_file(&file_name, "md", markdown)?)),
+        None => Ok(None),
+    }
+}
+
+// 分析 ELF/map 文件的内存占用，file_name 不为空时把 Markdown 报告保存到 generate 目录
+#[tauri::command]
+pub async fn memory_usage_analyze(
+    input: MemoryInput,
+    top_n: Option<usize>,
+    file_name: Option<String>,
+) -> Result<MemoryReportResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let report = memory_analyze(&input, top_n)?;
+        let markdown = memory_report_markdown(&report);
+        let output_path = write_markdown(file_name, &markdown)?;
+        Ok(MemoryReportResult {
+            report,
+            markdown,
+            output_path,
+        })
+    })
+    .await
+    .map_err(|e| format!("内存占用分析失败: {}", e))?
+}
+
+// 对比两次构建
+#[tauri::command]
+pub async fn memory_usage_diff(
+    base: MemoryInput,
+    current: MemoryInput,
+    top_n: Option<usize>,
+    file_name: Option<String>,
+) -> Result<MemoryDiffResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let diff = memory_diff(&memory_analyze(&base, top_n)?, &memory_analyze(&current, top_n)?);
+        let markdown = memory_diff_markdown(&diff);
+        let output_path = write_markdown(file_name, &markdown)?;
+        Ok(MemoryDiffResult {
+            diff,
+            markdown,
+            output_path,
+        })
+    })
+    .await
+    .map_err(|e| format!("内存占用对比失败: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // arm-none-eabi-gcc -Wl,-Map 输出的典型片段
+    const TEST_MAP: &str = "\
+Archive member included to satisfy reference by file (symbol)
+
+/opt/gcc-arm/arm-none-eabi/lib/thumb/v7e-m/libc.a(lib_a-memcpy.o)
+                              build/main.o (memcpy)
+
+Memory Configuration
+
+Name             Origin             Length             Attributes
+FLASH            0x08000000         0x00010000         xr
+RAM              0x20000000         0x00005000         xrw
+*default*        0x00000000         0xffffffff
+
+Linker script and memory map
+
+LOAD build/startup.o
+LOAD build/main.o
+                0x20005000                _estack = (ORIGIN (RAM) + LENGTH (RAM))
+
+.isr_vector     0x08000000       0x10
+                0x08000000                . = ALIGN (0x4)
+ *(.isr_vector)
+ .isr_vector    0x08000000       0x10 build/startup.o
+                0x08000000                g_pfnVectors
+                0x08000010                . = ALIGN (0x4)
+
+.text           0x08000010       0x5c
+ *(.text)
+ .text          0x08000010        0x0 build/main.o
+ *(.text*)
+ .text.main     0x08000010       0x24 build/main.o
+                0x08000010                main
+ .text.Reset_Handler
+                0x08000034       0x18 build/startup.o
+                0x08000034                Reset_Handler
+ .text          0x0800004c       0x1e /opt/gcc-arm/arm-none-eabi/lib/thumb/v7e-m/libc.a(lib_a-memcpy.o)
+                0x0800004c                memcpy
+ *fill*         0x0800006a        0x2 
+                0x0800006c                . = ALIGN (0x4)
+
+.data           0x20000000        0x8 load address 0x0800006c
+                0x20000000                _sdata = .
+ *(.data*)
+ .data.counter  0x20000000        0x4 build/main.o
+                0x20000000                counter
+ .data.ticks    0x20000004        0x4 build/main.o
+                0x20000004                ticks
+
+.bss            0x20000008      0x100 load address 0x08000074
+ .bss.buffer    0x20000008      0x100 build/main.o
+                0x20000008                buffer
+
+.ARM.attributes
+                0x00000000       0x2e
+ .ARM.attributes
+                0x00000000       0x22 build/main.o
+";
+
+    const RUST_SYMBOL: &str = "_ZN4core3fmt5write17h0123456789abcdefE";
+
+    fn push_u16(out: &mut Vec<u8>, value: u16) {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn push_u32(out: &mut Vec<u8>, values: &[u32]) {
+        for value in values {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    // 构造与 TEST_MAP 对应的 ARM ELF32：.text / .data（加载地址在 Flash）/ .bss 与符号表
+    fn test_elf() -> Vec<u8> {
+        const TEXT_OFFSET: u32 = 52 + 2 * 32;
+        const TEXT_SIZE: u32 = 0x5C;
+        const DATA_OFFSET: u32 = TEXT_OFFSET + TEXT_SIZE;
+        const SYMTAB_OFFSET: u32 = DATA_OFFSET + 8;
+
+        let mut strtab = vec![0u8];
+        let mut symtab = vec![0u8; 16];
+        // (名称, 地址, 大小, st_info, 段索引)
+        let symbols = [
+            ("main", 0x0800_0011, 0x24, 0x12, 1),
+            (RUST_SYMBOL, 0x0800_0035, 0x18, 0x12, 1),
+            ("memcpy", 0x0800_004D, 0x1E, 0x12, 1),
+            ("counter", 0x2000_0000, 4, 0x11, 2),
+            ("ticks", 0x2000_0004, 4, 0x11, 2),
+            ("buffer", 0x2000_0008, 0x100, 0x11, 3),
+            ("_sdata", 0x2000_0000, 0, 0x10, 2), // 无大小，不计入
+        ];
+        for (name, address, size, info, section) in symbols {
+            push_u32(&mut symtab, &[strtab.len() as u32, address, size]);
+            symtab.extend_from_slice(&[info, 0]);
+            push_u16(&mut symtab, section);
+            strtab.extend_from_slice(name.as_bytes());
+            strtab.push(0);
+        }
+        let mut shstrtab = vec![0u8];
+        let mut names = Vec::new();
+        for name in [".text", ".data", ".bss", ".symtab", ".strtab", ".shstrtab"] {
+            names.push(shstrtab.len() as u32);
+            shstrtab.extend_from_slice(name.as_bytes());
+            shstrtab.push(0);
+        }
+        let strtab_offset = SYMTAB_OFFSET + symtab.len() as u32;
+        let shstrtab_offset = strtab_offset + strtab.len() as u32;
+        let shoff = (shstrtab_offset + shstrtab.len() as u32 + 3) & !3;
+
+        let mut out = b"\x7FELF\x01\x01\x01".to_vec();
+        out.resize(16, 0);
+        push_u16(&mut out, 2); // ET_EXEC
+        push_u16(&mut out, 40); // EM_ARM
+        push_u32(&mut out, &[1, 0x0800_0035, 52, shoff, 0x0500_0000]);
+        for value in [52, 32, 2, 40, 7, 6] {
+            push_u16(&mut out, value);
+        }
+        // PT_LOAD：Flash 中的代码，以及运行于 RAM、加载于 Flash 的数据
+        push_u32(&mut out, &[1, TEXT_OFFSET, 0x0800_0010, 0x0800_0010, TEXT_SIZE, TEXT_SIZE, 5, 4]);
+        push_u32(&mut out, &[1, DATA_OFFSET, 0x2000_0000, 0x0800_006C, 8, 0x108, 6, 4]);
+        out.resize(DATA_OFFSET as usize, 0);
+        push_u32(&mut out, &[1, 2]);
+        out.extend_from_slice(&symtab);
+        out.extend_from_slice(&strtab);
+        out.extend_from_slice(&shstrtab);
+        out.resize(shoff as usize, 0);
+
+        out.resize(out.len() + 40, 0); // SHN_UNDEF
+        let sections = [
+            [names[0], 1, 0x6, 0x0800_0010, TEXT_OFFSET, TEXT_SIZE, 0, 0, 4, 0],
+            [names[1], 1, 0x3, 0x2000_0000, DATA_OFFSET, 8, 0, 0, 4, 0],
+            [names[2], 8, 0x3, 0x2000_0008, SYMTAB_OFFSET, 0x100, 0, 0, 4, 0],
+            [names[3], 2, 0, 0, SYMTAB_OFFSET, symtab.len() as u32, 5, 1, 4, 16],
+            [names[4], 3, 0, 0, strtab_offset, strtab.len() as u32, 0, 0, 1, 0],
+            [names[5], 3, 0, 0, shstrtab_offset, shstrtab.len() as u32, 0, 0, 1, 0],
+        ];
+        for section in sections {
+            push_u32(&mut out, &section);
+        }
+        out
+    }
+
+    #[test]
+    fn parses_ld_map() {
+        let map = parse_map(TEST_MAP).unwrap();
+
+        let regions: Vec<(&str, u64, u64, u64)> =
+            map.regions.iter().map(|r| (r.name.as_str(), r.origin, r.length, r.used)).collect();
+        assert_eq!(regions, [("FLASH", 0x0800_0000, 0x1_0000, 0x74), ("RAM", 0x2000_0000, 0x5000, 0x108)]);
+
+        let sections: Vec<(&str, u64, Option<u64>, u64, MemoryClass)> = map
+            .sections
+            .iter()
+            .map(|s| (s.name.as_str(), s.address, s.load_address, s.size, s.class))
+            .collect();
+        assert_eq!(
+            sections,
+            [
+                (".isr_vector", 0x0800_0000, None, 0x10, MemoryClass::Flash),
+                (".text", 0x0800_0010, None, 0x5C, MemoryClass::Flash),
+                (".data", 0x2000_0000, Some(0x0800_006C), 8, MemoryClass::FlashRam),
+                (".bss", 0x2000_0008, None, 0x100, MemoryClass::Ram),
+            ]
+        );
+
+        let objects: Vec<(&str, u64, u64)> = map.objects.iter().map(|o| (o.name.as_str(), o.flash, o.ram)).collect();
+        assert_eq!(objects, [("main.o", 0x2C, 0x108), ("startup.o", 0x28, 0), ("libc.a(lib_a-memcpy.o)", 0x1E, 0)]);
+
+        let symbols: Vec<(&str, u64, u64, Option<&str>)> = map
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.address, s.size, s.object.as_deref()))
+            .collect();
+        assert_eq!(
+            symbols,
+            [
+                ("g_pfnVectors", 0x0800_0000, 0x10, Some("startup.o")),
+                ("main", 0x0800_0010, 0x24, Some("main.o")),
+                ("Reset_Handler", 0x0800_0034, 0x18, Some("startup.o")),
+                ("memcpy", 0x0800_004C, 0x1E, Some("libc.a(lib_a-memcpy.o)")),
+                ("counter", 0x2000_0000, 4, Some("main.o")),
+                ("ticks", 0x2000_0004, 4, Some("main.o")),
+                ("buffer", 0x2000_0008, 0x100, Some("main.o")),
+            ]
+        );
+        assert!(map.symbols.iter().all(|s| s.size_estimated));
+    }
+
+    // Memory Configuration 出现在内存映射之后时忽略，不应越界
+    #[test]
+    fn ignores_memory_configuration_after_map() {
+        let content = "Linker script and memory map\n\n.text 0x08000000 0x10\n\nMemory Configuration\n\nFLASH 0x08000000 0x00010000 xr\n";
+        let map = parse_map(content).unwrap();
+        assert!(map.regions.is_empty());
+        assert_eq!(map.sections[0].name, ".text");
+        assert!(parse_map("Memory Configuration\n").is_err());
+    }
+
+    #[test]
+    fn parses_elf() {
+        let elf = parse_elf(&test_elf()).unwrap();
+        assert_eq!(elf.architecture, "Arm");
+
+        let sections: Vec<(&str, u64, Option<u64>, u64, MemoryClass)> = elf
+            .sections
+            .iter()
+            .map(|s| (s.name.as_str(), s.address, s.load_address, s.size, s.class))
+            .collect();
+        assert_eq!(
+            sections,
+            [
+                (".text", 0x0800_0010, None, 0x5C, MemoryClass::Flash),
+                (".data", 0x2000_0000, Some(0x0800_006C), 8, MemoryClass::FlashRam),
+                (".bss", 0x2000_0008, None, 0x100, MemoryClass::Ram),
+            ]
+        );
+
+        let symbols: Vec<(&str, u64, &str)> =
+            elf.symbols.iter().map(|s| (s.name.as_str(), s.size, s.section.as_str())).collect();
+        assert_eq!(
+            symbols,
+            [
+                ("main", 0x24, ".text"),
+                (RUST_SYMBOL, 0x18, ".text"),
+                ("memcpy", 0x1E, ".text"),
+                ("counter", 4, ".data"),
+                ("ticks", 4, ".data"),
+                ("buffer", 0x100, ".bss"),
+            ]
+        );
+        assert_eq!(elf.symbols[1].demangled.as_deref(), Some("core::fmt::write"));
+        assert!(elf.symbols.iter().all(|s| !s.size_estimated && s.object.is_none()));
+        assert!(parse_elf(TEST_MAP.as_bytes()).is_err());
+    }
+
+    // 同时提供 ELF 与 map：段和符号取自 ELF，目标文件归属取自 map
+    #[test]
+    fn analyzes_elf_with_map() {
+        let dir = std::env::temp_dir();
+        let elf_path = dir.join(format!("tmh-memory-{}.elf", std::process::id()));
+        let map_path = dir.join(format!("tmh-memory-{}.map", std::process::id()));
+        std::fs::write(&elf_path, test_elf()).unwrap();
+        std::fs::write(&map_path, TEST_MAP).unwrap();
+        let input = MemoryInput {
+            elf_path: Some(elf_path.to_string_lossy().to_string()),
+            map_path: Some(map_path.to_string_lossy().to_string()),
+        };
+        let report = memory_analyze(&input, Some(3)).unwrap();
+        std::fs::remove_file(elf_path).unwrap();
+        std::fs::remove_file(map_path).unwrap();
+
+        assert_eq!((report.flash_total, report.ram_total), (0x5C + 8, 8 + 0x100));
+        assert_eq!(report.regions.len(), 2);
+        assert_eq!(report.objects[0].name, "main.o");
+        let top: Vec<(&str, Option<&str>)> =
+            report.top_symbols().iter().map(|s| (s.name.as_str(), s.object.as_deref())).collect();
+        assert_eq!(
+            top,
+            [("buffer", Some("main.o")), ("main", Some("main.o")), ("memcpy", Some("libc.a(lib_a-memcpy.o)"))]
+        );
+        assert_eq!(report.symbols.iter().find(|s| s.name == RUST_SYMBOL).unwrap().object, None);
+
+        let markdown = memory_report_markdown(&report);
+        assert!(markdown.contains("| FLASH | 0x08000000 | 65536 | 116 | 0.2% |"));
+        assert!(markdown.contains("| libc.a(lib_a-memcpy.o) | 30 | 0 |"));
+        assert!(markdown.contains("| buffer | 256 | .bss | main.o |"));
+    }
+}
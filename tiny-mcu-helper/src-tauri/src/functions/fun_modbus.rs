@@ -0,0 +1,711 @@
+use serde::{Deserialize, Serialize};
+
+use crate::functions::fun_checksum::{crc_compute, crc_find_preset};
+
+// 功能码
+pub const FC_READ_COILS: u8 = 0x01;
+pub const FC_READ_DISCRETE_INPUTS: u8 = 0x02;
+pub const FC_READ_HOLDING_REGISTERS: u8 = 0x03;
+pub const FC_READ_INPUT_REGISTERS: u8 = 0x04;
+pub const FC_WRITE_SINGLE_COIL: u8 = 0x05;
+pub const FC_WRITE_SINGLE_REGISTER: u8 = 0x06;
+pub const FC_WRITE_MULTIPLE_COILS: u8 = 0x0F;
+pub const FC_WRITE_MULTIPLE_REGISTERS: u8 = 0x10;
+pub const FC_READ_WRITE_MULTIPLE_REGISTERS: u8 = 0x17;
+
+// 异常码
+pub const EX_ILLEGAL_FUNCTION: u8 = 0x01;
+pub const EX_ILLEGAL_DATA_ADDRESS: u8 = 0x02;
+pub const EX_ILLEGAL_DATA_VALUE: u8 = 0x03;
+pub const EX_GATEWAY_TARGET_FAILED: u8 = 0x0B;
+
+/// Modbus 请求（覆盖功能码 01 ~ 06、15、16、23）
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "function", rename_all = "snake_case")]
+pub enum ModbusRequest {
+    ReadCoils { address: u16, quantity: u16 },
+    ReadDiscreteInputs { address: u16, quantity: u16 },
+    ReadHoldingRegisters { address: u16, quantity: u16 },
+    ReadInputRegisters { address: u16, quantity: u16 },
+    WriteSingleCoil { address: u16, value: bool },
+    WriteSingleRegister { address: u16, value: u16 },
+    WriteMultipleCoils { address: u16, values: Vec<bool> },
+    WriteMultipleRegisters { address: u16, values: Vec<u16> },
+    ReadWriteMultipleRegisters {
+        read_address: u16,
+        read_quantity: u16,
+        write_address: u16,
+        values: Vec<u16>,
+    },
+}
+
+/// Modbus 响应
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModbusResponse {
+    Bits { values: Vec<bool> },
+    Registers { values: Vec<u16> },
+    WriteSingle { address: u16, value: u16 },
+    WriteMultiple { address: u16, quantity: u16 },
+    Exception { function: u8, code: u8 },
+}
+
+impl ModbusRequest {
+    pub fn function_code(&self) -> u8 {
+        match self {
+            Self::ReadCoils { .. } => FC_READ_COILS,
+            Self::ReadDiscreteInputs { .. } => FC_READ_DISCRETE_INPUTS,
+            Self::ReadHoldingRegisters { .. } => FC_READ_HOLDING_REGISTERS,
+            Self::ReadInputRegisters { .. } => FC_READ_INPUT_REGISTERS,
+            Self::WriteSingleCoil { .. } => FC_WRITE_SINGLE_COIL,
+            Self::WriteSingleRegister { .. } => FC_WRITE_SINGLE_REGISTER,
+            Self::WriteMultipleCoils { .. } => FC_WRITE_MULTIPLE_COILS,
+            Self::WriteMultipleRegisters { .. } => FC_WRITE_MULTIPLE_REGISTERS,
+            Self::ReadWriteMultipleRegisters { .. } => FC_READ_WRITE_MULTIPLE_REGISTERS,
+        }
+    }
+
+    // 检查数量是否在协议允许范围内
+    pub fn validate(&self) -> Result<(), String> {
+        let check = |name: &str, value: usize, max: usize| {
+            if value == 0 || value > max {
+                Err(format!("{} 数量必须在 1 ~ {} 之间: {}", name, max, value))
+            } else {
+                Ok(())
+            }
+        };
+        match self {
+            Self::ReadCoils { quantity, .. } | Self::ReadDiscreteInputs { quantity, .. } => {
+                check("读取线圈", *quantity as usize, 2000)
+            }
+            Self::ReadHoldingRegisters { quantity, .. } | Self::ReadInputRegisters { quantity, .. } => {
+                check("读取寄存器", *quantity as usize, 125)
+            }
+            Self::WriteSingleCoil { .. } | Self::WriteSingleRegister { .. } => Ok(()),
+            Self::WriteMultipleCoils { values, .. } => check("写入线圈", values.len(), 1968),
+            Self::WriteMultipleRegisters { values, .. } => check("写入寄存器", values.len(), 123),
+            Self::ReadWriteMultipleRegisters {
+                read_quantity,
+                values,
+                ..
+            } => {
+                check("读取寄存器", *read_quantity as usize, 125)?;
+                check("写入寄存器", values.len(), 121)
+            }
+        }
+    }
+
+    /// 编码为 PDU（功能码 + 数据）
+    pub fn encode(&self) -> Result<Vec<u8>, String> {
+        self.validate()?;
+        let mut pdu = vec![self.function_code()];
+        match self {
+            Self::ReadCoils { address, quantity }
+            | Self::ReadDiscreteInputs { address, quantity }
+            | Self::ReadHoldingRegisters { address, quantity }
+            | Self::ReadInputRegisters { address, quantity } => {
+                pdu.extend_from_slice(&address.to_be_bytes());
+                pdu.extend_from_slice(&quantity.to_be_bytes());
+            }
+            Self::WriteSingleCoil { address, value } => {
+                pdu.extend_from_slice(&address.to_be_bytes());
+                pdu.extend_from_slice(if *value { &[0xFF, 0x00] } else { &[0x00, 0x00] });
+            }
+            Self::WriteSingleRegister { address, value } => {
+                pdu.extend_from_slice(&address.to_be_bytes());
+                pdu.extend_from_slice(&value.to_be_bytes());
+            }
+            Self::WriteMultipleCoils { address, values } => {
+                let packed = pack_bits(values);
+                pdu.extend_from_slice(&address.to_be_bytes());
+                pdu.extend_from_slice(&(values.len() as u16).to_be_bytes());
+                pdu.push(packed.len() as u8);
+                pdu.extend_from_slice(&packed);
+            }
+            Self::WriteMultipleRegisters { address, values } => {
+                pdu.extend_from_slice(&address.to_be_bytes());
+                pdu.extend_from_slice(&(values.len() as u16).to_be_bytes());
+                pdu.push((values.len() * 2) as u8);
+                values.iter().for_each(|v| pdu.extend_from_slice(&v.to_be_bytes()));
+            }
+            Self::ReadWriteMultipleRegisters {
+                read_address,
+                read_quantity,
+                write_address,
+                values,
+            } => {
+                pdu.extend_from_slice(&read_address.to_be_bytes());
+                pdu.extend_from_slice(&read_quantity.to_be_bytes());
+                pdu.extend_from_slice(&write_address.to_be_bytes());
+                pdu.extend_from_slice(&(values.len() as u16).to_be_bytes());
+                pdu.push((values.len() * 2) as u8);
+                values.iter().for_each(|v| pdu.extend_from_slice(&v.to_be_bytes()));
+            }
+        }
+        Ok(pdu)
+    }
+
+    /// 从 PDU 解析请求（从站模拟器使用）；失败时返回异常码
+    pub fn decode(pdu: &[u8]) -> Result<Self, u8> {
+        let function = *pdu.first().ok_or(EX_ILLEGAL_FUNCTION)?;
+        let word = |i: usize| -> Result<u16, u8> {
+            pdu.get(i..i + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .ok_or(EX_ILLEGAL_DATA_VALUE)
+        };
+        let registers = |start: usize, count: usize| -> Result<Vec<u16>, u8> {
+            if pdu.len() != start + count * 2 {
+                return Err(EX_ILLEGAL_DATA_VALUE);
+            }
+            (0..count).map(|i| word(start + i * 2)).collect()
+        };
+
+        let request = match function {
+            FC_READ_COILS | FC_READ_DISCRETE_INPUTS | FC_READ_HOLDING_REGISTERS | FC_READ_INPUT_REGISTERS => {
+                if pdu.len() != 5 {
+                    return Err(EX_ILLEGAL_DATA_VALUE);
+                }
+                let (address, quantity) = (word(1)?, word(3)?);
+                match function {
+                    FC_READ_COILS => Self::ReadCoils { address, quantity },
+                    FC_READ_DISCRETE_INPUTS => Self::ReadDiscreteInputs { address, quantity },
+                    FC_READ_HOLDING_REGISTERS => Self::ReadHoldingRegisters { address, quantity },
+                    _ => Self::ReadInputRegisters { address, quantity },
+                }
+            }
+            FC_WRITE_SINGLE_COIL => {
+                if pdu.len() != 5 {
+                    return Err(EX_ILLEGAL_DATA_VALUE);
+                }
+                let value = match word(3)? {
+                    0xFF00 => true,
+                    0x0000 => false,
+                    _ => return Err(EX_ILLEGAL_DATA_VALUE),
+                };
+                Self::WriteSingleCoil { address: word(1)?, value }
+            }
+            FC_WRITE_SINGLE_REGISTER => {
+                if pdu.len() != 5 {
+                    return Err(EX_ILLEGAL_DATA_VALUE);
+                }
+                Self::WriteSingleRegister {
+                    address: word(1)?,
+                    value: word(3)?,
+                }
+            }
+            FC_WRITE_MULTIPLE_COILS => {
+                let quantity = word(3)? as usize;
+                let byte_count = *pdu.get(5).ok_or(EX_ILLEGAL_DATA_VALUE)? as usize;
+                if byte_count != quantity.div_ceil(8) || pdu.len() != 6 + byte_count {
+                    return Err(EX_ILLEGAL_DATA_VALUE);
+                }
+                Self::WriteMultipleCoils {
+                    address: word(1)?,
+                    values: unpack_bits(&pdu[6..], quantity),
+                }
+            }
+            FC_WRITE_MULTIPLE_REGISTERS => {
+                let quantity = word(3)? as usize;
+                if *pdu.get(5).ok_or(EX_ILLEGAL_DATA_VALUE)? as usize != quantity * 2 {
+                    return Err(EX_ILLEGAL_DATA_VALUE);
+                }
+                Self::WriteMultipleRegisters {
+                    address: word(1)?,
+                    values: registers(6, quantity)?,
+                }
+            }
+            FC_READ_WRITE_MULTIPLE_REGISTERS => {
+                let quantity = word(7)? as usize;
+                if *pdu.get(9).ok_or(EX_ILLEGAL_DATA_VALUE)? as usize != quantity * 2 {
+                    return Err(EX_ILLEGAL_DATA_VALUE);
+                }
+                Self::ReadWriteMultipleRegisters {
+                    read_address: word(1)?,
+                    read_quantity: word(3)?,
+                    write_address: word(5)?,
+                    values: registers(10, quantity)?,
+                }
+            }
+            _ => return Err(EX_ILLEGAL_FUNCTION),
+        };
+        request.validate().map_err(|_| EX_ILLEGAL_DATA_VALUE)?;
+        Ok(request)
+    }
+
+    /// 解析与本请求对应的响应 PDU
+    pub fn decode_response(&self, pdu: &[u8]) -> Result<ModbusResponse, String> {
+        let function = *pdu.first().ok_or("响应为空")?;
+        if function == self.function_code() | 0x80 {
+            let code = *pdu.get(1).ok_or("异常响应长度无效")?;
+            return Ok(ModbusResponse::Exception {
+                function: self.function_code(),
+                code,
+            });
+        }
+        if function != self.function_code() {
+            return Err(format!(
+                "响应功能码不匹配: 期望 {:02X}，实际 {:02X}",
+                self.function_code(),
+                function
+            ));
+        }
+
+        let word = |i: usize| -> Result<u16, String> {
+            pdu.get(i..i + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .ok_or("响应长度无效".to_string())
+        };
+        let byte_count = |expected: usize| -> Result<&[u8], String> {
+            let count = *pdu.get(1).ok_or("响应长度无效")? as usize;
+            if count != expected || pdu.len() != 2 + count {
+                return Err(format!("响应字节数无效: 期望 {}，实际 {}", expected, count));
+            }
+            Ok(&pdu[2..])
+        };
+
+        match self {
+            Self::ReadCoils { quantity, .. } | Self::ReadDiscreteInputs { quantity, .. } => {
+                let data = byte_count((*quantity as usize).div_ceil(8))?;
+                Ok(ModbusResponse::Bits {
+                    values: unpack_bits(data, *quantity as usize),
+                })
+            }
+            Self::ReadHoldingRegisters { quantity, .. }
+            | Self::ReadInputRegisters { quantity, .. }
+            | Self::ReadWriteMultipleRegisters {
+                read_quantity: quantity,
+                ..
+            } => {
+                let data = byte_count(*quantity as usize * 2)?;
+                Ok(ModbusResponse::Registers {
+                    values: data
+                        .chunks(2)
+                        .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                        .collect(),
+                })
+            }
+            Self::WriteSingleCoil { .. } | Self::WriteSingleRegister { .. } => {
+                let expected = self.encode()?;
+                if pdu != expected.as_slice() {
+                    return Err("写单个线圈/寄存器的回显与请求不一致".to_string());
+                }
+                Ok(ModbusResponse::WriteSingle {
+                    address: word(1)?,
+                    value: word(3)?,
+                })
+            }
+            Self::WriteMultipleCoils { address, values } => {
+                self.check_write_echo(pdu, *address, values.len())
+            }
+            Self::WriteMultipleRegisters { address, values } => {
+                self.check_write_echo(pdu, *address, values.len())
+            }
+        }
+    }
+
+    fn check_write_echo(&self, pdu: &[u8], address: u16, quantity: usize) -> Result<ModbusResponse, String> {
+        if pdu.len() != 5 {
+            return Err("写多个线圈/寄存器的响应长度无效".to_string());
+        }
+        let echo_address = u16::from_be_bytes([pdu[1], pdu[2]]);
+        let echo_quantity = u16::from_be_bytes([pdu[3], pdu[4]]);
+        if echo_address != address || echo_quantity as usize != quantity {
+            return Err("写多个线圈/寄存器的回显与请求不一致".to_string());
+        }
+        Ok(ModbusResponse::WriteMultiple {
+            address,
+            quantity: echo_quantity,
+        })
+    }
+
+    /// RTU 模式下正常响应的总长度（含从站地址与 CRC）；需要字节数字段时返回 None
+    pub fn rtu_response_len(&self, byte_count: Option<u8>) -> Option<usize> {
+        match self {
+            Self::WriteSingleCoil { .. }
+            | Self::WriteSingleRegister { .. }
+            | Self::WriteMultipleCoils { .. }
+            | Self::WriteMultipleRegisters { .. } => Some(8),
+            _ => byte_count.map(|n| 5 + n as usize),
+        }
+    }
+}
+
+impl ModbusResponse {
+    /// 编码为响应 PDU（从站模拟器使用）
+    pub fn encode(&self, request: &ModbusRequest) -> Vec<u8> {
+        let function = request.function_code();
+        match self {
+            Self::Exception { code, .. } => vec![function | 0x80, *code],
+            Self::Bits { values } => {
+                let packed = pack_bits(values);
+                let mut pdu = vec![function, packed.len() as u8];
+                pdu.extend(packed);
+                pdu
+            }
+            Self::Registers { values } => {
+                let mut pdu = vec![function, (values.len() * 2) as u8];
+                values.iter().for_each(|v| pdu.extend_from_slice(&v.to_be_bytes()));
+                pdu
+            }
+            Self::WriteSingle { address, value } => {
+                let mut pdu = vec![function];
+                pdu.extend_from_slice(&address.to_be_bytes());
+                pdu.extend_from_slice(&value.to_be_bytes());
+                pdu
+            }
+            Self::WriteMultiple { address, quantity } => {
+                let mut pdu = vec![function];
+                pdu.extend_from_slice(&address.to_be_bytes());
+                pdu.extend_from_slice(&quantity.to_be_bytes());
+                pdu
+            }
+        }
+    }
+}
+
+/// 异常码说明
+pub fn modbus_exception_text(code: u8) -> &'static str {
+    match code {
+        0x01 => "非法功能码",
+        0x02 => "非法数据地址",
+        0x03 => "非法数据值",
+        0x04 => "从站设备故障",
+        0x05 => "确认（处理中）",
+        0x06 => "从站设备忙",
+        0x08 => "存储奇偶性错误",
+        0x0A => "网关路径不可用",
+        0x0B => "网关目标设备无响应",
+        _ => "未知异常",
+    }
+}
+
+pub fn pack_bits(values: &[bool]) -> Vec<u8> {
+    let mut packed = vec![0u8; values.len().div_ceil(8)];
+    for (i, value) in values.iter().enumerate() {
+        if *value {
+            packed[i / 8] |= 1 << (i % 8);
+        }
+    }
+    packed
+}
+
+pub fn unpack_bits(data: &[u8], quantity: usize) -> Vec<bool> {
+    (0..quantity)
+        .map(|i| data.get(i / 8).is_some_and(|b| b & (1 << (i % 8)) != 0))
+        .collect()
+}
+
+// ==================== RTU / ASCII 帧 ====================
+
+/// CRC-16/MODBUS
+pub fn modbus_crc16(data: &[u8]) -> u16 {
+    let params = crc_find_preset("CRC-16/MODBUS").unwrap().params;
+    crc_compute(params, data).unwrap_or(0) as u16
+}
+
+/// LRC（字节和的二进制补码）
+pub fn modbus_lrc(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)).wrapping_neg()
+}
+
+/// RTU 帧：从站地址 + PDU + CRC（低字节在前）
+pub fn encode_rtu_frame(unit_id: u8, pdu: &[u8]) -> Vec<u8> {
+    let mut frame = vec![unit_id];
+    frame.extend_from_slice(pdu);
+    let crc = modbus_crc16(&frame);
+    frame.extend_from_slice(&crc.to_le_bytes());
+    frame
+}
+
+/// 解析 RTU 帧，返回 (从站地址, PDU)
+pub fn decode_rtu_frame(frame: &[u8]) -> Result<(u8, Vec<u8>), String> {
+    if frame.len() < 4 {
+        return Err(format!("RTU 帧长度过短: {}", frame.len()));
+    }
+    let (body, crc) = frame.split_at(frame.len() - 2);
+    let expected = modbus_crc16(body);
+    let actual = u16::from_le_bytes([crc[0], crc[1]]);
+    if expected != actual {
+        return Err(format!("CRC 校验失败: 期望 {:04X}，实际 {:04X}", expected, actual));
+    }
+    Ok((body[0], body[1..].to_vec()))
+}
+
+/// ASCII 帧：':' + 十六进制(从站地址 + PDU + LRC) + CRLF
+pub fn encode_ascii_frame(unit_id: u8, pdu: &[u8]) -> Vec<u8> {
+    let mut body = vec![unit_id];
+    body.extend_from_slice(pdu);
+    body.push(modbus_lrc(&body));
+
+    let mut frame = String::from(":");
+    for b in body {
+        frame.push_str(&format!("{:02X}", b));
+    }
+    frame.push_str("\r\n");
+    frame.into_bytes()
+}
+
+/// 解析 ASCII 帧，返回 (从站地址, PDU)
+pub fn decode_ascii_frame(frame: &[u8]) -> Result<(u8, Vec<u8>), String> {
+    let text = std::str::from_utf8(frame).map_err(|_| "ASCII 帧包含非法字符".to_string())?;
+    let text = text.trim_end_matches(['\r', '\n']);
+    let hex = text.strip_prefix(':').ok_or("ASCII 帧必须以 ':' 开头")?;
+    if hex.len() < 6 || !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return Err("ASCII 帧长度无效".to_string());
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| "ASCII 帧包含非法字符".to_string()))
+        .collect::<Result<Vec<u8>, String>>()?;
+
+    let (body, lrc) = bytes.split_at(bytes.len() - 1);
+    if modbus_lrc(body) != lrc[0] {
+        return Err(format!("LRC 校验失败: 期望 {:02X}，实际 {:02X}", modbus_lrc(body), lrc[0]));
+    }
+    Ok((body[0], body[1..].to_vec()))
+}
+
+// ==================== TCP (MBAP) 帧 ====================
+
+// MBAP 报文头长度：事务号(2) + 协议号(2) + 长度(2) + 单元号(1)
+pub const MBAP_HEADER_LEN: usize = 7;
+
+/// MBAP 报文头
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MbapHeader {
+    pub transaction_id: u16,
+    pub unit_id: u8,
+    pub pdu_len: usize, // 报文头之后的 PDU 字节数
+}
+
+/// TCP 帧：MBAP 报文头 + PDU
+pub fn encode_tcp_frame(transaction_id: u16, unit_id: u8, pdu: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(MBAP_HEADER_LEN + pdu.len());
+    frame.extend_from_slice(&transaction_id.to_be_bytes());
+    frame.extend_from_slice(&0u16.to_be_bytes());
+    frame.extend_from_slice(&(pdu.len() as u16 + 1).to_be_bytes());
+    frame.push(unit_id);
+    frame.extend_from_slice(pdu);
+    frame
+}
+
+/// 解析 MBAP 报文头
+pub fn decode_mbap_header(header: &[u8; MBAP_HEADER_LEN]) -> Result<MbapHeader, String> {
+    let protocol_id = u16::from_be_bytes([header[2], header[3]]);
+    if protocol_id != 0 {
+        return Err(format!("非 Modbus 协议号: {}", protocol_id));
+    }
+    let length = u16::from_be_bytes([header[4], header[5]]) as usize;
+    if !(2..=254).contains(&length) {
+        return Err(format!("MBAP 长度字段无效: {}", length));
+    }
+    Ok(MbapHeader {
+        transaction_id: u16::from_be_bytes([header[0], header[1]]),
+        unit_id: header[6],
+        pdu_len: length - 1,
+    })
+}
+
+/// 串口帧模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModbusSerialMode {
+    Rtu,
+    Ascii,
+}
+
+/// 根据串口参数计算 RTU 帧间隔 t3.5 与字符间隔 t1.5（微秒）
+/// 波特率高于 19200 时按协议规定固定为 1750us / 750us
+pub fn modbus_rtu_timing_us(baud_rate: u32, data_bits: u8, parity: bool, stop_bits: u8) -> (u64, u64) {
+    if baud_rate > 19200 {
+        return (1750, 750);
+    }
+    let bits_per_char = 1 + data_bits as u64 + parity as u64 + stop_bits as u64;
+    let char_us = bits_per_char * 1_000_000 / baud_rate.max(1) as u64;
+    (char_us * 7 / 2, char_us * 3 / 2)
+}
+
+// ==================== 数据模型（从站） ====================
+
+/// 从站数据区
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModbusDataModel {
+    pub coils: Vec<bool>,
+    pub discrete_inputs: Vec<bool>,
+    pub holding_registers: Vec<u16>,
+    pub input_registers: Vec<u16>,
+}
+
+impl ModbusDataModel {
+    pub fn with_sizes(coils: usize, discrete_inputs: usize, holding: usize, input: usize) -> Self {
+        Self {
+            coils: vec![false; coils],
+            discrete_inputs: vec![false; discrete_inputs],
+            holding_registers: vec![0; holding],
+            input_registers: vec![0; input],
+        }
+    }
+
+    /// 处理请求并返回响应（地址越界时返回非法数据地址异常）
+    pub fn process(&mut self, request: &ModbusRequest) -> ModbusResponse {
+        fn range<T>(table: &[T], address: u16, quantity: usize) -> Option<std::ops::Range<usize>> {
+            let start = address as usize;
+            let end = start + quantity;
+            (end <= table.len()).then_some(start..end)
+        }
+        let exception = ModbusResponse::Exception {
+            function: request.function_code(),
+            code: EX_ILLEGAL_DATA_ADDRESS,
+        };
+
+        match request {
+            ModbusRequest::ReadCoils { address, quantity } => match range(&self.coils, *address, *quantity as usize) {
+                Some(r) => ModbusResponse::Bits { values: self.coils[r].to_vec() },
+                None => exception,
+            },
+            ModbusRequest::ReadDiscreteInputs { address, quantity } => {
+                match range(&self.discrete_inputs, *address, *quantity as usize) {
+                    Some(r) => ModbusResponse::Bits { values: self.discrete_inputs[r].to_vec() },
+                    None => exception,
+                }
+            }
+            ModbusRequest::ReadHoldingRegisters { address, quantity } => {
+                match range(&self.holding_registers, *address, *quantity as usize) {
+                    Some(r) => ModbusResponse::Registers { values: self.holding_registers[r].to_vec() },
+                    None => exception,
+                }
+            }
+            ModbusRequest::ReadInputRegisters { address, quantity } => {
+                match range(&self.input_registers, *address, *quantity as usize) {
+                    Some(r) => ModbusResponse::Registers { values: self.input_registers[r].to_vec() },
+                    None => exception,
+                }
+            }
+            ModbusRequest::WriteSingleCoil { address, value } => match range(&self.coils, *address, 1) {
+                Some(r) => {
+                    self.coils[r.start] = *value;
+                    ModbusResponse::WriteSingle {
+                        address: *address,
+                        value: if *value { 0xFF00 } else { 0x0000 },
+                    }
+                }
+                None => exception,
+            },
+            ModbusRequest::WriteSingleRegister { address, value } => {
+                match range(&self.holding_registers, *address, 1) {
+                    Some(r) => {
+                        self.holding_registers[r.start] = *value;
+                        ModbusResponse::WriteSingle {
+                            address: *address,
+                            value: *value,
+                        }
+                    }
+                    None => exception,
+                }
+            }
+            ModbusRequest::WriteMultipleCoils { address, values } => {
+                match range(&self.coils, *address, values.len()) {
+                    Some(r) => {
+                        self.coils[r].copy_from_slice(values);
+                        ModbusResponse::WriteMultiple {
+                            address: *address,
+                            quantity: values.len() as u16,
+                        }
+                    }
+                    None => exception,
+                }
+            }
+            ModbusRequest::WriteMultipleRegisters { address, values } => {
+                match range(&self.holding_registers, *address, values.len()) {
+                    Some(r) => {
+                        self.holding_registers[r].copy_from_slice(values);
+                        ModbusResponse::WriteMultiple {
+                            address: *address,
+                            quantity: values.len() as u16,
+                        }
+                    }
+                    None => exception,
+                }
+            }
+            ModbusRequest::ReadWriteMultipleRegisters {
+                read_address,
+                read_quantity,
+                write_address,
+                values,
+            } => {
+                // 协议规定先写后读
+                let write = range(&self.holding_registers, *write_address, values.len());
+                let read = range(&self.holding_registers, *read_address, *read_quantity as usize);
+                match (write, read) {
+                    (Some(w), Some(r)) => {
+                        self.holding_registers[w].copy_from_slice(values);
+                        ModbusResponse::Registers {
+                            values: self.holding_registers[r].to_vec(),
+                        }
+                    }
+                    _ => exception,
+                }
+            }
+        }
+    }
+
+    /// 处理请求 PDU 并返回响应 PDU
+    pub fn process_pdu(&mut self, pdu: &[u8]) -> Vec<u8> {
+        match ModbusRequest::decode(pdu) {
+            Ok(request) => self.process(&request).encode(&request),
+            Err(code) => vec![pdu.first().copied().unwrap_or(0) | 0x80, code],
+        }
+    }
+}
+
+// ==================== 寄存器值解码 ====================
+
+/// 数据类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModbusDataType {
+    Bool,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+}
+
+impl ModbusDataType {
+    // 占用的寄存器数（bool 占 1 个线圈）
+    pub fn register_count(&self) -> usize {
+        match self {
+            Self::Bool | Self::U16 | Self::I16 => 1,
+            Self::U32 | Self::I32 | Self::F32 => 2,
+        }
+    }
+}
+
+/// 32 位数据的字序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WordOrder {
+    #[default]
+    Big, // 高字在前（ABCD）
+    Little, // 低字在前（CDAB）
+}
+
+/// 把寄存器解码为数值
+pub fn decode_registers(registers: &[u16], data_type: ModbusDataType, word_order: WordOrder) -> Result<f64, String> {
+    if registers.len() < data_type.register_count() {
+        return Err("寄存器数量不足".to_string());
+    }
+    let dword = || match word_order {
+        WordOrder::Big => ((registers[0] as u32) << 16) | registers[1] as u32,
+        WordOrder::Little => ((registers[1] as u32) << 16) | registers[0] as u32,
+    };
+    Ok(match data_type {
+        ModbusDataType::Bool => (registers[0] != 0) as u8 as f64,
+        ModbusDataType::U16 => registers[0] as f64,
+        ModbusDataType::I16 => registers[0] as i16 as f64,
+        ModbusDataType::U32 => dword() as f64,
+        ModbusDataType::I32 => dword() as i32 as f64,
+        ModbusDataType::F32 => f32::from_bits(dword()) as f64,
+    })
+}
@@ -0,0 +1,427 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{util_format_c_array, util_format_c_value, util_format_csv, util_write_generate_file, TableFormat};
+
+const KELVIN: f64 = 273.15;
+
+/// NTC 特性模型
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NtcModel {
+    Beta {
+        r0_ohm: f64,        // 标称阻值
+        beta: f64,          // B 值
+        t0_c: Option<f64>, // 标称温度，默认 25℃
+    },
+    SteinhartHart {
+        a: f64,
+        b: f64,
+        c: f64,
+    },
+    Points {
+        csv: String, // 每行 "温度(℃),阻值(Ω)"，可带表头
+    },
+}
+
+/// 分压电路：NTC 与固定电阻串联，ADC 与分压器共用参考（比例测量）
+#[derive(Debug, Clone, Deserialize)]
+pub struct NtcDivider {
+    pub fixed_ohm: f64,
+    #[serde(default)]
+    pub ntc_on_top: bool, // NTC 接电源侧，默认 NTC 接地侧
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NtcTableConfig {
+    pub model: NtcModel,
+    pub divider: NtcDivider,
+    pub resolution_bits: u8,
+    pub t_min_c: f64,
+    pub t_max_c: f64,
+    pub step_shift: Option<u8>,    // 表项间隔 2^step_shift 个原始码，不填时按 max_error_c 自动选择
+    pub max_error_c: Option<f64>,  // 自动选择间隔时允许的最大插值误差，默认 0.5℃
+    pub temp_scale: Option<f64>,   // 存储值 = 温度 * temp_scale，默认 10（0.1℃）
+    pub format: TableFormat,
+    pub name: Option<String>,      // 默认 ntc_table
+    pub c_type: Option<String>,    // 默认 int16_t
+    pub file_name: Option<String>, // 填写时保存到 generate 目录
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NtcTableResult {
+    pub content: String,
+    pub entries: usize,
+    pub step_shift: u8,
+    pub step_codes: u32,
+    pub max_error_c: f64,       // 线性插值 + 存储量化后的最大误差
+    pub max_error_code: u32,    // 出现最大误差的原始码
+    pub max_error_temp_c: f64,  // 该原始码对应的真实温度
+    pub code_min: u32,          // t_min ~ t_max 对应的原始码范围
+    pub code_max: u32,
+    pub output_path: Option<String>,
+}
+
+// 解析 R/T 点表并按阻值升序排列
+fn ntc_parse_points(csv: &str) -> Result<Vec<(f64, f64)>, String> {
+    let mut points = Vec::new();
+    for (index, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split([',', ';', '\t']).map(|s| s.trim()).collect();
+        if fields.len() < 2 {
+            return Err(format!("第 {} 行格式错误: {}", index + 1, line));
+        }
+        match (fields[0].parse::<f64>(), fields[1].parse::<f64>()) {
+            (Ok(t), Ok(r)) if r > 0.0 => points.push((r, t)),
+            (Ok(_), Ok(_)) => return Err(format!("第 {} 行阻值必须大于 0", index + 1)),
+            // 首行允许为表头
+            _ if points.is_empty() && index == 0 => continue,
+            _ => return Err(format!("第 {} 行解析失败: {}", index + 1, line)),
+        }
+    }
+    if points.len() < 2 {
+        return Err("R/T 点表至少需要 2 个点".to_string());
+    }
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    Ok(points)
+}
+
+// 阻值 -> 温度(℃)
+fn ntc_temperature(model: &NtcModel, points: &[(f64, f64)], r: f64) -> f64 {
+    let ln_r = r.ln();
+    let inv_t = match model {
+        NtcModel::Beta { r0_ohm, beta, t0_c } => 1.0 / (t0_c.unwrap_or(25.0) + KELVIN) + (r / r0_ohm).ln() / beta,
+        NtcModel::SteinhartHart { a, b, c } => a + b * ln_r + c * ln_r.powi(3),
+        NtcModel::Points { .. } => {
+            // 在 ln(R) - 1/T 平面分段线性插值，两端按端点线段外推
+            let i = points.partition_point(|p| p.0 < r).clamp(1, points.len() - 1);
+            let (r1, t1) = points[i - 1];
+            let (r2, t2) = points[i];
+            let (x1, y1) = (r1.ln(), 1.0 / (t1 + KELVIN));
+            let (x2, y2) = (r2.ln(), 1.0 / (t2 + KELVIN));
+            y1 + (ln_r - x1) * (y2 - y1) / (x2 - x1)
+        }
+    };
+    1.0 / inv_t - KELVIN
+}
+
+// 原始码 -> NTC 阻值，码值在两端时返回 None
+fn ntc_resistance(divider: &NtcDivider, code: u32, max_code: u32) -> Option<f64> {
+    if code == 0 || code >= max_code {
+        return None;
+    }
+    let ratio = code as f64 / max_code as f64;
+    Some(if divider.ntc_on_top {
+        divider.fixed_ohm * (1.0 - ratio) / ratio
+    } else {
+        divider.fixed_ohm * ratio / (1.0 - ratio)
+    })
+}
+
+/// 生成以原始码为索引的温度表：temp = table[code >> shift]，相邻表项间线性插值
+pub fn ntc_build_table(config: &NtcTableConfig) -> Result<NtcTableResult, String> {
+    if !(6..=16).contains(&config.resolution_bits) {
+        return Err(format!("不支持的 ADC 分辨率: {} 位", config.resolution_bits));
+    }
+    if config.divider.fixed_ohm <= 0.0 {
+        return Err("分压电阻必须大于 0".to_string());
+    }
+    if config.t_min_c >= config.t_max_c {
+        return Err("温度范围无效".to_string());
+    }
+    match &config.model {
+        NtcModel::Beta { r0_ohm, beta, .. } if *r0_ohm <= 0.0 || *beta <= 0.0 => {
+            return Err("标称阻值和 B 值必须大于 0".to_string())
+        }
+        _ => {}
+    }
+    let points = match &config.model {
+        NtcModel::Points { csv } => ntc_parse_points(csv)?,
+        _ => vec![],
+    };
+    let scale = config.temp_scale.unwrap_or(10.0);
+    if scale <= 0.0 {
+        return Err("温度缩放系数必须大于 0".to_string());
+    }
+    let c_type = config.c_type.as_deref().unwrap_or("int16_t");
+    let integer_type = c_type != "float" && c_type != "double";
+
+    // 每个原始码的真实温度，限制在设定范围内
+    let max_code = (1u32 << config.resolution_bits) - 1;
+    let exact: Vec<f64> = (0..=max_code)
+        .map(|code| match ntc_resistance(&config.divider, code, max_code) {
+            Some(r) => ntc_temperature(&config.model, &points, r),
+            // 端点码对应开路或短路，取温度范围的边界
+            None => {
+                let r_low = code == 0;
+                if r_low != config.divider.ntc_on_top {
+                    config.t_max_c
+                } else {
+                    config.t_min_c
+                }
+            }
+        })
+        .map(|t| if t.is_finite() { t } else { config.t_max_c })
+        .map(|t| t.clamp(config.t_min_c, config.t_max_c))
+        .collect();
+    let in_range: Vec<u32> = (0..=max_code)
+        .filter(|code| {
+            let t = exact[*code as usize];
+            t > config.t_min_c && t < config.t_max_c
+        })
+        .collect();
+    if in_range.is_empty() {
+        return Err("温度范围内没有可用的原始码，请检查分压电阻".to_string());
+    }
+
+    // 量化存储值
+    let stored = |t: f64| if integer_type { (t * scale).round() / scale } else { t };
+
+    // 计算某个间隔下的表项与最大误差
+    let evaluate = |shift: u8| {
+        let step = 1u32 << shift;
+        let entries: Vec<f64> = (0..=max_code.div_ceil(step))
+            .map(|i| stored(exact[((i * step).min(max_code)) as usize]))
+            .collect();
+        let mut worst = (0.0f64, in_range[0]);
+        for code in &in_range {
+            let i = (code / step) as usize;
+            let frac = (code % step) as f64 / step as f64;
+            let next = entries.get(i + 1).copied().unwrap_or(entries[i]);
+            let interpolated = entries[i] + (next - entries[i]) * frac;
+            let error = (interpolated - exact[*code as usize]).abs();
+            if error > worst.0 {
+                worst = (error, *code);
+            }
+        }
+        (entries, worst)
+    };
+
+    let shift = match config.step_shift {
+        Some(shift) if shift < config.resolution_bits => shift,
+        Some(shift) => return Err(format!("步进位移 {} 超出分辨率", shift)),
+        None => {
+            // 选择满足误差要求的最大间隔，范围与手动指定 step_shift 一致
+            let limit = config.max_error_c.unwrap_or(0.5);
+            (0..config.resolution_bits)
+                .rev()
+                .find(|shift| evaluate(*shift).1 .0 <= limit)
+                .ok_or(format!("无法满足最大误差 {}℃ 的要求", limit))?
+        }
+    };
+    let (entries, (max_error, max_error_code)) = evaluate(shift);
+    let step = 1u32 << shift;
+
+    let content = match config.format {
+        TableFormat::Csv => {
+            let rows: Vec<Vec<String>> = entries
+                .iter()
+                .enumerate()
+                .map(|(i, t)| vec![format!("{}", (i as u32 * step).min(max_code)), format!("{:.3}", t)])
+                .collect();
+            util_format_csv(&["code", "temperature_c"], &rows)
+        }
+        TableFormat::C => {
+            let name = config.name.as_deref().unwrap_or("ntc_table");
+            let values: Vec<String> = entries
+                .iter()
+                .map(|t| util_format_c_value(t * scale, c_type, 3))
+                .collect();
+            let model = match &config.model {
+                NtcModel::Beta { r0_ohm, beta, t0_c } => {
+                    format!("R{} = {} Ω，B = {}", t0_c.unwrap_or(25.0), r0_ohm, beta)
+                }
+                NtcModel::SteinhartHart { a, b, c } => format!("Steinhart-Hart A = {:e}, B = {:e}, C = {:e}", a, b, c),
+                NtcModel::Points { .. } => format!("R/T 点表 {} 点", points.len()),
+            };
+            let comment = format!(
+                "NTC 温度表：{}\n分压: {} {} Ω，ADC {} 位，{} ~ {}℃\n索引 i 对应原始码 i << {}，存储值 = 温度 * {}，相邻项线性插值\n最大误差 {:.3}℃（原始码 {}）",
+                model,
+                if config.divider.ntc_on_top { "NTC 接电源，下臂" } else { "NTC 接地，上臂" },
+                config.divider.fixed_ohm,
+                config.resolution_bits,
+                config.t_min_c,
+                config.t_max_c,
+                shift,
+                scale,
+                max_error,
+                max_error_code
+            );
+            format!(
+                "#pragma once\n\n{}#define {}_STEP_SHIFT {}\n#define {}_SCALE {}\n",
+                util_format_c_array(name, c_type, &values, 8, &comment),
+                name.to_uppercase(),
+                shift,
+                name.to_uppercase(),
+                scale
+            )
+        }
+    };
+
+    let output_path = match &config.file_name {
+        Some(file_name) => Some(util_write_generate_file(file_name, config.format.extension(), &content)?),
+        None => None,
+    };
+    Ok(NtcTableResult {
+        content,
+        entries: entries.len(),
+        step_shift: shift,
+        step_codes: step,
+        max_error_c: max_error,
+        max_error_code,
+        max_error_temp_c: exact[max_error_code as usize],
+        code_min: *in_range.first().unwrap(),
+        code_max: *in_range.last().unwrap(),
+        output_path,
+    })
+}
+
+// 生成 NTC 温度查找表
+#[tauri::command]
+pub fn ntc_lookup_table(config: NtcTableConfig) -> Result<NtcTableResult, String> {
+    ntc_build_table(&config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 10k B3950 NTC 接地，10k 上拉，12 位 ADC
+    fn config(step_shift: Option<u8>) -> NtcTableConfig {
+        NtcTableConfig {
+            model: NtcModel::Beta {
+                r0_ohm: 10_000.0,
+                beta: 3950.0,
+                t0_c: None,
+            },
+            divider: NtcDivider {
+                fixed_ohm: 10_000.0,
+                ntc_on_top: false,
+            },
+            resolution_bits: 12,
+            t_min_c: -40.0,
+            t_max_c: 125.0,
+            step_shift,
+            max_error_c: None,
+            temp_scale: None,
+            format: TableFormat::Csv,
+            name: None,
+            c_type: None,
+            file_name: None,
+        }
+    }
+
+    fn csv_entries(content: &str) -> Vec<(u32, f64)> {
+        content
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let (code, t) = line.split_once(',').unwrap();
+                (code.parse().unwrap(), t.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn builds_table() {
+        let result = ntc_build_table(&config(Some(4))).unwrap();
+        assert_eq!((result.step_shift, result.step_codes, result.entries), (4, 16, 257));
+        let entries = csv_entries(&result.content);
+        assert_eq!(entries.len(), 257);
+        assert_eq!(entries[0], (0, 125.0)); // 阻值为 0：短路，按最高温度
+        assert_eq!(entries[256], (4095, -40.0)); // 阻值无穷大：开路，按最低温度
+        // 分压中点 R ≈ 10kΩ，即标称温度 25℃
+        assert_eq!(entries[128], (2048, 25.0));
+        // NTC 接地时原始码越大温度越低，且已量化到 0.1℃
+        assert!(entries.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!(entries.iter().all(|(_, t)| (t * 10.0 - (t * 10.0).round()).abs() < 1e-9));
+        assert!(result.code_min < 2048 && result.code_max > 2048);
+
+        // NTC 接电源侧时方向相反
+        let mut top = config(Some(4));
+        top.divider.ntc_on_top = true;
+        let entries = csv_entries(&ntc_build_table(&top).unwrap().content);
+        assert_eq!((entries[0].1, entries[128].1, entries[256].1), (-40.0, 25.0, 125.0));
+
+        // C 数组：存储值 = 温度 * 10
+        let mut c = config(Some(4));
+        c.format = TableFormat::C;
+        let content = ntc_build_table(&c).unwrap().content;
+        assert!(content.contains("ntc_table[257]"));
+        assert!(content.contains("#define NTC_TABLE_STEP_SHIFT 4"));
+        assert!(content.contains("1250, "));
+    }
+
+    // 最大误差与按表重新插值得到的误差一致
+    #[test]
+    fn reports_max_error() {
+        let config = config(Some(6));
+        let result = ntc_build_table(&config).unwrap();
+        let entries = csv_entries(&result.content);
+        let max_code = 4095;
+        let exact = |code: u32| {
+            let r = ntc_resistance(&config.divider, code, max_code).unwrap();
+            ntc_temperature(&config.model, &[], r).clamp(-40.0, 125.0)
+        };
+        let mut worst = (0.0f64, 0);
+        for code in result.code_min..=result.code_max {
+            let i = (code / result.step_codes) as usize;
+            let frac = (code % result.step_codes) as f64 / result.step_codes as f64;
+            let interpolated = entries[i].1 + (entries[i + 1].1 - entries[i].1) * frac;
+            let error = (interpolated - exact(code)).abs();
+            if error > worst.0 {
+                worst = (error, code);
+            }
+        }
+        assert!((worst.0 - result.max_error_c).abs() < 1e-9, "{:?} {}", worst, result.max_error_c);
+        assert_eq!(worst.1, result.max_error_code);
+        assert_eq!(result.max_error_temp_c, exact(result.max_error_code));
+        assert!(result.max_error_c > 0.0);
+    }
+
+    #[test]
+    fn auto_step_shift() {
+        let mut auto = config(None);
+        auto.max_error_c = Some(0.5);
+        let result = ntc_build_table(&auto).unwrap();
+        assert!(result.max_error_c <= 0.5);
+        // 选择的是满足要求的最大间隔
+        let coarser = ntc_build_table(&config(Some(result.step_shift + 1))).unwrap();
+        assert!(coarser.max_error_c > 0.5);
+
+        // 误差要求足够宽松时可选到 resolution_bits - 1，与手动指定的上限一致
+        auto.max_error_c = Some(1000.0);
+        assert_eq!(ntc_build_table(&auto).unwrap().step_shift, 11);
+        assert_eq!(ntc_build_table(&config(Some(11))).unwrap().entries, 3);
+        assert!(ntc_build_table(&config(Some(12))).is_err());
+
+        auto.max_error_c = Some(0.0);
+        assert!(ntc_build_table(&auto).is_err());
+    }
+
+    // 由 B 值模型生成的点表应得到相同的温度
+    #[test]
+    fn points_match_beta_model() {
+        let beta = NtcModel::Beta {
+            r0_ohm: 10_000.0,
+            beta: 3950.0,
+            t0_c: None,
+        };
+        let csv: String = std::iter::once("温度,阻值".to_string())
+            .chain((-40..=125).step_by(5).map(|t| {
+                let r = 10_000.0 * (3950.0 * (1.0 / (t as f64 + KELVIN) - 1.0 / (25.0 + KELVIN))).exp();
+                format!("{},{}", t, r)
+            }))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let points = ntc_parse_points(&csv).unwrap();
+        for r in [500.0, 3_000.0, 10_000.0, 47_000.0, 300_000.0] {
+            let expected = ntc_temperature(&beta, &[], r);
+            assert!((ntc_temperature(&NtcModel::Points { csv: csv.clone() }, &points, r) - expected).abs() < 1e-6);
+        }
+        assert!(ntc_parse_points("25,10000").is_err());
+        assert!(ntc_parse_points("25,10000\n50,-1").is_err());
+    }
+}
@@ -0,0 +1,533 @@
+use serde::{Deserialize, Serialize};
+
+use crate::functions::fun_svd::{SvdDevice, SvdField, SvdPeripheral, SvdRegister};
+
+/// 代码生成风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegisterCodegenStyle {
+    CDefines,   // 基地址、寄存器地址与字段 _Pos/_Msk 宏
+    CStruct,    // 外设结构体 + 每个寄存器的位域联合体
+    RustPac,    // 类 svd2rust 风格：RegisterBlock + 字段读写访问器
+    RustConsts, // 纯常量模块
+}
+
+impl RegisterCodegenStyle {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RegisterCodegenStyle::CDefines | RegisterCodegenStyle::CStruct => "h",
+            RegisterCodegenStyle::RustPac | RegisterCodegenStyle::RustConsts => "rs",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegisterCodegenConfig {
+    pub style: RegisterCodegenStyle,
+    pub peripherals: Option<Vec<String>>, // 只生成指定外设，为空生成全部
+    #[serde(default)]
+    pub with_enums: bool, // 同时生成字段枚举值
+}
+
+// 仅保留字母数字与下划线，数字开头时补下划线
+fn ident(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "static",
+    "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
+    "final", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+// Rust 小写标识符，关键字后加下划线
+fn rust_ident(name: &str) -> String {
+    let out = ident(name).to_lowercase();
+    if RUST_KEYWORDS.contains(&out.as_str()) {
+        format!("{}_", out)
+    } else {
+        out
+    }
+}
+
+fn c_type(size: u32) -> &'static str {
+    match size {
+        8 => "uint8_t",
+        16 => "uint16_t",
+        64 => "uint64_t",
+        _ => "uint32_t",
+    }
+}
+
+fn rust_type(size: u32) -> &'static str {
+    match size {
+        8 => "u8",
+        16 => "u16",
+        64 => "u64",
+        _ => "u32",
+    }
+}
+
+// 字段值使用的最小 Rust 类型
+fn rust_field_type(width: u32) -> &'static str {
+    match width {
+        1 => "bool",
+        2..=8 => "u8",
+        9..=16 => "u16",
+        17..=32 => "u32",
+        _ => "u64",
+    }
+}
+
+fn field_mask(width: u32) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << width) - 1
+    }
+}
+
+// 单行注释内容（去掉换行）
+fn comment(text: &Option<String>) -> String {
+    text.as_deref()
+        .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+        .unwrap_or_default()
+}
+
+// Rust 文档注释行，无描述时省略
+fn rust_doc(indent: &str, text: &Option<String>) -> String {
+    let text = comment(text);
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("{}/// {}\n", indent, text)
+    }
+}
+
+// 派生外设复用基外设的类型定义
+fn type_owner<'a>(device: &'a SvdDevice, peripheral: &'a SvdPeripheral) -> &'a SvdPeripheral {
+    peripheral
+        .derived_from
+        .as_ref()
+        .and_then(|base| device.peripherals.iter().find(|p| &p.name == base))
+        .unwrap_or(peripheral)
+}
+
+// ==================== C ====================
+
+fn c_header_begin(device: &SvdDevice, out: &mut String) -> String {
+    let guard = format!("{}_REGS_H", ident(&device.name).to_uppercase());
+    out.push_str(&format!("/*\n * {} 寄存器定义\n", device.name));
+    if device.description.is_some() {
+        out.push_str(&format!(" * {}\n", comment(&device.description)));
+    }
+    out.push_str(&format!(
+        " * 由 tiny-mcu-helper 生成，请勿手动修改\n */\n\n#ifndef {}\n#define {}\n\n#include <stdint.h>\n\n",
+        guard, guard
+    ));
+    guard
+}
+
+fn c_field_defines(prefix: &str, register: &SvdRegister, with_enums: bool, out: &mut String) {
+    let suffix = if register.size == 64 { "ULL" } else { "UL" };
+    for field in &register.fields {
+        let name = format!("{}_{}", prefix, ident(&field.name).to_uppercase());
+        out.push_str(&format!("#define {}_Pos ({}U)", name, field.bit_offset));
+        out.push_str(&format!(
+            "\n#define {}_Msk (0x{:X}{} << {}_Pos)",
+            name,
+            field_mask(field.bit_width),
+            suffix,
+            name
+        ));
+        let text = comment(&field.description);
+        if text.is_empty() {
+            out.push('\n');
+        } else {
+            out.push_str(&format!(" /* {} */\n", text));
+        }
+        out.push_str(&format!("#define {} {}_Msk\n", name, name));
+        if with_enums {
+            for value in field.enumerated_values.iter().filter(|v| v.value.is_some()) {
+                out.push_str(&format!(
+                    "#define {}_{} (0x{:X}{} << {}_Pos)\n",
+                    name,
+                    ident(&value.name).to_uppercase(),
+                    value.value.unwrap(),
+                    suffix,
+                    name
+                ));
+            }
+        }
+    }
+}
+
+fn c_defines(device: &SvdDevice, peripherals: &[&SvdPeripheral], with_enums: bool) -> String {
+    let mut out = String::new();
+    let guard = c_header_begin(device, &mut out);
+    for peripheral in peripherals {
+        let name = ident(&peripheral.name).to_uppercase();
+        out.push_str(&format!("/* ---------- {}: {} ---------- */\n", peripheral.name, comment(&peripheral.description)));
+        out.push_str(&format!("#define {}_BASE (0x{:08X}UL)\n", name, peripheral.base_address));
+        for register in &peripheral.registers {
+            let reg = format!("{}_{}", name, ident(&register.name).to_uppercase());
+            out.push_str(&format!(
+                "#define {}_OFFSET (0x{:03X}UL)\n#define {} (*(volatile {} *)({}_BASE + {}_OFFSET))\n",
+                reg,
+                register.address_offset,
+                reg,
+                c_type(register.size),
+                name,
+                reg
+            ));
+        }
+        // 派生外设的字段宏与基外设相同，不再重复生成
+        if std::ptr::eq(type_owner(device, peripheral), *peripheral) {
+            for register in &peripheral.registers {
+                if !register.fields.is_empty() {
+                    out.push('\n');
+                    let reg = format!("{}_{}", name, ident(&register.name).to_uppercase());
+                    c_field_defines(&reg, register, with_enums, &mut out);
+                }
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str(&format!("#endif /* {} */\n", guard));
+    out
+}
+
+// 位域联合体：按字段顺序填充，空隙用匿名位域补齐
+fn c_register_union(type_name: &str, register: &SvdRegister, out: &mut String) {
+    let base = c_type(register.size);
+    out.push_str(&format!("typedef union {{\n    {} w;\n    struct {{\n", base));
+    let mut next_bit = 0;
+    for field in &register.fields {
+        if field.bit_offset < next_bit {
+            // 与前一字段重叠的别名字段无法放入位域
+            out.push_str(&format!("        /* {} 与其他字段重叠，已省略 */\n", field.name));
+            continue;
+        }
+        if field.bit_offset > next_bit {
+            out.push_str(&format!("        {} : {};\n", base, field.bit_offset - next_bit));
+        }
+        let text = comment(&field.description);
+        out.push_str(&format!("        {} {} : {};", base, ident(&field.name), field.bit_width));
+        if text.is_empty() {
+            out.push('\n');
+        } else {
+            out.push_str(&format!(" /* {} */\n", text));
+        }
+        next_bit = field.bit_offset + field.bit_width;
+    }
+    if next_bit < register.size {
+        out.push_str(&format!("        {} : {};\n", base, register.size - next_bit));
+    }
+    out.push_str(&format!("    }} b;\n}} {};\n\n", type_name));
+}
+
+fn c_structs(device: &SvdDevice, peripherals: &[&SvdPeripheral]) -> String {
+    let mut out = String::new();
+    let guard = c_header_begin(device, &mut out);
+    let mut instances = String::new();
+    for peripheral in peripherals {
+        let owner = type_owner(device, peripheral);
+        let type_prefix = ident(&owner.name).to_uppercase();
+        if std::ptr::eq(owner, *peripheral) {
+            out.push_str(&format!("/* ---------- {}: {} ---------- */\n", peripheral.name, comment(&peripheral.description)));
+            for register in peripheral.registers.iter().filter(|r| !r.fields.is_empty()) {
+                let type_name = format!("{}_{}_Type", type_prefix, ident(&register.name).to_uppercase());
+                c_register_union(&type_name, register, &mut out);
+            }
+            out.push_str("typedef struct {\n");
+            let mut offset = 0u64;
+            let mut reserved = 0;
+            for register in &peripheral.registers {
+                if register.address_offset < offset {
+                    // 与前一个寄存器地址重叠（同地址不同含义），结构体中跳过
+                    out.push_str(&format!(
+                        "    /* {} 与前一寄存器共用偏移 0x{:03X}，已省略 */\n",
+                        register.name, register.address_offset
+                    ));
+                    continue;
+                }
+                if register.address_offset > offset {
+                    out.push_str(&format!(
+                        "    uint8_t RESERVED{}[{}];\n",
+                        reserved,
+                        register.address_offset - offset
+                    ));
+                    reserved += 1;
+                }
+                let member_type = if register.fields.is_empty() {
+                    c_type(register.size).to_string()
+                } else {
+                    format!("{}_{}_Type", type_prefix, ident(&register.name).to_uppercase())
+                };
+                out.push_str(&format!(
+                    "    volatile {} {}; /* 0x{:03X} {} */\n",
+                    member_type,
+                    ident(&register.name),
+                    register.address_offset,
+                    comment(&register.description)
+                ).replace("  */", " */"));
+                offset = register.address_offset + (register.size / 8) as u64;
+            }
+            out.push_str(&format!("}} {}_TypeDef;\n\n", type_prefix));
+        }
+        let name = ident(&peripheral.name).to_uppercase();
+        instances.push_str(&format!(
+            "#define {}_BASE (0x{:08X}UL)\n#define {} ((volatile {}_TypeDef *){}_BASE)\n",
+            name, peripheral.base_address, name, type_prefix, name
+        ));
+    }
+    out.push_str(&instances);
+    out.push_str(&format!("\n#endif /* {} */\n", guard));
+    out
+}
+
+// ==================== Rust ====================
+
+fn rust_header(device: &SvdDevice, out: &mut String) {
+    out.push_str(&format!("//! {} 寄存器定义\n", device.name));
+    if device.description.is_some() {
+        out.push_str(&format!("//! {}\n", comment(&device.description)));
+    }
+    out.push_str("//! 由 tiny-mcu-helper 生成，请勿手动修改\n#![allow(non_upper_case_globals, non_camel_case_types, dead_code, clippy::all)]\n\n");
+}
+
+fn rust_enum_consts(field: &SvdField, indent: &str, out: &mut String) {
+    let ty = rust_field_type(field.bit_width);
+    for value in field.enumerated_values.iter().filter(|v| v.value.is_some()) {
+        let literal = if ty == "bool" {
+            (value.value.unwrap() != 0).to_string()
+        } else {
+            format!("0x{:X}", value.value.unwrap())
+        };
+        out.push_str(&format!(
+            "{}pub const {}: {} = {};\n",
+            indent,
+            ident(&value.name).to_uppercase(),
+            ty,
+            literal
+        ));
+    }
+}
+
+fn rust_consts(device: &SvdDevice, peripherals: &[&SvdPeripheral], with_enums: bool) -> String {
+    let mut out = String::new();
+    rust_header(device, &mut out);
+    for peripheral in peripherals {
+        out.push_str(&format!("{}pub mod {} {{\n", rust_doc("", &peripheral.description), rust_ident(&peripheral.name)));
+        out.push_str(&format!("    pub const BASE: usize = 0x{:08X};\n", peripheral.base_address));
+        for register in &peripheral.registers {
+            let ty = rust_type(register.size);
+            out.push_str(&format!(
+                "\n{}    pub mod {} {{\n        pub const OFFSET: usize = 0x{:03X};\n        pub const ADDRESS: usize = 0x{:08X};\n        pub const RESET: {} = 0x{:X};\n",
+                rust_doc("    ", &register.description),
+                rust_ident(&register.name),
+                register.address_offset,
+                register.address,
+                ty,
+                register.reset_value
+            ));
+            for field in &register.fields {
+                out.push_str(&format!(
+                    "        pub mod {} {{\n            pub const POS: u32 = {};\n            pub const WIDTH: u32 = {};\n            pub const MASK: {} = 0x{:X} << POS;\n",
+                    rust_ident(&field.name),
+                    field.bit_offset,
+                    field.bit_width,
+                    ty,
+                    field_mask(field.bit_width)
+                ));
+                if with_enums {
+                    rust_enum_consts(field, "            ", &mut out);
+                }
+                out.push_str("        }\n");
+            }
+            out.push_str("    }\n");
+        }
+        out.push_str("}\n\n");
+    }
+    out
+}
+
+// 寄存器访问封装：volatile 读写
+const RUST_PAC_PRELUDE: &str = "use core::cell::UnsafeCell;
+
+/// 寄存器单元，所有访问均为 volatile
+#[repr(transparent)]
+pub struct Reg<T: Copy>(UnsafeCell<T>);
+
+impl<T: Copy> Reg<T> {
+    #[inline(always)]
+    pub fn read(&self) -> T {
+        unsafe { core::ptr::read_volatile(self.0.get()) }
+    }
+
+    #[inline(always)]
+    pub fn write(&self, value: T) {
+        unsafe { core::ptr::write_volatile(self.0.get(), value) }
+    }
+
+    #[inline(always)]
+    pub fn modify<F: FnOnce(T) -> T>(&self, f: F) {
+        self.write(f(self.read()));
+    }
+}
+
+";
+
+fn rust_pac_register(register: &SvdRegister, with_enums: bool, out: &mut String) {
+    let ty = rust_type(register.size);
+    out.push_str(&format!(
+        "\n{}    pub mod {} {{\n        pub const RESET: {} = 0x{:X};\n\n",
+        rust_doc("    ", &register.description),
+        rust_ident(&register.name),
+        ty,
+        register.reset_value
+    ));
+    // 读视图
+    out.push_str(&format!("        #[derive(Clone, Copy)]\n        pub struct R(pub {});\n\n        impl R {{\n", ty));
+    for field in &register.fields {
+        let field_ty = rust_field_type(field.bit_width);
+        let expr = format!("(self.0 >> {}) & 0x{:X}", field.bit_offset, field_mask(field.bit_width));
+        let body = if field_ty == "bool" {
+            format!("{} != 0", expr)
+        } else {
+            format!("({}) as {}", expr, field_ty)
+        };
+        out.push_str(&format!(
+            "{}            #[inline(always)]\n            pub fn {}(&self) -> {} {{\n                {}\n            }}\n",
+            rust_doc("            ", &field.description),
+            rust_ident(&field.name),
+            field_ty,
+            body
+        ));
+    }
+    out.push_str("        }\n\n");
+    // 写视图，以复位值为初值
+    out.push_str(&format!(
+        "        #[derive(Clone, Copy)]\n        pub struct W(pub {});\n\n        impl Default for W {{\n            fn default() -> Self {{\n                W(RESET)\n            }}\n        }}\n\n        impl W {{\n",
+        ty
+    ));
+    for field in &register.fields {
+        let field_ty = rust_field_type(field.bit_width);
+        out.push_str(&format!(
+            "            #[inline(always)]\n            pub fn {}(&mut self, value: {}) -> &mut Self {{\n                self.0 = (self.0 & !(0x{:X} << {})) | (((value as {}) & 0x{:X}) << {});\n                self\n            }}\n",
+            rust_ident(&field.name),
+            field_ty,
+            field_mask(field.bit_width),
+            field.bit_offset,
+            ty,
+            field_mask(field.bit_width),
+            field.bit_offset
+        ));
+    }
+    out.push_str("        }\n");
+    if with_enums {
+        for field in register.fields.iter().filter(|f| !f.enumerated_values.is_empty()) {
+            out.push_str(&format!("\n        pub mod {} {{\n", rust_ident(&field.name)));
+            rust_enum_consts(field, "            ", out);
+            out.push_str("        }\n");
+        }
+    }
+    out.push_str("    }\n");
+}
+
+fn rust_pac(device: &SvdDevice, peripherals: &[&SvdPeripheral], with_enums: bool) -> String {
+    let mut out = String::new();
+    rust_header(device, &mut out);
+    out.push_str(RUST_PAC_PRELUDE);
+    let mut instances = String::new();
+    for peripheral in peripherals {
+        let owner = type_owner(device, peripheral);
+        let module = rust_ident(&owner.name);
+        if std::ptr::eq(owner, *peripheral) {
+            out.push_str(&format!("{}pub mod {} {{\n    use super::Reg;\n\n", rust_doc("", &peripheral.description), module));
+            out.push_str("    #[repr(C)]\n    pub struct RegisterBlock {\n");
+            let mut offset = 0u64;
+            let mut reserved = 0;
+            for register in &peripheral.registers {
+                if register.address_offset < offset {
+                    out.push_str(&format!("        // {} 与前一寄存器共用偏移，已省略\n", register.name));
+                    continue;
+                }
+                if register.address_offset > offset {
+                    out.push_str(&format!(
+                        "        _reserved{}: [u8; {}],\n",
+                        reserved,
+                        register.address_offset - offset
+                    ));
+                    reserved += 1;
+                }
+                out.push_str(&format!(
+                    "        /// 0x{:03X} {}\n        pub {}: Reg<{}>,\n",
+                    register.address_offset,
+                    comment(&register.description),
+                    rust_ident(&register.name),
+                    rust_type(register.size)
+                ).replace(" \n", "\n"));
+                offset = register.address_offset + (register.size / 8) as u64;
+            }
+            out.push_str("    }\n");
+            for register in &peripheral.registers {
+                rust_pac_register(register, with_enums, &mut out);
+            }
+            out.push_str("}\n\n");
+        }
+        instances.push_str(&format!(
+            "pub const {}: *const {}::RegisterBlock = 0x{:08X} as *const _;\n",
+            ident(&peripheral.name).to_uppercase(),
+            module,
+            peripheral.base_address
+        ));
+    }
+    out.push_str(&instances);
+    out
+}
+
+/// 根据寄存器模型生成 C 头文件或 Rust 模块
+pub fn register_codegen(device: &SvdDevice, config: &RegisterCodegenConfig) -> Result<String, String> {
+    let peripherals: Vec<&SvdPeripheral> = match &config.peripherals {
+        Some(names) if !names.is_empty() => {
+            let mut selected = Vec::new();
+            for name in names {
+                selected.push(
+                    device
+                        .peripherals
+                        .iter()
+                        .find(|p| p.name.eq_ignore_ascii_case(name))
+                        .ok_or(format!("未找到外设: {}", name))?,
+                );
+            }
+            // 派生外设依赖基外设的类型定义，一并带上
+            for peripheral in selected.clone() {
+                let owner = type_owner(device, peripheral);
+                if !selected.iter().any(|p| std::ptr::eq(*p, owner)) {
+                    selected.push(owner);
+                }
+            }
+            selected.sort_by_key(|p| p.base_address);
+            selected
+        }
+        _ => device.peripherals.iter().collect(),
+    };
+    if peripherals.is_empty() {
+        return Err("没有可生成的外设".to_string());
+    }
+    Ok(match config.style {
+        RegisterCodegenStyle::CDefines => c_defines(device, &peripherals, config.with_enums),
+        RegisterCodegenStyle::CStruct => c_structs(device, &peripherals),
+        RegisterCodegenStyle::RustPac => rust_pac(device, &peripherals, config.with_enums),
+        RegisterCodegenStyle::RustConsts => rust_consts(device, &peripherals, config.with_enums),
+    })
+}
@@ -0,0 +1,559 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use zeroize::Zeroize;
+
+use crate::functions::fun_checksum::{crc_compute, crc_find_preset};
+use crate::functions::fun_firmware::{save_firmware, FirmwareInfo, FirmwareInput, FirmwareOutput, MemoryMap};
+use crate::utils::{util_format_c_array, util_get_generate_path, util_parse_hex_str, util_random_bytes, util_to_hex_str};
+
+// 签名块格式（版本 1，共 148 字节，多字节字段均为小端）：
+// | 偏移 | 长度 | 内容                                        |
+// | 0    | 4    | 魔数 "TSIG"                                 |
+// | 4    | 1    | 版本 1                                      |
+// | 5    | 1    | 算法：1 = Ed25519，2 = ECDSA P-256          |
+// | 6    | 2    | 保留，填 0                                  |
+// | 8    | 4    | 签名数据起始地址                            |
+// | 12   | 4    | 签名数据长度                                |
+// | 16   | 32   | 签名数据的 SHA-256                          |
+// | 48   | 32   | 公钥指纹（公钥字节的 SHA-256）              |
+// | 80   | 64   | 对块内 0..48 字节的签名（R||S 或 r||s）     |
+// | 144  | 4    | 块内 0..144 字节的 CRC-32/ISO-HDLC          |
+// 设备端校验：先比对 CRC 与 SHA-256，再用公钥验证 0..48 字节的签名
+const SIGNATURE_MAGIC: &[u8; 4] = b"TSIG";
+const SIGNATURE_VERSION: u8 = 1;
+pub const SIGNATURE_BLOCK_LEN: usize = 148;
+const SIGNATURE_SIGNED_LEN: usize = 48;
+const SIGNATURE_CRC_OFFSET: usize = 144;
+
+// 分离签名文件的扩展名
+const SIGNATURE_FILE_EXTENSION: &str = "sig";
+
+/// 签名算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningAlgorithm {
+    Ed25519,
+    EcdsaP256, // 消息先做 SHA-256，确定性签名（RFC 6979）
+}
+
+impl SigningAlgorithm {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Ed25519 => 1,
+            Self::EcdsaP256 => 2,
+        }
+    }
+
+    fn from_byte(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::Ed25519),
+            2 => Some(Self::EcdsaP256),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ed25519 => "Ed25519",
+            Self::EcdsaP256 => "ECDSA P-256",
+        }
+    }
+}
+
+/// 签名密钥对，私钥为十六进制字符串，释放时清零
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SigningKeyPair {
+    pub algorithm: SigningAlgorithm,
+    pub private_key: String,
+    pub public_key: String, // Ed25519 为 32 字节，P-256 为未压缩格式 65 字节（0x04 || X || Y）
+}
+
+impl Drop for SigningKeyPair {
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+/// 签名块
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureBlock {
+    pub algorithm: SigningAlgorithm,
+    pub address: u32,
+    pub length: u32,
+    pub digest: [u8; 32],
+    pub key_fingerprint: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+/// 返回给前端的签名块信息
+#[derive(Debug, Clone, Serialize)]
+pub struct SignatureInfo {
+    pub algorithm: SigningAlgorithm,
+    pub address: u32,
+    pub length: u32,
+    pub digest: String,
+    pub key_fingerprint: String,
+    pub signature: String,
+    pub block_address: Option<u32>, // 签名块在固件中的地址，分离签名为空
+}
+
+impl SignatureInfo {
+    fn from_block(block: &SignatureBlock, block_address: Option<u32>) -> Self {
+        Self {
+            algorithm: block.algorithm,
+            address: block.address,
+            length: block.length,
+            digest: util_to_hex_str(&block.digest),
+            key_fingerprint: util_to_hex_str(&block.key_fingerprint),
+            signature: util_to_hex_str(&block.signature),
+            block_address,
+        }
+    }
+}
+
+// 块内 0..144 字节的 CRC-32
+fn block_crc(bytes: &[u8]) -> u32 {
+    let params = crc_find_preset("CRC-32/ISO-HDLC").unwrap().params;
+    crc_compute(params, &bytes[..SIGNATURE_CRC_OFFSET]).unwrap_or(0) as u32
+}
+
+impl SignatureBlock {
+    // 被签名的块头（0..48 字节）
+    fn signed_bytes(&self) -> [u8; SIGNATURE_SIGNED_LEN] {
+        let mut out = [0u8; SIGNATURE_SIGNED_LEN];
+        out[0..4].copy_from_slice(SIGNATURE_MAGIC);
+        out[4] = SIGNATURE_VERSION;
+        out[5] = self.algorithm.to_byte();
+        out[8..12].copy_from_slice(&self.address.to_le_bytes());
+        out[12..16].copy_from_slice(&self.length.to_le_bytes());
+        out[16..48].copy_from_slice(&self.digest);
+        out
+    }
+
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_BLOCK_LEN] {
+        let mut out = [0u8; SIGNATURE_BLOCK_LEN];
+        out[..SIGNATURE_SIGNED_LEN].copy_from_slice(&self.signed_bytes());
+        out[48..80].copy_from_slice(&self.key_fingerprint);
+        out[80..144].copy_from_slice(&self.signature);
+        let crc = block_crc(&out);
+        out[SIGNATURE_CRC_OFFSET..].copy_from_slice(&crc.to_le_bytes());
+        out
+    }
+
+    /// 解析签名块，校验魔数、版本与 CRC
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < SIGNATURE_BLOCK_LEN || !bytes.starts_with(SIGNATURE_MAGIC) {
+            return Err("不是有效的签名块".to_string());
+        }
+        if bytes[4] != SIGNATURE_VERSION {
+            return Err(format!("不支持的签名块版本: {}", bytes[4]));
+        }
+        let crc = u32::from_le_bytes(bytes[SIGNATURE_CRC_OFFSET..SIGNATURE_BLOCK_LEN].try_into().unwrap());
+        if crc != block_crc(bytes) {
+            return Err("签名块 CRC 校验失败".to_string());
+        }
+        let algorithm = SigningAlgorithm::from_byte(bytes[5]).ok_or(format!("未知的签名算法: {}", bytes[5]))?;
+        Ok(Self {
+            algorithm,
+            address: u32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            length: u32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+            digest: bytes[16..48].try_into().unwrap(),
+            key_fingerprint: bytes[48..80].try_into().unwrap(),
+            signature: bytes[80..144].try_into().unwrap(),
+        })
+    }
+}
+
+// ==================== 密钥 ====================
+
+// 解析固定长度的十六进制私钥
+fn parse_private_key(hex: &str) -> Result<[u8; 32], String> {
+    let mut bytes = util_parse_hex_str(hex)?;
+    let result = <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| "私钥长度应为 32 字节".to_string());
+    bytes.zeroize();
+    result
+}
+
+// 由私钥计算公钥，私钥无效（P-256 超出范围或为 0）时返回错误
+fn public_key_of(algorithm: SigningAlgorithm, private_key: &[u8; 32]) -> Result<Vec<u8>, String> {
+    match algorithm {
+        SigningAlgorithm::Ed25519 => {
+            let key = ed25519_dalek::SigningKey::from_bytes(private_key);
+            Ok(key.verifying_key().to_bytes().to_vec())
+        }
+        SigningAlgorithm::EcdsaP256 => {
+            let key = p256::ecdsa::SigningKey::from_slice(private_key).map_err(|_| "无效的 P-256 私钥".to_string())?;
+            Ok(key.verifying_key().to_encoded_point(false).as_bytes().to_vec())
+        }
+    }
+}
+
+/// 生成新的密钥对
+pub fn signing_generate_keypair(algorithm: SigningAlgorithm) -> Result<SigningKeyPair, String> {
+    let mut private_key = [0u8; 32];
+    // P-256 私钥需小于曲线阶，随机值无效的概率极低，重试即可
+    let result = loop {
+        util_random_bytes(&mut private_key).map_err(|e| e.to_string())?;
+        if let Ok(public_key) = public_key_of(algorithm, &private_key) {
+            break SigningKeyPair {
+                algorithm,
+                private_key: util_to_hex_str(&private_key),
+                public_key: util_to_hex_str(&public_key),
+            };
+        }
+    };
+    private_key.zeroize();
+    Ok(result)
+}
+
+/// 导入已有私钥（十六进制，32 字节）
+pub fn signing_import_keypair(algorithm: SigningAlgorithm, private_key: &str) -> Result<SigningKeyPair, String> {
+    let mut bytes = parse_private_key(private_key)?;
+    let public_key = public_key_of(algorithm, &bytes);
+    let result = public_key.map(|public_key| SigningKeyPair {
+        algorithm,
+        private_key: util_to_hex_str(&bytes),
+        public_key: util_to_hex_str(&public_key),
+    });
+    bytes.zeroize();
+    result
+}
+
+/// 公钥指纹（公钥字节的 SHA-256）
+pub fn signing_fingerprint(public_key: &[u8]) -> [u8; 32] {
+    Sha256::digest(public_key).into()
+}
+
+// 签名消息
+fn sign_message(keypair: &SigningKeyPair, message: &[u8]) -> Result<[u8; 64], String> {
+    let mut private_key = parse_private_key(&keypair.private_key)?;
+    let signature = match keypair.algorithm {
+        SigningAlgorithm::Ed25519 => {
+            use ed25519_dalek::Signer;
+            Ok(ed25519_dalek::SigningKey::from_bytes(&private_key).sign(message).to_bytes())
+        }
+        SigningAlgorithm::EcdsaP256 => {
+            use p256::ecdsa::signature::Signer;
+            p256::ecdsa::SigningKey::from_slice(&private_key)
+                .map_err(|_| "无效的 P-256 私钥".to_string())
+                .map(|key| {
+                    let signature: p256::ecdsa::Signature = key.sign(message);
+                    signature.to_bytes().into()
+                })
+        }
+    };
+    private_key.zeroize();
+    signature
+}
+
+// 验证签名，公钥格式不正确时返回错误
+fn verify_message(
+    algorithm: SigningAlgorithm,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<bool, String> {
+    match algorithm {
+        SigningAlgorithm::Ed25519 => {
+            use ed25519_dalek::Verifier;
+            let key = <[u8; 32]>::try_from(public_key)
+                .ok()
+                .and_then(|bytes| ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok())
+                .ok_or("无效的 Ed25519 公钥")?;
+            let signature = ed25519_dalek::Signature::from_bytes(signature);
+            Ok(key.verify(message, &signature).is_ok())
+        }
+        SigningAlgorithm::EcdsaP256 => {
+            use p256::ecdsa::signature::Verifier;
+            let key = p256::ecdsa::VerifyingKey::from_sec1_bytes(public_key).map_err(|_| "无效的 P-256 公钥")?;
+            match p256::ecdsa::Signature::from_slice(signature) {
+                Ok(signature) => Ok(key.verify(message, &signature).is_ok()),
+                Err(_) => Ok(false),
+            }
+        }
+    }
+}
+
+/// 将公钥导出为 C 数组（P-256 为未压缩格式）
+pub fn signing_public_key_c_array(name: &str, algorithm: SigningAlgorithm, public_key: &[u8]) -> String {
+    let values: Vec<String> = public_key.iter().map(|b| format!("0x{:02X}", b)).collect();
+    let comment = format!(
+        "{} 公钥，指纹 (SHA-256): {}",
+        algorithm.name(),
+        util_to_hex_str(&signing_fingerprint(public_key))
+    );
+    util_format_c_array(name, "uint8_t", &values, 8, &comment)
+}
+
+// ==================== 签名 / 验证 ====================
+
+/// 对一段数据生成签名块
+pub fn signing_sign_data(keypair: &SigningKeyPair, address: u32, data: &[u8]) -> Result<SignatureBlock, String> {
+    let length = u32::try_from(data.len()).map_err(|_| "签名数据超过 4 GiB".to_string())?;
+    let public_key = util_parse_hex_str(&keypair.public_key)?;
+    let mut block = SignatureBlock {
+        algorithm: keypair.algorithm,
+        address,
+        length,
+        digest: Sha256::digest(data).into(),
+        key_fingerprint: signing_fingerprint(&public_key),
+        signature: [0u8; 64],
+    };
+    block.signature = sign_message(keypair, &block.signed_bytes())?;
+    Ok(block)
+}
+
+/// 验证结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SignatureCheck {
+    pub valid: bool,           // 以下三项全部通过
+    pub digest_match: bool,    // 数据 SHA-256 与签名块一致
+    pub key_match: bool,       // 公钥指纹与签名块一致
+    pub signature_valid: bool, // 签名验证通过
+}
+
+/// 用公钥验证签名块及其覆盖的数据
+pub fn signing_verify_data(block: &SignatureBlock, data: &[u8], public_key: &[u8]) -> Result<SignatureCheck, String> {
+    let digest: [u8; 32] = Sha256::digest(data).into();
+    let digest_match = data.len() == block.length as usize && digest == block.digest;
+    let key_match = signing_fingerprint(public_key) == block.key_fingerprint;
+    let signature_valid = verify_message(block.algorithm, public_key, &block.signed_bytes(), &block.signature)?;
+    Ok(SignatureCheck {
+        valid: digest_match && key_match && signature_valid,
+        digest_match,
+        key_match,
+        signature_valid,
+    })
+}
+
+/// 在固件中查找签名块（有多个时取地址最高的一个），返回 (块地址, 签名块)
+pub fn signing_find_block(map: &MemoryMap) -> Option<(u32, SignatureBlock)> {
+    let mut found = None;
+    for (address, data) in map.segments() {
+        let mut offset = 0;
+        while offset + SIGNATURE_BLOCK_LEN <= data.len() {
+            if data[offset..].starts_with(SIGNATURE_MAGIC) {
+                if let Ok(block) = SignatureBlock::parse(&data[offset..]) {
+                    found = Some((address + offset as u32, block));
+                    offset += SIGNATURE_BLOCK_LEN;
+                    continue;
+                }
+            }
+            offset += 1;
+        }
+    }
+    found
+}
+
+/// 签名输出方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureMode {
+    Append,   // 写入固件（默认紧跟签名范围之后，4 字节对齐）
+    Detached, // 输出单独的 .sig 文件
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirmwareSignRequest {
+    pub input: FirmwareInput,
+    pub range_start: Option<u32>,   // 默认固件最低地址
+    pub range_end: Option<u64>,     // 默认固件最高地址（不包含）
+    pub pad_byte: Option<u8>,       // 签名范围内空洞的填充值，默认 0xFF
+    pub mode: SignatureMode,
+    pub block_address: Option<u32>, // 追加模式下签名块的地址
+    pub output: FirmwareOutput,     // 分离模式仅使用 file_name
+}
+
+#[derive(Debug, Serialize)]
+pub struct FirmwareSignResult {
+    pub output_path: String,
+    pub signature: SignatureInfo,
+    pub info: FirmwareInfo,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirmwareVerifyRequest {
+    pub input: FirmwareInput,
+    pub signature_path: Option<String>, // 分离签名文件；为空时在固件中查找签名块
+    pub pad_byte: Option<u8>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FirmwareVerifyResult {
+    #[serde(flatten)]
+    pub check: SignatureCheck,
+    pub signature: SignatureInfo,
+}
+
+/// 签名固件并输出到 generate 目录
+pub fn firmware_sign_with(keypair: &SigningKeyPair, request: &FirmwareSignRequest) -> Result<FirmwareSignResult, String> {
+    let (_, mut map) = request.input.load()?;
+    let start = request.range_start.or(map.min_address()).ok_or("固件内容为空")?;
+    let end = request.range_end.or(map.max_address()).unwrap_or(start as u64);
+    if end <= start as u64 {
+        return Err(format!("签名范围无效: 0x{:08X} - 0x{:08X}", start, end));
+    }
+    let data = map.read_range(start, end, request.pad_byte.unwrap_or(0xFF))?;
+    let block = signing_sign_data(keypair, start, &data)?;
+    let bytes = block.to_bytes();
+
+    match request.mode {
+        SignatureMode::Append => {
+            let location = match request.block_address {
+                Some(location) => location,
+                None => u32::try_from(end.next_multiple_of(4)).map_err(|_| "签名块地址超出 32 位地址空间".to_string())?,
+            };
+            if (location as u64) < end && location as u64 + SIGNATURE_BLOCK_LEN as u64 > start as u64 {
+                return Err(format!("签名块地址 0x{:08X} 位于签名范围内", location));
+            }
+            map.write(location, &bytes, false)
+                .map_err(|e| format!("写入签名块失败: {}", e))?;
+            let output_path = save_firmware(&map, &request.output)?;
+            Ok(FirmwareSignResult {
+                output_path,
+                signature: SignatureInfo::from_block(&block, Some(location)),
+                info: FirmwareInfo::from_map(Some(request.output.format), &map),
+            })
+        }
+        SignatureMode::Detached => {
+            let file_name = format!("{}.{}", request.output.file_name, SIGNATURE_FILE_EXTENSION);
+            let file_path = util_get_generate_path()?.join(file_name);
+            std::fs::write(&file_path, bytes).map_err(|e| format!("写入文件失败: {}", e))?;
+            Ok(FirmwareSignResult {
+                output_path: file_path.to_string_lossy().to_string(),
+                signature: SignatureInfo::from_block(&block, None),
+                info: FirmwareInfo::from_map(None, &map),
+            })
+        }
+    }
+}
+
+/// 读取签名块（分离签名文件或固件内嵌），并用公钥验证
+pub fn firmware_verify_with(public_key: &[u8], request: &FirmwareVerifyRequest) -> Result<FirmwareVerifyResult, String> {
+    let (_, map) = request.input.load()?;
+    let (block_address, block) = match &request.signature_path {
+        Some(path) => {
+            let bytes = std::fs::read(Path::new(path)).map_err(|e| format!("读取签名文件失败 {}: {}", path, e))?;
+            (None, SignatureBlock::parse(&bytes)?)
+        }
+        None => {
+            let (address, block) = signing_find_block(&map).ok_or("固件中未找到签名块")?;
+            (Some(address), block)
+        }
+    };
+    let data = map.read_range(
+        block.address,
+        block.address as u64 + block.length as u64,
+        request.pad_byte.unwrap_or(0xFF),
+    )?;
+    Ok(FirmwareVerifyResult {
+        check: signing_verify_data(&block, &data, public_key)?,
+        signature: SignatureInfo::from_block(&block, block_address),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::fun_firmware::FirmwareFormat;
+
+    fn crc32(bytes: &[u8]) -> u32 {
+        crc_compute(crc_find_preset("CRC-32/ISO-HDLC").unwrap().params, bytes).unwrap() as u32
+    }
+
+    #[test]
+    fn block_layout() {
+        let block = SignatureBlock {
+            algorithm: SigningAlgorithm::EcdsaP256,
+            address: 0x0800_4000,
+            length: 0x1234,
+            digest: [0x11; 32],
+            key_fingerprint: [0x22; 32],
+            signature: [0x33; 64],
+        };
+        let bytes = block.to_bytes();
+        assert_eq!(&bytes[0..8], b"TSIG\x01\x02\x00\x00");
+        assert_eq!(&bytes[8..16], &[0x00, 0x40, 0x00, 0x08, 0x34, 0x12, 0x00, 0x00]);
+        assert_eq!(&bytes[16..48], &[0x11; 32]);
+        assert_eq!(&bytes[48..80], &[0x22; 32]);
+        assert_eq!(&bytes[80..144], &[0x33; 64]);
+        assert_eq!(bytes[144..148], crc32(&bytes[..144]).to_le_bytes());
+        assert_eq!(SignatureBlock::parse(&bytes).unwrap(), block);
+
+        let mut tampered = bytes;
+        tampered[100] ^= 1;
+        assert_eq!(SignatureBlock::parse(&tampered), Err("签名块 CRC 校验失败".to_string()));
+        let mut tampered = bytes;
+        tampered[4] = 2;
+        assert!(SignatureBlock::parse(&tampered).is_err());
+        assert!(SignatureBlock::parse(&bytes[..SIGNATURE_BLOCK_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let data: Vec<u8> = (0..=255).collect();
+        for algorithm in [SigningAlgorithm::Ed25519, SigningAlgorithm::EcdsaP256] {
+            let keypair = signing_generate_keypair(algorithm).unwrap();
+            let public_key = util_parse_hex_str(&keypair.public_key).unwrap();
+            let block = signing_sign_data(&keypair, 0x0800_0000, &data).unwrap();
+            let block = SignatureBlock::parse(&block.to_bytes()).unwrap();
+            assert!(signing_verify_data(&block, &data, &public_key).unwrap().valid, "{:?}", algorithm);
+
+            let mut tampered = data.clone();
+            tampered[10] ^= 0xFF;
+            let check = signing_verify_data(&block, &tampered, &public_key).unwrap();
+            assert!(!check.valid && !check.digest_match && check.key_match && check.signature_valid);
+
+            let other = signing_generate_keypair(algorithm).unwrap();
+            let other = util_parse_hex_str(&other.public_key).unwrap();
+            let check = signing_verify_data(&block, &data, &other).unwrap();
+            assert!(!check.valid && check.digest_match && !check.key_match && !check.signature_valid);
+
+            // 篡改块头后签名失效
+            let mut forged = block.clone();
+            forged.address += 4;
+            assert!(!signing_verify_data(&forged, &data, &public_key).unwrap().signature_valid);
+        }
+    }
+
+    #[test]
+    fn imported_key_matches_public_key() {
+        let keypair = signing_generate_keypair(SigningAlgorithm::Ed25519).unwrap();
+        let imported = signing_import_keypair(SigningAlgorithm::Ed25519, &keypair.private_key).unwrap();
+        assert_eq!(imported.public_key, keypair.public_key);
+        assert!(signing_import_keypair(SigningAlgorithm::EcdsaP256, "00").is_err());
+    }
+
+    #[test]
+    fn append_rejects_block_inside_range() {
+        let path = std::env::temp_dir().join(format!("tmh-sign-{}.bin", std::process::id()));
+        std::fs::write(&path, [0x5A; 0x100]).unwrap();
+        let keypair = signing_generate_keypair(SigningAlgorithm::Ed25519).unwrap();
+        let request = |block_address| FirmwareSignRequest {
+            input: FirmwareInput {
+                path: path.to_string_lossy().to_string(),
+                format: Some(FirmwareFormat::Bin),
+                base_address: Some(0x0800_0000),
+            },
+            range_start: None,
+            range_end: None,
+            pad_byte: None,
+            mode: SignatureMode::Append,
+            block_address: Some(block_address),
+            output: FirmwareOutput {
+                format: FirmwareFormat::Bin,
+                file_name: "signed".to_string(),
+                record_length: None,
+                srec_type: None,
+                pad_byte: None,
+            },
+        };
+        // 块与签名范围 0x08000000..0x08000100 重叠：起点在范围内、跨越起点
+        for address in [0x0800_0000, 0x0800_00FC, 0x0800_0000 - 0x10] {
+            let error = firmware_sign_with(&keypair, &request(address)).unwrap_err();
+            assert!(error.contains("位于签名范围内"), "0x{:08X}: {}", address, error);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,606 @@
+use roxmltree::{Document, Node};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{util_de_u64_hex, util_parse_u64};
+
+/// 枚举值（value 为 None 表示 isDefault）
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SvdEnumValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub value: Option<u64>,
+    pub mask: Option<u64>, // 二进制写法中含 x（无关位）时的有效位掩码
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SvdField {
+    pub name: String,
+    pub description: Option<String>,
+    pub bit_offset: u32,
+    pub bit_width: u32,
+    pub access: Option<String>,
+    #[serde(default)]
+    pub enumerated_values: Vec<SvdEnumValue>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SvdRegister {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub address_offset: u64,
+    #[serde(default)]
+    pub address: u64, // 绝对地址，由外设基地址 + 偏移计算
+    #[serde(default = "default_width")]
+    pub size: u32,
+    pub access: Option<String>,
+    #[serde(default)]
+    pub reset_value: u64,
+    #[serde(default = "default_reset_mask")]
+    pub reset_mask: u64,
+    #[serde(default)]
+    pub fields: Vec<SvdField>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SvdPeripheral {
+    pub name: String,
+    pub description: Option<String>,
+    pub group_name: Option<String>,
+    pub base_address: u64,
+    pub derived_from: Option<String>,
+    #[serde(default)]
+    pub registers: Vec<SvdRegister>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SvdDevice {
+    pub name: String,
+    pub vendor: Option<String>,
+    pub description: Option<String>,
+    pub cpu: Option<String>,
+    #[serde(default = "default_width")]
+    pub width: u32,
+    #[serde(default)]
+    pub peripherals: Vec<SvdPeripheral>,
+}
+
+fn default_width() -> u32 {
+    32
+}
+
+fn default_reset_mask() -> u64 {
+    u64::MAX
+}
+
+impl SvdRegister {
+    // 位宽必须为 8/16/32/64，字段不能为空或超出寄存器位宽，否则解码/编码时移位越界
+    fn check_bounds(&self) -> Result<(), String> {
+        if ![8, 16, 32, 64].contains(&self.size) {
+            return Err(format!("寄存器 {} 的位宽 {} 无效", self.name, self.size));
+        }
+        for field in &self.fields {
+            if field.bit_width == 0 || field.bit_offset as u64 + field.bit_width as u64 > self.size as u64 {
+                return Err(format!("字段 {}.{} 超出寄存器位宽", self.name, field.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SvdDevice {
+    pub fn register_count(&self) -> usize {
+        self.peripherals.iter().map(|p| p.registers.len()).sum()
+    }
+
+    pub fn find_register(&self, peripheral: &str, register: &str) -> Result<&SvdRegister, String> {
+        let peripheral = self
+            .peripherals
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(peripheral))
+            .ok_or(format!("未找到外设: {}", peripheral))?;
+        peripheral
+            .registers
+            .iter()
+            .find(|r| r.name.eq_ignore_ascii_case(register))
+            .ok_or(format!("外设 {} 中未找到寄存器: {}", peripheral.name, register))
+    }
+
+    // 校验用户编辑的寄存器映射，并补全绝对地址等计算字段
+    pub fn normalize(&mut self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("器件名称不能为空".to_string());
+        }
+        let mut peripheral_names = std::collections::HashSet::new();
+        for peripheral in self.peripherals.iter_mut() {
+            if !peripheral_names.insert(peripheral.name.to_uppercase()) {
+                return Err(format!("外设名称重复: {}", peripheral.name));
+            }
+            let mut register_names = std::collections::HashSet::new();
+            for register in peripheral.registers.iter_mut() {
+                if !register_names.insert(register.name.to_uppercase()) {
+                    return Err(format!("外设 {} 中寄存器名称重复: {}", peripheral.name, register.name));
+                }
+                register.check_bounds()?;
+                register.address = peripheral.base_address + register.address_offset;
+                register.reset_value &= bit_mask(register.size);
+                register.reset_mask &= bit_mask(register.size);
+                // 厂商 SVD 中存在同一位段的别名字段，这里不检查重叠
+                register.fields.sort_by_key(|f| f.bit_offset);
+            }
+            peripheral.registers.sort_by_key(|r| r.address_offset);
+        }
+        Ok(())
+    }
+}
+
+// 自上而下继承的寄存器属性（device -> peripheral -> cluster -> register）
+#[derive(Debug, Clone, Default)]
+struct RegisterProps {
+    size: Option<u32>,
+    access: Option<String>,
+    reset_value: Option<u64>,
+    reset_mask: Option<u64>,
+}
+
+impl RegisterProps {
+    fn inherit(&self, node: Node) -> Result<Self, String> {
+        Ok(RegisterProps {
+            size: child_u64(node, "size")?.map(|v| v as u32).or(self.size),
+            access: child_text(node, "access").map(str::to_string).or(self.access.clone()),
+            reset_value: child_u64(node, "resetValue")?.or(self.reset_value),
+            reset_mask: child_u64(node, "resetMask")?.or(self.reset_mask),
+        })
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, tag: &str) -> Option<&'a str> {
+    child(node, tag).and_then(|n| n.text()).map(str::trim)
+}
+
+// 描述文本中常带换行与多余空格
+fn child_description(node: Node, tag: &str) -> Option<String> {
+    child_text(node, tag).map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+// SVD 整数：十进制、0x 十六进制、# 或 0b 二进制
+fn parse_svd_u64(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    match value.strip_prefix('#').or_else(|| value.strip_prefix("0b")) {
+        Some(bin) => u64::from_str_radix(bin, 2).map_err(|_| format!("无效的数字: {}", value)),
+        None => util_parse_u64(value),
+    }
+}
+
+fn child_u64(node: Node, tag: &str) -> Result<Option<u64>, String> {
+    child_text(node, tag).map(parse_svd_u64).transpose()
+}
+
+fn required_text<'a>(node: Node<'a, '_>, tag: &str) -> Result<&'a str, String> {
+    child_text(node, tag).ok_or(format!(
+        "<{}> 缺少 <{}>（第 {} 行）",
+        node.tag_name().name(),
+        tag,
+        node.document().text_pos_at(node.range().start).row
+    ))
+}
+
+// 展开 dim 数组：返回 (名称, 偏移增量) 列表，无 dim 时返回自身
+fn expand_dim(node: Node, name: &str) -> Result<Vec<(String, u64)>, String> {
+    let Some(dim) = child_u64(node, "dim")? else {
+        return Ok(vec![(name.to_string(), 0)]);
+    };
+    let increment = child_u64(node, "dimIncrement")?.unwrap_or(0);
+    let indexes: Vec<String> = match child_text(node, "dimIndex") {
+        Some(index) if index.contains('-') && !index.contains(',') => {
+            let (start, end) = index.split_once('-').unwrap();
+            match (start.trim().parse::<u64>(), end.trim().parse::<u64>()) {
+                (Ok(start), Ok(end)) => (start..=end).map(|i| i.to_string()).collect(),
+                // 字母区间，如 A-D
+                _ => (start.trim().chars().next().unwrap_or('A')..=end.trim().chars().next().unwrap_or('A'))
+                    .map(|c| c.to_string())
+                    .collect(),
+            }
+        }
+        Some(index) => index.split(',').map(|s| s.trim().to_string()).collect(),
+        None => (0..dim).map(|i| i.to_string()).collect(),
+    };
+    if indexes.len() as u64 != dim {
+        return Err(format!("{} 的 dimIndex 数量与 dim 不一致", name));
+    }
+    Ok(indexes
+        .iter()
+        .enumerate()
+        .map(|(i, index)| (name.replace("[%s]", index).replace("%s", index), i as u64 * increment))
+        .collect())
+}
+
+fn parse_enum_value(node: Node) -> Result<SvdEnumValue, String> {
+    let is_default = child_text(node, "isDefault").is_some_and(|v| v == "true" || v == "1");
+    let (value, mask) = match child_text(node, "value") {
+        // 二进制写法中的 x 表示无关位
+        Some(text) if text.starts_with('#') && text.contains(['x', 'X']) => {
+            let bits = &text[1..];
+            let value = u64::from_str_radix(&bits.replace(['x', 'X'], "0"), 2)
+                .map_err(|_| format!("无效的枚举值: {}", text))?;
+            let mask = u64::from_str_radix(&bits.replace('0', "1").replace(['x', 'X'], "0"), 2)
+                .map_err(|_| format!("无效的枚举值: {}", text))?;
+            (Some(value), Some(mask))
+        }
+        Some(text) => (Some(parse_svd_u64(text)?), None),
+        None => (None, None),
+    };
+    Ok(SvdEnumValue {
+        name: required_text(node, "name")?.to_string(),
+        description: child_description(node, "description"),
+        value,
+        mask,
+        is_default: is_default || value.is_none(),
+    })
+}
+
+// 由 lsb / msb 计算 (位偏移, 位宽)
+fn field_span(name: &str, lsb: u64, msb: u64) -> Result<(u64, u64), String> {
+    if lsb > msb {
+        return Err(format!("字段 {} 的位范围错误: lsb {} 大于 msb {}", name, lsb, msb));
+    }
+    Ok((lsb, msb + 1 - lsb))
+}
+
+fn parse_field(node: Node, register_access: &Option<String>) -> Result<SvdField, String> {
+    let name = required_text(node, "name")?;
+    let (bit_offset, bit_width) = if let Some(offset) = child_u64(node, "bitOffset")? {
+        (offset, child_u64(node, "bitWidth")?.unwrap_or(1))
+    } else if let (Some(lsb), Some(msb)) = (child_u64(node, "lsb")?, child_u64(node, "msb")?) {
+        field_span(name, lsb, msb)?
+    } else if let Some(range) = child_text(node, "bitRange") {
+        // 形如 [msb:lsb]
+        let (msb, lsb) = range
+            .trim_matches(['[', ']'])
+            .split_once(':')
+            .ok_or(format!("字段 {} 的 bitRange 格式错误: {}", name, range))?;
+        let msb = parse_svd_u64(msb)?;
+        let lsb = parse_svd_u64(lsb)?;
+        field_span(name, lsb, msb)?
+    } else {
+        return Err(format!("字段 {} 缺少位置定义", name));
+    };
+
+    let mut enumerated_values: Vec<SvdEnumValue> = Vec::new();
+    // 读写可能各有一组枚举值，按名称合并
+    for values in node.children().filter(|n| n.has_tag_name("enumeratedValues")) {
+        for value in values.children().filter(|n| n.has_tag_name("enumeratedValue")) {
+            let value = parse_enum_value(value)?;
+            if !enumerated_values.iter().any(|v| v.name == value.name) {
+                enumerated_values.push(value);
+            }
+        }
+    }
+
+    Ok(SvdField {
+        name: name.to_string(),
+        description: child_description(node, "description"),
+        bit_offset: u32::try_from(bit_offset).map_err(|_| format!("字段 {} 的位偏移 {} 无效", name, bit_offset))?,
+        bit_width: u32::try_from(bit_width).map_err(|_| format!("字段 {} 的位宽 {} 无效", name, bit_width))?,
+        access: child_text(node, "access").map(str::to_string).or(register_access.clone()),
+        enumerated_values,
+    })
+}
+
+fn parse_register(node: Node, props: &RegisterProps, prefix: &str, base_offset: u64) -> Result<Vec<(SvdRegister, Option<String>)>, String> {
+    let props = props.inherit(node)?;
+    let name = required_text(node, "name")?;
+    let offset = parse_svd_u64(required_text(node, "addressOffset")?)?;
+    let mut fields = Vec::new();
+    if let Some(list) = child(node, "fields") {
+        for field in list.children().filter(|n| n.has_tag_name("field")) {
+            fields.push(parse_field(field, &props.access)?);
+        }
+        fields.sort_by_key(|f: &SvdField| f.bit_offset);
+    }
+    let size = props.size.unwrap_or(32);
+    let derived_from = node.attribute("derivedFrom").map(str::to_string);
+
+    Ok(expand_dim(node, name)?
+        .into_iter()
+        .map(|(name, increment)| {
+            let register = SvdRegister {
+                name: format!("{}{}", prefix, name),
+                display_name: child_text(node, "displayName").map(str::to_string),
+                description: child_description(node, "description"),
+                address_offset: base_offset + offset + increment,
+                address: 0,
+                size,
+                access: props.access.clone(),
+                reset_value: props.reset_value.unwrap_or(0),
+                reset_mask: props.reset_mask.unwrap_or(bit_mask(size)),
+                fields: fields.clone(),
+            };
+            (register, derived_from.clone())
+        })
+        .collect())
+}
+
+// 解析 <registers> 或 <cluster> 下的寄存器，簇内寄存器以 "簇名_" 为前缀平铺
+fn parse_register_block(node: Node, props: &RegisterProps, prefix: &str, base_offset: u64) -> Result<Vec<(SvdRegister, Option<String>)>, String> {
+    let mut registers = Vec::new();
+    for item in node.children().filter(|n| n.is_element()) {
+        match item.tag_name().name() {
+            "register" => registers.extend(parse_register(item, props, prefix, base_offset)?),
+            "cluster" => {
+                let name = required_text(item, "name")?;
+                let offset = parse_svd_u64(required_text(item, "addressOffset")?)?;
+                let cluster_props = props.inherit(item)?;
+                for (name, increment) in expand_dim(item, name)? {
+                    registers.extend(parse_register_block(
+                        item,
+                        &cluster_props,
+                        &format!("{}{}_", prefix, name),
+                        base_offset + offset + increment,
+                    )?);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(registers)
+}
+
+fn parse_peripheral(node: Node, props: &RegisterProps) -> Result<SvdPeripheral, String> {
+    let props = props.inherit(node)?;
+    let mut parsed = match child(node, "registers") {
+        Some(block) => parse_register_block(block, &props, "", 0)?,
+        None => vec![],
+    };
+    // 处理寄存器级 derivedFrom：未定义字段时沿用被派生寄存器的字段
+    let snapshot: Vec<SvdRegister> = parsed.iter().map(|(r, _)| r.clone()).collect();
+    for (register, derived_from) in parsed.iter_mut() {
+        if let Some(base) = derived_from {
+            let base = base.rsplit('.').next().unwrap_or(base);
+            let base = snapshot
+                .iter()
+                .find(|r| r.name == base)
+                .ok_or(format!("寄存器 {} 派生自不存在的寄存器 {}", register.name, base))?;
+            if register.fields.is_empty() {
+                register.fields = base.fields.clone();
+            }
+        }
+    }
+    let mut registers: Vec<SvdRegister> = parsed.into_iter().map(|(r, _)| r).collect();
+    registers.sort_by_key(|r| r.address_offset);
+
+    Ok(SvdPeripheral {
+        name: required_text(node, "name")?.to_string(),
+        description: child_description(node, "description"),
+        group_name: child_text(node, "groupName").map(str::to_string),
+        base_address: parse_svd_u64(required_text(node, "baseAddress")?)?,
+        derived_from: node.attribute("derivedFrom").map(str::to_string),
+        registers,
+    })
+}
+
+/// 解析 CMSIS-SVD 文件内容
+pub fn svd_parse(content: &str) -> Result<SvdDevice, String> {
+    let doc = Document::parse(content).map_err(|e| format!("解析 SVD 失败: {}", e))?;
+    let device = doc.root_element();
+    if !device.has_tag_name("device") {
+        return Err("不是有效的 SVD 文件：根元素不是 <device>".to_string());
+    }
+    let props = RegisterProps::default().inherit(device)?;
+    let peripheral_nodes: Vec<Node> = child(device, "peripherals")
+        .map(|n| n.children().filter(|n| n.has_tag_name("peripheral")).collect())
+        .unwrap_or_default();
+
+    let mut peripherals = Vec::new();
+    for node in &peripheral_nodes {
+        peripherals.push(parse_peripheral(*node, &props)?);
+    }
+
+    // 外设级 derivedFrom：未定义寄存器时沿用基外设的寄存器
+    let snapshot = peripherals.clone();
+    for peripheral in peripherals.iter_mut() {
+        if let Some(base) = &peripheral.derived_from {
+            let base = snapshot
+                .iter()
+                .find(|p| &p.name == base)
+                .ok_or(format!("外设 {} 派生自不存在的外设 {}", peripheral.name, base))?;
+            if peripheral.registers.is_empty() {
+                peripheral.registers = base.registers.clone();
+            }
+            if peripheral.description.is_none() {
+                peripheral.description = base.description.clone();
+            }
+            if peripheral.group_name.is_none() {
+                peripheral.group_name = base.group_name.clone();
+            }
+        }
+        for register in peripheral.registers.iter_mut() {
+            register.check_bounds().map_err(|e| format!("外设 {}: {}", peripheral.name, e))?;
+            register.address = peripheral.base_address + register.address_offset;
+        }
+    }
+
+    Ok(SvdDevice {
+        name: required_text(device, "name")?.to_string(),
+        vendor: child_text(device, "vendor").map(str::to_string),
+        description: child_description(device, "description"),
+        cpu: child(device, "cpu").and_then(|cpu| child_text(cpu, "name")).map(str::to_string),
+        width: child_u64(device, "width")?.unwrap_or(32) as u32,
+        peripherals,
+    })
+}
+
+// ==================== 寄存器解码 / 编码 ====================
+
+fn bit_mask(width: u32) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << width) - 1
+    }
+}
+
+/// 单个字段的解码结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SvdFieldDecode {
+    pub name: String,
+    pub description: Option<String>,
+    pub bit_offset: u32,
+    pub bit_width: u32,
+    pub access: Option<String>,
+    pub value: u64,
+    pub hex: String,
+    pub enum_name: Option<String>,
+    pub enum_description: Option<String>,
+    pub changed_from_reset: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SvdRegisterDecode {
+    pub peripheral: String,
+    pub register: String,
+    pub address: String,
+    pub value: u64,
+    pub hex: String,
+    pub binary: String,
+    pub fields: Vec<SvdFieldDecode>,
+}
+
+/// 字段修改：value 与 enum_name 二选一
+#[derive(Debug, Clone, Deserialize)]
+pub struct SvdFieldEdit {
+    pub field: String,
+    #[serde(default, deserialize_with = "de_option_u64_hex")]
+    pub value: Option<u64>,
+    pub enum_name: Option<String>,
+}
+
+fn de_option_u64_hex<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(deserialize_with = "util_de_u64_hex")] u64);
+    Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|w| w.0))
+}
+
+fn match_enum(field: &SvdField, value: u64) -> Option<&SvdEnumValue> {
+    field
+        .enumerated_values
+        .iter()
+        .find(|e| match (e.value, e.mask) {
+            (Some(v), Some(mask)) => value & mask == v,
+            (Some(v), None) => value == v,
+            (None, _) => false,
+        })
+        .or_else(|| field.enumerated_values.iter().find(|e| e.is_default))
+}
+
+/// 将寄存器值拆分为各字段
+pub fn svd_decode(peripheral: &str, register: &SvdRegister, value: u64) -> SvdRegisterDecode {
+    let value = value & bit_mask(register.size);
+    let digits = (register.size as usize).div_ceil(4);
+    let fields = register
+        .fields
+        .iter()
+        .map(|field| {
+            let mask = bit_mask(field.bit_width);
+            let field_value = (value >> field.bit_offset) & mask;
+            let reset = (register.reset_value >> field.bit_offset) & mask;
+            let matched = match_enum(field, field_value);
+            SvdFieldDecode {
+                name: field.name.clone(),
+                description: field.description.clone(),
+                bit_offset: field.bit_offset,
+                bit_width: field.bit_width,
+                access: field.access.clone(),
+                value: field_value,
+                hex: format!("0x{:0width$X}", field_value, width = (field.bit_width as usize).div_ceil(4)),
+                enum_name: matched.map(|e| e.name.clone()),
+                enum_description: matched.and_then(|e| e.description.clone()),
+                changed_from_reset: field_value != reset,
+            }
+        })
+        .collect();
+    SvdRegisterDecode {
+        peripheral: peripheral.to_string(),
+        register: register.name.clone(),
+        address: format!("0x{:08X}", register.address),
+        value,
+        hex: format!("0x{:0width$X}", value, width = digits),
+        binary: format!("{:0width$b}", value, width = register.size as usize),
+        fields,
+    }
+}
+
+/// 在 base 基础上应用字段修改，返回新的寄存器值
+pub fn svd_encode(register: &SvdRegister, base: u64, edits: &[SvdFieldEdit]) -> Result<u64, String> {
+    let mut value = base & bit_mask(register.size);
+    for edit in edits {
+        let field = register
+            .fields
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(&edit.field))
+            .ok_or(format!("寄存器 {} 中未找到字段: {}", register.name, edit.field))?;
+        let field_value = match (&edit.enum_name, edit.value) {
+            (Some(name), _) => field
+                .enumerated_values
+                .iter()
+                .find(|e| e.name.eq_ignore_ascii_case(name))
+                .and_then(|e| e.value)
+                .ok_or(format!("字段 {} 没有枚举值 {}", field.name, name))?,
+            (None, Some(v)) => v,
+            (None, None) => return Err(format!("字段 {} 未指定新值", field.name)),
+        };
+        let mask = bit_mask(field.bit_width);
+        if field_value > mask {
+            return Err(format!(
+                "字段 {} 的值 {} 超出 {} 位范围",
+                field.name, field_value, field.bit_width
+            ));
+        }
+        value = (value & !(mask << field.bit_offset)) | (field_value << field.bit_offset);
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device_with_field(position: &str) -> String {
+        format!(
+            "<device><name>T</name><peripherals><peripheral><name>P</name><baseAddress>0x40000000</baseAddress>\
+             <registers><register><name>R</name><addressOffset>0</addressOffset><size>32</size>\
+             <fields><field><name>F</name>{}</field></fields></register></registers></peripheral></peripherals></device>",
+            position
+        )
+    }
+
+    #[test]
+    fn field_positions() {
+        for position in ["<lsb>4</lsb><msb>7</msb>", "<bitRange>[7:4]</bitRange>", "<bitOffset>4</bitOffset><bitWidth>4</bitWidth>"] {
+            let device = svd_parse(&device_with_field(position)).unwrap();
+            let field = &device.find_register("P", "R").unwrap().fields[0];
+            assert_eq!((field.bit_offset, field.bit_width), (4, 4), "{}", position);
+        }
+    }
+
+    #[test]
+    fn rejects_reversed_bit_range() {
+        assert!(svd_parse(&device_with_field("<lsb>7</lsb><msb>4</msb>")).is_err());
+        assert!(svd_parse(&device_with_field("<bitRange>[0:5]</bitRange>")).is_err());
+    }
+
+    #[test]
+    fn rejects_field_outside_register() {
+        for position in ["<bitOffset>30</bitOffset><bitWidth>4</bitWidth>", "<bitRange>[64:0]</bitRange>", "<bitOffset>0</bitOffset><bitWidth>0</bitWidth>"] {
+            assert!(svd_parse(&device_with_field(position)).is_err(), "{}", position);
+        }
+        let bad_size = device_with_field("<bitOffset>0</bitOffset>").replace("<size>32</size>", "<size>24</size>");
+        assert!(svd_parse(&bad_size).is_err());
+    }
+}
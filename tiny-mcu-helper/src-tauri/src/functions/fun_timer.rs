@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+
+/// 计数模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CounterMode {
+    #[default]
+    Edge,   // 边沿对齐：周期 = (ARR + 1) 个计数
+    Center, // 中心对齐：周期 = 2 * ARR 个计数
+}
+
+/// 结果排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PwmSortKey {
+    #[default]
+    Error,      // 频率误差优先，其次分辨率
+    Resolution, // 分辨率优先，其次频率误差
+}
+
+/// PWM 求解参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct PwmSolveConfig {
+    pub timer_clock_hz: u64, // 定时器输入时钟（已计入 APB 倍频）
+    pub target_freq_hz: f64,
+    pub duty_percent: Option<f64>,       // 默认 50
+    pub counter_bits: Option<u8>,        // 计数器位宽 16 / 32，默认 16
+    pub counter_mode: Option<CounterMode>,
+    pub repetition_counter: Option<u16>, // RCR，仅影响更新事件频率
+    pub tolerance_percent: Option<f64>,  // 允许的频率误差，默认 1%
+    pub min_resolution_bits: Option<f64>,
+    pub sort: Option<PwmSortKey>,
+    pub limit: Option<usize>, // 最多返回条数，不填返回全部
+}
+
+/// 一组可行的 PSC / ARR
+#[derive(Debug, Clone, Serialize)]
+pub struct PwmSolution {
+    pub prescaler: u32,   // PSC 寄存器值（分频系数 = PSC + 1）
+    pub auto_reload: u64, // ARR 寄存器值
+    pub actual_freq_hz: f64,
+    pub error_percent: f64,
+    pub resolution_bits: f64, // 占空比分辨率：边沿对齐 log2(ARR + 1)，中心对齐 log2(ARR)
+    pub ccr: u64,             // 对应目标占空比的比较值
+    pub actual_duty_percent: f64,
+    pub update_freq_hz: f64, // 更新事件频率（计入重复计数器）
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PwmSolveResult {
+    pub total: usize, // 满足条件的组合总数（不受 limit 影响）
+    pub arr_per_prescaler: usize, // 每个 PSC 最多考察的 ARR 个数（最接近目标频率的向下 / 向上取整值）
+    pub solutions: Vec<PwmSolution>,
+}
+
+// 根据 PSC / ARR 计算完整的求解结果
+fn pwm_solution(config: &PwmSolveConfig, mode: CounterMode, prescaler: u32, auto_reload: u64) -> PwmSolution {
+    let ticks = match mode {
+        CounterMode::Edge => auto_reload + 1,
+        CounterMode::Center => 2 * auto_reload,
+    };
+    let actual_freq_hz = config.timer_clock_hz as f64 / ((prescaler as f64 + 1.0) * ticks as f64);
+
+    // PWM 模式 1：边沿对齐占空比 = CCR / (ARR + 1)，中心对齐占空比 = CCR / ARR
+    let duty = config.duty_percent.unwrap_or(50.0) / 100.0;
+    let duty_steps = match mode {
+        CounterMode::Edge => auto_reload + 1,
+        CounterMode::Center => auto_reload,
+    };
+    let ccr = ((duty * duty_steps as f64).round() as u64).min(duty_steps);
+
+    // 中心对齐模式在上溢和下溢时都会产生更新事件
+    let events_per_period = match mode {
+        CounterMode::Edge => 1.0,
+        CounterMode::Center => 2.0,
+    };
+    let update_freq_hz =
+        actual_freq_hz * events_per_period / (config.repetition_counter.unwrap_or(0) as f64 + 1.0);
+
+    PwmSolution {
+        prescaler,
+        auto_reload,
+        actual_freq_hz,
+        error_percent: (actual_freq_hz - config.target_freq_hz) / config.target_freq_hz * 100.0,
+        resolution_bits: (duty_steps as f64).log2(),
+        ccr,
+        actual_duty_percent: ccr as f64 / duty_steps as f64 * 100.0,
+        update_freq_hz,
+    }
+}
+
+// 每个 PSC 考察的 ARR 个数
+const ARR_PER_PRESCALER: usize = 2;
+
+/// 枚举所有 PSC，每个 PSC 只取最接近目标频率的 ARR（向下 / 向上取整各一个），
+/// 保留误差在容差内且满足最小分辨率的组合并排序。
+///
+/// 同一 PSC 下离目标更远、但仍在容差内的 ARR 不会列出：它们的分辨率与保留的 ARR
+/// 几乎相同而误差更大，全部枚举在 32 位计数器下会产生上百万条结果。
+/// total 为剪枝后的组合数，结果中的 arr_per_prescaler 注明了这一点
+pub fn pwm_solve(config: &PwmSolveConfig) -> Result<PwmSolveResult, String> {
+    if config.timer_clock_hz == 0 {
+        return Err("定时器时钟必须大于 0".to_string());
+    }
+    if config.target_freq_hz.is_nan() || config.target_freq_hz <= 0.0 {
+        return Err("目标频率必须大于 0".to_string());
+    }
+    if let Some(duty) = config.duty_percent {
+        if !(0.0..=100.0).contains(&duty) {
+            return Err(format!("占空比超出范围: {}", duty));
+        }
+    }
+    let arr_max: u64 = match config.counter_bits.unwrap_or(16) {
+        16 => u16::MAX as u64,
+        32 => u32::MAX as u64,
+        other => return Err(format!("不支持的计数器位宽: {}", other)),
+    };
+    let mode = config.counter_mode.unwrap_or_default();
+    let tolerance = config.tolerance_percent.unwrap_or(1.0).abs();
+    let min_bits = config.min_resolution_bits.unwrap_or(0.0);
+
+    let mut solutions = Vec::new();
+    for prescaler in 0..=u16::MAX as u32 {
+        // 理想计数值：每个 PWM 周期需要的计数器时钟数
+        let ticks = config.timer_clock_hz as f64 / ((prescaler as f64 + 1.0) * config.target_freq_hz);
+        let ideal_arr = match mode {
+            CounterMode::Edge => ticks - 1.0,
+            CounterMode::Center => ticks / 2.0,
+        };
+        if ideal_arr < 0.5 {
+            break; // PSC 继续增大只会更小
+        }
+        if ideal_arr > arr_max as f64 + 1.0 {
+            continue;
+        }
+
+        let floor = ideal_arr.floor() as u64;
+        for auto_reload in (floor..).take(ARR_PER_PRESCALER) {
+            if auto_reload == 0 || auto_reload > arr_max {
+                continue;
+            }
+            let solution = pwm_solution(config, mode, prescaler, auto_reload);
+            if solution.error_percent.abs() <= tolerance && solution.resolution_bits >= min_bits {
+                solutions.push(solution);
+            }
+        }
+    }
+
+    match config.sort.unwrap_or_default() {
+        PwmSortKey::Error => solutions.sort_by(|a, b| {
+            a.error_percent
+                .abs()
+                .total_cmp(&b.error_percent.abs())
+                .then(b.resolution_bits.total_cmp(&a.resolution_bits))
+        }),
+        PwmSortKey::Resolution => solutions.sort_by(|a, b| {
+            b.resolution_bits
+                .total_cmp(&a.resolution_bits)
+                .then(a.error_percent.abs().total_cmp(&b.error_percent.abs()))
+        }),
+    }
+
+    let total = solutions.len();
+    if let Some(limit) = config.limit {
+        solutions.truncate(limit);
+    }
+    Ok(PwmSolveResult {
+        total,
+        arr_per_prescaler: ARR_PER_PRESCALER,
+        solutions,
+    })
+}
+
+// 求解定时器 PWM 的全部可行 PSC / ARR 组合
+#[tauri::command]
+pub fn timer_pwm_solve(config: PwmSolveConfig) -> Result<PwmSolveResult, String> {
+    pwm_solve(&config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(timer_clock_hz: u64, target_freq_hz: f64, counter_mode: CounterMode) -> PwmSolveConfig {
+        PwmSolveConfig {
+            timer_clock_hz,
+            target_freq_hz,
+            duty_percent: None,
+            counter_bits: None,
+            counter_mode: Some(counter_mode),
+            repetition_counter: None,
+            tolerance_percent: None,
+            min_resolution_bits: None,
+            sort: None,
+            limit: None,
+        }
+    }
+
+    fn find(result: &PwmSolveResult, prescaler: u32, auto_reload: u64) -> Option<&PwmSolution> {
+        result.solutions.iter().find(|s| s.prescaler == prescaler && s.auto_reload == auto_reload)
+    }
+
+    // 72 MHz / (71 + 1) / (999 + 1) = 1 kHz
+    #[test]
+    fn edge_aligned() {
+        let result = pwm_solve(&config(72_000_000, 1000.0, CounterMode::Edge)).unwrap();
+        let solution = find(&result, 71, 999).unwrap();
+        assert_eq!(solution.actual_freq_hz, 1000.0);
+        assert_eq!(solution.error_percent, 0.0);
+        assert_eq!(solution.resolution_bits, 1000f64.log2());
+        assert_eq!(solution.ccr, 500);
+        assert_eq!(solution.actual_duty_percent, 50.0);
+        assert_eq!(solution.update_freq_hz, 1000.0);
+        // 误差为 0 的组合中分辨率最高的排第一：PSC = 1，ARR = 35999
+        let first = &result.solutions[0];
+        assert_eq!((first.prescaler, first.auto_reload, first.error_percent), (1, 35999, 0.0));
+    }
+
+    // 中心对齐：周期 2 * ARR 个计数，占空比 CCR / ARR，分辨率 log2(ARR)
+    #[test]
+    fn center_aligned() {
+        let mut config = config(72_000_000, 1000.0, CounterMode::Center);
+        config.duty_percent = Some(25.0);
+        config.repetition_counter = Some(1);
+        let result = pwm_solve(&config).unwrap();
+        let solution = find(&result, 0, 36000).unwrap();
+        assert_eq!(solution.actual_freq_hz, 1000.0);
+        assert_eq!(solution.resolution_bits, 36000f64.log2());
+        assert_eq!(solution.ccr, 9000);
+        assert_eq!(solution.actual_duty_percent, 25.0);
+        assert_eq!(solution.update_freq_hz, 1000.0); // 每周期 2 次更新，RCR = 1 再二分频
+
+        // ARR = 1 只有两级占空比（0 / 1），分辨率为 0 bit
+        let mut config = self::config(4, 1.0, CounterMode::Center);
+        config.duty_percent = Some(100.0);
+        let result = pwm_solve(&config).unwrap();
+        let solution = find(&result, 1, 1).unwrap();
+        assert_eq!(solution.resolution_bits, 0.0);
+        assert_eq!((solution.ccr, solution.actual_duty_percent), (1, 100.0));
+    }
+
+    // 每个 PSC 只保留向下 / 向上取整两个 ARR，更远但仍在容差内的 ARR 不列出
+    #[test]
+    fn keeps_nearest_arr_per_prescaler() {
+        let mut config = config(72_000_000, 1000.0, CounterMode::Edge);
+        config.tolerance_percent = Some(1.0);
+        let result = pwm_solve(&config).unwrap();
+        assert_eq!(result.arr_per_prescaler, 2);
+        assert_eq!(result.total, result.solutions.len());
+        for prescaler in 0..=u16::MAX as u32 {
+            assert!(result.solutions.iter().filter(|s| s.prescaler == prescaler).count() <= result.arr_per_prescaler);
+        }
+        // PSC = 71 时 ARR = 995（误差 +0.4%）在容差内，但不是最接近的 ARR
+        assert!(find(&result, 71, 999).is_some());
+        assert!(find(&result, 71, 1000).is_some());
+        assert!(find(&result, 71, 995).is_none());
+        assert!(result.solutions.iter().all(|s| s.error_percent.abs() <= 1.0));
+    }
+
+    #[test]
+    fn filters_sorts_and_limits() {
+        let mut config = config(72_000_000, 1000.0, CounterMode::Edge);
+        config.min_resolution_bits = Some(12.0);
+        config.sort = Some(PwmSortKey::Resolution);
+        config.limit = Some(3);
+        let result = pwm_solve(&config).unwrap();
+        assert!(result.total > 3);
+        assert_eq!(result.solutions.len(), 3);
+        // 16 位计数器下 PSC = 0 需要 ARR = 71999，超出范围
+        assert!(result.solutions.windows(2).all(|w| w[0].resolution_bits >= w[1].resolution_bits));
+        assert!(result.solutions.iter().all(|s| s.resolution_bits >= 12.0 && s.auto_reload <= u16::MAX as u64));
+
+        config.counter_bits = Some(32);
+        let result = pwm_solve(&config).unwrap();
+        // 分辨率优先：向上取整的 ARR = 72000 比精确的 71999 多一级
+        assert_eq!((result.solutions[0].prescaler, result.solutions[0].auto_reload), (0, 72000));
+        assert_eq!((result.solutions[1].prescaler, result.solutions[1].auto_reload), (0, 71999));
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(pwm_solve(&config(0, 1000.0, CounterMode::Edge)).is_err());
+        assert!(pwm_solve(&config(72_000_000, 0.0, CounterMode::Edge)).is_err());
+        let mut invalid = config(72_000_000, 1000.0, CounterMode::Edge);
+        invalid.duty_percent = Some(120.0);
+        assert!(pwm_solve(&invalid).is_err());
+        invalid.duty_percent = None;
+        invalid.counter_bits = Some(24);
+        assert!(pwm_solve(&invalid).is_err());
+    }
+}
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+
+// 未指定波特率时计算的常用波特率
+pub const UART_COMMON_BAUD_RATES: [u32; 12] = [
+    1200, 2400, 4800, 9600, 14400, 19200, 38400, 57600, 115200, 230400, 460800, 921600,
+];
+
+/// UART 外设型号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UartModel {
+    Stm32 {
+        oversampling: u8, // 8 或 16
+    },
+    C51Timer1 {
+        smod: bool,
+        clock_div: Option<u32>, // 12T 传统 8051 为 12（默认），1T 增强型为 1
+    },
+    AvrUbrr {
+        u2x: bool,
+    },
+    Esp32, // 20 位整数 + 4 位小数分频
+}
+
+/// 计算时使用的寄存器值
+#[derive(Debug, Clone, Serialize)]
+pub struct UartRegister {
+    pub name: String,
+    pub value: u32,
+    pub hex: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UartBaudResult {
+    pub model: UartModel,
+    pub baud_rate: u32,
+    pub registers: Vec<UartRegister>,
+    pub actual_baud: f64,
+    pub error_percent: f64,
+    pub within_tolerance: bool,
+    pub error: Option<String>, // 分频超出寄存器范围等无法实现的情况
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UartBaudConfig {
+    pub clock_hz: u64, // 外设时钟（8051 / AVR 为晶振频率）
+    #[serde(default)]
+    pub baud_rates: Vec<u32>, // 为空时计算常用波特率
+    pub models: Vec<UartModel>,
+    pub tolerance_percent: Option<f64>, // 默认 2%
+}
+
+fn register(name: &str, value: u32, digits: usize) -> UartRegister {
+    UartRegister {
+        name: name.to_string(),
+        value,
+        hex: format!("0x{:0width$X}", value, width = digits),
+    }
+}
+
+// 计算单个型号在指定波特率下的寄存器值与实际波特率
+fn uart_divisor(model: UartModel, clock_hz: f64, baud: f64) -> Result<(Vec<UartRegister>, f64), String> {
+    match model {
+        UartModel::Stm32 { oversampling } => {
+            if oversampling != 8 && oversampling != 16 {
+                return Err(format!("不支持的过采样倍数: {}", oversampling));
+            }
+            // USARTDIV 以 1/16（OVER8 为 1/8）为单位
+            let scale = if oversampling == 8 { 2.0 } else { 1.0 };
+            let usartdiv = (clock_hz * scale / baud).round();
+            if usartdiv < 16.0 || usartdiv > 0xFFFF as f64 {
+                return Err(format!("USARTDIV {} 超出范围 16 ~ 65535", usartdiv));
+            }
+            let usartdiv = usartdiv as u32;
+            // OVER8 时 BRR[2:0] = USARTDIV[3:0] >> 1，BRR[3] 必须为 0
+            let brr = if oversampling == 8 {
+                (usartdiv & 0xFFF0) | ((usartdiv & 0x0F) >> 1)
+            } else {
+                usartdiv
+            };
+            Ok((
+                vec![register("BRR", brr, 4), register("USARTDIV", usartdiv, 4)],
+                clock_hz * scale / usartdiv as f64,
+            ))
+        }
+        UartModel::C51Timer1 { smod, clock_div } => {
+            // 定时器 1 方式 2：baud = 2^SMOD * fosc / (32 * clock_div * (256 - TH1))
+            let factor = if smod { 2.0 } else { 1.0 } / (32.0 * clock_div.unwrap_or(12) as f64);
+            let count = (factor * clock_hz / baud).round();
+            if !(1.0..=256.0).contains(&count) {
+                return Err(format!("重装载计数 {} 超出范围 1 ~ 256", count));
+            }
+            let th1 = 256 - count as u32;
+            Ok((
+                vec![register("TH1", th1, 2), register("SMOD", smod as u32, 1)],
+                factor * clock_hz / count,
+            ))
+        }
+        UartModel::AvrUbrr { u2x } => {
+            // baud = fosc / (16 * (UBRR + 1))，U2X 时为 8
+            let divider = if u2x { 8.0 } else { 16.0 };
+            let ubrr = (clock_hz / (divider * baud)).round() - 1.0;
+            if !(0.0..=4095.0).contains(&ubrr) {
+                return Err(format!("UBRR {} 超出范围 0 ~ 4095", ubrr));
+            }
+            Ok((
+                vec![register("UBRR", ubrr as u32, 3), register("U2X", u2x as u32, 1)],
+                clock_hz / (divider * (ubrr + 1.0)),
+            ))
+        }
+        UartModel::Esp32 => {
+            // CLKDIV 以 1/16 为单位：整数部分 20 位，小数部分 4 位
+            let div16 = (clock_hz * 16.0 / baud).round();
+            if div16 < 16.0 || div16 > ((1u64 << 24) - 1) as f64 {
+                return Err(format!("分频 {} 超出范围", div16 / 16.0));
+            }
+            let div16 = div16 as u32;
+            Ok((
+                vec![
+                    register("UART_CLKDIV", div16 >> 4, 5),
+                    register("UART_CLKDIV_FRAG", div16 & 0x0F, 1),
+                ],
+                clock_hz * 16.0 / div16 as f64,
+            ))
+        }
+    }
+}
+
+/// 计算各型号在各波特率下的分频与误差
+pub fn uart_baud_calculate(config: &UartBaudConfig) -> Result<Vec<UartBaudResult>, String> {
+    if config.clock_hz == 0 {
+        return Err("时钟频率必须大于 0".to_string());
+    }
+    if config.models.is_empty() {
+        return Err("请至少选择一种 UART 型号".to_string());
+    }
+    let baud_rates = if config.baud_rates.is_empty() {
+        UART_COMMON_BAUD_RATES.to_vec()
+    } else {
+        config.baud_rates.clone()
+    };
+    let tolerance = config.tolerance_percent.unwrap_or(2.0).abs();
+
+    let mut results = Vec::new();
+    for model in &config.models {
+        for baud_rate in &baud_rates {
+            if *baud_rate == 0 {
+                return Err("波特率必须大于 0".to_string());
+            }
+            let result = match uart_divisor(*model, config.clock_hz as f64, *baud_rate as f64) {
+                Ok((registers, actual_baud)) => {
+                    let error_percent = (actual_baud - *baud_rate as f64) / *baud_rate as f64 * 100.0;
+                    UartBaudResult {
+                        model: *model,
+                        baud_rate: *baud_rate,
+                        registers,
+                        actual_baud,
+                        error_percent,
+                        within_tolerance: error_percent.abs() <= tolerance,
+                        error: None,
+                    }
+                }
+                Err(e) => UartBaudResult {
+                    model: *model,
+                    baud_rate: *baud_rate,
+                    registers: vec![],
+                    actual_baud: 0.0,
+                    error_percent: 0.0,
+                    within_tolerance: false,
+                    error: Some(e),
+                },
+            };
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+// UART 波特率分频计算
+#[tauri::command]
+pub fn uart_baud_calc(config: UartBaudConfig) -> Result<Vec<UartBaudResult>, String> {
+    uart_baud_calculate(&config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculate(clock_hz: u64, baud_rate: u32, model: UartModel) -> UartBaudResult {
+        let config = UartBaudConfig {
+            clock_hz,
+            baud_rates: vec![baud_rate],
+            models: vec![model],
+            tolerance_percent: None,
+        };
+        uart_baud_calculate(&config).unwrap().remove(0)
+    }
+
+    fn registers(result: &UartBaudResult) -> Vec<(&str, &str)> {
+        result.registers.iter().map(|r| (r.name.as_str(), r.hex.as_str())).collect()
+    }
+
+    // RM0008：72 MHz、115200 时 USARTDIV = 39.0625，误差 0
+    #[test]
+    fn stm32_brr() {
+        let result = calculate(72_000_000, 115_200, UartModel::Stm32 { oversampling: 16 });
+        assert_eq!(registers(&result), [("BRR", "0x0271"), ("USARTDIV", "0x0271")]);
+        assert_eq!((result.actual_baud, result.error_percent), (115_200.0, 0.0));
+
+        // OVER8：USARTDIV = 78.125 -> 0x4E2，BRR[2:0] = 0x2 >> 1，BRR[3] 为 0
+        let result = calculate(72_000_000, 115_200, UartModel::Stm32 { oversampling: 8 });
+        assert_eq!(registers(&result), [("BRR", "0x04E1"), ("USARTDIV", "0x04E2")]);
+        assert_eq!((result.actual_baud, result.error_percent), (115_200.0, 0.0));
+
+        // 36 MHz、921600：USARTDIV = 39.0625 取整为 39，误差 +0.16%
+        let result = calculate(36_000_000, 921_600, UartModel::Stm32 { oversampling: 16 });
+        assert_eq!(result.registers[0].value, 39);
+        assert!((result.error_percent - 0.1603).abs() < 1e-4, "{}", result.error_percent);
+        assert!(result.within_tolerance);
+
+        // USARTDIV 小于 16 时无法实现
+        let result = calculate(8_000_000, 921_600, UartModel::Stm32 { oversampling: 16 });
+        assert!(result.error.is_some() && !result.within_tolerance);
+    }
+
+    #[test]
+    fn other_models() {
+        // 11.0592 MHz 12T 8051，9600 时 TH1 = 0xFD
+        let result = calculate(11_059_200, 9600, UartModel::C51Timer1 { smod: false, clock_div: None });
+        assert_eq!(registers(&result), [("TH1", "0xFD"), ("SMOD", "0x0")]);
+        assert_eq!(result.error_percent, 0.0);
+
+        // ATmega328P 数据手册：16 MHz、9600 时 UBRR = 103（+0.2%），115200 时 UBRR = 8（-3.5%）
+        let result = calculate(16_000_000, 9600, UartModel::AvrUbrr { u2x: false });
+        assert_eq!(result.registers[0].value, 103);
+        assert!((result.error_percent - 0.16).abs() < 0.01);
+        let result = calculate(16_000_000, 115_200, UartModel::AvrUbrr { u2x: false });
+        assert_eq!(result.registers[0].value, 8);
+        assert!((result.error_percent + 3.55).abs() < 0.01);
+        assert!(!result.within_tolerance);
+        let result = calculate(16_000_000, 115_200, UartModel::AvrUbrr { u2x: true });
+        assert_eq!(result.registers[0].value, 16);
+        assert!((result.error_percent - 2.12).abs() < 0.01);
+
+        // ESP32 APB 80 MHz、115200：分频 694 + 7/16
+        let result = calculate(80_000_000, 115_200, UartModel::Esp32);
+        assert_eq!(registers(&result), [("UART_CLKDIV", "0x002B6"), ("UART_CLKDIV_FRAG", "0x7")]);
+        assert!(result.error_percent.abs() < 0.002);
+    }
+
+    #[test]
+    fn default_baud_rates() {
+        let config = UartBaudConfig {
+            clock_hz: 72_000_000,
+            baud_rates: vec![],
+            models: vec![UartModel::Stm32 { oversampling: 16 }, UartModel::Esp32],
+            tolerance_percent: Some(1.0),
+        };
+        let results = uart_baud_calculate(&config).unwrap();
+        assert_eq!(results.len(), 2 * UART_COMMON_BAUD_RATES.len());
+        assert_eq!(results[8].baud_rate, 115_200);
+        assert!(uart_baud_calculate(&UartBaudConfig { models: vec![], ..config.clone() }).is_err());
+        assert!(uart_baud_calculate(&UartBaudConfig { baud_rates: vec![0], ..config }).is_err());
+    }
+}
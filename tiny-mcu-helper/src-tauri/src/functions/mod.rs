@@ -1,2 +1,42 @@
 pub mod fun_file_convert;
 pub use fun_file_convert::*;
+pub mod fun_markdown_export;
+pub use fun_markdown_export::*;
+pub mod fun_markdown_pdf;
+pub use fun_markdown_pdf::*;
+pub mod fun_doc_template;
+pub use fun_doc_template::*;
+pub mod fun_checksum;
+pub use fun_checksum::*;
+pub mod fun_hash;
+pub use fun_hash::*;
+pub mod fun_firmware;
+pub use fun_firmware::*;
+pub mod fun_firmware_patch;
+pub use fun_firmware_patch::*;
+pub mod fun_signing;
+pub use fun_signing::*;
+pub mod fun_firmware_crypto;
+pub use fun_firmware_crypto::*;
+pub mod fun_modbus;
+pub use fun_modbus::*;
+pub mod fun_timer;
+pub use fun_timer::*;
+pub mod fun_clock;
+pub use fun_clock::*;
+pub mod fun_uart;
+pub use fun_uart::*;
+pub mod fun_bus_timing;
+pub use fun_bus_timing::*;
+pub mod fun_adc;
+pub use fun_adc::*;
+pub mod fun_ntc;
+pub use fun_ntc::*;
+pub mod fun_svd;
+pub use fun_svd::*;
+pub mod fun_register_codegen;
+pub use fun_register_codegen::*;
+pub mod fun_memory_usage;
+pub use fun_memory_usage::*;
+pub mod fun_memory_layout;
+pub use fun_memory_layout::*;
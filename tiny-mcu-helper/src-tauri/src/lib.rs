@@ -10,16 +10,136 @@ use launcher::init_system_tray;
 use plugins::get_all_system_info;
 use plugins::get_system_info;
 use plugins::init_store;
+use plugins::modbus_close;
+use plugins::modbus_list_sessions;
+use plugins::modbus_poll_start;
+use plugins::modbus_poll_stop;
+use plugins::modbus_request;
+use plugins::modbus_serial_open;
+use plugins::modbus_simulator_get;
+use plugins::modbus_simulator_list;
+use plugins::modbus_simulator_start;
+use plugins::modbus_simulator_stop;
+use plugins::modbus_simulator_write;
+use plugins::modbus_tcp_open;
+use plugins::ModbusSimulatorState;
+use plugins::ModbusState;
+use plugins::svd_decode_register;
+use plugins::svd_delete_device;
+use plugins::svd_encode_register;
+use plugins::svd_get_peripheral;
+use plugins::svd_list_devices;
+use plugins::svd_list_peripherals;
+use plugins::svd_load;
+use plugins::SvdState;
+use plugins::register_codegen_generate;
+use plugins::register_map_delete;
+use plugins::register_map_from_svd;
+use plugins::register_map_get;
+use plugins::register_map_list;
+use plugins::register_map_save;
+use plugins::memory_layout_delete;
+use plugins::memory_layout_get;
+use plugins::memory_layout_list;
+use plugins::memory_layout_save;
+use plugins::job_cancel;
+use plugins::job_clear_history;
+use plugins::job_delete;
+use plugins::job_get;
+use plugins::job_list;
+use plugins::job_retry;
+use plugins::job_submit;
+use plugins::markdown_export;
+use plugins::markdown_style_default;
+use plugins::markdown_style_delete;
+use plugins::markdown_style_list;
+use plugins::markdown_style_save;
+use plugins::doc_template_delete;
+use plugins::doc_template_get;
+use plugins::doc_template_list;
+use plugins::doc_template_presets;
+use plugins::doc_template_render;
+use plugins::doc_template_save;
+use plugins::doc_template_versions;
+use plugins::JobState;
+use plugins::SecretState;
+use plugins::HashState;
 use plugins::run_calc;
 use plugins::run_get_running_path;
 use plugins::run_notepad;
+use plugins::serial_capture_load;
+use plugins::serial_capture_start;
+use plugins::serial_capture_stop;
+use plugins::serial_close;
+use plugins::serial_list_opened;
+use plugins::serial_list_ports;
+use plugins::serial_open;
+use plugins::serial_replay_start;
+use plugins::serial_replay_stop;
+use plugins::serial_set_signals;
+use plugins::serial_write;
+use plugins::SerialCaptureState;
+use plugins::SerialReplayState;
+use plugins::SerialState;
 use plugins::store_delete;
 use plugins::store_get;
 use plugins::store_set;
+use plugins::store_set_secret;
+use plugins::store_get_secret;
+use plugins::store_list_secrets;
+use plugins::store_delete_secret;
+use plugins::secret_status;
+use plugins::secret_unlock;
+use plugins::secret_lock;
+use plugins::secret_set_passphrase;
+use plugins::secret_init;
+use plugins::signing_key_list;
+use plugins::signing_key_generate;
+use plugins::signing_key_import;
+use plugins::signing_key_delete;
+use plugins::signing_key_export_public;
+use plugins::firmware_sign;
+use plugins::firmware_verify;
+use plugins::firmware_key_list;
+use plugins::firmware_key_generate;
+use plugins::firmware_key_import;
+use plugins::firmware_key_delete;
+use plugins::firmware_key_export;
+use plugins::firmware_encrypt;
+use plugins::firmware_decrypt;
+use plugins::hash_file;
+use plugins::hash_cancel;
 use plugins::create_txt_file;
 use functions::convert_markdown_to_pdf;
+use functions::crc_calculate;
+use functions::crc_list_presets;
+use functions::crc_verify_presets;
+use functions::firmware_convert;
+use functions::firmware_inspect;
+use functions::firmware_patch;
+use functions::firmware_process;
+use functions::timer_pwm_solve;
+use functions::clock_list_families;
+use functions::clock_tree_solve;
+use functions::uart_baud_calc;
+use functions::i2c_legacy_calc;
+use functions::i2c_timingr_calc;
+use functions::spi_prescaler_calc;
+use functions::adc_convert;
+use functions::adc_list_sampling_families;
+use functions::adc_lookup_table;
+use functions::adc_sampling_time_solve;
+use functions::ntc_lookup_table;
+use functions::memory_usage_analyze;
+use functions::memory_usage_diff;
+use functions::memory_layout_parse_range;
+use functions::memory_layout_validate;
+use functions::memory_layout_generate;
+use functions::hash_list_algorithms;
+use functions::hash_calculate;
 
 use db::create_todo_migrations;
+use db::init_db;
 use tauri::App;
 use tauri_plugin_sql::{Migration, MigrationKind};
 use tauri_plugin_system_info::SysInfoState;
@@ -43,6 +163,15 @@ pub fn run() {
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_shell::init())
         .manage(SysInfoState::default())
+        .manage(SerialState::default())
+        .manage(SerialCaptureState::default())
+        .manage(SerialReplayState::default())
+        .manage(ModbusState::default())
+        .manage(ModbusSimulatorState::default())
+        .manage(SvdState::default())
+        .manage(JobState::default())
+        .manage(SecretState::default())
+        .manage(HashState::default())
         .invoke_handler(tauri::generate_handler![
             get_system_info,
             get_all_system_info,
@@ -52,8 +181,118 @@ pub fn run() {
             store_set,
             store_get,
             store_delete,
+            store_set_secret,
+            store_get_secret,
+            store_list_secrets,
+            store_delete_secret,
+            secret_status,
+            secret_unlock,
+            secret_lock,
+            secret_set_passphrase,
+            secret_init,
+            signing_key_list,
+            signing_key_generate,
+            signing_key_import,
+            signing_key_delete,
+            signing_key_export_public,
+            firmware_sign,
+            firmware_verify,
+            firmware_key_list,
+            firmware_key_generate,
+            firmware_key_import,
+            firmware_key_delete,
+            firmware_key_export,
+            firmware_encrypt,
+            firmware_decrypt,
+            hash_list_algorithms,
+            hash_calculate,
+            hash_file,
+            hash_cancel,
             create_txt_file,
-            convert_markdown_to_pdf
+            convert_markdown_to_pdf,
+            crc_calculate,
+            crc_list_presets,
+            crc_verify_presets,
+            firmware_inspect,
+            firmware_process,
+            firmware_convert,
+            firmware_patch,
+            serial_list_ports,
+            serial_open,
+            serial_close,
+            serial_write,
+            serial_set_signals,
+            serial_list_opened,
+            serial_capture_start,
+            serial_capture_stop,
+            serial_capture_load,
+            serial_replay_start,
+            serial_replay_stop,
+            modbus_serial_open,
+            modbus_close,
+            modbus_request,
+            modbus_poll_start,
+            modbus_poll_stop,
+            modbus_list_sessions,
+            modbus_tcp_open,
+            modbus_simulator_start,
+            modbus_simulator_stop,
+            modbus_simulator_get,
+            modbus_simulator_write,
+            modbus_simulator_list,
+            timer_pwm_solve,
+            clock_list_families,
+            clock_tree_solve,
+            uart_baud_calc,
+            i2c_timingr_calc,
+            i2c_legacy_calc,
+            spi_prescaler_calc,
+            adc_convert,
+            adc_lookup_table,
+            adc_list_sampling_families,
+            adc_sampling_time_solve,
+            ntc_lookup_table,
+            svd_load,
+            svd_list_devices,
+            svd_delete_device,
+            svd_list_peripherals,
+            svd_get_peripheral,
+            svd_decode_register,
+            svd_encode_register,
+            register_map_list,
+            register_map_get,
+            register_map_save,
+            register_map_delete,
+            register_map_from_svd,
+            register_codegen_generate,
+            memory_usage_analyze,
+            memory_usage_diff,
+            memory_layout_parse_range,
+            memory_layout_validate,
+            memory_layout_generate,
+            memory_layout_list,
+            memory_layout_get,
+            memory_layout_save,
+            memory_layout_delete,
+            job_submit,
+            job_cancel,
+            job_list,
+            job_get,
+            job_retry,
+            job_delete,
+            job_clear_history,
+            markdown_style_list,
+            markdown_style_default,
+            markdown_style_save,
+            markdown_style_delete,
+            markdown_export,
+            doc_template_presets,
+            doc_template_list,
+            doc_template_get,
+            doc_template_versions,
+            doc_template_save,
+            doc_template_delete,
+            doc_template_render
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -73,6 +312,9 @@ fn setup_app(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
     // 初始化简单的键值对数据存储
     init_store(app).expect("TODO: panic message");
 
+    // 初始化 Rust 侧数据库连接（与 tauri-plugin-sql 共用 todo.db）
+    init_db(app).expect("初始化数据库失败");
+
     // 窗口生命周期管理
     handle_lifecycle(app.handle());
 
@@ -9,3 +9,48 @@ pub use plugin_store::*;
 
 pub mod plugin_fs;
 pub use plugin_fs::*;
+
+pub mod plugin_serial;
+pub use plugin_serial::*;
+
+pub mod plugin_serial_capture;
+pub use plugin_serial_capture::*;
+
+pub mod plugin_modbus;
+pub use plugin_modbus::*;
+
+pub mod plugin_modbus_tcp;
+pub use plugin_modbus_tcp::*;
+
+pub mod plugin_modbus_simulator;
+pub use plugin_modbus_simulator::*;
+
+pub mod plugin_svd;
+pub use plugin_svd::*;
+
+pub mod plugin_register_map;
+pub use plugin_register_map::*;
+
+pub mod plugin_memory_layout;
+pub use plugin_memory_layout::*;
+
+pub mod plugin_job;
+pub use plugin_job::*;
+
+pub mod plugin_markdown_style;
+pub use plugin_markdown_style::*;
+
+pub mod plugin_doc_template;
+pub use plugin_doc_template::*;
+
+pub mod plugin_secret_store;
+pub use plugin_secret_store::*;
+
+pub mod plugin_signing;
+pub use plugin_signing::*;
+
+pub mod plugin_firmware_crypto;
+pub use plugin_firmware_crypto::*;
+
+pub mod plugin_hash;
+pub use plugin_hash::*;
@@ -0,0 +1,148 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::db::{
+    db_doc_template_delete, db_doc_template_get, db_doc_template_list, db_doc_template_save,
+    db_doc_template_versions, db_todo_list, DbState, DocTemplateRecord, DocTemplateVersionRecord,
+};
+use crate::functions::fun_doc_template::{
+    doc_collect_data, doc_template_check, doc_template_fill, DocRenderRequest, DocRenderResult, DocTemplatePreset,
+    DOC_TEMPLATE_PRESETS,
+};
+use crate::functions::fun_file_convert::markdown_export_file;
+use crate::plugins::plugin_fs::write_txt_file;
+use crate::plugins::plugin_markdown_style::markdown_style_load;
+
+/// 模板某一版本的正文
+#[derive(Debug, Clone, Serialize)]
+pub struct DocTemplateDetail {
+    pub template: DocTemplateRecord,
+    pub version: i64,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DocTemplateSaveResult {
+    pub id: i64,
+    pub version: i64,
+}
+
+// 内置模板
+#[tauri::command]
+pub fn doc_template_presets() -> Vec<DocTemplatePreset> {
+    DOC_TEMPLATE_PRESETS.to_vec()
+}
+
+// 列出已保存的文档模板
+#[tauri::command]
+pub async fn doc_template_list(app: AppHandle) -> Result<Vec<DocTemplateRecord>, String> {
+    db_doc_template_list(&app.state::<DbState>().pool).await
+}
+
+// 读取模板正文（version 为空时取最新版本）
+#[tauri::command]
+pub async fn doc_template_get(app: AppHandle, template_id: i64, version: Option<i64>) -> Result<DocTemplateDetail, String> {
+    let (template, version, content) = db_doc_template_get(&app.state::<DbState>().pool, template_id, version)
+        .await?
+        .ok_or(format!("文档模板 {} 不存在", template_id))?;
+    Ok(DocTemplateDetail {
+        template,
+        version,
+        content,
+    })
+}
+
+// 列出模板的历史版本
+#[tauri::command]
+pub async fn doc_template_versions(app: AppHandle, template_id: i64) -> Result<Vec<DocTemplateVersionRecord>, String> {
+    db_doc_template_versions(&app.state::<DbState>().pool, template_id).await
+}
+
+// 保存模板（template_id 为空时新建）；正文有改动时生成新版本
+#[tauri::command]
+pub async fn doc_template_save(
+    app: AppHandle,
+    template_id: Option<i64>,
+    name: String,
+    description: Option<String>,
+    content: String,
+    comment: Option<String>,
+) -> Result<DocTemplateSaveResult, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("文档模板名称不能为空".to_string());
+    }
+    doc_template_check(&content)?;
+    let (id, version) = db_doc_template_save(
+        &app.state::<DbState>().pool,
+        template_id,
+        name,
+        description.as_deref(),
+        &content,
+        comment.as_deref().filter(|c| !c.trim().is_empty()),
+    )
+    .await?;
+    Ok(DocTemplateSaveResult { id, version })
+}
+
+// 删除模板及其全部版本
+#[tauri::command]
+pub async fn doc_template_delete(app: AppHandle, template_id: i64) -> Result<(), String> {
+    db_doc_template_delete(&app.state::<DbState>().pool, template_id).await
+}
+
+// 填充模板生成文档；指定 output 时写入 generate 目录
+#[tauri::command]
+pub async fn doc_template_render(app: AppHandle, request: DocRenderRequest) -> Result<DocRenderResult, String> {
+    let pool = &app.state::<DbState>().pool;
+    let content = match request.content.clone() {
+        Some(content) => content,
+        None => {
+            let template_id = request.template_id.ok_or("请选择文档模板")?;
+            db_doc_template_get(pool, template_id, request.version)
+                .await?
+                .ok_or(format!("文档模板 {} 不存在", template_id))?
+                .2
+        }
+    };
+    // 待办事项按完成状态分组，模板中通过 todos.all / todos.open / todos.done 引用
+    let todos = if request.sources.todos {
+        let items = db_todo_list(pool).await?;
+        let (done, open): (Vec<_>, Vec<_>) = items.iter().cloned().partition(|t| t.completed);
+        Some(json!({ "all": items, "open": open, "done": done }))
+    } else {
+        None
+    };
+    let profile = match request.output.as_ref().and_then(|o| o.style.as_deref()).filter(|s| !s.is_empty()) {
+        Some(name) => Some(markdown_style_load(&app, name)?),
+        None => None,
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut data = doc_collect_data(request.data, &request.sources)?;
+        if let Some(todos) = todos {
+            data.insert("todos".to_string(), todos);
+        }
+        let data = Value::Object(data);
+        let markdown = doc_template_fill(&content, &data, request.strict)?;
+
+        let output_path = match &request.output {
+            Some(output) => {
+                let path = match output.format.markdown_format() {
+                    Some(format) => markdown_export_file(&markdown, &output.file_name, format, profile.as_ref(), None)?,
+                    None => write_txt_file(&output.file_name, &markdown)?,
+                };
+                Some(path.to_string_lossy().to_string())
+            }
+            None => None,
+        };
+        Ok(DocRenderResult {
+            markdown,
+            data,
+            output_path,
+        })
+    })
+    .await
+    .map_err(|e| format!("文档生成任务异常: {}", e))?
+}
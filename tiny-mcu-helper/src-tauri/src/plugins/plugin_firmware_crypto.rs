@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::functions::fun_firmware_crypto::{
+    firmware_decrypt_with, firmware_encrypt_with, firmware_key_check_value, firmware_key_generate_bytes,
+    FirmwareDecryptRequest, FirmwareDecryptResult, FirmwareEncryptRequest, FirmwareEncryptResult,
+};
+use crate::plugins::plugin_secret_store::{SecretKeyRegistry, SecretState};
+use crate::utils::{util_format_c_array, util_parse_hex_str, util_to_hex_str, util_write_generate_file};
+
+// Store 中保存固件密钥信息的键，值为 { 名称: FirmwareKeyInfo }；密钥本身加密保存在机密存储中
+pub(crate) const FIRMWARE_KEYS_KEY: &str = "firmware_keys";
+const FIRMWARE_KEYS: SecretKeyRegistry = SecretKeyRegistry {
+    store_key: FIRMWARE_KEYS_KEY,
+    prefix: "firmware_key/",
+    label: "固件密钥",
+};
+
+/// 固件加密密钥的公开信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirmwareKeyInfo {
+    pub name: String,
+    pub key_bits: u16,
+    pub kcv: String, // 密钥校验值
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FirmwareKeyExport {
+    pub content: String,
+    pub output_path: Option<String>,
+}
+
+// 密钥写入机密存储，长度与校验值写入 Store
+fn save_key(app: &AppHandle, state: &SecretState, name: &str, key: &[u8]) -> Result<FirmwareKeyInfo, String> {
+    let kcv = firmware_key_check_value(key)?;
+    let mut hex = util_to_hex_str(key);
+    let saved = FIRMWARE_KEYS.insert(app, state, name, &Value::String(hex.clone()), |name| {
+        Ok(FirmwareKeyInfo {
+            name: name.to_string(),
+            key_bits: key.len() as u16 * 8,
+            kcv,
+        })
+    });
+    hex.zeroize();
+    saved
+}
+
+// 从机密存储读取密钥（需已解锁）
+fn load_key(app: &AppHandle, state: &SecretState, name: &str) -> Result<Zeroizing<Vec<u8>>, String> {
+    match FIRMWARE_KEYS.secret(app, state, name)? {
+        Value::String(mut hex) => {
+            let key = util_parse_hex_str(&hex).map(Zeroizing::new);
+            hex.zeroize();
+            key.map_err(|_| format!("固件密钥 {} 已损坏", name))
+        }
+        _ => Err(format!("固件密钥 {} 已损坏", name)),
+    }
+}
+
+// 列出固件加密密钥（锁定时也可查看）
+#[tauri::command]
+pub fn firmware_key_list(app: AppHandle) -> Vec<FirmwareKeyInfo> {
+    FIRMWARE_KEYS.list(&app)
+}
+
+// 随机生成 AES-128 / AES-256 密钥
+#[tauri::command]
+pub fn firmware_key_generate(
+    app: AppHandle,
+    state: State<'_, SecretState>,
+    name: &str,
+    key_bits: u16,
+) -> Result<FirmwareKeyInfo, String> {
+    let key = firmware_key_generate_bytes(key_bits)?;
+    save_key(&app, &state, name, &key)
+}
+
+// 导入已有密钥（十六进制，16 或 32 字节）
+#[tauri::command]
+pub fn firmware_key_import(
+    app: AppHandle,
+    state: State<'_, SecretState>,
+    name: &str,
+    mut key: String,
+) -> Result<FirmwareKeyInfo, String> {
+    let bytes = util_parse_hex_str(&key).map(Zeroizing::new);
+    key.zeroize();
+    save_key(&app, &state, name, &bytes?)
+}
+
+// 删除固件密钥（需已解锁，同时删除机密存储中的密钥）
+#[tauri::command]
+pub fn firmware_key_delete(app: AppHandle, state: State<'_, SecretState>, name: &str) -> Result<bool, String> {
+    FIRMWARE_KEYS.delete(&app, &state, name)
+}
+
+// 导出密钥为 C 数组供 Bootloader 使用（需已解锁），file_name 不为空时同时写入 generate 目录
+#[tauri::command]
+pub fn firmware_key_export(
+    app: AppHandle,
+    state: State<'_, SecretState>,
+    name: &str,
+    array_name: Option<String>,
+    file_name: Option<String>,
+) -> Result<FirmwareKeyExport, String> {
+    let key = load_key(&app, &state, name)?;
+    let values: Vec<String> = key.iter().map(|b| format!("0x{:02X}", b)).collect();
+    let array_name = array_name.filter(|n| !n.trim().is_empty()).unwrap_or("firmware_aes_key".to_string());
+    let comment = format!("AES-{} 固件密钥，KCV: {}", key.len() * 8, firmware_key_check_value(&key)?);
+    let content = util_format_c_array(array_name.trim(), "uint8_t", &values, 8, &comment);
+    let output_path = match file_name.filter(|n| !n.trim().is_empty()) {
+        Some(file_name) => Some(util_write_generate_file(file_name.trim(), "h", &content)?),
+        None => None,
+    };
+    Ok(FirmwareKeyExport { content, output_path })
+}
+
+// 用指定密钥加密固件，输出带头部的加密镜像
+#[tauri::command]
+pub async fn firmware_encrypt(
+    app: AppHandle,
+    key_name: String,
+    request: FirmwareEncryptRequest,
+) -> Result<FirmwareEncryptResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let key = load_key(&app, &app.state::<SecretState>(), &key_name)?;
+        firmware_encrypt_with(&key, &request)
+    })
+    .await
+    .map_err(|e| format!("加密任务异常: {}", e))?
+}
+
+// 解密加密镜像并校验 CRC，用于核对加密结果
+#[tauri::command]
+pub async fn firmware_decrypt(
+    app: AppHandle,
+    key_name: String,
+    request: FirmwareDecryptRequest,
+) -> Result<FirmwareDecryptResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let key = load_key(&app, &app.state::<SecretState>(), &key_name)?;
+        firmware_decrypt_with(&key, &request)
+    })
+    .await
+    .map_err(|e| format!("解密任务异常: {}", e))?
+}
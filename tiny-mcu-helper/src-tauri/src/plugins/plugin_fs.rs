@@ -1,11 +1,16 @@
 // 创建文件的函数
-use std::path::Path;
-use std::fs::File;
+use std::path::PathBuf;
 
 use crate::utils::util_get_app_path;
 
 #[tauri::command]
 pub fn create_txt_file(file_title: &str, content: &str) -> Result<(), String> {
+  write_txt_file(file_title, content)?;
+  Ok(())
+}
+
+// 写入 generate 目录下的文本文件，返回文件路径（后台任务共用）
+pub fn write_txt_file(file_title: &str, content: &str) -> Result<PathBuf, String> {
   // 获取程序运行目录
   let exe_dir = util_get_app_path();
 
@@ -28,5 +33,5 @@ pub fn create_txt_file(file_title: &str, content: &str) -> Result<(), String> {
   std::fs::write(&file_path, content)
       .map_err(|e| format!("写入文件失败: {}", e))?;
 
-  Ok(())
+  Ok(file_path)
 }
\ No newline at end of file
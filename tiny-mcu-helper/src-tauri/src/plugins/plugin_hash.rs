@@ -0,0 +1,83 @@
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::functions::fun_hash::{hash_compute_file, hash_parse_key, HashAlgorithm, HashResult};
+use crate::plugins::plugin_job::{ProgressThrottle, TaskCancelFlags};
+
+// 文件摘要进度事件名
+pub const HASH_PROGRESS_EVENT: &str = "hash-progress";
+
+// 进度事件的最小间隔，避免大文件时事件过多
+const HASH_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 文件摘要进度事件
+#[derive(Debug, Clone, Serialize)]
+pub struct HashProgressEvent {
+    pub task_id: String,
+    pub processed: u64,
+    pub total: u64,
+}
+
+/// 文件摘要结果
+#[derive(Debug, Clone, Serialize)]
+pub struct HashFileResult {
+    pub path: String,
+    pub size: u64,
+    pub results: Vec<HashResult>,
+}
+
+/// 运行中的文件摘要任务（task_id -> 取消标志）
+#[derive(Default)]
+pub struct HashState {
+    cancel: TaskCancelFlags<String>,
+}
+
+// 计算文件摘要，进度通过 hash-progress 推送；task_id 由前端生成，用于区分进度与取消
+#[tauri::command]
+pub async fn hash_file(
+    app: AppHandle,
+    task_id: String,
+    path: String,
+    algorithms: Vec<HashAlgorithm>,
+    hmac_key: Option<String>,
+    key_type: Option<String>,
+) -> Result<HashFileResult, String> {
+    let key = hash_parse_key(hmac_key.as_deref(), key_type.as_deref())?;
+    let cancel = app.state::<HashState>().cancel.register(task_id.clone())?;
+
+    let handle = app.clone();
+    let id = task_id.clone();
+    let outcome = tauri::async_runtime::spawn_blocking(move || {
+        let mut throttle = ProgressThrottle::new(HASH_PROGRESS_INTERVAL);
+        let mut size = 0;
+        let results = hash_compute_file(Path::new(&path), &algorithms, key.as_deref().map(|k| k.as_slice()), |processed, total| {
+            size = processed;
+            if throttle.ready(processed >= total) {
+                let _ = handle.emit(
+                    HASH_PROGRESS_EVENT,
+                    HashProgressEvent {
+                        task_id: id.clone(),
+                        processed,
+                        total,
+                    },
+                );
+            }
+            !cancel.load(Ordering::SeqCst)
+        })?;
+        Ok(HashFileResult { path, size, results })
+    })
+    .await
+    .unwrap_or_else(|e| Err(format!("摘要任务异常: {}", e)));
+
+    app.state::<HashState>().cancel.remove(&task_id);
+    outcome
+}
+
+// 取消文件摘要任务
+#[tauri::command]
+pub fn hash_cancel(state: State<'_, HashState>, task_id: &str) -> Result<(), String> {
+    state.cancel.cancel(task_id)
+}
@@ -0,0 +1,499 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_log::log;
+
+use crate::db::{
+    db_job_clear_history, db_job_delete, db_job_finish, db_job_get, db_job_insert, db_job_list, db_job_progress,
+    db_job_start, DbState, JobRecord,
+};
+use crate::functions::fun_file_convert::{markdown_export_file, markdown_to_pdf_file, MarkdownExportFormat};
+use crate::functions::fun_markdown_export::MarkdownStyleProfile;
+use crate::functions::fun_firmware::{firmware_process, FirmwareInput, FirmwareOperation, FirmwareOutput};
+use crate::plugins::plugin_fs::write_txt_file;
+
+// 任务事件名
+pub const JOB_PROGRESS_EVENT: &str = "job-progress";
+pub const JOB_DONE_EVENT: &str = "job-done";
+
+// 默认列出的历史条数
+const DEFAULT_JOB_LIST_LIMIT: i64 = 100;
+
+/// 单个任务项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobTask {
+    // 固件格式转换
+    FirmwareConvert {
+        input: FirmwareInput,
+        output: FirmwareOutput,
+    },
+    // 固件合并 / 处理
+    FirmwareProcess {
+        inputs: Vec<FirmwareInput>,
+        #[serde(default)]
+        operations: Vec<FirmwareOperation>,
+        output: FirmwareOutput,
+        overwrite: Option<bool>,
+    },
+    // Markdown 转 PDF，markdown 与 markdown_path 二选一
+    MarkdownToPdf {
+        markdown: Option<String>,
+        markdown_path: Option<String>,
+        pdf_file_name: String,
+    },
+    // Markdown 按样式导出为 PDF / HTML / DOCX；样式随任务保存，重试时结果一致
+    MarkdownExport {
+        markdown: Option<String>,
+        markdown_path: Option<String>,
+        file_name: String,
+        format: MarkdownExportFormat,
+        profile: Option<Box<MarkdownStyleProfile>>,
+    },
+    // 写入文本文件
+    TextFile {
+        file_title: String,
+        content: String,
+    },
+}
+
+impl JobTask {
+    // 进度事件中显示的名称
+    fn label(&self) -> String {
+        match self {
+            JobTask::FirmwareConvert { input, output } => format!("{} -> {}", file_name(&input.path), output.file_name),
+            JobTask::FirmwareProcess { output, .. } => output.file_name.clone(),
+            JobTask::MarkdownToPdf { pdf_file_name, .. } => format!("{}.pdf", pdf_file_name),
+            JobTask::MarkdownExport { file_name, format, .. } => format!("{}.{}", file_name, format.extension()),
+            JobTask::TextFile { file_title, .. } => file_title.clone(),
+        }
+    }
+
+    // 执行任务项，返回输出文件路径
+    fn run(self) -> Result<String, String> {
+        match self {
+            JobTask::FirmwareConvert { input, output } => Ok(firmware_process(vec![input], vec![], output, None)?.output_path),
+            JobTask::FirmwareProcess {
+                inputs,
+                operations,
+                output,
+                overwrite,
+            } => Ok(firmware_process(inputs, operations, output, overwrite)?.output_path),
+            JobTask::MarkdownToPdf {
+                markdown,
+                markdown_path,
+                pdf_file_name,
+            } => {
+                let markdown = read_markdown(markdown, markdown_path.as_deref())?;
+                Ok(markdown_to_pdf_file(&markdown, &pdf_file_name)?.to_string_lossy().to_string())
+            }
+            JobTask::MarkdownExport {
+                markdown,
+                markdown_path,
+                file_name,
+                format,
+                profile,
+            } => {
+                let content = read_markdown(markdown, markdown_path.as_deref())?;
+                let base_dir = markdown_path.as_deref().and_then(|p| Path::new(p).parent());
+                let path = markdown_export_file(&content, &file_name, format, profile.as_deref(), base_dir)?;
+                Ok(path.to_string_lossy().to_string())
+            }
+            JobTask::TextFile { file_title, content } => {
+                Ok(write_txt_file(&file_title, &content)?.to_string_lossy().to_string())
+            }
+        }
+    }
+}
+
+// markdown 与 markdown_path 二选一
+fn read_markdown(markdown: Option<String>, markdown_path: Option<&str>) -> Result<String, String> {
+    match (markdown, markdown_path) {
+        (Some(markdown), _) => Ok(markdown),
+        (None, Some(path)) => {
+            std::fs::read_to_string(path).map_err(|e| format!("读取 Markdown 文件失败 {}: {}", path, e))
+        }
+        (None, None) => Err("缺少 Markdown 内容".to_string()),
+    }
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or(path.to_string())
+}
+
+/// 提交的批量任务
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobSpec {
+    pub title: Option<String>,
+    pub tasks: Vec<JobTask>,
+    #[serde(default)]
+    pub stop_on_error: bool, // 某一项失败时停止后续项，默认继续
+}
+
+/// 任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// 单个任务项的执行结果
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JobItemResult {
+    pub index: usize,
+    pub label: String,
+    pub output_path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// 进度事件（每完成一项推送一次）
+#[derive(Debug, Clone, Serialize)]
+pub struct JobProgressEvent {
+    pub job_id: i64,
+    pub total: usize,
+    pub done: usize,
+    pub failed: usize,
+    pub item: JobItemResult,
+}
+
+/// 任务结束事件
+#[derive(Debug, Clone, Serialize)]
+pub struct JobDoneEvent {
+    pub job_id: i64,
+    pub status: JobStatus,
+    pub done: usize,
+    pub failed: usize,
+    pub message: Option<String>,
+}
+
+/// 任务详情
+#[derive(Debug, Clone, Serialize)]
+pub struct JobDetail {
+    pub record: JobRecord,
+    pub spec: serde_json::Value,
+    pub results: Vec<JobItemResult>,
+}
+
+/// 可取消任务的取消标志表（任务 id -> 取消标志），后台任务队列与文件摘要共用
+pub struct TaskCancelFlags<K> {
+    flags: Mutex<HashMap<K, Arc<AtomicBool>>>,
+}
+
+impl<K> Default for TaskCancelFlags<K> {
+    fn default() -> Self {
+        Self {
+            flags: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Display> TaskCancelFlags<K> {
+    /// 登记任务，id 已在运行时报错
+    pub fn register(&self, id: K) -> Result<Arc<AtomicBool>, String> {
+        let mut flags = self.flags.lock().unwrap();
+        if flags.contains_key(&id) {
+            return Err(format!("任务 {} 正在运行", id));
+        }
+        let flag = Arc::new(AtomicBool::new(false));
+        flags.insert(id, flag.clone());
+        Ok(flag)
+    }
+
+    fn get<Q: Eq + Hash + ?Sized>(&self, id: &Q) -> Option<Arc<AtomicBool>>
+    where
+        K: Borrow<Q>,
+    {
+        self.flags.lock().unwrap().get(id).cloned()
+    }
+
+    /// 请求取消，由任务自行检查标志后停止
+    pub fn cancel<Q: Eq + Hash + Display + ?Sized>(&self, id: &Q) -> Result<(), String>
+    where
+        K: Borrow<Q>,
+    {
+        let flags = self.flags.lock().unwrap();
+        let flag = flags.get(id).ok_or(format!("任务 {} 未在运行", id))?;
+        flag.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// 任务结束后移除
+    pub fn remove<Q: Eq + Hash + ?Sized>(&self, id: &Q)
+    where
+        K: Borrow<Q>,
+    {
+        self.flags.lock().unwrap().remove(id);
+    }
+}
+
+/// 进度推送节流：首次、完成时及每隔 interval 才推送一次
+pub struct ProgressThrottle {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl ProgressThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, last: None }
+    }
+
+    pub fn ready(&mut self, finished: bool) -> bool {
+        if finished || self.last.is_none_or(|t| t.elapsed() >= self.interval) {
+            self.last = Some(Instant::now());
+            return true;
+        }
+        false
+    }
+}
+
+/// 后台任务队列：按提交顺序逐个执行，同一时间只有一个任务在运行
+#[derive(Default)]
+pub struct JobState {
+    queue: Mutex<VecDeque<(i64, JobSpec)>>,
+    cancel: TaskCancelFlags<i64>, // 排队中与运行中任务的取消标志
+    worker_running: AtomicBool,
+}
+
+// 提交任务并在需要时启动工作协程
+async fn job_enqueue(app: &AppHandle, spec_json: serde_json::Value) -> Result<i64, String> {
+    let spec: JobSpec =
+        serde_json::from_value(spec_json.clone()).map_err(|e| format!("任务参数无效: {}", e))?;
+    if spec.tasks.is_empty() {
+        return Err("任务列表为空".to_string());
+    }
+    let title = spec
+        .title
+        .clone()
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or(format!("批量任务（{} 项）", spec.tasks.len()));
+    let job_id = db_job_insert(
+        &app.state::<DbState>().pool,
+        &title,
+        &spec_json.to_string(),
+        spec.tasks.len() as i64,
+    )
+    .await?;
+
+    let state = app.state::<JobState>();
+    state.cancel.register(job_id)?;
+    let mut queue = state.queue.lock().unwrap();
+    queue.push_back((job_id, spec));
+    if !state.worker_running.swap(true, Ordering::SeqCst) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move { job_worker(app).await });
+    }
+    Ok(job_id)
+}
+
+async fn job_worker(app: AppHandle) {
+    loop {
+        let next = {
+            let state = app.state::<JobState>();
+            let mut queue = state.queue.lock().unwrap();
+            match queue.pop_front() {
+                Some(next) => next,
+                None => {
+                    // 与 job_enqueue 在同一把锁内判断，避免任务入队后无人执行
+                    state.worker_running.store(false, Ordering::SeqCst);
+                    return;
+                }
+            }
+        };
+        let (job_id, spec) = next;
+        let cancel = app.state::<JobState>().cancel.get(&job_id).unwrap_or_default();
+        job_run(&app, job_id, spec, &cancel).await;
+        app.state::<JobState>().cancel.remove(&job_id);
+    }
+}
+
+async fn job_run(app: &AppHandle, job_id: i64, spec: JobSpec, cancel: &AtomicBool) {
+    let pool = app.state::<DbState>().pool.clone();
+    let total = spec.tasks.len();
+    let mut results: Vec<JobItemResult> = Vec::new();
+    let (mut done, mut failed) = (0usize, 0usize);
+    let mut status = JobStatus::Completed;
+    let mut message = None;
+
+    if cancel.load(Ordering::SeqCst) {
+        status = JobStatus::Cancelled;
+    } else if let Err(e) = db_job_start(&pool, job_id).await {
+        log::warn!("后台任务 {} 写入数据库失败: {}", job_id, e);
+    }
+
+    for (index, task) in spec.tasks.into_iter().enumerate() {
+        if status == JobStatus::Cancelled || cancel.load(Ordering::SeqCst) {
+            status = JobStatus::Cancelled;
+            break;
+        }
+        let label = task.label();
+        let outcome = tauri::async_runtime::spawn_blocking(move || task.run())
+            .await
+            .unwrap_or_else(|e| Err(format!("任务执行异常: {}", e)));
+        let item = match outcome {
+            Ok(path) => JobItemResult {
+                index,
+                label,
+                output_path: Some(path),
+                error: None,
+            },
+            Err(e) => {
+                failed += 1;
+                JobItemResult {
+                    index,
+                    label,
+                    output_path: None,
+                    error: Some(e),
+                }
+            }
+        };
+        done += 1;
+        results.push(item.clone());
+
+        let results_json = serde_json::to_string(&results).unwrap_or_default();
+        if let Err(e) = db_job_progress(&pool, job_id, done as i64, failed as i64, &results_json).await {
+            log::warn!("后台任务 {} 写入数据库失败: {}", job_id, e);
+        }
+        let stop = spec.stop_on_error && item.error.is_some();
+        let _ = app.emit(
+            JOB_PROGRESS_EVENT,
+            JobProgressEvent {
+                job_id,
+                total,
+                done,
+                failed,
+                item,
+            },
+        );
+        if stop {
+            status = JobStatus::Failed;
+            message = Some(format!("第 {} 项失败，已停止后续任务", index + 1));
+            break;
+        }
+    }
+
+    if status == JobStatus::Completed && failed > 0 {
+        status = JobStatus::Failed;
+        message = Some(format!("{} 项失败", failed));
+    }
+    if status == JobStatus::Cancelled {
+        message = Some(format!("已取消，完成 {}/{} 项", done, total));
+    }
+    if let Err(e) = db_job_finish(&pool, job_id, status.as_str(), message.as_deref()).await {
+        log::warn!("后台任务 {} 写入数据库失败: {}", job_id, e);
+    }
+    let _ = app.emit(
+        JOB_DONE_EVENT,
+        JobDoneEvent {
+            job_id,
+            status,
+            done,
+            failed,
+            message,
+        },
+    );
+}
+
+// 提交批量任务，返回任务 id；进度通过 job-progress 推送，结束时推送 job-done
+#[tauri::command]
+pub async fn job_submit(app: AppHandle, spec: serde_json::Value) -> Result<i64, String> {
+    job_enqueue(&app, spec).await
+}
+
+// 取消任务：排队中的任务不再执行，运行中的任务在当前项完成后停止
+#[tauri::command]
+pub fn job_cancel(state: State<'_, JobState>, job_id: i64) -> Result<(), String> {
+    state.cancel.cancel(&job_id)
+}
+
+// 任务历史（含运行中与启动时标记为 interrupted 的任务）
+#[tauri::command]
+pub async fn job_list(app: AppHandle, limit: Option<i64>) -> Result<Vec<JobRecord>, String> {
+    db_job_list(&app.state::<DbState>().pool, limit.unwrap_or(DEFAULT_JOB_LIST_LIMIT)).await
+}
+
+// 任务详情与每一项的结果
+#[tauri::command]
+pub async fn job_get(app: AppHandle, job_id: i64) -> Result<JobDetail, String> {
+    let (record, spec, results) = db_job_get(&app.state::<DbState>().pool, job_id)
+        .await?
+        .ok_or(format!("任务 {} 不存在", job_id))?;
+    Ok(JobDetail {
+        record,
+        spec: serde_json::from_str(&spec).unwrap_or_default(),
+        results: results
+            .and_then(|r| serde_json::from_str(&r).ok())
+            .unwrap_or_default(),
+    })
+}
+
+// 以原参数重新提交任务（如被中断或失败的任务），返回新任务 id
+#[tauri::command]
+pub async fn job_retry(app: AppHandle, job_id: i64) -> Result<i64, String> {
+    let (_, spec, _) = db_job_get(&app.state::<DbState>().pool, job_id)
+        .await?
+        .ok_or(format!("任务 {} 不存在", job_id))?;
+    let spec = serde_json::from_str(&spec).map_err(|e| format!("读取任务参数失败: {}", e))?;
+    job_enqueue(&app, spec).await
+}
+
+// 删除已结束的任务
+#[tauri::command]
+pub async fn job_delete(app: AppHandle, job_id: i64) -> Result<(), String> {
+    db_job_delete(&app.state::<DbState>().pool, job_id).await
+}
+
+// 清空已结束的任务历史
+#[tauri::command]
+pub async fn job_clear_history(app: AppHandle) -> Result<u64, String> {
+    db_job_clear_history(&app.state::<DbState>().pool).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_flags() {
+        let flags = TaskCancelFlags::<String>::default();
+        let flag = flags.register("a".to_string()).unwrap();
+        assert!(flags.register("a".to_string()).is_err());
+        assert!(flags.cancel("b").is_err());
+        flags.cancel("a").unwrap();
+        assert!(flag.load(Ordering::SeqCst));
+        flags.remove("a");
+        assert!(flags.cancel("a").is_err());
+        assert!(!flags.register("a".to_string()).unwrap().load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn progress_throttle() {
+        let mut throttle = ProgressThrottle::new(Duration::from_secs(3600));
+        assert!(throttle.ready(false));
+        assert!(!throttle.ready(false));
+        assert!(throttle.ready(true));
+        let mut throttle = ProgressThrottle::new(Duration::ZERO);
+        assert!(throttle.ready(false) && throttle.ready(false));
+    }
+}
@@ -0,0 +1,87 @@
+use serde_json::{Map, Value};
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::functions::fun_file_convert::{markdown_export_file, MarkdownExportFormat};
+use crate::functions::fun_markdown_export::{markdown_style_check, MarkdownStyleProfile};
+use crate::plugins::plugin_store::get_store;
+
+// Store 中保存导出样式的键，值为 { 样式名: 样式 }
+const MARKDOWN_STYLE_KEY: &str = "markdown_styles";
+
+fn load_styles(app: &AppHandle) -> Map<String, Value> {
+    match get_store(app.clone()).get(MARKDOWN_STYLE_KEY) {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+/// 按名称读取导出样式
+pub fn markdown_style_load(app: &AppHandle, name: &str) -> Result<MarkdownStyleProfile, String> {
+    let value = load_styles(app)
+        .remove(name)
+        .ok_or(format!("导出样式 {} 不存在", name))?;
+    serde_json::from_value(value).map_err(|e| format!("读取导出样式失败: {}", e))
+}
+
+// 列出已保存的导出样式
+#[tauri::command]
+pub fn markdown_style_list(app: AppHandle) -> Result<Vec<MarkdownStyleProfile>, String> {
+    let mut styles: Vec<MarkdownStyleProfile> = load_styles(&app)
+        .into_values()
+        .filter_map(|v| serde_json::from_value(v).ok())
+        .collect();
+    styles.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(styles)
+}
+
+// 默认样式，作为新建样式的模板
+#[tauri::command]
+pub fn markdown_style_default() -> MarkdownStyleProfile {
+    MarkdownStyleProfile::default()
+}
+
+// 保存导出样式（同名覆盖）
+#[tauri::command]
+pub fn markdown_style_save(app: AppHandle, mut profile: MarkdownStyleProfile) -> Result<(), String> {
+    profile.name = profile.name.trim().to_string();
+    markdown_style_check(&profile)?;
+    let mut styles = load_styles(&app);
+    let value = serde_json::to_value(&profile).map_err(|e| format!("序列化导出样式失败: {}", e))?;
+    styles.insert(profile.name.clone(), value);
+    get_store(app).set(MARKDOWN_STYLE_KEY.to_string(), Value::Object(styles));
+    Ok(())
+}
+
+// 删除导出样式
+#[tauri::command]
+pub fn markdown_style_delete(app: AppHandle, name: &str) -> Result<bool, String> {
+    let mut styles = load_styles(&app);
+    let removed = styles.remove(name).is_some();
+    get_store(app).set(MARKDOWN_STYLE_KEY.to_string(), Value::Object(styles));
+    Ok(removed)
+}
+
+// 按导出样式转换 Markdown，返回输出文件路径
+// style 为空时 PDF 使用 markdown2pdf 默认样式；markdown_path 用于解析图片的相对路径
+#[tauri::command]
+pub async fn markdown_export(
+    app: AppHandle,
+    markdown_content: String,
+    file_name: String,
+    format: MarkdownExportFormat,
+    style: Option<String>,
+    markdown_path: Option<String>,
+) -> Result<String, String> {
+    let profile = match style.as_deref().filter(|s| !s.is_empty()) {
+        Some(name) => Some(markdown_style_load(&app, name)?),
+        None => None,
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let base_dir = markdown_path.as_deref().and_then(|p| Path::new(p).parent());
+        markdown_export_file(&markdown_content, &file_name, format, profile.as_ref(), base_dir)
+            .map(|path| path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| format!("导出任务异常: {}", e))?
+}
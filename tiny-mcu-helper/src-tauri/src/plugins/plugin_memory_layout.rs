@@ -0,0 +1,45 @@
+use tauri::{AppHandle, Manager};
+
+use crate::db::{
+    db_memory_layout_delete, db_memory_layout_get_model, db_memory_layout_list, db_memory_layout_save, DbState,
+    MemoryLayoutRecord,
+};
+use crate::functions::fun_memory_layout::MemoryLayout;
+
+// 列出已保存的内存布局
+#[tauri::command]
+pub async fn memory_layout_list(app: AppHandle) -> Result<Vec<MemoryLayoutRecord>, String> {
+    db_memory_layout_list(&app.state::<DbState>().pool).await
+}
+
+// 获取内存布局
+#[tauri::command]
+pub async fn memory_layout_get(app: AppHandle, layout_id: i64) -> Result<MemoryLayout, String> {
+    let model = db_memory_layout_get_model(&app.state::<DbState>().pool, layout_id)
+        .await?
+        .ok_or(format!("内存布局 {} 不存在", layout_id))?;
+    serde_json::from_str(&model).map_err(|e| format!("读取内存布局失败: {}", e))
+}
+
+// 保存内存布局（layout_id 为空时新建），返回布局 id；允许保存未通过校验的草稿
+#[tauri::command]
+pub async fn memory_layout_save(app: AppHandle, layout_id: Option<i64>, layout: MemoryLayout) -> Result<i64, String> {
+    if layout.name.trim().is_empty() {
+        return Err("内存布局名称不能为空".to_string());
+    }
+    let model = serde_json::to_string(&layout).map_err(|e| format!("序列化内存布局失败: {}", e))?;
+    db_memory_layout_save(
+        &app.state::<DbState>().pool,
+        layout_id,
+        &layout.name,
+        layout.description.as_deref(),
+        &model,
+    )
+    .await
+}
+
+// 删除内存布局
+#[tauri::command]
+pub async fn memory_layout_delete(app: AppHandle, layout_id: i64) -> Result<(), String> {
+    db_memory_layout_delete(&app.state::<DbState>().pool, layout_id).await
+}
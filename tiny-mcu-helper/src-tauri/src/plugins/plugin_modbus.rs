@@ -0,0 +1,697 @@
+use serde::{Deserialize, Serialize};
+use serialport::{ClearBuffer, SerialPort};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{ErrorKind, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::functions::fun_modbus::{
+    decode_ascii_frame, decode_registers, decode_rtu_frame, encode_ascii_frame, encode_rtu_frame,
+    modbus_exception_text, modbus_rtu_timing_us, ModbusDataModel, ModbusDataType, ModbusRequest,
+    ModbusResponse, ModbusSerialMode, WordOrder,
+};
+use crate::plugins::plugin_serial::SerialConfig;
+use crate::utils::util_timestamp_us;
+
+// 轮询结果事件名
+pub const MODBUS_POLL_EVENT: &str = "modbus-poll";
+
+/// Modbus 传输层（串口 RTU/ASCII、TCP 共用同一套请求与轮询逻辑）
+pub trait ModbusTransport: Send {
+    fn transact(&mut self, unit_id: u8, request: &ModbusRequest) -> Result<ModbusResponse, String>;
+}
+
+// 广播（从站地址 0）时从站不应答，按请求内容构造确认结果
+fn broadcast_ack(request: &ModbusRequest) -> Result<ModbusResponse, String> {
+    match request {
+        ModbusRequest::WriteSingleCoil { address, value } => Ok(ModbusResponse::WriteSingle {
+            address: *address,
+            value: if *value { 0xFF00 } else { 0 },
+        }),
+        ModbusRequest::WriteSingleRegister { address, value } => Ok(ModbusResponse::WriteSingle {
+            address: *address,
+            value: *value,
+        }),
+        ModbusRequest::WriteMultipleCoils { address, values } => Ok(ModbusResponse::WriteMultiple {
+            address: *address,
+            quantity: values.len() as u16,
+        }),
+        ModbusRequest::WriteMultipleRegisters { address, values } => Ok(ModbusResponse::WriteMultiple {
+            address: *address,
+            quantity: values.len() as u16,
+        }),
+        _ => Err("广播地址 0 只能用于写操作".to_string()),
+    }
+}
+
+/// 串口主站（RTU / ASCII）
+pub struct ModbusSerialMaster {
+    port: Box<dyn SerialPort>,
+    mode: ModbusSerialMode,
+    frame_gap: Duration,        // RTU 帧间隔 t3.5
+    response_timeout: Duration, // 等待从站响应的超时
+    turnaround_delay: Duration, // 广播后等待从站处理的时间
+    last_frame_end: Instant,
+}
+
+impl ModbusSerialMaster {
+    pub fn new(
+        mut port: Box<dyn SerialPort>,
+        mode: ModbusSerialMode,
+        frame_gap_us: u64,
+        response_timeout: Duration,
+    ) -> Result<Self, String> {
+        // 读超时取较短值，便于按帧间隔和总超时自行判断
+        port.set_timeout(Duration::from_millis(5))
+            .map_err(|e| format!("设置串口超时失败: {}", e))?;
+        Ok(Self {
+            port,
+            mode,
+            frame_gap: Duration::from_micros(frame_gap_us),
+            response_timeout,
+            turnaround_delay: Duration::from_millis(100),
+            last_frame_end: Instant::now(),
+        })
+    }
+
+    fn send_frame(&mut self, frame: &[u8]) -> Result<(), String> {
+        // RTU 帧之间至少间隔 t3.5
+        let elapsed = self.last_frame_end.elapsed();
+        if elapsed < self.frame_gap {
+            std::thread::sleep(self.frame_gap - elapsed);
+        }
+        let _ = self.port.clear(ClearBuffer::Input);
+        self.port
+            .write_all(frame)
+            .and_then(|_| self.port.flush())
+            .map_err(|e| format!("发送 Modbus 帧失败: {}", e))?;
+        self.last_frame_end = Instant::now();
+        Ok(())
+    }
+
+    // 读取一段数据，超时返回 0
+    fn read_some(&mut self, buffer: &mut [u8]) -> Result<usize, String> {
+        match self.port.read(buffer) {
+            Ok(n) => Ok(n),
+            Err(e) if matches!(e.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock | ErrorKind::Interrupted) => {
+                Ok(0)
+            }
+            Err(e) => Err(format!("读取 Modbus 响应失败: {}", e)),
+        }
+    }
+
+    fn read_rtu_response(&mut self, unit_id: u8, request: &ModbusRequest) -> Result<Vec<u8>, String> {
+        let deadline = Instant::now() + self.response_timeout;
+        let mut frame = Vec::new();
+        let mut buffer = [0u8; 256];
+
+        loop {
+            // 根据已收到的功能码 / 字节数推算完整帧长度
+            let expected = match frame.get(1) {
+                Some(function) if function & 0x80 != 0 => Some(5),
+                Some(_) => request.rtu_response_len(frame.get(2).copied()),
+                None => None,
+            };
+            if let Some(expected) = expected {
+                if frame.len() >= expected {
+                    frame.truncate(expected);
+                    break;
+                }
+            }
+            if Instant::now() >= deadline {
+                return Err(if frame.is_empty() {
+                    format!("从站 {} 响应超时", unit_id)
+                } else {
+                    format!("从站 {} 响应不完整: 已收到 {} 字节", unit_id, frame.len())
+                });
+            }
+            let n = self.read_some(&mut buffer)?;
+            frame.extend_from_slice(&buffer[..n]);
+        }
+
+        self.last_frame_end = Instant::now();
+        Ok(frame)
+    }
+
+    fn read_ascii_response(&mut self, unit_id: u8) -> Result<Vec<u8>, String> {
+        let deadline = Instant::now() + self.response_timeout;
+        let mut frame = Vec::new();
+        let mut buffer = [0u8; 256];
+
+        while !frame.ends_with(b"\r\n") {
+            if Instant::now() >= deadline {
+                return Err(format!("从站 {} 响应超时", unit_id));
+            }
+            let n = self.read_some(&mut buffer)?;
+            frame.extend_from_slice(&buffer[..n]);
+            // 丢弃起始符 ':' 之前的杂散数据
+            if let Some(start) = frame.iter().position(|b| *b == b':') {
+                frame.drain(..start);
+            } else {
+                frame.clear();
+            }
+        }
+
+        self.last_frame_end = Instant::now();
+        Ok(frame)
+    }
+}
+
+impl ModbusTransport for ModbusSerialMaster {
+    fn transact(&mut self, unit_id: u8, request: &ModbusRequest) -> Result<ModbusResponse, String> {
+        let pdu = request.encode()?;
+        let frame = match self.mode {
+            ModbusSerialMode::Rtu => encode_rtu_frame(unit_id, &pdu),
+            ModbusSerialMode::Ascii => encode_ascii_frame(unit_id, &pdu),
+        };
+        // 广播只允许写操作，先校验再发送，避免读请求被发出后才报错
+        let ack = if unit_id == 0 { Some(broadcast_ack(request)?) } else { None };
+        self.send_frame(&frame)?;
+
+        if let Some(ack) = ack {
+            std::thread::sleep(self.turnaround_delay);
+            return Ok(ack);
+        }
+
+        let (response_unit, response_pdu) = match self.mode {
+            ModbusSerialMode::Rtu => decode_rtu_frame(&self.read_rtu_response(unit_id, request)?)?,
+            ModbusSerialMode::Ascii => decode_ascii_frame(&self.read_ascii_response(unit_id)?)?,
+        };
+        if response_unit != unit_id {
+            return Err(format!("响应从站地址不匹配: 期望 {}，实际 {}", unit_id, response_unit));
+        }
+        request.decode_response(&response_pdu)
+    }
+}
+
+/// 串口从站循环：从 port 接收请求帧，用 model 处理后应答，直到 stop 被置位
+/// （供从站模拟器使用，也可配合 pty 在无硬件环境下联调主站）
+pub fn modbus_serial_slave_serve(
+    mut port: Box<dyn SerialPort>,
+    mode: ModbusSerialMode,
+    unit_id: u8,
+    frame_gap_us: u64,
+    model: Arc<Mutex<ModbusDataModel>>,
+    stop: Arc<AtomicBool>,
+) -> Result<(), String> {
+    // RTU 以 t3.5 静默判定帧结束，读超时取 t3.5（至少 1ms）
+    let gap = Duration::from_micros(frame_gap_us.max(1000));
+    port.set_timeout(gap)
+        .map_err(|e| format!("设置串口超时失败: {}", e))?;
+
+    let mut frame = Vec::new();
+    let mut buffer = [0u8; 512];
+    while !stop.load(Ordering::SeqCst) {
+        let n = match port.read(&mut buffer) {
+            Ok(n) => n,
+            Err(e) if matches!(e.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock | ErrorKind::Interrupted) => 0,
+            Err(e) => return Err(format!("从站读取失败: {}", e)),
+        };
+        frame.extend_from_slice(&buffer[..n]);
+
+        let complete = match mode {
+            ModbusSerialMode::Rtu => n == 0 && !frame.is_empty(),
+            ModbusSerialMode::Ascii => frame.ends_with(b"\r\n"),
+        };
+        if !complete {
+            continue;
+        }
+
+        let decoded = match mode {
+            ModbusSerialMode::Rtu => decode_rtu_frame(&frame),
+            ModbusSerialMode::Ascii => decode_ascii_frame(&frame),
+        };
+        frame.clear();
+
+        // 校验失败或非本站地址的帧直接丢弃；广播帧执行但不应答
+        let (request_unit, pdu) = match decoded {
+            Ok(decoded) => decoded,
+            Err(_) => continue,
+        };
+        if request_unit != unit_id && request_unit != 0 {
+            continue;
+        }
+        let response = model.lock().unwrap().process_pdu(&pdu);
+        if request_unit == 0 {
+            continue;
+        }
+
+        let reply = match mode {
+            ModbusSerialMode::Rtu => encode_rtu_frame(unit_id, &response),
+            ModbusSerialMode::Ascii => encode_ascii_frame(unit_id, &response),
+        };
+        port.write_all(&reply)
+            .and_then(|_| port.flush())
+            .map_err(|e| format!("从站应答失败: {}", e))?;
+    }
+    Ok(())
+}
+
+// ==================== 寄存器映射轮询 ====================
+
+/// 数据区
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModbusTable {
+    Coil,
+    DiscreteInput,
+    HoldingRegister,
+    InputRegister,
+}
+
+impl ModbusTable {
+    fn is_bit(&self) -> bool {
+        matches!(self, Self::Coil | Self::DiscreteInput)
+    }
+
+    // 单次读取的最大数量
+    fn max_quantity(&self) -> usize {
+        if self.is_bit() {
+            2000
+        } else {
+            125
+        }
+    }
+
+    fn read_request(&self, address: u16, quantity: u16) -> ModbusRequest {
+        match self {
+            Self::Coil => ModbusRequest::ReadCoils { address, quantity },
+            Self::DiscreteInput => ModbusRequest::ReadDiscreteInputs { address, quantity },
+            Self::HoldingRegister => ModbusRequest::ReadHoldingRegisters { address, quantity },
+            Self::InputRegister => ModbusRequest::ReadInputRegisters { address, quantity },
+        }
+    }
+}
+
+/// 寄存器映射条目
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RegisterMapEntry {
+    pub name: String,
+    pub unit_id: u8,
+    pub table: ModbusTable,
+    pub address: u16,
+    pub data_type: ModbusDataType,
+    #[serde(default)]
+    pub word_order: WordOrder,
+    pub scale: Option<f64>,  // 工程值 = 原始值 * scale + offset
+    pub offset: Option<f64>,
+}
+
+impl RegisterMapEntry {
+    fn quantity(&self) -> usize {
+        if self.table.is_bit() {
+            1
+        } else {
+            self.data_type.register_count()
+        }
+    }
+}
+
+/// 单次读取块：同一从站、同一数据区内的连续地址
+#[derive(Debug, Clone)]
+struct PollBlock {
+    unit_id: u8,
+    table: ModbusTable,
+    address: u16,
+    quantity: usize,
+    entries: Vec<usize>, // 对应 entries 中的下标
+}
+
+// 按从站和数据区分组，把地址连续（间隔不超过 max_gap）的条目合并为一次读取
+fn plan_poll_blocks(entries: &[RegisterMapEntry], max_gap: usize) -> Vec<PollBlock> {
+    let mut groups: BTreeMap<(u8, ModbusTable), Vec<usize>> = BTreeMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        groups.entry((entry.unit_id, entry.table)).or_default().push(index);
+    }
+
+    let mut blocks = Vec::new();
+    for ((unit_id, table), mut indexes) in groups {
+        indexes.sort_by_key(|i| entries[*i].address);
+        let mut current: Option<PollBlock> = None;
+        for index in indexes {
+            let entry = &entries[index];
+            let start = entry.address as usize;
+            let end = start + entry.quantity();
+
+            if let Some(block) = current.as_mut() {
+                let block_end = block.address as usize + block.quantity;
+                let merged_len = end.max(block_end) - block.address as usize;
+                if start <= block_end + max_gap && merged_len <= table.max_quantity() {
+                    block.quantity = merged_len;
+                    block.entries.push(index);
+                    continue;
+                }
+                blocks.push(current.take().unwrap());
+            }
+            current = Some(PollBlock {
+                unit_id,
+                table,
+                address: entry.address,
+                quantity: end - start,
+                entries: vec![index],
+            });
+        }
+        blocks.extend(current);
+    }
+    blocks
+}
+
+/// 单个条目的轮询结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ModbusPollValue {
+    pub name: String,
+    pub value: Option<f64>,
+    pub raw: Vec<u16>,
+    pub error: Option<String>,
+}
+
+/// 一轮轮询的结果事件
+#[derive(Debug, Clone, Serialize)]
+pub struct ModbusPollEvent {
+    pub session: String,
+    pub cycle: u64,
+    pub timestamp_us: u64,
+    pub values: Vec<ModbusPollValue>,
+}
+
+// 读取一个块并解码其中的条目
+fn poll_block(
+    transport: &Mutex<Box<dyn ModbusTransport>>,
+    block: &PollBlock,
+    entries: &[RegisterMapEntry],
+    values: &mut [ModbusPollValue],
+) {
+    let request = block.table.read_request(block.address, block.quantity as u16);
+    let response = transport.lock().unwrap().transact(block.unit_id, &request);
+
+    let raw: Result<Vec<u16>, String> = match response {
+        Ok(ModbusResponse::Bits { values }) => Ok(values.into_iter().map(|b| b as u16).collect()),
+        Ok(ModbusResponse::Registers { values }) => Ok(values),
+        Ok(ModbusResponse::Exception { code, .. }) => {
+            Err(format!("从站异常 {:02X}: {}", code, modbus_exception_text(code)))
+        }
+        Ok(other) => Err(format!("意外的响应: {:?}", other)),
+        Err(e) => Err(e),
+    };
+
+    for index in &block.entries {
+        let entry = &entries[*index];
+        let slot = &mut values[*index];
+        match &raw {
+            Ok(raw) => {
+                let offset = (entry.address - block.address) as usize;
+                let registers = &raw[offset..offset + entry.quantity()];
+                let data_type = if entry.table.is_bit() {
+                    ModbusDataType::Bool
+                } else {
+                    entry.data_type
+                };
+                slot.raw = registers.to_vec();
+                match decode_registers(registers, data_type, entry.word_order) {
+                    Ok(value) => {
+                        slot.value = Some(value * entry.scale.unwrap_or(1.0) + entry.offset.unwrap_or(0.0));
+                        slot.error = None;
+                    }
+                    Err(e) => slot.error = Some(e),
+                }
+            }
+            Err(e) => {
+                slot.value = None;
+                slot.error = Some(e.clone());
+            }
+        }
+    }
+}
+
+// ==================== 会话管理与命令 ====================
+
+type SharedTransport = Arc<Mutex<Box<dyn ModbusTransport>>>;
+
+/// Modbus 会话状态（通过 Tauri manage 注册）
+#[derive(Default)]
+pub struct ModbusState {
+    sessions: Mutex<HashMap<String, SharedTransport>>,
+    pollers: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl ModbusState {
+    /// 注册新会话（会话名重复时报错）
+    pub fn add_session(&self, session: &str, transport: Box<dyn ModbusTransport>) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().unwrap();
+        if sessions.contains_key(session) {
+            return Err(format!("Modbus 会话 {} 已存在", session));
+        }
+        sessions.insert(session.to_string(), Arc::new(Mutex::new(transport)));
+        Ok(())
+    }
+
+    fn get_session(&self, session: &str) -> Result<SharedTransport, String> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .get(session)
+            .cloned()
+            .ok_or(format!("Modbus 会话 {} 不存在", session))
+    }
+
+    fn stop_poller(&self, session: &str) -> bool {
+        match self.pollers.lock().unwrap().remove(session) {
+            Some(stop) => {
+                stop.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 串口主站打开参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModbusSerialConfig {
+    #[serde(flatten)]
+    pub serial: SerialConfig,
+    pub mode: ModbusSerialMode,
+    pub response_timeout_ms: Option<u64>, // 默认 1000ms
+}
+
+impl ModbusSerialConfig {
+    // 按串口参数计算 RTU 帧间隔
+    pub fn frame_gap_us(&self) -> u64 {
+        let parity = !matches!(self.serial.parity.as_deref(), None | Some("none"));
+        modbus_rtu_timing_us(
+            self.serial.baud_rate,
+            self.serial.data_bits.unwrap_or(8),
+            parity,
+            self.serial.stop_bits.unwrap_or(1),
+        )
+        .0
+    }
+}
+
+// 打开串口 Modbus 主站，会话名为串口名
+#[tauri::command]
+pub fn modbus_serial_open(state: State<'_, ModbusState>, config: ModbusSerialConfig) -> Result<String, String> {
+    let session = config.serial.port_name.clone();
+    let port = config.serial.open()?;
+    let master = ModbusSerialMaster::new(
+        port,
+        config.mode,
+        config.frame_gap_us(),
+        Duration::from_millis(config.response_timeout_ms.unwrap_or(1000)),
+    )?;
+    state.add_session(&session, Box::new(master))?;
+    Ok(session)
+}
+
+// 关闭会话（同时停止轮询）
+#[tauri::command]
+pub fn modbus_close(state: State<'_, ModbusState>, session: &str) -> Result<(), String> {
+    state.stop_poller(session);
+    state
+        .sessions
+        .lock()
+        .unwrap()
+        .remove(session)
+        .map(|_| ())
+        .ok_or(format!("Modbus 会话 {} 不存在", session))
+}
+
+// 发送单次请求（串口 IO 放到阻塞线程池，避免卡住界面）
+#[tauri::command]
+pub async fn modbus_request(
+    app: AppHandle,
+    session: String,
+    unit_id: u8,
+    request: ModbusRequest,
+) -> Result<ModbusResponse, String> {
+    let transport = app.state::<ModbusState>().get_session(&session)?;
+    tauri::async_runtime::spawn_blocking(move || transport.lock().unwrap().transact(unit_id, &request))
+        .await
+        .map_err(|e| format!("Modbus 请求执行失败: {}", e))?
+}
+
+// 按寄存器映射周期轮询，每轮结果以 modbus-poll 事件推送
+#[tauri::command]
+pub fn modbus_poll_start(
+    app: AppHandle,
+    state: State<'_, ModbusState>,
+    session: String,
+    entries: Vec<RegisterMapEntry>,
+    interval_ms: u64,
+    max_gap: Option<usize>,
+) -> Result<(), String> {
+    if entries.is_empty() {
+        return Err("寄存器映射为空".to_string());
+    }
+    let transport = state.get_session(&session)?;
+    let blocks = plan_poll_blocks(&entries, max_gap.unwrap_or(0));
+
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let mut pollers = state.pollers.lock().unwrap();
+        if pollers.contains_key(&session) {
+            return Err(format!("会话 {} 已在轮询中", session));
+        }
+        pollers.insert(session.clone(), stop.clone());
+    }
+
+    let interval = Duration::from_millis(interval_ms.max(10));
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut values: Vec<ModbusPollValue> = entries
+            .iter()
+            .map(|e| ModbusPollValue {
+                name: e.name.clone(),
+                value: None,
+                raw: vec![],
+                error: None,
+            })
+            .collect();
+
+        let mut cycle = 0u64;
+        while !stop.load(Ordering::SeqCst) {
+            let started = Instant::now();
+            for block in &blocks {
+                if stop.load(Ordering::SeqCst) {
+                    return;
+                }
+                poll_block(&transport, block, &entries, &mut values);
+            }
+
+            let _ = app.emit(
+                MODBUS_POLL_EVENT,
+                ModbusPollEvent {
+                    session: session.clone(),
+                    cycle,
+                    timestamp_us: util_timestamp_us(),
+                    values: values.clone(),
+                },
+            );
+            cycle += 1;
+
+            // 等待到下一个周期，期间响应停止请求
+            while started.elapsed() < interval && !stop.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(10).min(interval - started.elapsed().min(interval)));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// 停止轮询
+#[tauri::command]
+pub fn modbus_poll_stop(state: State<'_, ModbusState>, session: &str) -> Result<(), String> {
+    if state.stop_poller(session) {
+        Ok(())
+    } else {
+        Err(format!("会话 {} 未在轮询", session))
+    }
+}
+
+// 列出当前会话
+#[tauri::command]
+pub fn modbus_list_sessions(state: State<'_, ModbusState>) -> Vec<String> {
+    state.sessions.lock().unwrap().keys().cloned().collect()
+}
+
+// 用 pty 对连接主站与从站循环，无需串口硬件
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serialport::TTYPort;
+
+    fn run_master_slave(mode: ModbusSerialMode) {
+        let (master_port, slave_port) = TTYPort::pair().expect("创建 pty 失败");
+        let (frame_gap_us, _) = modbus_rtu_timing_us(115200, 8, false, 1);
+        let model = Arc::new(Mutex::new(ModbusDataModel::with_sizes(16, 8, 16, 8)));
+        model.lock().unwrap().input_registers[2] = 0x1234;
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let slave = {
+            let (model, stop) = (model.clone(), stop.clone());
+            std::thread::spawn(move || {
+                modbus_serial_slave_serve(Box::new(slave_port), mode, 7, frame_gap_us, model, stop)
+            })
+        };
+        let mut master =
+            ModbusSerialMaster::new(Box::new(master_port), mode, frame_gap_us, Duration::from_secs(1)).unwrap();
+
+        let write = ModbusRequest::WriteMultipleRegisters { address: 3, values: vec![0xBEEF, 42] };
+        assert_eq!(
+            master.transact(7, &write).unwrap(),
+            ModbusResponse::WriteMultiple { address: 3, quantity: 2 }
+        );
+        let read = ModbusRequest::ReadHoldingRegisters { address: 2, quantity: 3 };
+        assert_eq!(
+            master.transact(7, &read).unwrap(),
+            ModbusResponse::Registers { values: vec![0, 0xBEEF, 42] }
+        );
+        let coil = ModbusRequest::WriteSingleCoil { address: 9, value: true };
+        master.transact(7, &coil).unwrap();
+        assert_eq!(
+            master.transact(7, &ModbusRequest::ReadCoils { address: 8, quantity: 3 }).unwrap(),
+            ModbusResponse::Bits { values: vec![false, true, false] }
+        );
+        assert_eq!(
+            master.transact(7, &ModbusRequest::ReadInputRegisters { address: 2, quantity: 1 }).unwrap(),
+            ModbusResponse::Registers { values: vec![0x1234] }
+        );
+
+        // 越界地址返回异常码，其他从站地址不应答
+        let out_of_range = ModbusRequest::ReadHoldingRegisters { address: 15, quantity: 2 };
+        assert_eq!(
+            master.transact(7, &out_of_range).unwrap(),
+            ModbusResponse::Exception { function: 0x03, code: 0x02 }
+        );
+        master.response_timeout = Duration::from_millis(200);
+        assert!(master.transact(8, &read).unwrap_err().contains("超时"));
+
+        // 广播写入执行但不应答
+        let broadcast = ModbusRequest::WriteSingleRegister { address: 0, value: 5 };
+        master.transact(0, &broadcast).unwrap();
+        assert_eq!(model.lock().unwrap().holding_registers[0], 5);
+        // 广播读请求在发送前就被拒绝，不等待从站处理时间
+        let started = Instant::now();
+        assert!(master.transact(0, &read).unwrap_err().contains("只能用于写操作"));
+        assert!(started.elapsed() < master.turnaround_delay);
+        assert_eq!(
+            master.transact(7, &read).unwrap(),
+            ModbusResponse::Registers { values: vec![0, 0xBEEF, 42] }
+        );
+
+        stop.store(true, Ordering::SeqCst);
+        slave.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn rtu_master_slave_over_pty() {
+        run_master_slave(ModbusSerialMode::Rtu);
+    }
+
+    #[test]
+    fn ascii_master_slave_over_pty() {
+        run_master_slave(ModbusSerialMode::Ascii);
+    }
+}
@@ -0,0 +1,406 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_log::log;
+
+use crate::functions::fun_modbus::ModbusDataModel;
+use crate::plugins::plugin_modbus::{modbus_serial_slave_serve, ModbusSerialConfig, ModbusTable};
+use crate::plugins::plugin_modbus_tcp::modbus_tcp_serve;
+use crate::plugins::plugin_store::get_store;
+
+// 从站模拟器数据在 Store 中的键名
+pub const MODBUS_SIMULATOR_STORE_KEY: &str = "modbus_simulator";
+// 模拟器异常退出事件名
+pub const MODBUS_SIMULATOR_STOPPED_EVENT: &str = "modbus-simulator-stopped";
+// 运行期间检查主站写入并保存数据区的间隔
+const MODBUS_SIMULATOR_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 模拟器传输方式
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ModbusSimulatorTransport {
+    Tcp {
+        host: Option<String>, // 默认 127.0.0.1
+        port: u16,            // 0 表示自动分配
+    },
+    Serial(ModbusSerialConfig),
+}
+
+/// 模拟器启动参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModbusSimulatorConfig {
+    pub name: String,
+    pub transport: ModbusSimulatorTransport,
+    pub unit_id: Option<u8>, // TCP 默认响应任意单元号，串口默认 1
+    // 各数据区大小，默认 1000
+    pub coils: Option<usize>,
+    pub discrete_inputs: Option<usize>,
+    pub holding_registers: Option<usize>,
+    pub input_registers: Option<usize>,
+}
+
+/// 运行中的模拟器信息
+#[derive(Debug, Clone, Serialize)]
+pub struct ModbusSimulatorInfo {
+    pub name: String,
+    pub endpoint: String, // TCP 为实际监听地址，串口为串口名
+}
+
+/// 模拟器异常退出事件
+#[derive(Debug, Clone, Serialize)]
+pub struct ModbusSimulatorStoppedEvent {
+    pub name: String,
+    pub reason: String,
+}
+
+// 运行中的模拟器
+struct SimulatorInstance {
+    endpoint: String,
+    model: Arc<Mutex<ModbusDataModel>>,
+    stop: Arc<AtomicBool>,
+}
+
+/// 从站模拟器状态（通过 Tauri manage 注册）
+#[derive(Default)]
+pub struct ModbusSimulatorState {
+    simulators: Mutex<HashMap<String, SimulatorInstance>>,
+}
+
+impl ModbusSimulatorState {
+    fn get_model(&self, name: &str) -> Result<Arc<Mutex<ModbusDataModel>>, String> {
+        self.simulators
+            .lock()
+            .unwrap()
+            .get(name)
+            .map(|s| s.model.clone())
+            .ok_or(format!("模拟器 {} 未运行", name))
+    }
+
+    // 移除模拟器；stop 为 Some 时仅在是同一次启动的实例时移除，防止旧线程误删重新启动的同名模拟器
+    fn remove(&self, name: &str, stop: Option<&Arc<AtomicBool>>) -> Option<SimulatorInstance> {
+        let mut simulators = self.simulators.lock().unwrap();
+        match simulators.get(name) {
+            Some(instance) if stop.is_none_or(|s| Arc::ptr_eq(s, &instance.stop)) => {
+                instance.stop.store(true, Ordering::SeqCst);
+                simulators.remove(name)
+            }
+            _ => None,
+        }
+    }
+}
+
+type SimulatorServe = Box<dyn FnOnce() -> Result<(), String> + Send>;
+
+// 读取保存的数据区，并按配置调整各区大小
+fn load_simulator_model(app: &AppHandle, config: &ModbusSimulatorConfig) -> ModbusDataModel {
+    let mut model: ModbusDataModel = get_store(app.clone())
+        .get(MODBUS_SIMULATOR_STORE_KEY)
+        .and_then(|all| all.get(&config.name).cloned())
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default();
+
+    model.coils.resize(config.coils.unwrap_or(1000), false);
+    model.discrete_inputs.resize(config.discrete_inputs.unwrap_or(1000), false);
+    model.holding_registers.resize(config.holding_registers.unwrap_or(1000), 0);
+    model.input_registers.resize(config.input_registers.unwrap_or(1000), 0);
+    model
+}
+
+// 保存数据区到 Store
+fn save_simulator_model(app: &AppHandle, name: &str, model: &ModbusDataModel) -> Result<(), String> {
+    let store = get_store(app.clone());
+    let mut all = store
+        .get(MODBUS_SIMULATOR_STORE_KEY)
+        .filter(|v| v.is_object())
+        .unwrap_or(json!({}));
+    all[name] = serde_json::to_value(model).map_err(|e| format!("序列化模拟器数据失败: {}", e))?;
+    store.set(MODBUS_SIMULATOR_STORE_KEY.to_string(), all);
+    Ok(())
+}
+
+// 打开监听端口或串口，返回实际地址（或串口名）与从站服务循环
+fn simulator_bind(
+    config: &ModbusSimulatorConfig,
+    model: Arc<Mutex<ModbusDataModel>>,
+    stop: Arc<AtomicBool>,
+) -> Result<(String, SimulatorServe), String> {
+    match &config.transport {
+        ModbusSimulatorTransport::Tcp { host, port } => {
+            let address = format!("{}:{}", host.as_deref().unwrap_or("127.0.0.1"), port);
+            let listener = TcpListener::bind(&address).map_err(|e| format!("监听 {} 失败: {}", address, e))?;
+            let endpoint = listener
+                .local_addr()
+                .map(|a| a.to_string())
+                .unwrap_or(address);
+            let unit_id = config.unit_id;
+            Ok((endpoint, Box::new(move || modbus_tcp_serve(listener, unit_id, model, stop))))
+        }
+        ModbusSimulatorTransport::Serial(serial) => {
+            let port = serial.serial.open()?;
+            let (mode, frame_gap_us, unit_id) = (serial.mode, serial.frame_gap_us(), config.unit_id.unwrap_or(1));
+            Ok((
+                serial.serial.port_name.clone(),
+                Box::new(move || modbus_serial_slave_serve(port, mode, unit_id, frame_gap_us, model, stop)),
+            ))
+        }
+    }
+}
+
+// 运行期间定期检查数据区，主站写入（FC05/06/15/16/23）后自动保存；停止时的最终保存由调用方负责
+fn simulator_autosave(app: &AppHandle, name: &str, model: &Mutex<ModbusDataModel>, stop: &AtomicBool) {
+    let mut saved = model.lock().unwrap().clone();
+    loop {
+        std::thread::sleep(MODBUS_SIMULATOR_SAVE_INTERVAL);
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+        let current = model.lock().unwrap().clone();
+        if current != saved {
+            if let Err(e) = save_simulator_model(app, name, &current) {
+                log::warn!("模拟器 {} 保存数据区失败: {}", name, e);
+            }
+            saved = current;
+        }
+    }
+}
+
+// 启动从站模拟器，返回实际监听地址（或串口名）
+#[tauri::command]
+pub fn modbus_simulator_start(
+    app: AppHandle,
+    state: State<'_, ModbusSimulatorState>,
+    config: ModbusSimulatorConfig,
+) -> Result<String, String> {
+    // 提前检查以免重复打开端口，真正的占位在下方完成
+    if state.simulators.lock().unwrap().contains_key(&config.name) {
+        return Err(format!("模拟器 {} 已在运行", config.name));
+    }
+
+    let model = Arc::new(Mutex::new(load_simulator_model(&app, &config)));
+    let stop = Arc::new(AtomicBool::new(false));
+    let (endpoint, serve) = simulator_bind(&config, model.clone(), stop.clone())?;
+
+    // 检查与插入在同一次加锁中完成；重名时 serve 随之丢弃，端口 / 串口被关闭
+    match state.simulators.lock().unwrap().entry(config.name.clone()) {
+        Entry::Occupied(_) => return Err(format!("模拟器 {} 已在运行", config.name)),
+        Entry::Vacant(slot) => {
+            slot.insert(SimulatorInstance {
+                endpoint: endpoint.clone(),
+                model: model.clone(),
+                stop: stop.clone(),
+            });
+        }
+    }
+
+    {
+        let (app, name, model, stop) = (app.clone(), config.name.clone(), model.clone(), stop.clone());
+        tauri::async_runtime::spawn_blocking(move || simulator_autosave(&app, &name, &model, &stop));
+    }
+
+    let name = config.name.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(reason) = serve() {
+            // 异常退出时移除实例以便重新启动，并保存已写入的数据
+            if app.state::<ModbusSimulatorState>().remove(&name, Some(&stop)).is_some() {
+                let model = model.lock().unwrap().clone();
+                if let Err(e) = save_simulator_model(&app, &name, &model) {
+                    log::warn!("模拟器 {} 保存数据区失败: {}", name, e);
+                }
+            }
+            let _ = app.emit(MODBUS_SIMULATOR_STOPPED_EVENT, ModbusSimulatorStoppedEvent { name, reason });
+        }
+    });
+
+    Ok(endpoint)
+}
+
+// 停止模拟器并保存数据区
+#[tauri::command]
+pub fn modbus_simulator_stop(app: AppHandle, state: State<'_, ModbusSimulatorState>, name: &str) -> Result<(), String> {
+    let instance = state.remove(name, None).ok_or(format!("模拟器 {} 未运行", name))?;
+    let model = instance.model.lock().unwrap().clone();
+    save_simulator_model(&app, name, &model)
+}
+
+// 获取模拟器当前数据区
+#[tauri::command]
+pub fn modbus_simulator_get(state: State<'_, ModbusSimulatorState>, name: &str) -> Result<ModbusDataModel, String> {
+    Ok(state.get_model(name)?.lock().unwrap().clone())
+}
+
+// 修改模拟器数据区（线圈 / 离散输入以非 0 为 true），修改后立即保存
+#[tauri::command]
+pub fn modbus_simulator_write(
+    app: AppHandle,
+    state: State<'_, ModbusSimulatorState>,
+    name: &str,
+    table: ModbusTable,
+    address: u16,
+    values: Vec<u16>,
+) -> Result<(), String> {
+    let model = state.get_model(name)?;
+    let mut model = model.lock().unwrap();
+
+    let start = address as usize;
+    let end = start + values.len();
+    let table_len = match table {
+        ModbusTable::Coil => model.coils.len(),
+        ModbusTable::DiscreteInput => model.discrete_inputs.len(),
+        ModbusTable::HoldingRegister => model.holding_registers.len(),
+        ModbusTable::InputRegister => model.input_registers.len(),
+    };
+    if end > table_len {
+        return Err(format!("地址越界: {} ~ {}，数据区大小 {}", start, end, table_len));
+    }
+
+    match table {
+        ModbusTable::Coil => model.coils[start..end].iter_mut().zip(&values).for_each(|(d, v)| *d = *v != 0),
+        ModbusTable::DiscreteInput => model.discrete_inputs[start..end]
+            .iter_mut()
+            .zip(&values)
+            .for_each(|(d, v)| *d = *v != 0),
+        ModbusTable::HoldingRegister => model.holding_registers[start..end].copy_from_slice(&values),
+        ModbusTable::InputRegister => model.input_registers[start..end].copy_from_slice(&values),
+    }
+    save_simulator_model(&app, name, &model)
+}
+
+// 列出运行中的模拟器
+#[tauri::command]
+pub fn modbus_simulator_list(state: State<'_, ModbusSimulatorState>) -> Vec<ModbusSimulatorInfo> {
+    state
+        .simulators
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(name, s)| ModbusSimulatorInfo {
+            name: name.clone(),
+            endpoint: s.endpoint.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::fun_modbus::{ModbusRequest, ModbusResponse};
+    use crate::plugins::plugin_modbus::ModbusTransport;
+    use crate::plugins::plugin_modbus_tcp::ModbusTcpClient;
+
+    #[test]
+    fn tcp_client_reads_and_writes_simulator() {
+        let config = ModbusSimulatorConfig {
+            name: "test".to_string(),
+            transport: ModbusSimulatorTransport::Tcp { host: None, port: 0 },
+            unit_id: Some(1),
+            coils: Some(32),
+            discrete_inputs: Some(8),
+            holding_registers: Some(64),
+            input_registers: Some(8),
+        };
+        let mut data = ModbusDataModel::with_sizes(32, 8, 64, 8);
+        data.input_registers[0] = 0x0102;
+        data.discrete_inputs[3] = true;
+        let model = Arc::new(Mutex::new(data));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (endpoint, serve) = simulator_bind(&config, model.clone(), stop.clone()).unwrap();
+        assert!(endpoint.starts_with("127.0.0.1:") && !endpoint.ends_with(":0"));
+        let server = std::thread::spawn(serve);
+
+        let mut client = ModbusTcpClient::connect(&endpoint, Duration::from_secs(1)).unwrap();
+        let requests = [
+            (
+                ModbusRequest::WriteSingleRegister { address: 10, value: 0xABCD },
+                ModbusResponse::WriteSingle { address: 10, value: 0xABCD },
+            ),
+            (
+                ModbusRequest::WriteMultipleRegisters { address: 11, values: vec![1, 2, 3] },
+                ModbusResponse::WriteMultiple { address: 11, quantity: 3 },
+            ),
+            (
+                ModbusRequest::ReadHoldingRegisters { address: 10, quantity: 4 },
+                ModbusResponse::Registers { values: vec![0xABCD, 1, 2, 3] },
+            ),
+            (
+                ModbusRequest::WriteMultipleCoils { address: 4, values: vec![true, false, true] },
+                ModbusResponse::WriteMultiple { address: 4, quantity: 3 },
+            ),
+            (
+                ModbusRequest::ReadCoils { address: 3, quantity: 5 },
+                ModbusResponse::Bits { values: vec![false, true, false, true, false] },
+            ),
+            (
+                ModbusRequest::ReadDiscreteInputs { address: 2, quantity: 2 },
+                ModbusResponse::Bits { values: vec![false, true] },
+            ),
+            (
+                ModbusRequest::ReadInputRegisters { address: 0, quantity: 1 },
+                ModbusResponse::Registers { values: vec![0x0102] },
+            ),
+            (
+                ModbusRequest::ReadHoldingRegisters { address: 63, quantity: 2 },
+                ModbusResponse::Exception { function: 0x03, code: 0x02 },
+            ),
+        ];
+        for (request, expected) in requests {
+            assert_eq!(client.transact(1, &request).unwrap(), expected, "{:?}", request);
+        }
+        // 单元号不匹配时按网关返回“目标设备无响应”
+        assert_eq!(
+            client.transact(9, &ModbusRequest::ReadCoils { address: 0, quantity: 1 }).unwrap(),
+            ModbusResponse::Exception { function: 0x01, code: 0x0B }
+        );
+
+        // 主站写入直接反映到数据区（自动保存据此判断是否需要保存）
+        let written = model.lock().unwrap().clone();
+        assert_eq!(written.holding_registers[10..14], [0xABCD, 1, 2, 3]);
+        assert_ne!(written, ModbusDataModel::with_sizes(32, 8, 64, 8));
+
+        stop.store(true, Ordering::SeqCst);
+        server.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn bind_failure_reports_address() {
+        let occupied = TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = ModbusSimulatorConfig {
+            name: "busy".to_string(),
+            transport: ModbusSimulatorTransport::Tcp {
+                host: None,
+                port: occupied.local_addr().unwrap().port(),
+            },
+            unit_id: None,
+            coils: None,
+            discrete_inputs: None,
+            holding_registers: None,
+            input_registers: None,
+        };
+        let model = Arc::new(Mutex::new(ModbusDataModel::default()));
+        let error = simulator_bind(&config, model, Arc::new(AtomicBool::new(false))).err().unwrap();
+        assert!(error.contains("监听"));
+    }
+
+    #[test]
+    fn remove_ignores_restarted_instance() {
+        let state = ModbusSimulatorState::default();
+        let (old_stop, new_stop) = (Arc::new(AtomicBool::new(false)), Arc::new(AtomicBool::new(false)));
+        state.simulators.lock().unwrap().insert(
+            "sim".to_string(),
+            SimulatorInstance {
+                endpoint: String::new(),
+                model: Arc::new(Mutex::new(ModbusDataModel::default())),
+                stop: new_stop.clone(),
+            },
+        );
+        assert!(state.remove("sim", Some(&old_stop)).is_none());
+        assert!(state.remove("sim", Some(&new_stop)).is_some());
+        assert!(new_stop.load(Ordering::SeqCst));
+        assert!(state.get_model("sim").is_err());
+    }
+}
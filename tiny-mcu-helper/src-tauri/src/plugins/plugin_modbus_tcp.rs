@@ -0,0 +1,216 @@
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use crate::functions::fun_modbus::{
+    decode_mbap_header, encode_tcp_frame, MbapHeader, ModbusDataModel, ModbusRequest, ModbusResponse,
+    EX_GATEWAY_TARGET_FAILED, MBAP_HEADER_LEN,
+};
+use crate::plugins::plugin_modbus::{ModbusState, ModbusTransport};
+
+// 从接收缓冲区中取出一个完整的 TCP 帧，数据不足时返回 None
+fn take_tcp_frame(buffer: &mut Vec<u8>) -> Result<Option<(MbapHeader, Vec<u8>)>, String> {
+    if buffer.len() < MBAP_HEADER_LEN {
+        return Ok(None);
+    }
+    let header: [u8; MBAP_HEADER_LEN] = buffer[..MBAP_HEADER_LEN].try_into().unwrap();
+    let header = decode_mbap_header(&header)?;
+    let frame_len = MBAP_HEADER_LEN + header.pdu_len;
+    if buffer.len() < frame_len {
+        return Ok(None);
+    }
+    let pdu = buffer[MBAP_HEADER_LEN..frame_len].to_vec();
+    buffer.drain(..frame_len);
+    Ok(Some((header, pdu)))
+}
+
+fn is_timeout(e: &std::io::Error) -> bool {
+    matches!(e.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock | ErrorKind::Interrupted)
+}
+
+/// Modbus TCP 客户端
+pub struct ModbusTcpClient {
+    stream: TcpStream,
+    transaction_id: u16,
+    response_timeout: Duration,
+    buffer: Vec<u8>,
+}
+
+impl ModbusTcpClient {
+    pub fn connect(address: &str, response_timeout: Duration) -> Result<Self, String> {
+        let socket_addr = address
+            .to_socket_addrs()
+            .map_err(|e| format!("解析地址 {} 失败: {}", address, e))?
+            .next()
+            .ok_or(format!("解析地址 {} 失败", address))?;
+        let stream = TcpStream::connect_timeout(&socket_addr, response_timeout)
+            .map_err(|e| format!("连接 {} 失败: {}", address, e))?;
+        // 读超时取较短值，由 transact 按总超时判断
+        stream
+            .set_read_timeout(Some(Duration::from_millis(20)))
+            .and_then(|_| stream.set_nodelay(true))
+            .map_err(|e| format!("设置连接参数失败: {}", e))?;
+        Ok(Self {
+            stream,
+            transaction_id: 0,
+            response_timeout,
+            buffer: Vec::new(),
+        })
+    }
+
+    // MBAP 头部无效时已无法确定下一帧的边界，清空缓冲区，避免后续请求都卡在同一段数据上
+    fn take_frame(&mut self) -> Result<Option<(MbapHeader, Vec<u8>)>, String> {
+        take_tcp_frame(&mut self.buffer).inspect_err(|_| self.buffer.clear())
+    }
+}
+
+impl ModbusTransport for ModbusTcpClient {
+    fn transact(&mut self, unit_id: u8, request: &ModbusRequest) -> Result<ModbusResponse, String> {
+        let pdu = request.encode()?;
+        self.transaction_id = self.transaction_id.wrapping_add(1);
+        let transaction_id = self.transaction_id;
+
+        self.stream
+            .write_all(&encode_tcp_frame(transaction_id, unit_id, &pdu))
+            .map_err(|e| format!("发送 Modbus 帧失败: {}", e))?;
+
+        let deadline = Instant::now() + self.response_timeout;
+        let mut chunk = [0u8; 512];
+        loop {
+            while let Some((header, response_pdu)) = self.take_frame()? {
+                // 丢弃之前超时请求的迟到响应
+                if header.transaction_id != transaction_id {
+                    continue;
+                }
+                if header.unit_id != unit_id {
+                    return Err(format!("响应单元号不匹配: 期望 {}，实际 {}", unit_id, header.unit_id));
+                }
+                return request.decode_response(&response_pdu);
+            }
+            if Instant::now() >= deadline {
+                return Err(format!("单元 {} 响应超时", unit_id));
+            }
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err("连接已被服务器关闭".to_string()),
+                Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                Err(e) if is_timeout(&e) => {}
+                Err(e) => return Err(format!("读取 Modbus 响应失败: {}", e)),
+            }
+        }
+    }
+}
+
+// 处理单个客户端连接，直到对方断开或 stop 被置位
+fn modbus_tcp_handle_connection(
+    mut stream: TcpStream,
+    unit_id: Option<u8>,
+    model: Arc<Mutex<ModbusDataModel>>,
+    stop: Arc<AtomicBool>,
+) -> Result<(), String> {
+    stream
+        .set_read_timeout(Some(Duration::from_millis(50)))
+        .and_then(|_| stream.set_nodelay(true))
+        .map_err(|e| format!("设置连接参数失败: {}", e))?;
+
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 512];
+    while !stop.load(Ordering::SeqCst) {
+        match stream.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+            Err(e) if is_timeout(&e) => continue,
+            Err(e) => return Err(format!("读取请求失败: {}", e)),
+        }
+
+        while let Some((header, pdu)) = take_tcp_frame(&mut buffer)? {
+            // 单元号不匹配时按网关行为返回“目标设备无响应”（0 与 0xFF 视为本机）
+            let response = match unit_id {
+                Some(id) if header.unit_id != id && header.unit_id != 0 && header.unit_id != 0xFF => {
+                    vec![pdu[0] | 0x80, EX_GATEWAY_TARGET_FAILED]
+                }
+                _ => model.lock().unwrap().process_pdu(&pdu),
+            };
+            stream
+                .write_all(&encode_tcp_frame(header.transaction_id, header.unit_id, &response))
+                .map_err(|e| format!("发送响应失败: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// TCP 从站服务：接受连接并用 model 应答，直到 stop 被置位
+/// unit_id 为 None 时响应任意单元号
+pub fn modbus_tcp_serve(
+    listener: TcpListener,
+    unit_id: Option<u8>,
+    model: Arc<Mutex<ModbusDataModel>>,
+    stop: Arc<AtomicBool>,
+) -> Result<(), String> {
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("设置监听参数失败: {}", e))?;
+
+    while !stop.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let _ = stream.set_nonblocking(false);
+                let (model, stop) = (model.clone(), stop.clone());
+                std::thread::spawn(move || modbus_tcp_handle_connection(stream, unit_id, model, stop));
+            }
+            Err(e) if is_timeout(&e) => std::thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(format!("接受连接失败: {}", e)),
+        }
+    }
+    Ok(())
+}
+
+// 连接 Modbus TCP 服务器，会话名为 host:port
+#[tauri::command]
+pub fn modbus_tcp_open(
+    state: State<'_, ModbusState>,
+    host: &str,
+    port: Option<u16>, // 默认 502
+    response_timeout_ms: Option<u64>,
+) -> Result<String, String> {
+    let session = format!("{}:{}", host, port.unwrap_or(502));
+    let client = ModbusTcpClient::connect(
+        &session,
+        Duration::from_millis(response_timeout_ms.unwrap_or(1000)),
+    )?;
+    state.add_session(&session, Box::new(client))?;
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_header_does_not_poison_session() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let model = Arc::new(Mutex::new(ModbusDataModel::with_sizes(8, 8, 8, 8)));
+        let stop = Arc::new(AtomicBool::new(false));
+        let server = {
+            let (model, stop) = (model.clone(), stop.clone());
+            std::thread::spawn(move || modbus_tcp_serve(listener, None, model, stop))
+        };
+
+        let mut client = ModbusTcpClient::connect(&address, Duration::from_secs(1)).unwrap();
+        let request = ModbusRequest::ReadHoldingRegisters { address: 0, quantity: 2 };
+        // 协议号非 0 的残留数据
+        client.buffer.extend_from_slice(&[0x00, 0x01, 0x12, 0x34, 0x00, 0x03, 0x01, 0x03, 0x00]);
+        assert!(client.transact(1, &request).is_err());
+        assert!(client.buffer.is_empty());
+        assert_eq!(
+            client.transact(1, &request).unwrap(),
+            ModbusResponse::Registers { values: vec![0, 0] }
+        );
+
+        stop.store(true, Ordering::SeqCst);
+        server.join().unwrap().unwrap();
+    }
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use crate::db::{
+    db_register_map_delete, db_register_map_get_model, db_register_map_list, db_register_map_save, DbState,
+    RegisterMapRecord,
+};
+use crate::functions::fun_register_codegen::{register_codegen, RegisterCodegenConfig};
+use crate::functions::fun_svd::SvdDevice;
+use crate::plugins::plugin_svd::SvdState;
+use crate::utils::util_write_generate_file;
+
+/// 代码生成的数据来源
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RegisterSource {
+    Svd { device_id: i64 },  // 已加载的 SVD 器件
+    Map { map_id: i64 },     // 用户自建的寄存器映射
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisterCodegenResult {
+    pub content: String,
+    pub output_path: Option<String>,
+}
+
+async fn register_map_load(app: &AppHandle, map_id: i64) -> Result<SvdDevice, String> {
+    let model = db_register_map_get_model(&app.state::<DbState>().pool, map_id)
+        .await?
+        .ok_or(format!("寄存器映射 {} 不存在", map_id))?;
+    serde_json::from_str(&model).map_err(|e| format!("读取寄存器映射失败: {}", e))
+}
+
+// 列出用户寄存器映射
+#[tauri::command]
+pub async fn register_map_list(app: AppHandle) -> Result<Vec<RegisterMapRecord>, String> {
+    db_register_map_list(&app.state::<DbState>().pool).await
+}
+
+// 获取寄存器映射的完整模型
+#[tauri::command]
+pub async fn register_map_get(app: AppHandle, map_id: i64) -> Result<SvdDevice, String> {
+    register_map_load(&app, map_id).await
+}
+
+// 保存寄存器映射（map_id 为空时新建），返回映射 id
+#[tauri::command]
+pub async fn register_map_save(app: AppHandle, map_id: Option<i64>, mut device: SvdDevice) -> Result<i64, String> {
+    device.normalize()?;
+    let model = serde_json::to_string(&device).map_err(|e| format!("序列化寄存器映射失败: {}", e))?;
+    db_register_map_save(
+        &app.state::<DbState>().pool,
+        map_id,
+        &device.name,
+        device.description.as_deref(),
+        &model,
+    )
+    .await
+}
+
+// 删除寄存器映射
+#[tauri::command]
+pub async fn register_map_delete(app: AppHandle, map_id: i64) -> Result<(), String> {
+    db_register_map_delete(&app.state::<DbState>().pool, map_id).await
+}
+
+// 以 SVD 器件（可只取部分外设）为模板创建寄存器映射
+#[tauri::command]
+pub async fn register_map_from_svd(
+    app: AppHandle,
+    device_id: i64,
+    name: String,
+    peripherals: Option<Vec<String>>,
+) -> Result<i64, String> {
+    let source = app.state::<SvdState>().get_device(&app, device_id).await?;
+    let mut device = SvdDevice::clone(&source);
+    device.name = name;
+    if let Some(names) = peripherals.filter(|names| !names.is_empty()) {
+        device
+            .peripherals
+            .retain(|p| names.iter().any(|n| n.eq_ignore_ascii_case(&p.name)));
+    }
+    register_map_save(app, None, device).await
+}
+
+// 生成 C 头文件或 Rust 模块，填写 file_name 时保存到 generate 目录
+#[tauri::command]
+pub async fn register_codegen_generate(
+    app: AppHandle,
+    source: RegisterSource,
+    config: RegisterCodegenConfig,
+    file_name: Option<String>,
+) -> Result<RegisterCodegenResult, String> {
+    let device = match source {
+        RegisterSource::Svd { device_id } => app.state::<SvdState>().get_device(&app, device_id).await?,
+        RegisterSource::Map { map_id } => Arc::new(register_map_load(&app, map_id).await?),
+    };
+    let content = register_codegen(&device, &config)?;
+    let output_path = match file_name {
+        Some(file_name) => Some(util_write_generate_file(&file_name, config.style.extension(), &content)?),
+        None => None,
+    };
+    Ok(RegisterCodegenResult { content, output_path })
+}
@@ -0,0 +1,539 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::plugins::plugin_store::get_store;
+use crate::utils::{
+    decrypt_bytes, decrypt_data, encrypt_bytes, encrypt_data, migrate_legacy_data, util_crypto_derive_key,
+    util_crypto_install_key, util_crypto_is_legacy, util_crypto_new_salt, CryptoKey, CryptoKeyKind, CRYPTO_SALT_LEN,
+};
+
+// Store 中保存密文的键，值为 { 名称: Base64 密文 }
+pub(crate) const SECRET_ENTRIES_KEY: &str = "secrets";
+// Store 中保存密钥信息的键（密钥类型、口令盐、校验密文）
+pub(crate) const SECRET_VAULT_KEY: &str = "secret_vault";
+// 解锁时解密该常量以校验口令
+const SECRET_VAULT_CHECK: &[u8] = b"tiny-mcu-helper secret vault";
+
+/// 密钥信息，保存在 Store 中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SecretVault {
+    mode: CryptoKeyKind,
+    salt: Option<String>, // 口令模式的盐（Base64）
+    check: String,        // SECRET_VAULT_CHECK 的密文（Base64）
+}
+
+/// 解锁状态：解锁后持有密钥，锁定时清除
+#[derive(Default)]
+pub struct SecretState {
+    key: Mutex<Option<CryptoKey>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretStatus {
+    pub initialized: bool,
+    pub mode: CryptoKeyKind,
+    pub unlocked: bool,
+    pub count: usize,
+}
+
+/// 机密存储的持久化接口：应用中为 Store + 应用数据目录，测试中可替换为内存实现
+pub trait SecretBackend {
+    fn load(&self, key: &str) -> Option<Value>;
+    fn save(&self, key: &str, value: Value);
+    fn install_key(&self) -> Result<CryptoKey, String>;
+}
+
+impl SecretBackend for AppHandle {
+    fn load(&self, key: &str) -> Option<Value> {
+        get_store(self.clone()).get(key)
+    }
+
+    fn save(&self, key: &str, value: Value) {
+        get_store(self.clone()).set(key.to_string(), value)
+    }
+
+    // 读取安装密钥（应用数据目录下的 secret.key）
+    fn install_key(&self) -> Result<CryptoKey, String> {
+        let dir = self.path().app_data_dir().map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+        util_crypto_install_key(&dir).map_err(|e| e.to_string())
+    }
+}
+
+fn load_vault<B: SecretBackend + ?Sized>(backend: &B) -> Option<SecretVault> {
+    backend.load(SECRET_VAULT_KEY).and_then(|v| serde_json::from_value(v).ok())
+}
+
+fn load_entries<B: SecretBackend + ?Sized>(backend: &B) -> Map<String, Value> {
+    match backend.load(SECRET_ENTRIES_KEY) {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+// 按模式生成新密钥及对应的密钥信息
+fn new_vault_key<B: SecretBackend + ?Sized>(backend: &B, passphrase: Option<&str>) -> Result<(CryptoKey, SecretVault), String> {
+    let (key, salt) = match passphrase {
+        Some(passphrase) => {
+            let salt = util_crypto_new_salt().map_err(|e| e.to_string())?;
+            let key = util_crypto_derive_key(passphrase, &salt).map_err(|e| e.to_string())?;
+            (key, Some(BASE64.encode(salt)))
+        }
+        None => (backend.install_key()?, None),
+    };
+    let check = encrypt_bytes(SECRET_VAULT_CHECK, &key).map_err(|e| e.to_string())?;
+    let vault = SecretVault {
+        mode: key.kind(),
+        salt,
+        check: BASE64.encode(check),
+    };
+    Ok((key, vault))
+}
+
+// 用 vault 中的盐派生口令密钥或读取安装密钥，并校验
+fn open_vault<B: SecretBackend + ?Sized>(backend: &B, vault: &SecretVault, passphrase: Option<&str>) -> Result<CryptoKey, String> {
+    let key = match vault.mode {
+        CryptoKeyKind::Install => backend.install_key()?,
+        CryptoKeyKind::Passphrase => {
+            let passphrase = passphrase.filter(|p| !p.is_empty()).ok_or("请输入口令")?;
+            let salt: [u8; CRYPTO_SALT_LEN] = vault
+                .salt
+                .as_deref()
+                .and_then(|s| BASE64.decode(s).ok())
+                .and_then(|s| s.try_into().ok())
+                .ok_or("密钥信息已损坏")?;
+            util_crypto_derive_key(passphrase, &salt).map_err(|e| e.to_string())?
+        }
+    };
+    let check = BASE64.decode(&vault.check).map_err(|_| "密钥信息已损坏")?;
+    match decrypt_bytes(&check, &key) {
+        Ok(plain) if plain == SECRET_VAULT_CHECK => Ok(key),
+        _ => Err(match vault.mode {
+            CryptoKeyKind::Install => "安装密钥与保存的数据不匹配".to_string(),
+            CryptoKeyKind::Passphrase => "口令错误".to_string(),
+        }),
+    }
+}
+
+// 旧版本保存的 XOR 数据：Base64 字符串或字节数组
+fn legacy_entry_bytes(value: &Value) -> Option<Vec<u8>> {
+    let bytes = match value {
+        Value::String(s) => BASE64.decode(s).ok()?,
+        Value::Array(items) => items
+            .iter()
+            .map(|v| v.as_u64().and_then(|b| u8::try_from(b).ok()))
+            .collect::<Option<Vec<u8>>>()?,
+        _ => return None,
+    };
+    util_crypto_is_legacy(&bytes).then_some(bytes)
+}
+
+// 用当前密钥重新加密旧版 XOR 数据，返回迁移的条数；调用方需持有 SecretState 的锁
+fn migrate_legacy_entries<B: SecretBackend + ?Sized>(backend: &B, key: &CryptoKey) -> Result<usize, String> {
+    let mut entries = load_entries(backend);
+    let mut migrated = 0;
+    for (name, value) in entries.iter_mut() {
+        if let Some(legacy) = legacy_entry_bytes(value) {
+            let encrypted = migrate_legacy_data(&legacy, key).map_err(|e| format!("迁移 {} 失败: {}", name, e))?;
+            *value = Value::String(BASE64.encode(encrypted));
+            migrated += 1;
+        }
+    }
+    if migrated > 0 {
+        backend.save(SECRET_ENTRIES_KEY, Value::Object(entries));
+    }
+    Ok(migrated)
+}
+
+/// 机密存储状态
+pub fn secret_vault_status<B: SecretBackend + ?Sized>(backend: &B, state: &SecretState) -> SecretStatus {
+    let vault = load_vault(backend);
+    SecretStatus {
+        initialized: vault.is_some(),
+        mode: vault.map(|v| v.mode).unwrap_or(CryptoKeyKind::Install),
+        unlocked: state.key.lock().unwrap().is_some(),
+        count: load_entries(backend).len(),
+    }
+}
+
+/// 初始化机密存储，已初始化时报错；初始化后处于解锁状态
+pub fn secret_vault_init<B: SecretBackend + ?Sized>(backend: &B, state: &SecretState, passphrase: Option<&str>) -> Result<(), String> {
+    // 持有锁完成检查与写入，避免重复初始化
+    let mut guard = state.key.lock().unwrap();
+    if load_vault(backend).is_some() {
+        return Err("机密存储已初始化".to_string());
+    }
+    let (key, vault) = new_vault_key(backend, passphrase.filter(|p| !p.is_empty()))?;
+    migrate_legacy_entries(backend, &key)?;
+    let value = serde_json::to_value(&vault).map_err(|e| format!("保存密钥信息失败: {}", e))?;
+    backend.save(SECRET_VAULT_KEY, value);
+    *guard = Some(key);
+    Ok(())
+}
+
+/// 校验口令并解锁，旧版 XOR 数据在解锁时用新密钥重新加密
+pub fn secret_vault_unlock<B: SecretBackend + ?Sized>(backend: &B, state: &SecretState, passphrase: Option<&str>) -> Result<(), String> {
+    // 迁移会整体改写机密条目，持有锁避免与 secret_value_set 等并发写入互相覆盖
+    let mut guard = state.key.lock().unwrap();
+    let vault = load_vault(backend).ok_or("机密存储尚未初始化")?;
+    let key = open_vault(backend, &vault, passphrase)?;
+    migrate_legacy_entries(backend, &key)?;
+    *guard = Some(key);
+    Ok(())
+}
+
+/// 设置或清除口令（passphrase 为空时改用安装密钥），已有数据用新密钥重新加密；口令模式下需校验当前口令
+pub fn secret_vault_set_passphrase<B: SecretBackend + ?Sized>(
+    backend: &B,
+    state: &SecretState,
+    current_passphrase: Option<&str>,
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    let mut guard = state.key.lock().unwrap();
+    let old_key = guard.as_ref().ok_or("机密存储已锁定")?;
+    let vault = load_vault(backend).ok_or("机密存储尚未初始化")?;
+    open_vault(backend, &vault, current_passphrase).map_err(|e| match vault.mode {
+        CryptoKeyKind::Passphrase => format!("当前{}", e),
+        CryptoKeyKind::Install => e,
+    })?;
+    let (new_key, vault) = new_vault_key(backend, passphrase.filter(|p| !p.is_empty()))?;
+
+    // 全部重新加密成功后再写回
+    let mut entries = load_entries(backend);
+    for (name, value) in entries.iter_mut() {
+        let encrypted = value
+            .as_str()
+            .and_then(|s| BASE64.decode(s).ok())
+            .ok_or(format!("机密数据 {} 已损坏", name))?;
+        let plain: Value = decrypt_data(&encrypted, old_key).map_err(|e| format!("解密 {} 失败: {}", name, e))?;
+        let encrypted = encrypt_data(&plain, &new_key).map_err(|e| format!("加密失败: {}", e))?;
+        *value = Value::String(BASE64.encode(encrypted));
+    }
+    let vault = serde_json::to_value(&vault).map_err(|e| format!("保存密钥信息失败: {}", e))?;
+    backend.save(SECRET_ENTRIES_KEY, Value::Object(entries));
+    backend.save(SECRET_VAULT_KEY, vault);
+    *guard = Some(new_key);
+    Ok(())
+}
+
+/// 加密保存一项机密数据（需已解锁）
+pub fn secret_value_set<B: SecretBackend + ?Sized>(backend: &B, state: &SecretState, name: &str, value: &Value) -> Result<(), String> {
+    let guard = state.key.lock().unwrap();
+    let key = guard.as_ref().ok_or("机密存储已锁定")?;
+    let encrypted = encrypt_data(value, key).map_err(|e| format!("加密失败: {}", e))?;
+    let mut entries = load_entries(backend);
+    entries.insert(name.to_string(), Value::String(BASE64.encode(encrypted)));
+    backend.save(SECRET_ENTRIES_KEY, Value::Object(entries));
+    Ok(())
+}
+
+/// 删除一项机密数据（需已解锁），返回是否存在
+pub fn secret_value_delete<B: SecretBackend + ?Sized>(backend: &B, state: &SecretState, name: &str) -> Result<bool, String> {
+    let guard = state.key.lock().unwrap();
+    guard.as_ref().ok_or("机密存储已锁定")?;
+    let mut entries = load_entries(backend);
+    let removed = entries.remove(name).is_some();
+    if removed {
+        backend.save(SECRET_ENTRIES_KEY, Value::Object(entries));
+    }
+    Ok(removed)
+}
+
+/// 读取并解密一项机密数据（需已解锁），不存在时返回 None
+pub fn secret_value_get<B: SecretBackend + ?Sized>(backend: &B, state: &SecretState, name: &str) -> Result<Option<Value>, String> {
+    let guard = state.key.lock().unwrap();
+    let key = guard.as_ref().ok_or("机密存储已锁定")?;
+    let encrypted = match load_entries(backend).remove(name) {
+        Some(Value::String(s)) => BASE64.decode(s).map_err(|_| format!("机密数据 {} 已损坏", name))?,
+        Some(_) => return Err(format!("机密数据 {} 已损坏", name)),
+        None => return Ok(None),
+    };
+    decrypt_data(&encrypted, key)
+        .map(Some)
+        .map_err(|e| format!("解密 {} 失败: {}", name, e))
+}
+
+/// 机密数据保存在机密存储、公开信息保存在 Store 的密钥列表（签名密钥、固件密钥等）
+pub struct SecretKeyRegistry {
+    pub store_key: &'static str, // Store 中保存公开信息的键，值为 { 名称: 公开信息 }
+    pub prefix: &'static str,    // 机密存储中条目的名称前缀
+    pub label: &'static str,     // 提示信息中的类型名称
+}
+
+impl SecretKeyRegistry {
+    fn secret_name(&self, name: &str) -> String {
+        format!("{}{}", self.prefix, name)
+    }
+
+    fn load_infos<B: SecretBackend + ?Sized>(&self, backend: &B) -> Map<String, Value> {
+        match backend.load(self.store_key) {
+            Some(Value::Object(map)) => map,
+            _ => Map::new(),
+        }
+    }
+
+    /// 按名称排序列出公开信息（锁定时也可查看）
+    pub fn list<T: DeserializeOwned, B: SecretBackend + ?Sized>(&self, backend: &B) -> Vec<T> {
+        let mut infos: Vec<(String, Value)> = self.load_infos(backend).into_iter().collect();
+        infos.sort_by(|a, b| a.0.cmp(&b.0));
+        infos.into_iter().filter_map(|(_, v)| serde_json::from_value(v).ok()).collect()
+    }
+
+    pub fn find<T: DeserializeOwned, B: SecretBackend + ?Sized>(&self, backend: &B, name: &str) -> Result<T, String> {
+        self.load_infos(backend)
+            .remove(name)
+            .and_then(|v| serde_json::from_value(v).ok())
+            .ok_or(format!("{} {} 不存在", self.label, name))
+    }
+
+    /// 新增一项（需已解锁）：机密数据写入机密存储，info 由去除首尾空白的名称生成公开信息
+    pub fn insert<T: Serialize, B: SecretBackend + ?Sized>(
+        &self,
+        backend: &B,
+        state: &SecretState,
+        name: &str,
+        secret: &Value,
+        info: impl FnOnce(&str) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("名称不能为空".to_string());
+        }
+        let mut infos = self.load_infos(backend);
+        if infos.contains_key(name) {
+            return Err(format!("{} {} 已存在", self.label, name));
+        }
+        let info = info(name)?;
+        let value = serde_json::to_value(&info).map_err(|e| format!("保存{}失败: {}", self.label, e))?;
+        secret_value_set(backend, state, &self.secret_name(name), secret)?;
+        infos.insert(name.to_string(), value);
+        backend.save(self.store_key, Value::Object(infos));
+        Ok(info)
+    }
+
+    /// 读取机密数据（需已解锁）
+    pub fn secret<B: SecretBackend + ?Sized>(&self, backend: &B, state: &SecretState, name: &str) -> Result<Value, String> {
+        secret_value_get(backend, state, &self.secret_name(name))?.ok_or(format!("{} {} 不存在", self.label, name))
+    }
+
+    /// 删除一项（需已解锁），返回公开信息是否存在
+    pub fn delete<B: SecretBackend + ?Sized>(&self, backend: &B, state: &SecretState, name: &str) -> Result<bool, String> {
+        // 先删除机密数据，锁定时不修改列表
+        secret_value_delete(backend, state, &self.secret_name(name))?;
+        let mut infos = self.load_infos(backend);
+        let removed = infos.remove(name).is_some();
+        if removed {
+            backend.save(self.store_key, Value::Object(infos));
+        }
+        Ok(removed)
+    }
+}
+
+// 查看机密存储状态
+#[tauri::command]
+pub fn secret_status(app: AppHandle, state: State<'_, SecretState>) -> SecretStatus {
+    secret_vault_status(&app, &state)
+}
+
+// 初始化机密存储：提供口令时使用口令保护，否则使用本机安装密钥；初始化后处于解锁状态
+#[tauri::command]
+pub async fn secret_init(app: AppHandle, passphrase: Option<String>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || secret_vault_init(&app, &app.state::<SecretState>(), passphrase.as_deref()))
+        .await
+        .map_err(|e| format!("初始化任务异常: {}", e))?
+}
+
+// 解锁机密存储，口令模式需要 passphrase；尚未初始化时需先调用 secret_init
+#[tauri::command]
+pub async fn secret_unlock(app: AppHandle, passphrase: Option<String>) -> Result<(), String> {
+    // Argon2 派生较慢，放到后台线程
+    tauri::async_runtime::spawn_blocking(move || secret_vault_unlock(&app, &app.state::<SecretState>(), passphrase.as_deref()))
+        .await
+        .map_err(|e| format!("解锁任务异常: {}", e))?
+}
+
+// 锁定机密存储，清除内存中的密钥
+#[tauri::command]
+pub fn secret_lock(state: State<'_, SecretState>) {
+    state.key.lock().unwrap().take();
+}
+
+// 设置或清除口令（passphrase 为空时改用安装密钥），已有数据用新密钥重新加密；
+// 口令模式下需提供当前口令 current_passphrase
+#[tauri::command]
+pub async fn secret_set_passphrase(
+    app: AppHandle,
+    current_passphrase: Option<String>,
+    passphrase: Option<String>,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        secret_vault_set_passphrase(
+            &app,
+            &app.state::<SecretState>(),
+            current_passphrase.as_deref(),
+            passphrase.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("修改口令任务异常: {}", e))?
+}
+
+// 加密保存机密数据
+#[tauri::command]
+pub fn store_set_secret(app: AppHandle, state: State<'_, SecretState>, key: &str, value: Value) -> Result<(), String> {
+    if key.trim().is_empty() {
+        return Err("名称不能为空".to_string());
+    }
+    secret_value_set(&app, &state, key, &value)
+}
+
+// 读取机密数据
+#[tauri::command]
+pub fn store_get_secret(app: AppHandle, state: State<'_, SecretState>, key: &str) -> Result<Option<Value>, String> {
+    secret_value_get(&app, &state, key)
+}
+
+// 列出机密数据名称（锁定时也可查看名称）
+#[tauri::command]
+pub fn store_list_secrets(app: AppHandle) -> Vec<String> {
+    let mut names: Vec<String> = load_entries(&app).into_iter().map(|(k, _)| k).collect();
+    names.sort();
+    names
+}
+
+// 删除机密数据（需已解锁）
+#[tauri::command]
+pub fn store_delete_secret(app: AppHandle, state: State<'_, SecretState>, key: &str) -> Result<bool, String> {
+    secret_value_delete(&app, &state, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // 内存中的 Store，安装密钥放在独立的临时目录
+    struct MemoryBackend {
+        values: Mutex<Map<String, Value>>,
+        dir: PathBuf,
+    }
+
+    impl MemoryBackend {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("tmh-secret-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            MemoryBackend { values: Mutex::new(Map::new()), dir }
+        }
+    }
+
+    impl Drop for MemoryBackend {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    impl SecretBackend for MemoryBackend {
+        fn load(&self, key: &str) -> Option<Value> {
+            self.values.lock().unwrap().get(key).cloned()
+        }
+
+        fn save(&self, key: &str, value: Value) {
+            self.values.lock().unwrap().insert(key.to_string(), value);
+        }
+
+        fn install_key(&self) -> Result<CryptoKey, String> {
+            util_crypto_install_key(&self.dir).map_err(|e| e.to_string())
+        }
+    }
+
+    fn lock(state: &SecretState) {
+        state.key.lock().unwrap().take();
+    }
+
+    #[test]
+    fn init_and_unlock_with_passphrase() {
+        let backend = MemoryBackend::new("passphrase");
+        let state = SecretState::default();
+        assert!(secret_vault_unlock(&backend, &state, Some("pw")).is_err());
+        secret_vault_init(&backend, &state, Some("pw")).unwrap();
+        assert!(secret_vault_init(&backend, &state, Some("pw")).is_err());
+        secret_value_set(&backend, &state, "a", &Value::from(42)).unwrap();
+
+        lock(&state);
+        assert!(secret_value_get(&backend, &state, "a").is_err());
+        assert!(secret_value_delete(&backend, &state, "a").is_err());
+        assert_eq!(secret_vault_unlock(&backend, &state, Some("wrong")), Err("口令错误".to_string()));
+        assert!(secret_vault_unlock(&backend, &state, None).is_err());
+        assert!(!secret_vault_status(&backend, &state).unlocked);
+
+        secret_vault_unlock(&backend, &state, Some("pw")).unwrap();
+        assert_eq!(secret_value_get(&backend, &state, "a").unwrap(), Some(Value::from(42)));
+        let status = secret_vault_status(&backend, &state);
+        assert!(status.initialized && status.unlocked);
+        assert_eq!((status.mode, status.count), (CryptoKeyKind::Passphrase, 1));
+    }
+
+    #[test]
+    fn set_passphrase_reencrypts_entries() {
+        let backend = MemoryBackend::new("rekey");
+        let state = SecretState::default();
+        secret_vault_init(&backend, &state, None).unwrap();
+        secret_value_set(&backend, &state, "a", &Value::from("x")).unwrap();
+        let before = backend.load(SECRET_ENTRIES_KEY).unwrap();
+
+        secret_vault_set_passphrase(&backend, &state, None, Some("new")).unwrap();
+        assert_ne!(backend.load(SECRET_ENTRIES_KEY).unwrap(), before);
+        assert_eq!(secret_vault_status(&backend, &state).mode, CryptoKeyKind::Passphrase);
+        assert_eq!(
+            secret_vault_set_passphrase(&backend, &state, Some("bad"), Some("other")),
+            Err("当前口令错误".to_string())
+        );
+
+        lock(&state);
+        assert!(secret_vault_set_passphrase(&backend, &state, Some("new"), None).is_err());
+        assert!(secret_vault_unlock(&backend, &state, None).is_err());
+        secret_vault_unlock(&backend, &state, Some("new")).unwrap();
+        assert_eq!(secret_value_get(&backend, &state, "a").unwrap(), Some(Value::from("x")));
+
+        // 改回安装密钥
+        secret_vault_set_passphrase(&backend, &state, Some("new"), None).unwrap();
+        lock(&state);
+        secret_vault_unlock(&backend, &state, None).unwrap();
+        assert_eq!(secret_value_get(&backend, &state, "a").unwrap(), Some(Value::from("x")));
+        assert!(secret_value_delete(&backend, &state, "a").unwrap());
+        assert!(!secret_value_delete(&backend, &state, "a").unwrap());
+    }
+
+    #[test]
+    fn key_registry() {
+        const KEYS: SecretKeyRegistry = SecretKeyRegistry { store_key: "test_keys", prefix: "test_key/", label: "测试密钥" };
+        let backend = MemoryBackend::new("registry");
+        let state = SecretState::default();
+        let secret = Value::from("s");
+        assert!(KEYS.insert(&backend, &state, "k", &secret, |n| Ok(n.to_string())).is_err());
+        secret_vault_init(&backend, &state, None).unwrap();
+        assert_eq!(KEYS.insert(&backend, &state, " b ", &secret, |n| Ok(n.to_string())).unwrap(), "b");
+        KEYS.insert(&backend, &state, "a", &Value::from("t"), |n| Ok(n.to_string())).unwrap();
+        assert!(KEYS.insert(&backend, &state, "a", &secret, |n| Ok(n.to_string())).is_err());
+        assert!(KEYS.insert(&backend, &state, " ", &secret, |n| Ok(n.to_string())).is_err());
+        assert_eq!(KEYS.list::<String, _>(&backend), ["a", "b"]);
+        assert_eq!(KEYS.find::<String, _>(&backend, "b").unwrap(), "b");
+        assert_eq!(KEYS.secret(&backend, &state, "a").unwrap(), Value::from("t"));
+        assert_eq!(secret_vault_status(&backend, &state).count, 2);
+
+        lock(&state);
+        assert!(KEYS.secret(&backend, &state, "a").is_err());
+        assert!(KEYS.delete(&backend, &state, "a").is_err());
+        assert_eq!(KEYS.list::<String, _>(&backend).len(), 2);
+
+        secret_vault_unlock(&backend, &state, None).unwrap();
+        assert!(KEYS.delete(&backend, &state, "a").unwrap());
+        assert!(!KEYS.delete(&backend, &state, "a").unwrap());
+        assert!(KEYS.secret(&backend, &state, "a").is_err());
+        assert_eq!(KEYS.list::<String, _>(&backend), ["b"]);
+    }
+}
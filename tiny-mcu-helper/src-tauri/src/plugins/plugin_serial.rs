@@ -0,0 +1,410 @@
+use serde::{Deserialize, Serialize};
+use serialport::{DataBits, FlowControl, Parity, SerialPort, SerialPortType, StopBits};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::plugins::plugin_serial_capture::{SerialCaptureState, SerialDirection};
+use crate::utils::{util_parse_input_bytes, util_timestamp_us};
+
+// 推送给前端的事件名
+pub const SERIAL_RX_EVENT: &str = "serial-rx";
+pub const SERIAL_CLOSED_EVENT: &str = "serial-closed";
+
+// 读取返回 0 字节时的等待时间，避免空转占满 CPU
+const SERIAL_IDLE_DELAY: Duration = Duration::from_millis(10);
+
+/// 串口打开参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct SerialConfig {
+    pub port_name: String,
+    pub baud_rate: u32,
+    pub data_bits: Option<u8>,         // 5 ~ 8，默认 8
+    pub parity: Option<String>,        // none / odd / even，默认 none
+    pub stop_bits: Option<u8>,         // 1 / 2，默认 1
+    pub flow_control: Option<String>,  // none / software / hardware，默认 none
+    pub read_timeout_ms: Option<u64>,  // 读超时（决定关闭响应速度），默认 20ms
+}
+
+impl SerialConfig {
+    pub fn open(&self) -> Result<Box<dyn SerialPort>, String> {
+        let data_bits = match self.data_bits.unwrap_or(8) {
+            5 => DataBits::Five,
+            6 => DataBits::Six,
+            7 => DataBits::Seven,
+            8 => DataBits::Eight,
+            other => return Err(format!("不支持的数据位: {}", other)),
+        };
+        let parity = match self.parity.as_deref().unwrap_or("none") {
+            "none" => Parity::None,
+            "odd" => Parity::Odd,
+            "even" => Parity::Even,
+            other => return Err(format!("不支持的校验位: {}", other)),
+        };
+        let stop_bits = match self.stop_bits.unwrap_or(1) {
+            1 => StopBits::One,
+            2 => StopBits::Two,
+            other => return Err(format!("不支持的停止位: {}", other)),
+        };
+        let flow_control = match self.flow_control.as_deref().unwrap_or("none") {
+            "none" => FlowControl::None,
+            "software" => FlowControl::Software,
+            "hardware" => FlowControl::Hardware,
+            other => return Err(format!("不支持的流控方式: {}", other)),
+        };
+
+        serialport::new(&self.port_name, self.baud_rate)
+            .data_bits(data_bits)
+            .parity(parity)
+            .stop_bits(stop_bits)
+            .flow_control(flow_control)
+            .timeout(Duration::from_millis(self.read_timeout_ms.unwrap_or(20)))
+            .open()
+            .map_err(|e| format!("打开串口 {} 失败: {}", self.port_name, e))
+    }
+}
+
+/// 可用串口信息
+#[derive(Debug, Serialize)]
+pub struct SerialPortItem {
+    pub port_name: String,
+    pub port_type: String, // usb / pci / bluetooth / unknown
+    pub vid: Option<u16>,
+    pub pid: Option<u16>,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+    pub serial_number: Option<String>,
+}
+
+/// 接收数据事件
+#[derive(Debug, Clone, Serialize)]
+pub struct SerialRxEvent {
+    pub port_name: String,
+    pub data: Vec<u8>,
+    pub timestamp_us: u64,
+}
+
+/// 串口关闭事件（主动关闭或设备断开）
+#[derive(Debug, Clone, Serialize)]
+pub struct SerialClosedEvent {
+    pub port_name: String,
+    pub reason: String,
+}
+
+/// 读线程产生的事件
+#[derive(Debug)]
+pub enum SerialEvent {
+    Data { data: Vec<u8>, timestamp_us: u64 },
+    Closed { reason: String },
+}
+
+// 每个串口单独加锁，发送时不占用连接表
+type SharedPort = Arc<Mutex<Box<dyn SerialPort>>>;
+
+// 已打开的串口连接
+struct SerialConnection {
+    id: u64, // 区分同名串口的多次打开，防止旧读线程误删新连接
+    port: SharedPort,
+    stop: Arc<AtomicBool>,
+}
+
+/// 串口管理状态（通过 Tauri manage 注册）
+#[derive(Default)]
+pub struct SerialState {
+    connections: Mutex<HashMap<String, SerialConnection>>,
+    next_id: AtomicU64,
+}
+
+impl SerialState {
+    // 登记新连接，检查与插入在同一次加锁中完成；返回连接 id 与读线程停止标志
+    fn insert(&self, port_name: &str, port: Box<dyn SerialPort>) -> Result<(u64, Arc<AtomicBool>), String> {
+        let mut connections = self.connections.lock().unwrap();
+        match connections.entry(port_name.to_string()) {
+            Entry::Occupied(_) => Err(format!("串口 {} 已打开", port_name)),
+            Entry::Vacant(slot) => {
+                let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+                let stop = Arc::new(AtomicBool::new(false));
+                slot.insert(SerialConnection {
+                    id,
+                    port: Arc::new(Mutex::new(port)),
+                    stop: stop.clone(),
+                });
+                Ok((id, stop))
+            }
+        }
+    }
+
+    // 取出串口句柄后立即释放连接表锁
+    fn port(&self, port_name: &str) -> Result<SharedPort, String> {
+        self.connections
+            .lock()
+            .unwrap()
+            .get(port_name)
+            .map(|conn| conn.port.clone())
+            .ok_or(format!("串口 {} 未打开", port_name))
+    }
+
+    // 阻塞写入，只持有该串口自己的锁
+    fn write(&self, port_name: &str, data: &[u8]) -> Result<(), String> {
+        let port = self.port(port_name)?;
+        let mut port = port.lock().unwrap();
+        port.write_all(data)
+            .and_then(|_| port.flush())
+            .map_err(|e| format!("串口发送失败: {}", e))
+    }
+
+    // 关闭并移除连接；id 为 Some 时仅在 id 匹配时移除
+    fn remove(&self, port_name: &str, id: Option<u64>) -> bool {
+        let mut connections = self.connections.lock().unwrap();
+        match connections.get(port_name) {
+            Some(conn) if id.is_none() || id == Some(conn.id) => {
+                conn.stop.store(true, Ordering::SeqCst);
+                connections.remove(port_name);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 向已打开的串口发送数据（开启抓包时同时记录 TX）
+pub fn serial_send(app: &AppHandle, port_name: &str, data: &[u8]) -> Result<usize, String> {
+    app.state::<SerialState>().write(port_name, data)?;
+    app.state::<SerialCaptureState>()
+        .record(port_name, SerialDirection::Tx, data, util_timestamp_us());
+    Ok(data.len())
+}
+
+/// 读循环：持续读取直到 stop 被置位或读取出错（可直接用于 pty 等任意 Read 实现）
+pub fn serial_read_loop<R: Read>(mut reader: R, stop: &AtomicBool, mut on_event: impl FnMut(SerialEvent)) {
+    let mut buffer = [0u8; 4096];
+    let reason = loop {
+        if stop.load(Ordering::SeqCst) {
+            break "串口已关闭".to_string();
+        }
+        match reader.read(&mut buffer) {
+            Ok(0) => std::thread::sleep(SERIAL_IDLE_DELAY),
+            Ok(n) => on_event(SerialEvent::Data {
+                data: buffer[..n].to_vec(),
+                timestamp_us: util_timestamp_us(),
+            }),
+            Err(e) if matches!(e.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock | ErrorKind::Interrupted) => {
+                continue
+            }
+            Err(e) => break format!("串口读取失败: {}", e),
+        }
+    };
+    on_event(SerialEvent::Closed { reason });
+}
+
+// 列出可用串口
+#[tauri::command]
+pub fn serial_list_ports() -> Result<Vec<SerialPortItem>, String> {
+    let ports = serialport::available_ports().map_err(|e| format!("获取串口列表失败: {}", e))?;
+    Ok(ports
+        .into_iter()
+        .map(|p| {
+            let mut item = SerialPortItem {
+                port_name: p.port_name,
+                port_type: "unknown".to_string(),
+                vid: None,
+                pid: None,
+                manufacturer: None,
+                product: None,
+                serial_number: None,
+            };
+            match p.port_type {
+                SerialPortType::UsbPort(usb) => {
+                    item.port_type = "usb".to_string();
+                    item.vid = Some(usb.vid);
+                    item.pid = Some(usb.pid);
+                    item.manufacturer = usb.manufacturer;
+                    item.product = usb.product;
+                    item.serial_number = usb.serial_number;
+                }
+                SerialPortType::PciPort => item.port_type = "pci".to_string(),
+                SerialPortType::BluetoothPort => item.port_type = "bluetooth".to_string(),
+                SerialPortType::Unknown => {}
+            }
+            item
+        })
+        .collect())
+}
+
+// 打开串口并启动读线程，收到的数据以 serial-rx 事件推送
+#[tauri::command]
+pub fn serial_open(app: AppHandle, state: State<'_, SerialState>, config: SerialConfig) -> Result<(), String> {
+    let port_name = config.port_name.clone();
+    // 提前检查以给出明确提示，真正的占位在 insert 中完成
+    if state.connections.lock().unwrap().contains_key(&port_name) {
+        return Err(format!("串口 {} 已打开", port_name));
+    }
+
+    let port = config.open()?;
+    let reader = port
+        .try_clone()
+        .map_err(|e| format!("创建串口读句柄失败: {}", e))?;
+    let (id, stop) = state.insert(&port_name, port)?;
+
+    // 串口读取是阻塞调用，放到 tokio 的阻塞线程池中执行
+    tauri::async_runtime::spawn_blocking(move || {
+        serial_read_loop(reader, &stop, |event| match event {
+            SerialEvent::Data { data, timestamp_us } => {
+                app.state::<SerialCaptureState>()
+                    .record(&port_name, SerialDirection::Rx, &data, timestamp_us);
+                let _ = app.emit(
+                    SERIAL_RX_EVENT,
+                    SerialRxEvent {
+                        port_name: port_name.clone(),
+                        data,
+                        timestamp_us,
+                    },
+                );
+            }
+            SerialEvent::Closed { reason } => {
+                // 设备断开时由读线程清理连接并结束抓包（主动关闭时已在 serial_close 中处理）
+                if app.state::<SerialState>().remove(&port_name, Some(id)) {
+                    app.state::<SerialCaptureState>().stop(&port_name);
+                }
+                let _ = app.emit(
+                    SERIAL_CLOSED_EVENT,
+                    SerialClosedEvent {
+                        port_name: port_name.clone(),
+                        reason,
+                    },
+                );
+            }
+        });
+    });
+
+    Ok(())
+}
+
+// 关闭串口
+#[tauri::command]
+pub fn serial_close(
+    state: State<'_, SerialState>,
+    capture_state: State<'_, SerialCaptureState>,
+    port_name: &str,
+) -> Result<(), String> {
+    if state.remove(port_name, None) {
+        capture_state.stop(port_name);
+        Ok(())
+    } else {
+        Err(format!("串口 {} 未打开", port_name))
+    }
+}
+
+// 发送数据，input_type 为 "text" 或 "hex"，返回发送的字节数
+#[tauri::command]
+pub fn serial_write(app: AppHandle, port_name: &str, data: &str, input_type: &str) -> Result<usize, String> {
+    let bytes = util_parse_input_bytes(data, input_type)?;
+    serial_send(&app, port_name, &bytes)
+}
+
+// 设置 DTR / RTS 信号（常用于自动复位或进入 bootloader）
+#[tauri::command]
+pub fn serial_set_signals(
+    state: State<'_, SerialState>,
+    port_name: &str,
+    dtr: Option<bool>,
+    rts: Option<bool>,
+) -> Result<(), String> {
+    let port = state.port(port_name)?;
+    let mut port = port.lock().unwrap();
+    if let Some(dtr) = dtr {
+        port.write_data_terminal_ready(dtr)
+            .map_err(|e| format!("设置 DTR 失败: {}", e))?;
+    }
+    if let Some(rts) = rts {
+        port.write_request_to_send(rts)
+            .map_err(|e| format!("设置 RTS 失败: {}", e))?;
+    }
+    Ok(())
+}
+
+// 列出当前已打开的串口
+#[tauri::command]
+pub fn serial_list_opened(state: State<'_, SerialState>) -> Vec<String> {
+    state.connections.lock().unwrap().keys().cloned().collect()
+}
+
+// 用 Linux / macOS 的 pty 对模拟串口：master 作为被测连接，slave 作为对端设备
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serialport::TTYPort;
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    fn pty_pair() -> (Box<dyn SerialPort>, TTYPort) {
+        let (mut master, slave) = TTYPort::pair().expect("创建 pty 失败");
+        master.set_timeout(Duration::from_millis(20)).unwrap();
+        (Box::new(master), slave)
+    }
+
+    fn read_exact_timeout(port: &mut TTYPort, len: usize) -> Vec<u8> {
+        port.set_timeout(Duration::from_millis(50)).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let mut out = Vec::new();
+        let mut buffer = [0u8; 64];
+        while out.len() < len && Instant::now() < deadline {
+            match port.read(&mut buffer) {
+                Ok(n) => out.extend_from_slice(&buffer[..n]),
+                Err(e) if e.kind() == ErrorKind::TimedOut => {}
+                Err(e) => panic!("{}", e),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn read_loop_streams_until_stopped() {
+        let (master, mut slave) = pty_pair();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        let flag = stop.clone();
+        let reader = std::thread::spawn(move || serial_read_loop(master, &flag, |event| tx.send(event).unwrap()));
+
+        slave.write_all(b"hello\r\n").unwrap();
+        let mut received = Vec::new();
+        while received.len() < 7 {
+            match rx.recv_timeout(Duration::from_secs(2)).expect("未收到数据") {
+                SerialEvent::Data { data, .. } => received.extend(data),
+                SerialEvent::Closed { reason } => panic!("{}", reason),
+            }
+        }
+        assert_eq!(received, b"hello\r\n");
+
+        stop.store(true, Ordering::SeqCst);
+        reader.join().unwrap();
+        assert!(matches!(rx.try_recv(), Ok(SerialEvent::Closed { .. })));
+    }
+
+    #[test]
+    fn state_writes_through_port_lock() {
+        let state = SerialState::default();
+        let (master, mut slave) = pty_pair();
+        let (id, stop) = state.insert("pty0", master).unwrap();
+
+        let (other, _) = pty_pair();
+        assert!(state.insert("pty0", other).is_err());
+
+        // 持有一个串口的锁时，其他串口的连接表操作不受影响
+        let (second, _second_slave) = pty_pair();
+        state.insert("pty1", second).unwrap();
+        let held = state.port("pty1").unwrap();
+        let _guard = held.lock().unwrap();
+        state.write("pty0", &[0x01, 0x02, 0xFF]).unwrap();
+        assert_eq!(read_exact_timeout(&mut slave, 3), vec![0x01, 0x02, 0xFF]);
+
+        assert!(!state.remove("pty0", Some(id + 100)));
+        assert!(state.remove("pty0", Some(id)));
+        assert!(stop.load(Ordering::SeqCst));
+        assert!(state.write("pty0", b"x").is_err());
+    }
+}
@@ -0,0 +1,567 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_log::log;
+
+use crate::plugins::plugin_serial::serial_send;
+use crate::utils::{util_get_generate_path, util_timestamp_us};
+
+// 回放事件名
+pub const SERIAL_REPLAY_EVENT: &str = "serial-replay";
+pub const SERIAL_REPLAY_DONE_EVENT: &str = "serial-replay-done";
+
+// 二进制抓包文件魔数
+const CAPTURE_MAGIC: &[u8; 8] = b"TMHCAP01";
+// 文本抓包文件首行
+const CAPTURE_TEXT_HEADER: &str = "# TinyMcuHelper serial capture v1";
+
+/// 数据方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SerialDirection {
+    Rx,
+    Tx,
+}
+
+/// 抓包文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureFormat {
+    Text,   // 按行记录，便于直接贴到问题单中阅读
+    Binary, // 紧凑二进制
+}
+
+/// 单条抓包记录
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CaptureRecord {
+    pub timestamp_us: u64,
+    pub direction: SerialDirection,
+    pub data: Vec<u8>,
+}
+
+/// 抓包结束后的统计信息
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureSummary {
+    pub port_name: String,
+    pub path: String,
+    pub records: u64,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+}
+
+/// 抓包写入器
+pub struct CaptureWriter {
+    format: CaptureFormat,
+    writer: BufWriter<File>,
+    first_timestamp_us: Option<u64>,
+    summary: CaptureSummary,
+}
+
+impl CaptureWriter {
+    pub fn create(path: &Path, port_name: &str, format: CaptureFormat) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("创建抓包文件失败: {}", e))?;
+        let mut writer = BufWriter::new(file);
+
+        let header = match format {
+            CaptureFormat::Text => format!("{} port={}\n", CAPTURE_TEXT_HEADER, port_name).into_bytes(),
+            CaptureFormat::Binary => {
+                let mut header = CAPTURE_MAGIC.to_vec();
+                header.extend_from_slice(&(port_name.len() as u16).to_le_bytes());
+                header.extend_from_slice(port_name.as_bytes());
+                header
+            }
+        };
+        writer
+            .write_all(&header)
+            .and_then(|_| writer.flush())
+            .map_err(|e| format!("写入抓包文件失败: {}", e))?;
+
+        Ok(Self {
+            format,
+            writer,
+            first_timestamp_us: None,
+            summary: CaptureSummary {
+                port_name: port_name.to_string(),
+                path: path.to_string_lossy().to_string(),
+                records: 0,
+                rx_bytes: 0,
+                tx_bytes: 0,
+            },
+        })
+    }
+
+    pub fn record(&mut self, record: &CaptureRecord) -> Result<(), String> {
+        let first = *self.first_timestamp_us.get_or_insert(record.timestamp_us);
+        let bytes = match self.format {
+            CaptureFormat::Text => format_text_record(record, first).into_bytes(),
+            CaptureFormat::Binary => {
+                let mut bytes = record.timestamp_us.to_le_bytes().to_vec();
+                bytes.push(match record.direction {
+                    SerialDirection::Rx => 0,
+                    SerialDirection::Tx => 1,
+                });
+                bytes.extend_from_slice(&(record.data.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(&record.data);
+                bytes
+            }
+        };
+        // 每条记录都立即落盘，程序异常退出时也能保留已抓取的数据
+        self.writer
+            .write_all(&bytes)
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| format!("写入抓包文件失败: {}", e))?;
+
+        self.summary.records += 1;
+        match record.direction {
+            SerialDirection::Rx => self.summary.rx_bytes += record.data.len() as u64,
+            SerialDirection::Tx => self.summary.tx_bytes += record.data.len() as u64,
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> CaptureSummary {
+        let _ = self.writer.flush();
+        self.summary
+    }
+}
+
+// 文本格式：<绝对时间戳us> <+相对秒数> <RX|TX> <十六进制> |<可打印字符>|
+fn format_text_record(record: &CaptureRecord, first_timestamp_us: u64) -> String {
+    let elapsed = record.timestamp_us.saturating_sub(first_timestamp_us);
+    let hex = record
+        .data
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let ascii: String = record
+        .data
+        .iter()
+        .map(|b| if b.is_ascii_graphic() || *b == b' ' { *b as char } else { '.' })
+        .collect();
+    format!(
+        "{} +{}.{:06} {} {} |{}|\n",
+        record.timestamp_us,
+        elapsed / 1_000_000,
+        elapsed % 1_000_000,
+        match record.direction {
+            SerialDirection::Rx => "RX",
+            SerialDirection::Tx => "TX",
+        },
+        hex,
+        ascii
+    )
+}
+
+fn parse_text_capture(content: &str) -> Result<Vec<CaptureRecord>, String> {
+    let mut records = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || format!("第 {} 行: 抓包记录格式无效", index + 1);
+        // 十六进制部分不含 '|'，以第一个 " |" 分隔可打印字符预览
+        let fields = line.split(" |").next().unwrap_or_default();
+        let mut tokens = fields.split_whitespace();
+
+        let timestamp_us = tokens
+            .next()
+            .and_then(|t| t.parse::<u64>().ok())
+            .ok_or_else(invalid)?;
+        tokens.next().ok_or_else(invalid)?; // 相对时间，仅供阅读
+        let direction = match tokens.next() {
+            Some("RX") => SerialDirection::Rx,
+            Some("TX") => SerialDirection::Tx,
+            _ => return Err(invalid()),
+        };
+        let data = tokens
+            .map(|t| u8::from_str_radix(t, 16).map_err(|_| invalid()))
+            .collect::<Result<Vec<u8>, String>>()?;
+
+        records.push(CaptureRecord {
+            timestamp_us,
+            direction,
+            data,
+        });
+    }
+    Ok(records)
+}
+
+fn parse_binary_capture(content: &[u8]) -> Result<Vec<CaptureRecord>, String> {
+    let truncated = || "抓包文件已截断".to_string();
+    let name_len = content
+        .get(8..10)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+        .ok_or_else(truncated)?;
+    let mut pos = 10 + name_len;
+
+    let mut records = Vec::new();
+    while pos < content.len() {
+        let head = content.get(pos..pos + 13).ok_or_else(truncated)?;
+        let timestamp_us = u64::from_le_bytes(head[0..8].try_into().unwrap());
+        let direction = match head[8] {
+            0 => SerialDirection::Rx,
+            1 => SerialDirection::Tx,
+            other => return Err(format!("无效的数据方向: {}", other)),
+        };
+        let len = u32::from_le_bytes(head[9..13].try_into().unwrap()) as usize;
+        let data = content.get(pos + 13..pos + 13 + len).ok_or_else(truncated)?;
+        records.push(CaptureRecord {
+            timestamp_us,
+            direction,
+            data: data.to_vec(),
+        });
+        pos += 13 + len;
+    }
+    Ok(records)
+}
+
+/// 读取抓包文件（自动识别文本 / 二进制格式）
+pub fn load_capture(path: &Path) -> Result<Vec<CaptureRecord>, String> {
+    let mut content = Vec::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_end(&mut content))
+        .map_err(|e| format!("读取抓包文件失败: {}", e))?;
+
+    if content.starts_with(CAPTURE_MAGIC) {
+        parse_binary_capture(&content)
+    } else {
+        parse_text_capture(&String::from_utf8_lossy(&content))
+    }
+}
+
+/// 抓包状态：串口名 -> 写入器
+#[derive(Default)]
+pub struct SerialCaptureState {
+    captures: Mutex<HashMap<String, CaptureWriter>>,
+}
+
+impl SerialCaptureState {
+    /// 记录一段收发数据（该串口未开启抓包时忽略）
+    pub fn record(&self, port_name: &str, direction: SerialDirection, data: &[u8], timestamp_us: u64) {
+        let mut captures = self.captures.lock().unwrap();
+        if let Some(writer) = captures.get_mut(port_name) {
+            let record = CaptureRecord {
+                timestamp_us,
+                direction,
+                data: data.to_vec(),
+            };
+            if let Err(e) = writer.record(&record) {
+                log::warn!("串口 {} 抓包写入失败，已停止抓包: {}", port_name, e);
+                captures.remove(port_name);
+            }
+        }
+    }
+
+    pub fn stop(&self, port_name: &str) -> Option<CaptureSummary> {
+        self.captures
+            .lock()
+            .unwrap()
+            .remove(port_name)
+            .map(CaptureWriter::finish)
+    }
+}
+
+/// 回放状态：回放 id -> 取消标志
+#[derive(Default)]
+pub struct SerialReplayState {
+    running: Mutex<HashMap<u64, Arc<AtomicBool>>>,
+    next_id: AtomicU64,
+}
+
+/// 回放事件（发送到前端）
+#[derive(Debug, Clone, Serialize)]
+pub struct SerialReplayEvent {
+    pub replay_id: u64,
+    pub port_name: Option<String>,
+    #[serde(flatten)]
+    pub record: CaptureRecord,
+}
+
+/// 回放结束事件
+#[derive(Debug, Clone, Serialize)]
+pub struct SerialReplayDoneEvent {
+    pub replay_id: u64,
+    pub records: usize,
+    pub cancelled: bool,
+    pub error: Option<String>,
+}
+
+/// 回放参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct SerialReplayConfig {
+    pub path: String,
+    pub target: String,               // frontend：推送事件；port：写入已打开的串口
+    pub port_name: Option<String>,    // target 为 port 时必填
+    pub speed: Option<f64>,           // 回放倍速，默认 1.0；0 表示不等待
+    pub direction: Option<SerialDirection>, // 仅回放指定方向；默认回放到串口时只发 TX，推送到前端时全部
+}
+
+// 串口名中可能含有 "/"（如 /dev/ttyUSB0），转换为可用作文件名的形式
+fn sanitize_port_name(port_name: &str) -> String {
+    port_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string()
+}
+
+// 按记录间隔等待，期间定期检查取消标志；返回 false 表示已取消
+fn replay_wait(delay_us: u64, cancel: &AtomicBool) -> bool {
+    let mut remaining = Duration::from_micros(delay_us);
+    let slice = Duration::from_millis(20);
+    while !remaining.is_zero() {
+        if cancel.load(Ordering::SeqCst) {
+            return false;
+        }
+        let step = remaining.min(slice);
+        std::thread::sleep(step);
+        remaining -= step;
+    }
+    !cancel.load(Ordering::SeqCst)
+}
+
+// 开始抓包，文件保存在 generate 目录，返回文件路径
+#[tauri::command]
+pub fn serial_capture_start(
+    state: State<'_, SerialCaptureState>,
+    port_name: &str,
+    format: CaptureFormat,
+    file_name: Option<String>,
+) -> Result<String, String> {
+    let mut captures = state.captures.lock().unwrap();
+    if captures.contains_key(port_name) {
+        return Err(format!("串口 {} 已在抓包中", port_name));
+    }
+
+    let extension = match format {
+        CaptureFormat::Text => "log",
+        CaptureFormat::Binary => "cap",
+    };
+    let file_name = match file_name {
+        Some(name) if Path::new(&name).extension().is_some() => name,
+        Some(name) => format!("{}.{}", name, extension),
+        None => format!(
+            "serial_{}_{}.{}",
+            sanitize_port_name(port_name),
+            util_timestamp_us() / 1000,
+            extension
+        ),
+    };
+    let path: PathBuf = util_get_generate_path()?.join(file_name);
+
+    let writer = CaptureWriter::create(&path, port_name, format)?;
+    captures.insert(port_name.to_string(), writer);
+    Ok(path.to_string_lossy().to_string())
+}
+
+// 停止抓包并返回统计信息
+#[tauri::command]
+pub fn serial_capture_stop(
+    state: State<'_, SerialCaptureState>,
+    port_name: &str,
+) -> Result<CaptureSummary, String> {
+    state
+        .stop(port_name)
+        .ok_or(format!("串口 {} 未在抓包", port_name))
+}
+
+// 读取抓包文件内容（用于前端预览）
+#[tauri::command]
+pub fn serial_capture_load(path: &str) -> Result<Vec<CaptureRecord>, String> {
+    load_capture(Path::new(path))
+}
+
+// 回放抓包文件，返回回放 id；回放在后台进行，结束时推送 serial-replay-done 事件
+#[tauri::command]
+pub fn serial_replay_start(
+    app: AppHandle,
+    state: State<'_, SerialReplayState>,
+    config: SerialReplayConfig,
+) -> Result<u64, String> {
+    let to_port = match config.target.as_str() {
+        "frontend" => false,
+        "port" => true,
+        other => return Err(format!("不支持的回放目标: {}", other)),
+    };
+    // 回放到串口时 RX 是对端发来的数据，默认不写回串口
+    let direction = config.direction.or(to_port.then_some(SerialDirection::Tx));
+    let records: Vec<CaptureRecord> = load_capture(Path::new(&config.path))?
+        .into_iter()
+        .filter(|r| direction.is_none_or(|d| d == r.direction))
+        .collect();
+
+    if to_port && config.port_name.is_none() {
+        return Err("回放到串口时必须指定串口".to_string());
+    }
+    let speed = config.speed.unwrap_or(1.0);
+    if speed < 0.0 || !speed.is_finite() {
+        return Err(format!("回放倍速无效: {}", speed));
+    }
+
+    let replay_id = state.next_id.fetch_add(1, Ordering::SeqCst);
+    let cancel = Arc::new(AtomicBool::new(false));
+    state.running.lock().unwrap().insert(replay_id, cancel.clone());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut sent = 0usize;
+        let mut error = None;
+        let mut previous: Option<u64> = None;
+
+        for record in records {
+            if let Some(prev) = previous {
+                let delay = record.timestamp_us.saturating_sub(prev);
+                let delay = if speed == 0.0 { 0 } else { (delay as f64 / speed) as u64 };
+                if !replay_wait(delay, &cancel) {
+                    break;
+                }
+            } else if cancel.load(Ordering::SeqCst) {
+                break;
+            }
+            previous = Some(record.timestamp_us);
+
+            if to_port {
+                let port_name = config.port_name.as_deref().unwrap_or_default();
+                if let Err(e) = serial_send(&app, port_name, &record.data) {
+                    error = Some(e);
+                    break;
+                }
+            } else {
+                let _ = app.emit(
+                    SERIAL_REPLAY_EVENT,
+                    SerialReplayEvent {
+                        replay_id,
+                        port_name: config.port_name.clone(),
+                        record,
+                    },
+                );
+            }
+            sent += 1;
+        }
+
+        app.state::<SerialReplayState>()
+            .running
+            .lock()
+            .unwrap()
+            .remove(&replay_id);
+        let _ = app.emit(
+            SERIAL_REPLAY_DONE_EVENT,
+            SerialReplayDoneEvent {
+                replay_id,
+                records: sent,
+                cancelled: cancel.load(Ordering::SeqCst),
+                error,
+            },
+        );
+    });
+
+    Ok(replay_id)
+}
+
+// 取消回放
+#[tauri::command]
+pub fn serial_replay_stop(state: State<'_, SerialReplayState>, replay_id: u64) -> Result<(), String> {
+    let running = state.running.lock().unwrap();
+    let cancel = running
+        .get(&replay_id)
+        .ok_or(format!("回放任务 {} 不存在或已结束", replay_id))?;
+    cancel.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 包含换行、'|'、不可打印字节与空数据，覆盖文本格式的分隔符
+    fn test_records() -> Vec<CaptureRecord> {
+        vec![
+            CaptureRecord {
+                timestamp_us: 1_700_000_000_000_000,
+                direction: SerialDirection::Tx,
+                data: b"AT\r\n".to_vec(),
+            },
+            CaptureRecord {
+                timestamp_us: 1_700_000_001_250_000,
+                direction: SerialDirection::Rx,
+                data: vec![0x00, b'\n', b'|', b' ', b'|', 0xFF, b'\n'],
+            },
+            CaptureRecord {
+                timestamp_us: 1_700_000_001_250_007,
+                direction: SerialDirection::Rx,
+                data: vec![],
+            },
+            CaptureRecord {
+                timestamp_us: 1_700_000_002_000_000,
+                direction: SerialDirection::Tx,
+                data: (0..=255).collect(),
+            },
+        ]
+    }
+
+    fn write_capture(format: CaptureFormat, records: &[CaptureRecord]) -> (PathBuf, CaptureSummary) {
+        let path = std::env::temp_dir().join(format!("tmh-capture-{}-{:?}", std::process::id(), format));
+        let mut writer = CaptureWriter::create(&path, "/dev/ttyUSB0", format).unwrap();
+        for record in records {
+            writer.record(record).unwrap();
+        }
+        (path, writer.finish())
+    }
+
+    #[test]
+    fn round_trip() {
+        let records = test_records();
+        for format in [CaptureFormat::Text, CaptureFormat::Binary] {
+            let (path, summary) = write_capture(format, &records);
+            assert_eq!((summary.port_name.as_str(), summary.records), ("/dev/ttyUSB0", 4));
+            assert_eq!((summary.rx_bytes, summary.tx_bytes), (7, 4 + 256));
+
+            let content = std::fs::read(&path).unwrap();
+            let parsed = match format {
+                CaptureFormat::Text => parse_text_capture(&String::from_utf8(content).unwrap()).unwrap(),
+                CaptureFormat::Binary => parse_binary_capture(&content).unwrap(),
+            };
+            assert_eq!(parsed, records, "{:?}", format);
+            assert_eq!(load_capture(&path).unwrap(), records, "{:?}", format);
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn text_layout() {
+        let records = test_records();
+        let (path, _) = write_capture(CaptureFormat::Text, &records[..2]);
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            content,
+            "# TinyMcuHelper serial capture v1 port=/dev/ttyUSB0\n\
+             1700000000000000 +0.000000 TX 41 54 0D 0A |AT..|\n\
+             1700000001250000 +1.250000 RX 00 0A 7C 20 7C FF 0A |..| |..|\n"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_capture() {
+        assert!(parse_text_capture("1000 +0.000000 XX 41 |A|").is_err());
+        assert!(parse_text_capture("1000 +0.000000 RX 4G |A|").is_err());
+        assert!(parse_text_capture("abc +0.000000 RX").is_err());
+
+        let (path, _) = write_capture(CaptureFormat::Binary, &test_records());
+        let content = std::fs::read(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(parse_binary_capture(&content[..content.len() - 1]), Err("抓包文件已截断".to_string()));
+        assert_eq!(parse_binary_capture(&content[..9]), Err("抓包文件已截断".to_string()));
+        let mut invalid = content.clone();
+        invalid[8 + 2 + "/dev/ttyUSB0".len() + 8] = 2;
+        assert!(parse_binary_capture(&invalid).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::functions::fun_signing::{
+    firmware_sign_with, firmware_verify_with, signing_fingerprint, signing_generate_keypair, signing_import_keypair,
+    signing_public_key_c_array, FirmwareSignRequest, FirmwareSignResult, FirmwareVerifyRequest, FirmwareVerifyResult,
+    SigningAlgorithm, SigningKeyPair,
+};
+use crate::plugins::plugin_secret_store::{SecretKeyRegistry, SecretState};
+use crate::utils::{util_parse_hex_str, util_to_hex_str, util_write_generate_file};
+
+// Store 中保存签名公钥信息的键，值为 { 名称: SigningKeyInfo }；私钥加密保存在机密存储中
+pub(crate) const SIGNING_KEYS_KEY: &str = "signing_keys";
+const SIGNING_KEYS: SecretKeyRegistry = SecretKeyRegistry {
+    store_key: SIGNING_KEYS_KEY,
+    prefix: "signing_key/",
+    label: "签名密钥",
+};
+
+/// 签名密钥的公开信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningKeyInfo {
+    pub name: String,
+    pub algorithm: SigningAlgorithm,
+    pub public_key: String,
+    pub fingerprint: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SigningPublicKeyExport {
+    pub content: String,
+    pub output_path: Option<String>,
+}
+
+// 私钥写入机密存储，公钥信息写入 Store
+fn save_keypair(app: &AppHandle, state: &SecretState, name: &str, keypair: &SigningKeyPair) -> Result<SigningKeyInfo, String> {
+    let value = serde_json::to_value(keypair).map_err(|e| format!("保存签名密钥失败: {}", e))?;
+    let public_key = util_parse_hex_str(&keypair.public_key)?;
+    SIGNING_KEYS.insert(app, state, name, &value, |name| {
+        Ok(SigningKeyInfo {
+            name: name.to_string(),
+            algorithm: keypair.algorithm,
+            public_key: keypair.public_key.clone(),
+            fingerprint: util_to_hex_str(&signing_fingerprint(&public_key)),
+        })
+    })
+}
+
+// 从机密存储读取私钥（需已解锁）
+fn load_keypair(app: &AppHandle, state: &SecretState, name: &str) -> Result<SigningKeyPair, String> {
+    let value = SIGNING_KEYS.secret(app, state, name)?;
+    serde_json::from_value(value).map_err(|e| format!("签名密钥 {} 已损坏: {}", name, e))
+}
+
+// 列出签名密钥（锁定时也可查看公钥）
+#[tauri::command]
+pub fn signing_key_list(app: AppHandle) -> Vec<SigningKeyInfo> {
+    SIGNING_KEYS.list(&app)
+}
+
+// 生成签名密钥对
+#[tauri::command]
+pub fn signing_key_generate(
+    app: AppHandle,
+    state: State<'_, SecretState>,
+    name: &str,
+    algorithm: SigningAlgorithm,
+) -> Result<SigningKeyInfo, String> {
+    let keypair = signing_generate_keypair(algorithm)?;
+    save_keypair(&app, &state, name, &keypair)
+}
+
+// 导入已有私钥（十六进制，32 字节）
+#[tauri::command]
+pub fn signing_key_import(
+    app: AppHandle,
+    state: State<'_, SecretState>,
+    name: &str,
+    algorithm: SigningAlgorithm,
+    private_key: String,
+) -> Result<SigningKeyInfo, String> {
+    let keypair = signing_import_keypair(algorithm, &private_key)?;
+    save_keypair(&app, &state, name, &keypair)
+}
+
+// 删除签名密钥（需已解锁，同时删除机密存储中的私钥）
+#[tauri::command]
+pub fn signing_key_delete(app: AppHandle, state: State<'_, SecretState>, name: &str) -> Result<bool, String> {
+    SIGNING_KEYS.delete(&app, &state, name)
+}
+
+// 导出公钥为 C 数组，file_name 不为空时同时写入 generate 目录
+#[tauri::command]
+pub fn signing_key_export_public(
+    app: AppHandle,
+    name: &str,
+    array_name: Option<String>,
+    file_name: Option<String>,
+) -> Result<SigningPublicKeyExport, String> {
+    let info = SIGNING_KEYS.find::<SigningKeyInfo, _>(&app, name)?;
+    let public_key = util_parse_hex_str(&info.public_key)?;
+    let array_name = array_name.filter(|n| !n.trim().is_empty()).unwrap_or("signing_public_key".to_string());
+    let content = signing_public_key_c_array(array_name.trim(), info.algorithm, &public_key);
+    let output_path = match file_name.filter(|n| !n.trim().is_empty()) {
+        Some(file_name) => Some(util_write_generate_file(file_name.trim(), "h", &content)?),
+        None => None,
+    };
+    Ok(SigningPublicKeyExport { content, output_path })
+}
+
+// 用指定密钥签名固件（追加签名块或输出分离签名）
+#[tauri::command]
+pub async fn firmware_sign(
+    app: AppHandle,
+    key_name: String,
+    request: FirmwareSignRequest,
+) -> Result<FirmwareSignResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let keypair = load_keypair(&app, &app.state::<SecretState>(), &key_name)?;
+        firmware_sign_with(&keypair, &request)
+    })
+    .await
+    .map_err(|e| format!("签名任务异常: {}", e))?
+}
+
+// 验证固件签名：key_name 与 public_key（十六进制）二选一，key_name 优先
+#[tauri::command]
+pub async fn firmware_verify(
+    app: AppHandle,
+    key_name: Option<String>,
+    public_key: Option<String>,
+    request: FirmwareVerifyRequest,
+) -> Result<FirmwareVerifyResult, String> {
+    let public_key = match (key_name, public_key) {
+        (Some(name), _) => SIGNING_KEYS.find::<SigningKeyInfo, _>(&app, &name)?.public_key,
+        (None, Some(public_key)) => public_key,
+        (None, None) => return Err("请选择签名密钥或输入公钥".to_string()),
+    };
+    let public_key = util_parse_hex_str(&public_key)?;
+    tauri::async_runtime::spawn_blocking(move || firmware_verify_with(&public_key, &request))
+        .await
+        .map_err(|e| format!("验证任务异常: {}", e))?
+}
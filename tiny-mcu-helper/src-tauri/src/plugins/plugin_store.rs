@@ -10,10 +10,23 @@ use tauri_plugin_store::{Store, StoreExt};
 //
 //     Ok(exe_path)
 // }
+use crate::plugins::plugin_firmware_crypto::FIRMWARE_KEYS_KEY;
+use crate::plugins::plugin_secret_store::{SECRET_ENTRIES_KEY, SECRET_VAULT_KEY};
+use crate::plugins::plugin_signing::SIGNING_KEYS_KEY;
 use crate::utils::util_file::util_get_app_path;
 
+// 由机密存储管理的键，只能通过对应命令（需解锁）修改
+const RESERVED_KEYS: [&str; 4] = [SECRET_ENTRIES_KEY, SECRET_VAULT_KEY, SIGNING_KEYS_KEY, FIRMWARE_KEYS_KEY];
 
-fn get_store(app: AppHandle<Wry>) -> Arc<Store<Wry>> {
+fn check_reserved(key: &str) -> Result<(), String> {
+    if RESERVED_KEYS.contains(&key) {
+        return Err(format!("{} 由机密存储管理，不能直接修改", key));
+    }
+    Ok(())
+}
+
+
+pub fn get_store(app: AppHandle<Wry>) -> Arc<Store<Wry>> {
     app.store(util_get_app_path().join("data").join("app.cfg"))
         .expect("TODO: panic message")
 }
@@ -54,6 +67,7 @@ pub fn init_store(app: &App) -> Result<(), Box<dyn std::error::Error>> {
 // 存储值到Store的函数
 #[tauri::command]
 pub fn store_set(app: AppHandle<Wry>, key: &str, value: serde_json::Value) -> Result<(), String> {
+    check_reserved(key)?;
     Ok(get_store(app).set(key.to_string(), value))
 }
 
@@ -66,6 +80,7 @@ pub fn store_get(app: AppHandle<Wry>, key: &str) -> Result<Option<serde_json::Va
 
 // 从Store删除值的函数
 #[tauri::command]
-pub fn store_delete(app: AppHandle<Wry>, key: &str) -> bool {
-    get_store(app).delete(key)
+pub fn store_delete(app: AppHandle<Wry>, key: &str) -> Result<bool, String> {
+    check_reserved(key)?;
+    Ok(get_store(app).delete(key))
 }
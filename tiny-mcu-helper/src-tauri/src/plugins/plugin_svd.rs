@@ -0,0 +1,180 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+use crate::db::{db_svd_delete, db_svd_find_by_path, db_svd_get_model, db_svd_list, db_svd_save, DbState, SvdDeviceRecord};
+use crate::functions::fun_checksum::{crc_compute, crc_find_preset};
+use crate::functions::fun_svd::{svd_decode, svd_encode, svd_parse, SvdDevice, SvdFieldEdit, SvdPeripheral, SvdRegisterDecode};
+use crate::utils::util_parse_u64;
+
+/// 已加载的器件模型（按缓存记录 id 索引），避免每次操作都反序列化
+#[derive(Default)]
+pub struct SvdState {
+    devices: Mutex<HashMap<i64, Arc<SvdDevice>>>,
+}
+
+impl SvdState {
+    // 内存中没有时从数据库缓存加载
+    pub async fn get_device(&self, app: &AppHandle, device_id: i64) -> Result<Arc<SvdDevice>, String> {
+        if let Some(device) = self.devices.lock().unwrap().get(&device_id) {
+            return Ok(device.clone());
+        }
+        let pool = &app.state::<DbState>().pool;
+        let model = db_svd_get_model(pool, device_id)
+            .await?
+            .ok_or(format!("SVD 器件 {} 不存在", device_id))?;
+        let device: SvdDevice =
+            serde_json::from_str(&model).map_err(|e| format!("读取 SVD 缓存失败: {}", e))?;
+        let device = Arc::new(device);
+        self.devices.lock().unwrap().insert(device_id, device.clone());
+        Ok(device)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SvdLoadResult {
+    pub device: SvdDeviceRecord,
+    pub from_cache: bool, // 文件未变化，直接使用缓存
+}
+
+/// 外设列表项
+#[derive(Debug, Clone, Serialize)]
+pub struct SvdPeripheralSummary {
+    pub name: String,
+    pub description: Option<String>,
+    pub group_name: Option<String>,
+    pub base_address: u64,
+    pub derived_from: Option<String>,
+    pub register_count: usize,
+}
+
+// 文件内容指纹：CRC-32 + 长度
+fn svd_file_hash(content: &[u8]) -> Result<String, String> {
+    let preset = crc_find_preset("CRC-32").ok_or("缺少 CRC-32 预设".to_string())?;
+    Ok(format!("{:08X}-{}", crc_compute(preset.params, content)?, content.len()))
+}
+
+// 加载 SVD 文件，内容未变化时直接使用数据库缓存
+#[tauri::command]
+pub async fn svd_load(app: AppHandle, file_path: String, force: Option<bool>) -> Result<SvdLoadResult, String> {
+    let content = std::fs::read(&file_path).map_err(|e| format!("读取 SVD 文件失败: {}", e))?;
+    let file_hash = svd_file_hash(&content)?;
+    let pool = app.state::<DbState>().pool.clone();
+
+    if let Some(record) = db_svd_find_by_path(&pool, &file_path).await? {
+        if record.file_hash == file_hash && !force.unwrap_or(false) {
+            return Ok(SvdLoadResult {
+                device: record,
+                from_cache: true,
+            });
+        }
+    }
+
+    // 大型 SVD 有数十 MB，放到阻塞线程池解析
+    let (device, model) = tauri::async_runtime::spawn_blocking(move || {
+        let text = String::from_utf8(content).map_err(|_| "SVD 文件不是有效的 UTF-8 文本".to_string())?;
+        let device = svd_parse(&text)?;
+        let model = serde_json::to_string(&device).map_err(|e| format!("序列化 SVD 模型失败: {}", e))?;
+        Ok::<_, String>((device, model))
+    })
+    .await
+    .map_err(|e| format!("解析 SVD 失败: {}", e))??;
+
+    let record = db_svd_save(
+        &pool,
+        &device.name,
+        &file_path,
+        &file_hash,
+        device.peripherals.len() as i64,
+        device.register_count() as i64,
+        &model,
+    )
+    .await?;
+    app.state::<SvdState>()
+        .devices
+        .lock()
+        .unwrap()
+        .insert(record.id, Arc::new(device));
+    Ok(SvdLoadResult {
+        device: record,
+        from_cache: false,
+    })
+}
+
+// 列出已缓存的器件
+#[tauri::command]
+pub async fn svd_list_devices(app: AppHandle) -> Result<Vec<SvdDeviceRecord>, String> {
+    db_svd_list(&app.state::<DbState>().pool).await
+}
+
+// 删除器件缓存
+#[tauri::command]
+pub async fn svd_delete_device(app: AppHandle, device_id: i64) -> Result<(), String> {
+    app.state::<SvdState>().devices.lock().unwrap().remove(&device_id);
+    db_svd_delete(&app.state::<DbState>().pool, device_id).await
+}
+
+// 列出器件的外设
+#[tauri::command]
+pub async fn svd_list_peripherals(app: AppHandle, device_id: i64) -> Result<Vec<SvdPeripheralSummary>, String> {
+    let device = app.state::<SvdState>().get_device(&app, device_id).await?;
+    Ok(device
+        .peripherals
+        .iter()
+        .map(|p| SvdPeripheralSummary {
+            name: p.name.clone(),
+            description: p.description.clone(),
+            group_name: p.group_name.clone(),
+            base_address: p.base_address,
+            derived_from: p.derived_from.clone(),
+            register_count: p.registers.len(),
+        })
+        .collect())
+}
+
+// 获取外设的寄存器、字段与枚举值
+#[tauri::command]
+pub async fn svd_get_peripheral(app: AppHandle, device_id: i64, peripheral: String) -> Result<SvdPeripheral, String> {
+    let device = app.state::<SvdState>().get_device(&app, device_id).await?;
+    device
+        .peripherals
+        .iter()
+        .find(|p| p.name.eq_ignore_ascii_case(&peripheral))
+        .cloned()
+        .ok_or(format!("未找到外设: {}", peripheral))
+}
+
+// 将寄存器值解码为各字段（value 支持十进制与 0x 十六进制）
+#[tauri::command]
+pub async fn svd_decode_register(
+    app: AppHandle,
+    device_id: i64,
+    peripheral: String,
+    register: String,
+    value: String,
+) -> Result<SvdRegisterDecode, String> {
+    let device = app.state::<SvdState>().get_device(&app, device_id).await?;
+    let reg = device.find_register(&peripheral, &register)?;
+    Ok(svd_decode(&peripheral, reg, util_parse_u64(&value)?))
+}
+
+// 按字段修改重新编码寄存器值，base 为空时以复位值为基础
+#[tauri::command]
+pub async fn svd_encode_register(
+    app: AppHandle,
+    device_id: i64,
+    peripheral: String,
+    register: String,
+    base: Option<String>,
+    edits: Vec<SvdFieldEdit>,
+) -> Result<SvdRegisterDecode, String> {
+    let device = app.state::<SvdState>().get_device(&app, device_id).await?;
+    let reg = device.find_register(&peripheral, &register)?;
+    let base = match base {
+        Some(base) => util_parse_u64(&base)?,
+        None => reg.reset_value,
+    };
+    let value = svd_encode(reg, base, &edits)?;
+    Ok(svd_decode(&peripheral, reg, value))
+}
@@ -4,3 +4,9 @@ pub mod util_lifecycle;
 pub use util_lifecycle::*;
 pub mod util_crypto;
 pub use util_crypto::*;
+pub mod util_hex;
+pub use util_hex::*;
+pub mod util_time;
+pub use util_time::*;
+pub mod util_table;
+pub use util_table::*;
@@ -1,11 +1,246 @@
-use serde::{Serialize, Deserialize};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use serde::{Deserialize, Serialize};
 use std::error::Error;
+use std::io::Write;
+use std::path::Path;
+use zeroize::Zeroize;
 
-// XOR 密钥（可自定义长度，建议至少 8 字节）
-// 注意：生产环境需动态生成，不要硬编码！
-const XOR_KEY: &[u8] = b"secret_key_for_secret"; // 示例密钥
+// 密文格式（版本 1），头部同时作为 AEAD 附加数据，篡改任何字段都会导致解密失败：
+// | 魔数 "TMHC" (4) | 版本 (1) | 密钥类型 (1) | 盐 (16) | 随机数 (12) | 密文 + GCM 标签 (16) |
+const CRYPTO_MAGIC: &[u8; 4] = b"TMHC";
+const CRYPTO_VERSION: u8 = 1;
+pub const CRYPTO_SALT_LEN: usize = 16;
+const CRYPTO_NONCE_LEN: usize = 12;
+const CRYPTO_HEADER_LEN: usize = 4 + 1 + 1 + CRYPTO_SALT_LEN + CRYPTO_NONCE_LEN;
+const CRYPTO_TAG_LEN: usize = 16;
 
-// XOR 加解密（同一函数，加密后再加密一次即解密）
+// 安装密钥文件名（位于应用数据目录）
+pub const CRYPTO_INSTALL_KEY_FILE: &str = "secret.key";
+
+// 旧版本使用的 XOR 密钥，仅用于识别和迁移旧数据，不再用于加密
+const LEGACY_XOR_KEY: &[u8] = b"secret_key_for_secret";
+
+/// 密钥来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CryptoKeyKind {
+  Install,    // 每个安装随机生成，保存在应用数据目录
+  Passphrase, // 由用户口令经 Argon2id 派生
+}
+
+impl CryptoKeyKind {
+  fn to_byte(self) -> u8 {
+    match self {
+      Self::Install => 0,
+      Self::Passphrase => 1,
+    }
+  }
+
+  fn from_byte(value: u8) -> Option<Self> {
+    match value {
+      0 => Some(Self::Install),
+      1 => Some(Self::Passphrase),
+      _ => None,
+    }
+  }
+}
+
+/// AES-256-GCM 密钥，释放时清零
+pub struct CryptoKey {
+  key: [u8; 32],
+  kind: CryptoKeyKind,
+  salt: [u8; CRYPTO_SALT_LEN], // 口令派生使用的盐，安装密钥为全 0
+}
+
+impl Drop for CryptoKey {
+  fn drop(&mut self) {
+    self.key.zeroize();
+  }
+}
+
+impl CryptoKey {
+  pub fn kind(&self) -> CryptoKeyKind {
+    self.kind
+  }
+
+  pub fn salt(&self) -> &[u8; CRYPTO_SALT_LEN] {
+    &self.salt
+  }
+}
+
+/// 密文头部信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CryptoHeader {
+  pub version: u8,
+  pub kind: CryptoKeyKind,
+  pub salt: [u8; CRYPTO_SALT_LEN],
+}
+
+/// 填充系统安全随机数
+pub fn util_random_bytes(buf: &mut [u8]) -> Result<(), Box<dyn Error>> {
+  getrandom::getrandom(buf).map_err(|e| format!("生成随机数失败: {}", e))?;
+  Ok(())
+}
+
+/// 生成口令派生用的随机盐
+pub fn util_crypto_new_salt() -> Result<[u8; CRYPTO_SALT_LEN], Box<dyn Error>> {
+  let mut salt = [0u8; CRYPTO_SALT_LEN];
+  util_random_bytes(&mut salt)?;
+  Ok(salt)
+}
+
+/// 由口令派生密钥（Argon2id，使用 argon2 库的默认参数 m=19 MiB, t=2, p=1）
+pub fn util_crypto_derive_key(passphrase: &str, salt: &[u8; CRYPTO_SALT_LEN]) -> Result<CryptoKey, Box<dyn Error>> {
+  if passphrase.is_empty() {
+    return Err("口令不能为空".into());
+  }
+  let mut key = [0u8; 32];
+  Argon2::default()
+      .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+      .map_err(|e| format!("派生密钥失败: {}", e))?;
+  Ok(CryptoKey {
+    key,
+    kind: CryptoKeyKind::Passphrase,
+    salt: *salt,
+  })
+}
+
+// 读取安装密钥文件，长度不符视为损坏
+fn read_install_key(path: &Path) -> std::io::Result<Result<[u8; 32], Box<dyn Error>>> {
+  let mut content = std::fs::read(path)?;
+  let mut key = [0u8; 32];
+  let result = if content.len() == key.len() {
+    key.copy_from_slice(&content);
+    Ok(key)
+  } else {
+    Err(format!("安装密钥文件已损坏: {}", path.display()).into())
+  };
+  content.zeroize();
+  Ok(result)
+}
+
+// 将新密钥写入临时文件后链接到 path；path 已存在时返回 AlreadyExists（rename 会覆盖已有密钥，不能用于抢占）
+fn write_install_key(dir: &Path, path: &Path, key: &[u8; 32]) -> std::io::Result<()> {
+  let mut suffix = [0u8; 8];
+  util_random_bytes(&mut suffix).map_err(|e| std::io::Error::other(e.to_string()))?;
+  let tmp = dir.join(format!(
+    "{}.{}.tmp",
+    CRYPTO_INSTALL_KEY_FILE,
+    suffix.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+  ));
+  let mut options = std::fs::OpenOptions::new();
+  options.write(true).create_new(true);
+  // 仅当前用户可读写
+  #[cfg(unix)]
+  std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+  let result = options
+      .open(&tmp)
+      .and_then(|mut file| file.write_all(key).and_then(|_| file.sync_all()))
+      .and_then(|_| std::fs::hard_link(&tmp, path));
+  let _ = std::fs::remove_file(&tmp);
+  result
+}
+
+/// 读取安装密钥，不存在时随机生成并写入 dir/secret.key；多个进程同时生成时以先写入的为准
+pub fn util_crypto_install_key(dir: &Path) -> Result<CryptoKey, Box<dyn Error>> {
+  let path = dir.join(CRYPTO_INSTALL_KEY_FILE);
+  let key = match read_install_key(&path) {
+    Ok(key) => key?,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+      let mut key = [0u8; 32];
+      util_random_bytes(&mut key)?;
+      std::fs::create_dir_all(dir).map_err(|e| format!("创建密钥目录失败: {}", e))?;
+      match write_install_key(dir, &path, &key) {
+        Ok(()) => key,
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+          key.zeroize();
+          read_install_key(&path).map_err(|e| format!("读取安装密钥失败: {}", e))??
+        }
+        Err(e) => {
+          key.zeroize();
+          return Err(format!("保存安装密钥失败: {}", e).into());
+        }
+      }
+    }
+    Err(e) => return Err(format!("读取安装密钥失败: {}", e).into()),
+  };
+  Ok(CryptoKey {
+    key,
+    kind: CryptoKeyKind::Install,
+    salt: [0u8; CRYPTO_SALT_LEN],
+  })
+}
+
+/// 解析密文头部，不是本格式的数据返回 None
+pub fn util_crypto_header(data: &[u8]) -> Option<CryptoHeader> {
+  if data.len() < CRYPTO_HEADER_LEN || !data.starts_with(CRYPTO_MAGIC) {
+    return None;
+  }
+  let mut salt = [0u8; CRYPTO_SALT_LEN];
+  salt.copy_from_slice(&data[6..6 + CRYPTO_SALT_LEN]);
+  Some(CryptoHeader {
+    version: data[4],
+    kind: CryptoKeyKind::from_byte(data[5])?,
+    salt,
+  })
+}
+
+/// 加密字节数据（AES-256-GCM，每次使用新的随机数）
+pub fn encrypt_bytes(plain: &[u8], key: &CryptoKey) -> Result<Vec<u8>, Box<dyn Error>> {
+  let mut nonce = [0u8; CRYPTO_NONCE_LEN];
+  util_random_bytes(&mut nonce)?;
+
+  let mut out = Vec::with_capacity(CRYPTO_HEADER_LEN + plain.len() + CRYPTO_TAG_LEN);
+  out.extend_from_slice(CRYPTO_MAGIC);
+  out.push(CRYPTO_VERSION);
+  out.push(key.kind.to_byte());
+  out.extend_from_slice(&key.salt);
+  out.extend_from_slice(&nonce);
+
+  let cipher = Aes256Gcm::new_from_slice(&key.key).map_err(|e| format!("初始化加密失败: {}", e))?;
+  let encrypted = cipher
+      .encrypt(Nonce::from_slice(&nonce), Payload { msg: plain, aad: &out })
+      .map_err(|_| "加密失败")?;
+  out.extend_from_slice(&encrypted);
+  Ok(out)
+}
+
+/// 解密字节数据，密钥不匹配或数据被篡改时返回错误
+pub fn decrypt_bytes(data: &[u8], key: &CryptoKey) -> Result<Vec<u8>, Box<dyn Error>> {
+  let header = match util_crypto_header(data) {
+    Some(header) => header,
+    None if util_crypto_is_legacy(data) => return Err("旧版 XOR 加密数据，请先迁移".into()),
+    None => return Err("不是有效的加密数据".into()),
+  };
+  if header.version != CRYPTO_VERSION {
+    return Err(format!("不支持的加密数据版本: {}", header.version).into());
+  }
+  if header.kind != key.kind {
+    return Err(match header.kind {
+      CryptoKeyKind::Install => "数据使用安装密钥加密",
+      CryptoKeyKind::Passphrase => "数据使用口令加密",
+    }
+    .into());
+  }
+  if header.kind == CryptoKeyKind::Passphrase && header.salt != key.salt {
+    return Err("数据的盐与当前密钥不一致，请使用对应的口令重新派生密钥".into());
+  }
+  if data.len() < CRYPTO_HEADER_LEN + CRYPTO_TAG_LEN {
+    return Err("加密数据不完整".into());
+  }
+
+  let (aad, encrypted) = data.split_at(CRYPTO_HEADER_LEN);
+  let nonce = &aad[CRYPTO_HEADER_LEN - CRYPTO_NONCE_LEN..];
+  let cipher = Aes256Gcm::new_from_slice(&key.key).map_err(|e| format!("初始化解密失败: {}", e))?;
+  let plain = cipher
+      .decrypt(Nonce::from_slice(nonce), Payload { msg: encrypted, aad })
+      .map_err(|_| "解密失败：密钥错误或数据已被篡改")?;
+  Ok(plain)
+}
+
+// XOR 加解密（旧格式，加密后再加密一次即解密）
 fn xor_crypt(data: &[u8], key: &[u8]) -> Vec<u8> {
   data.iter()
       .zip(key.iter().cycle()) // 循环使用密钥（密钥长度可小于数据长度）
@@ -13,16 +248,71 @@ fn xor_crypt(data: &[u8], key: &[u8]) -> Vec<u8> {
       .collect()
 }
 
-// 加密数据（序列化 + XOR）
-pub fn encrypt_data<T: Serialize>(data: &T) -> Result<Vec<u8>, Box<dyn Error>> {
-  let json_str = serde_json::to_string(data)?; // 序列化为 JSON
-  let encrypted = xor_crypt(json_str.as_bytes(), XOR_KEY);
-  Ok(encrypted)
+/// 是否为旧版 XOR 加密的 JSON 数据
+pub fn util_crypto_is_legacy(data: &[u8]) -> bool {
+  !data.is_empty()
+      && !data.starts_with(CRYPTO_MAGIC)
+      && serde_json::from_slice::<serde_json::Value>(&xor_crypt(data, LEGACY_XOR_KEY)).is_ok()
+}
+
+/// 将旧版 XOR 数据解密后用新密钥重新加密
+pub fn migrate_legacy_data(legacy: &[u8], key: &CryptoKey) -> Result<Vec<u8>, Box<dyn Error>> {
+  if !util_crypto_is_legacy(legacy) {
+    return Err("不是旧版 XOR 加密数据".into());
+  }
+  let mut plain = xor_crypt(legacy, LEGACY_XOR_KEY);
+  let encrypted = encrypt_bytes(&plain, key);
+  plain.zeroize();
+  encrypted
+}
+
+// 加密数据（序列化 + AES-256-GCM）
+pub fn encrypt_data<T: Serialize>(data: &T, key: &CryptoKey) -> Result<Vec<u8>, Box<dyn Error>> {
+  let mut json = serde_json::to_vec(data)?; // 序列化为 JSON
+  let encrypted = encrypt_bytes(&json, key);
+  json.zeroize();
+  encrypted
 }
 
-// 解密数据（XOR + 反序列化）
-pub fn decrypt_data<T: for<'de> Deserialize<'de>>(encrypted_data: &[u8]) -> Result<T, Box<dyn Error>> {
-  let decrypted_bytes = xor_crypt(encrypted_data, XOR_KEY); // XOR 解密
-  let data: T = serde_json::from_slice(&decrypted_bytes)?; // 反序列化
-  Ok(data)
-}
\ No newline at end of file
+// 解密数据（AES-256-GCM + 反序列化）
+pub fn decrypt_data<T: for<'de> Deserialize<'de>>(encrypted_data: &[u8], key: &CryptoKey) -> Result<T, Box<dyn Error>> {
+  let mut decrypted_bytes = decrypt_bytes(encrypted_data, key)?;
+  let data = serde_json::from_slice(&decrypted_bytes); // 反序列化
+  decrypted_bytes.zeroize();
+  Ok(data?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn install_key_is_created_once() {
+    let dir = std::env::temp_dir().join(format!("tmh-install-key-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+          let dir = dir.clone();
+          std::thread::spawn(move || util_crypto_install_key(&dir).unwrap().key)
+        })
+        .collect();
+    let keys: Vec<[u8; 32]> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+    assert!(keys.iter().all(|k| *k == keys[0]));
+    assert_eq!(std::fs::read(dir.join(CRYPTO_INSTALL_KEY_FILE)).unwrap(), keys[0]);
+    // 不残留临时文件
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn migrates_legacy_xor_data() {
+    let key = util_crypto_derive_key("passphrase", &[7u8; CRYPTO_SALT_LEN]).unwrap();
+    let legacy = xor_crypt(br#"{"user":"admin"}"#, LEGACY_XOR_KEY);
+    assert!(util_crypto_is_legacy(&legacy));
+    assert!(decrypt_bytes(&legacy, &key).is_err());
+    let migrated = migrate_legacy_data(&legacy, &key).unwrap();
+    let value: serde_json::Value = decrypt_data(&migrated, &key).unwrap();
+    assert_eq!(value["user"], "admin");
+    assert!(migrate_legacy_data(&migrated, &key).is_err());
+  }
+}
@@ -8,4 +8,12 @@ pub fn util_get_app_path() -> PathBuf {
         .parent()
         .unwrap()
         .to_path_buf()
-}
\ No newline at end of file
+}
+
+// 获取 generate 输出目录（不存在则创建）
+pub fn util_get_generate_path() -> Result<PathBuf, String> {
+    let generate_dir = util_get_app_path().join("generate");
+    std::fs::create_dir_all(&generate_dir)
+        .map_err(|e| format!("创建generate文件夹失败: {}", e))?;
+    Ok(generate_dir)
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Deserializer, Serializer};
+
+// 解析十六进制字符串（允许空格、换行、逗号以及 0x 前缀，例如 "01 0x02,03"）
+pub fn util_parse_hex_str(hex_str: &str) -> Result<Vec<u8>, String> {
+    let cleaned: String = hex_str
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|s| s.trim_start_matches("0x").trim_start_matches("0X"))
+        .collect();
+
+    if !cleaned.is_ascii() {
+        return Err("无效的十六进制格式，只能包含0-9、A-F、a-f".to_string());
+    }
+    if !cleaned.len().is_multiple_of(2) {
+        return Err("无效的十六进制格式，必须为偶数长度".to_string());
+    }
+
+    (0..cleaned.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&cleaned[i..i + 2], 16)
+                .map_err(|_| format!("无效的十六进制字符: {}", &cleaned[i..i + 2]))
+        })
+        .collect()
+}
+
+// 字节数组转十六进制字符串（大写，以空格分隔）
+pub fn util_to_hex_str(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// 按输入类型（"text" 或 "hex"）把前端传入的内容转换为字节
+pub fn util_parse_input_bytes(data: &str, input_type: &str) -> Result<Vec<u8>, String> {
+    match input_type {
+        "hex" => util_parse_hex_str(data),
+        "text" => Ok(data.as_bytes().to_vec()),
+        _ => Err(format!("不支持的输入类型: {}", input_type)),
+    }
+}
+
+// 解析数字字符串，支持十进制与 0x 前缀的十六进制
+pub fn util_parse_u64(value: &str) -> Result<u64, String> {
+    let value = value.trim().replace('_', "");
+    let parsed = if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16)
+    } else {
+        value.parse::<u64>()
+    };
+    parsed.map_err(|_| format!("无效的数字: {}", value))
+}
+
+// u64 以 "0x..." 字符串形式序列化，避免前端 JS number 超过 2^53 时丢失精度
+pub fn util_ser_u64_hex<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("0x{:X}", value))
+}
+
+// u64 反序列化，同时接受 JSON 数字与 "0x..."/十进制字符串
+pub fn util_de_u64_hex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(u64),
+        String(String),
+    }
+
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(n) => Ok(n),
+        NumberOrString::String(s) => util_parse_u64(&s).map_err(serde::de::Error::custom),
+    }
+}
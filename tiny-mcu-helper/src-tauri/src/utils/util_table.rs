@@ -0,0 +1,67 @@
+use serde::Deserialize;
+
+use crate::utils::util_get_generate_path;
+
+/// 查找表输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormat {
+    Csv,
+    C,
+}
+
+impl TableFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TableFormat::Csv => "csv",
+            TableFormat::C => "h",
+        }
+    }
+}
+
+// 生成 CSV：第一行为表头，其余每行一条记录
+pub fn util_format_csv(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut out = headers.join(",");
+    out.push('\n');
+    for row in rows {
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+// 格式化单个数值：整数类型四舍五入，浮点类型保留 decimals 位小数
+pub fn util_format_c_value(value: f64, c_type: &str, decimals: usize) -> String {
+    match c_type {
+        "float" => format!("{:.*}f", decimals, value),
+        "double" => format!("{:.*}", decimals, value),
+        _ => format!("{}", value.round() as i64),
+    }
+}
+
+// 生成 C 数组定义，每行 per_line 个元素
+pub fn util_format_c_array(name: &str, c_type: &str, values: &[String], per_line: usize, comment: &str) -> String {
+    let mut out = String::new();
+    for line in comment.lines() {
+        out.push_str(&format!("// {}\n", line));
+    }
+    out.push_str(&format!("#define {}_SIZE {}\n\n", name.to_uppercase(), values.len()));
+    out.push_str(&format!("static const {} {}[{}] = {{\n", c_type, name, values.len()));
+    for chunk in values.chunks(per_line.max(1)) {
+        out.push_str(&format!("    {},\n", chunk.join(", ")));
+    }
+    out.push_str("};\n");
+    out
+}
+
+// 写入 generate 目录，未带扩展名时补上 extension，返回完整路径
+pub fn util_write_generate_file(file_name: &str, extension: &str, content: &str) -> Result<String, String> {
+    let file_name = if std::path::Path::new(file_name).extension().is_some() {
+        file_name.to_string()
+    } else {
+        format!("{}.{}", file_name, extension)
+    };
+    let file_path = util_get_generate_path()?.join(file_name);
+    std::fs::write(&file_path, content).map_err(|e| format!("写入文件失败: {}", e))?;
+    Ok(file_path.to_string_lossy().to_string())
+}
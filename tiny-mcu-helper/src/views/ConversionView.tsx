@@ -4,6 +4,9 @@ import TabPanel from "tdesign-react/es/tabs/TabPanel";
 import BaseConvertPage from "@views/conversion/BaseConvertPage.tsx";
 import StorageConvertPage from "@views/conversion/StorageConvertPage.tsx";
 import MarkdownPdfConvertPage from "@views/conversion/MarkdownPdfConvertPage.tsx";
+import MemoryUsagePage from "@views/conversion/MemoryUsagePage.tsx";
+import MemoryLayoutPage from "@views/conversion/MemoryLayoutPage.tsx";
+import JobQueuePage from "@views/conversion/JobQueuePage.tsx";
 
 function ConversionView() {
   return (
@@ -38,6 +41,21 @@ function ConversionView() {
               <MarkdownPdfConvertPage/>
             </div>
           </TabPanel>
+          <TabPanel value={4} label="内存占用">
+            <div className="h-[calc(100vh-80px)] w-full p-1 overflow-auto">
+              <MemoryUsagePage/>
+            </div>
+          </TabPanel>
+          <TabPanel value={5} label="内存布局">
+            <div className="h-[calc(100vh-80px)] w-full p-1 overflow-auto">
+              <MemoryLayoutPage/>
+            </div>
+          </TabPanel>
+          <TabPanel value={6} label="后台任务">
+            <div className="h-[calc(100vh-80px)] w-full p-1 overflow-auto">
+              <JobQueuePage/>
+            </div>
+          </TabPanel>
         </Tabs>
       </div>
   );
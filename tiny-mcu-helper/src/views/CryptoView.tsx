@@ -4,6 +4,10 @@ import TabPanel from "tdesign-react/es/tabs/TabPanel";
 import XorSumPage from "@views/crypto/XorSumPage.tsx";
 import Crc32Page from "@views/crypto/Crc32Page.tsx";
 import Base64Page from "@views/crypto/Base64Page.tsx";
+import HashPage from "@views/crypto/HashPage.tsx";
+import SecretStorePage from "@views/crypto/SecretStorePage.tsx";
+import SigningPage from "@views/crypto/SigningPage.tsx";
+import FirmwareEncryptPage from "@views/crypto/FirmwareEncryptPage.tsx";
 
 function CryptoView() {
   return (
@@ -38,6 +42,26 @@ function CryptoView() {
               <Base64Page/>
             </div>
           </TabPanel>
+          <TabPanel value={4} label="摘要/HMAC">
+            <div className="h-[calc(100vh-80px)] w-full p-1 overflow-auto">
+              <HashPage/>
+            </div>
+          </TabPanel>
+          <TabPanel value={5} label="密钥库">
+            <div className="h-[calc(100vh-80px)] w-full p-1 overflow-auto">
+              <SecretStorePage/>
+            </div>
+          </TabPanel>
+          <TabPanel value={6} label="固件签名">
+            <div className="h-[calc(100vh-80px)] w-full p-1 overflow-auto">
+              <SigningPage/>
+            </div>
+          </TabPanel>
+          <TabPanel value={7} label="固件加密">
+            <div className="h-[calc(100vh-80px)] w-full p-1 overflow-auto">
+              <FirmwareEncryptPage/>
+            </div>
+          </TabPanel>
         </Tabs>
       </div>
   );
@@ -5,6 +5,7 @@ import ClockCalcPage from "@views/peripheral/ClockCalcPage.tsx";
 import PwmCalcPage from "@views/peripheral/PwmCalcPage.tsx";
 import TodoListPage from "@views/tool/TodoListPage.tsx";
 import DocGeneratorPage from "@views/tool/DocGeneratorPage.tsx";
+import DocTemplatePage from "@views/tool/DocTemplatePage.tsx";
 
 function ToolView() {
   return (
@@ -33,6 +34,11 @@ function ToolView() {
               <DocGeneratorPage/>
             </div>
           </TabPanel>
+          <TabPanel value={3} label="发布文档">
+            <div className="h-[calc(100vh-80px)] w-full p-1 overflow-auto">
+              <DocTemplatePage/>
+            </div>
+          </TabPanel>
         </Tabs>
       </div>
   );
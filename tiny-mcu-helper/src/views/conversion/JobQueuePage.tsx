@@ -0,0 +1,108 @@
+import {useEffect, useState} from 'react';
+import {invoke} from "@tauri-apps/api/core";
+import {listen} from "@tauri-apps/api/event";
+import {message} from "@tauri-apps/plugin-dialog";
+import {Button, Progress, Space, Table, Tag} from "tdesign-react";
+import {RefreshIcon} from "tdesign-icons-react";
+
+// 与后端 JobRecord 对应
+interface JobRecord {
+  id: number;
+  title: string;
+  status: string;
+  total: number;
+  done: number;
+  failed: number;
+  message: string | null;
+  created_at: string;
+  finished_at: string | null;
+}
+
+const STATUS_LABELS: Record<string, { label: string, theme: 'default' | 'primary' | 'success' | 'danger' | 'warning' }> = {
+  queued: {label: '排队中', theme: 'default'},
+  running: {label: '运行中', theme: 'primary'},
+  completed: {label: '已完成', theme: 'success'},
+  failed: {label: '失败', theme: 'danger'},
+  cancelled: {label: '已取消', theme: 'warning'},
+  interrupted: {label: '已中断', theme: 'warning'},
+};
+
+// 后台任务队列与历史
+function JobQueuePage() {
+  const [jobs, setJobs] = useState<JobRecord[]>([]);
+
+  const refresh = async () => setJobs(await invoke<JobRecord[]>("job_list", {limit: 200}));
+
+  useEffect(() => {
+    refresh().catch(e => message(String(e)));
+    // 进度事件只更新对应行，结束事件重新拉取列表
+    const progress = listen<{ job_id: number, done: number, failed: number }>("job-progress", e => {
+      setJobs(list => list.map(j => j.id === e.payload.job_id
+          ? {...j, status: 'running', done: e.payload.done, failed: e.payload.failed}
+          : j));
+    });
+    const done = listen("job-done", () => refresh());
+    return () => {
+      progress.then(f => f());
+      done.then(f => f());
+    };
+  }, []);
+
+  const run = async (cmd: string, jobId: number) => {
+    try {
+      await invoke(cmd, {jobId});
+      await refresh();
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  const columns = [
+    {colKey: 'id', title: 'ID', width: 60},
+    {colKey: 'title', title: '任务'},
+    {
+      colKey: 'status', title: '状态', width: 90,
+      cell: ({row}: { row: JobRecord }) => {
+        const s = STATUS_LABELS[row.status] ?? {label: row.status, theme: 'default'};
+        return <Tag theme={s.theme} variant="light">{s.label}</Tag>;
+      },
+    },
+    {
+      colKey: 'progress', title: '进度', width: 200,
+      cell: ({row}: { row: JobRecord }) => (
+          <Progress percentage={row.total ? Math.round(row.done / row.total * 100) : 0}
+                    label={`${row.done}/${row.total}${row.failed ? `（失败 ${row.failed}）` : ''}`}/>
+      ),
+    },
+    {colKey: 'message', title: '说明'},
+    {colKey: 'created_at', title: '创建时间', width: 170},
+    {
+      colKey: 'op', title: '操作', width: 180,
+      cell: ({row}: { row: JobRecord }) => (
+          <Space size="small">
+            {(row.status === 'queued' || row.status === 'running') &&
+                <Button size="small" variant="text" theme="warning" onClick={() => run("job_cancel", row.id)}>取消</Button>}
+            {(row.status === 'failed' || row.status === 'interrupted' || row.status === 'cancelled') &&
+                <Button size="small" variant="text" onClick={() => run("job_retry", row.id)}>重试</Button>}
+            {row.status !== 'queued' && row.status !== 'running' &&
+                <Button size="small" variant="text" theme="danger" onClick={() => run("job_delete", row.id)}>删除</Button>}
+          </Space>
+      ),
+    },
+  ];
+
+  return (
+      <div className="h-full flex flex-col gap-3 p-4">
+        <Space>
+          <Button variant="outline" icon={<RefreshIcon/>} onClick={() => refresh()}>刷新</Button>
+          <Button variant="outline" theme="danger" onClick={async () => {
+            await invoke("job_clear_history");
+            await refresh();
+          }}>清空历史</Button>
+        </Space>
+        <Table rowKey="id" data={jobs} columns={columns} size="small"/>
+      </div>
+  );
+}
+
+export default JobQueuePage;
@@ -1,5 +1,5 @@
 import React, {useEffect, useRef, useState} from 'react';
-import {Alert, Button, Card, MessagePlugin, Textarea} from 'tdesign-react';
+import {Alert, Button, Card, Checkbox, Dialog, Input, InputNumber, MessagePlugin, Select, Textarea} from 'tdesign-react';
 import {marked} from 'marked';
 import hljs from 'highlight.js';
 // 引入代码高亮样式
@@ -7,8 +7,38 @@ import 'highlight.js/styles/github-dark.css';
 import 'highlight.js/styles/github.css';
 import '@styles/markdown-styles.css';
 import {useSettingStore} from "@stores/settingStore.ts";
-import {DownloadIcon, FolderOpenIcon} from 'tdesign-icons-react';
+import {DownloadIcon, EditIcon, FolderOpenIcon} from 'tdesign-icons-react';
 import {invoke} from "@tauri-apps/api/core";
+import {listen} from "@tauri-apps/api/event";
+
+// 导出格式
+const EXPORT_FORMATS = [
+  {label: 'PDF', value: 'pdf'},
+  {label: 'HTML', value: 'html'},
+  {label: 'DOCX', value: 'docx'},
+  {label: 'TXT（原文）', value: 'txt'},
+];
+
+// 与后端 MarkdownStyleProfile 对应，其余字段原样回传
+type MarkdownStyleProfile = { name: string } & Record<string, any>;
+
+const PAGE_SIZES = [
+  {label: 'A4', value: 'a4'},
+  {label: 'A5', value: 'a5'},
+  {label: 'Letter', value: 'letter'},
+  {label: '自定义', value: 'custom'},
+];
+
+// 样式编辑框中的数值字段
+const NUMBER_FIELDS: { key: string, label: string, step: number }[] = [
+  {key: 'font_size', label: '正文字号 (pt)', step: 0.5},
+  {key: 'code_font_size', label: '代码字号 (pt)', step: 0.5},
+  {key: 'line_height', label: '行高倍数', step: 0.1},
+  {key: 'margin_top_mm', label: '上边距 (mm)', step: 1},
+  {key: 'margin_bottom_mm', label: '下边距 (mm)', step: 1},
+  {key: 'margin_left_mm', label: '左边距 (mm)', step: 1},
+  {key: 'margin_right_mm', label: '右边距 (mm)', step: 1},
+];
 
 function MarkdownPdfConvertPage() {
   // 从 zustand 获取主题状态
@@ -24,6 +54,16 @@ function MarkdownPdfConvertPage() {
   const [isExporting, setIsExporting] = useState(false);
   const [noticeText, setNoticeText] = useState('目前没有导出任何文件...');
 
+  // 导出格式与样式（样式为空时 PDF 使用默认样式）
+  const [exportFormat, setExportFormat] = useState('pdf');
+  const [styles, setStyles] = useState<MarkdownStyleProfile[]>([]);
+  const [styleName, setStyleName] = useState('');
+  const [editingStyle, setEditingStyle] = useState<MarkdownStyleProfile | null>(null);
+
+  // 当前导出任务 id，导出在后台任务队列中执行；任务可能在 job_submit 返回前就已结束，先缓存事件
+  const exportJob = useRef<number | null>(null);
+  const jobEvents = useRef(new Map<number, { notice?: string, done?: boolean }>());
+
   // 文件输入框引用
   const fileInputRef = useRef(null);
 
@@ -38,17 +78,102 @@ function MarkdownPdfConvertPage() {
     });
   }, []);
 
-  const handleExportPDF = async () => {
+  // 读取已保存的导出样式
+  const loadStyles = () => invoke<MarkdownStyleProfile[]>("markdown_style_list")
+      .then(setStyles)
+      .catch(() => setStyles([]));
+
+  useEffect(() => {
+    loadStyles();
+  }, []);
+
+  // 编辑当前样式，未选择时以默认样式为模板新建
+  const openStyleEditor = async () => {
+    const current = styles.find(s => s.name === styleName);
+    setEditingStyle(current ? {...current} : await invoke<MarkdownStyleProfile>("markdown_style_default"));
+  };
+
+  const updateStyle = (key: string, value: unknown) => {
+    setEditingStyle(style => style && {...style, [key]: value});
+  };
+
+  const saveStyle = async () => {
+    if (!editingStyle) return;
+    try {
+      await invoke("markdown_style_save", {profile: editingStyle});
+      await loadStyles();
+      setStyleName(editingStyle.name.trim());
+      setEditingStyle(null);
+      MessagePlugin.success('样式已保存');
+    } catch (error) {
+      MessagePlugin.error(String(error));
+    }
+  };
+
+  const deleteStyle = async () => {
+    if (!editingStyle) return;
+    try {
+      await invoke("markdown_style_delete", {name: editingStyle.name});
+      await loadStyles();
+      if (styleName === editingStyle.name) setStyleName('');
+      setEditingStyle(null);
+    } catch (error) {
+      MessagePlugin.error(String(error));
+    }
+  };
+
+  // 将任务事件应用到当前导出
+  const applyJobEvent = (jobId: number) => {
+    const event = jobEvents.current.get(jobId);
+    if (jobId !== exportJob.current || !event) return;
+    if (event.notice) setNoticeText(event.notice);
+    if (event.done) {
+      jobEvents.current.delete(jobId);
+      exportJob.current = null;
+      setIsExporting(false);
+    }
+  };
+
+  // 导出结果来自后台任务事件
+  useEffect(() => {
+    const record = (jobId: number, update: { notice?: string, done?: boolean }) => {
+      if (exportJob.current !== null && jobId !== exportJob.current) return;
+      jobEvents.current.set(jobId, {...jobEvents.current.get(jobId), ...update});
+      applyJobEvent(jobId);
+    };
+    const progress = listen<{ job_id: number, item: { output_path: string | null, error: string | null } }>("job-progress", e => {
+      const item = e.payload.item;
+      record(e.payload.job_id, {notice: item.error ? `导出失败: ${item.error}` : `成功导出至 ${item.output_path}`});
+    });
+    const done = listen<{ job_id: number, status: string, message: string | null }>("job-done", e => {
+      const notice = e.payload.status === 'cancelled' ? e.payload.message ?? '导出已取消' : undefined;
+      record(e.payload.job_id, notice ? {notice, done: true} : {done: true});
+    });
+    return () => {
+      progress.then(f => f());
+      done.then(f => f());
+    };
+  }, []);
+
+  const handleExport = async () => {
+    const title = fileName.replace(/\.(md|markdown)$/, '') || 'export';
+    const task = exportFormat === 'txt'
+        ? {type: 'text_file', file_title: title, content: markdownContent}
+        : {
+          type: 'markdown_export',
+          markdown: markdownContent,
+          file_name: title,
+          format: exportFormat,
+          profile: styles.find(s => s.name === styleName) ?? null,
+        };
     setIsExporting(true)
+    jobEvents.current.clear();
     try {
-      await invoke("convert_markdown_to_pdf", {
-        markdownContent: markdownContent,
-        pdfFileName: "export",
-      })
-      setNoticeText("成功导出至根目录 generate/export.pdf")
-      setIsExporting(false)
+      setNoticeText('已提交到后台任务队列，正在导出...')
+      exportJob.current = await invoke<number>("job_submit", {spec: {title: `导出 ${title}`, tasks: [task]}});
+      applyJobEvent(exportJob.current);
     } catch (error) {
-      setNoticeText("导出失败")
+      setNoticeText(`导出失败: ${error}`)
       setIsExporting(false)
     }
   };
@@ -113,16 +238,39 @@ function MarkdownPdfConvertPage() {
           <div className="container mx-auto py-4 flex justify-between items-center">
 
             <div className='flex flex-row gap-2'>
+              <Select
+                  value={exportFormat}
+                  onChange={(v) => setExportFormat(v as string)}
+                  options={EXPORT_FORMATS}
+                  style={{width: 100}}
+              />
+              <Select
+                  value={styleName}
+                  onChange={(v) => setStyleName((v as string) ?? '')}
+                  options={styles.map(s => ({label: s.name, value: s.name}))}
+                  disabled={exportFormat === 'txt'}
+                  placeholder="默认样式"
+                  clearable
+                  style={{width: 160}}
+              />
+              <Button
+                  onClick={openStyleEditor}
+                  variant="outline"
+                  className="h-10"
+                  icon={<EditIcon/>}
+              >
+                样式
+              </Button>
               <Button
                   disabled={markdownContent === ''}
-                  onClick={handleExportPDF}
+                  onClick={handleExport}
                   theme="primary"
                   variant="base"
                   className="flex items-center gap-2 px-4 py-2 h-10"
                   loading={isExporting}
                   icon={<DownloadIcon/>}
               >
-                导出PDF
+                导出
               </Button>
 
               <Button
@@ -142,6 +290,57 @@ function MarkdownPdfConvertPage() {
             </div>
 
 
+            {/* 导出样式编辑 */}
+            <Dialog
+                header="导出样式"
+                visible={editingStyle !== null}
+                width={560}
+                onClose={() => setEditingStyle(null)}
+                onCancel={() => setEditingStyle(null)}
+                onConfirm={saveStyle}
+                confirmBtn="保存"
+            >
+              {editingStyle && (
+                  <div className="flex flex-col gap-2">
+                    <Input value={editingStyle.name} label="名称：" onChange={v => updateStyle('name', v)}/>
+                    <div className="flex flex-row gap-2 items-center">
+                      <Select value={editingStyle.page_size} options={PAGE_SIZES} style={{width: 120}}
+                              onChange={v => updateStyle('page_size', v)}/>
+                      {editingStyle.page_size === 'custom' && (
+                          <>
+                            <InputNumber value={editingStyle.page_width_mm} min={50} max={2000} suffix="mm"
+                                         onChange={v => updateStyle('page_width_mm', v)}/>
+                            <InputNumber value={editingStyle.page_height_mm} min={50} max={2000} suffix="mm"
+                                         onChange={v => updateStyle('page_height_mm', v)}/>
+                          </>
+                      )}
+                      <Checkbox checked={editingStyle.landscape} onChange={v => updateStyle('landscape', v)}>横向</Checkbox>
+                    </div>
+                    <div className="grid grid-cols-2 gap-2">
+                      {NUMBER_FIELDS.map(f => (
+                          <div key={f.key} className="flex flex-row items-center gap-2">
+                            <span className="text-sm w-28">{f.label}</span>
+                            <InputNumber value={editingStyle[f.key]} step={f.step} min={0}
+                                         onChange={v => updateStyle(f.key, v)}/>
+                          </div>
+                      ))}
+                    </div>
+                    <Input value={editingStyle.cjk_font_path ?? ''} label="中文字体文件：" placeholder="为空时查找系统字体"
+                           onChange={v => updateStyle('cjk_font_path', v || null)}/>
+                    <Input value={editingStyle.header ?? ''} label="页眉：" placeholder="左|中|右，可用 {title} {page} {pages} {date}"
+                           onChange={v => updateStyle('header', v || null)}/>
+                    <Input value={editingStyle.footer ?? ''} label="页脚：" placeholder="格式同页眉"
+                           onChange={v => updateStyle('footer', v || null)}/>
+                    <Input value={editingStyle.accent_color} label="强调色：" onChange={v => updateStyle('accent_color', v)}/>
+                    {styles.some(s => s.name === editingStyle.name) && (
+                        <div>
+                          <Button theme="danger" variant="text" onClick={deleteStyle}>删除此样式</Button>
+                        </div>
+                    )}
+                  </div>
+              )}
+            </Dialog>
+
             {/* 隐藏的文件输入框 */}
             <input
                 ref={fileInputRef}
@@ -0,0 +1,161 @@
+import {useEffect, useState} from 'react';
+import {invoke} from "@tauri-apps/api/core";
+import {message} from "@tauri-apps/plugin-dialog";
+import {Button, Input, Select, Space, Textarea} from "tdesign-react";
+import {CheckIcon, DeleteIcon, PlayIcon, SaveIcon} from "tdesign-icons-react";
+
+// 输出格式，与后端 LayoutOutputFormat 对应
+const OUTPUT_FORMATS = [
+  {value: 'linker_memory', label: 'GNU ld MEMORY 块'},
+  {value: 'c_header', label: 'C 头文件'},
+  {value: 'markdown', label: 'Markdown 分区表'},
+];
+
+// 新建布局的模板：地址与大小可写数字、"0x..." 或 "64K"
+const LAYOUT_TEMPLATE = {
+  name: 'new-layout',
+  description: '',
+  regions: [
+    {name: 'FLASH', kind: 'flash', origin: '0x08000000', length: '128K', sector_size: '2K'},
+    {name: 'RAM', kind: 'ram', origin: '0x20000000', length: '20K'},
+  ],
+  partitions: [
+    {name: 'bootloader', region: 'FLASH', role: 'bootloader', size: '16K'},
+    {name: 'app', region: 'FLASH', role: 'app', size: '104K', align: '0x200'},
+    {name: 'config', region: 'FLASH', role: 'config'},
+  ],
+};
+
+interface MemoryLayoutRecord {
+  id: number;
+  name: string;
+}
+
+interface LayoutIssue {
+  level: 'error' | 'warning';
+  message: string;
+}
+
+function MemoryLayoutPage() {
+  const [layouts, setLayouts] = useState<MemoryLayoutRecord[]>([]);
+  const [layoutId, setLayoutId] = useState<number | null>(null);
+  const [text, setText] = useState<string>(JSON.stringify(LAYOUT_TEMPLATE, null, 2));
+  const [format, setFormat] = useState<string>('linker_memory');
+  const [target, setTarget] = useState<string>('');
+  const [fileName, setFileName] = useState<string>('');
+  const [output, setOutput] = useState<string>('');
+
+  const refresh = async () => setLayouts(await invoke<MemoryLayoutRecord[]>("memory_layout_list"));
+
+  useEffect(() => {
+    refresh().catch(e => message(String(e)));
+  }, []);
+
+  const parse = () => {
+    try {
+      return JSON.parse(text);
+    } catch (e) {
+      message(`布局 JSON 格式错误: ${e}`);
+      return null;
+    }
+  };
+
+  const load = async (id: number) => {
+    try {
+      const layout = await invoke("memory_layout_get", {layoutId: id});
+      setLayoutId(id);
+      setText(JSON.stringify(layout, null, 2));
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  const save = async () => {
+    const layout = parse();
+    if (!layout) return;
+    try {
+      setLayoutId(await invoke<number>("memory_layout_save", {layoutId, layout}));
+      await refresh();
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  const remove = async () => {
+    if (layoutId === null) return;
+    try {
+      await invoke("memory_layout_delete", {layoutId});
+      setLayoutId(null);
+      await refresh();
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  const validate = async () => {
+    const layout = parse();
+    if (!layout) return;
+    try {
+      const plan = await invoke<{ issues: LayoutIssue[] }>("memory_layout_validate", {layout});
+      setOutput(plan.issues.length
+          ? plan.issues.map(i => `[${i.level === 'error' ? '错误' : '警告'}] ${i.message}`).join('\n')
+          : '未发现问题');
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  const generate = async () => {
+    const layout = parse();
+    if (!layout) return;
+    try {
+      const result = await invoke<{ content: string, output_path: string | null }>("memory_layout_generate", {
+        layout,
+        format,
+        target: target.trim() || null,
+        fileName: fileName.trim() || null,
+      });
+      setOutput(result.content);
+      if (result.output_path) {
+        await message(`已保存到 ${result.output_path}`);
+      }
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  return (
+      <div className="h-full flex flex-col gap-3 p-4">
+        <Space>
+          <Select
+              style={{width: 240}}
+              value={layoutId ?? undefined}
+              placeholder="选择已保存的布局"
+              options={layouts.map(l => ({value: l.id, label: l.name}))}
+              onChange={v => load(v as number)}
+          />
+          <Button variant="outline" onClick={() => {
+            setLayoutId(null);
+            setText(JSON.stringify(LAYOUT_TEMPLATE, null, 2));
+          }}>新建</Button>
+          <Button variant="outline" icon={<SaveIcon/>} onClick={save}>保存</Button>
+          <Button variant="outline" theme="danger" icon={<DeleteIcon/>} disabled={layoutId === null}
+                  onClick={remove}>删除</Button>
+        </Space>
+        <Space>
+          <Select style={{width: 200}} value={format} options={OUTPUT_FORMATS} onChange={v => setFormat(v as string)}/>
+          <Input style={{width: 200}} value={target} placeholder="链接目标分区（可选）" onChange={v => setTarget(v)}/>
+          <Input style={{width: 200}} value={fileName} placeholder="保存文件名（留空仅预览）"
+                 onChange={v => setFileName(v)}/>
+          <Button variant="outline" icon={<CheckIcon/>} onClick={validate}>校验</Button>
+          <Button icon={<PlayIcon/>} onClick={generate}>生成</Button>
+        </Space>
+        <div className="flex-1 flex gap-3">
+          <Textarea className="flex-1 font-mono" value={text} autosize={{minRows: 24}} onChange={v => setText(v)}/>
+          <Textarea className="flex-1 font-mono" value={output} readOnly autosize={{minRows: 24}}/>
+        </div>
+      </div>
+  );
+}
+
+export default MemoryLayoutPage;
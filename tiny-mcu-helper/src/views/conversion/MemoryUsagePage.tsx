@@ -0,0 +1,85 @@
+import {useState} from 'react';
+import {invoke} from "@tauri-apps/api/core";
+import {message, open} from "@tauri-apps/plugin-dialog";
+import {Button, Input, InputNumber, Space, Textarea} from "tdesign-react";
+import {FileImportIcon, PlayIcon} from "tdesign-icons-react";
+
+// 与后端 MemoryInput 对应
+interface MemoryInput {
+  elf_path: string | null;
+  map_path: string | null;
+}
+
+const EMPTY_INPUT: MemoryInput = {elf_path: null, map_path: null};
+
+// ELF / map 文件内存占用分析与构建对比
+function MemoryUsagePage() {
+  const [base, setBase] = useState<MemoryInput>(EMPTY_INPUT);
+  const [current, setCurrent] = useState<MemoryInput>(EMPTY_INPUT);
+  const [topN, setTopN] = useState<number>(20);
+  const [fileName, setFileName] = useState<string>('');
+  const [markdown, setMarkdown] = useState<string>('');
+  const [loading, setLoading] = useState<boolean>(false);
+
+  const pick = async (kind: 'elf' | 'map', input: MemoryInput, setInput: (v: MemoryInput) => void) => {
+    const filePath = await open({
+      filters: kind === 'elf'
+          ? [{name: 'ELF', extensions: ['elf', 'axf', 'out']}]
+          : [{name: 'Map', extensions: ['map']}],
+    });
+    if (!filePath) return;
+    setInput({...input, [`${kind}_path`]: filePath as string});
+  };
+
+  const hasInput = (input: MemoryInput) => !!(input.elf_path || input.map_path);
+
+  const run = async (diff: boolean) => {
+    if (!hasInput(current) || (diff && !hasInput(base))) {
+      await message('请先选择 ELF 或 map 文件');
+      return;
+    }
+    try {
+      setLoading(true);
+      const args = {topN, fileName: fileName.trim() || null};
+      const result = diff
+          ? await invoke<{ markdown: string, output_path: string | null }>("memory_usage_diff", {base, current, ...args})
+          : await invoke<{ markdown: string, output_path: string | null }>("memory_usage_analyze", {input: current, ...args});
+      setMarkdown(result.markdown);
+      if (result.output_path) {
+        await message(`已保存到 ${result.output_path}，可在 Markdown 页导出 PDF`);
+      }
+    } catch (e) {
+      await message(String(e));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const fileRow = (label: string, input: MemoryInput, setInput: (v: MemoryInput) => void) => (
+      <Space>
+        <span className="w-12">{label}</span>
+        <Input style={{width: 260}} value={input.elf_path ?? ''} placeholder="ELF 文件" readonly/>
+        <Button variant="outline" icon={<FileImportIcon/>} onClick={() => pick('elf', input, setInput)}>ELF</Button>
+        <Input style={{width: 260}} value={input.map_path ?? ''} placeholder="map 文件" readonly/>
+        <Button variant="outline" icon={<FileImportIcon/>} onClick={() => pick('map', input, setInput)}>Map</Button>
+        <Button variant="text" onClick={() => setInput(EMPTY_INPUT)}>清空</Button>
+      </Space>
+  );
+
+  return (
+      <div className="h-full flex flex-col gap-3 p-4">
+        {fileRow('当前', current, setCurrent)}
+        {fileRow('基准', base, setBase)}
+        <Space>
+          <InputNumber style={{width: 140}} value={topN} min={1} label="Top N" onChange={v => setTopN(Number(v))}/>
+          <Input style={{width: 240}} value={fileName} placeholder="保存文件名（留空仅预览）"
+                 onChange={v => setFileName(v)}/>
+          <Button icon={<PlayIcon/>} loading={loading} onClick={() => run(false)}>分析</Button>
+          <Button variant="outline" loading={loading} onClick={() => run(true)}>与基准对比</Button>
+        </Space>
+        <Textarea className="flex-1 font-mono" value={markdown} readOnly autosize={{minRows: 20}}/>
+      </div>
+  );
+}
+
+export default MemoryUsagePage;
@@ -1,6 +1,7 @@
 import {useRef, useState} from 'react';
 import {Button, Card, Select, Space, Textarea, Tooltip} from 'tdesign-react';
 import {CheckIcon, CopyIcon, InfoCircleIcon, RefreshIcon} from 'tdesign-icons-react';
+import {invoke} from "@tauri-apps/api/core";
 import {message} from "@tauri-apps/plugin-dialog";
 
 // 单位类型选项
@@ -35,24 +36,15 @@ export default function StorageConvertPage() {
   };
 
   // 解析输入值
-  const parseInput = (input: string): number | null => {
-    // 尝试匹配内存区间格式（仅对寄存器内存模式有效）
-    if (selectedUnit === 'register') {
-      // 尝试匹配内存区间格式，如 0x0000-0x4000 或 0x0-0x4000
-      const rangeRegex = /^(0x[0-9a-fA-F]+)\s*-\s*(0x[0-9a-fA-F]+)$/;
-      const rangeMatch = input.trim().match(rangeRegex);
-      
-      if (rangeMatch) {
-        const startAddr = parseInt(rangeMatch[1], 16);
-        const endAddr = parseInt(rangeMatch[2], 16);
-        
-        if (endAddr >= startAddr) {
-          // 计算内存大小（字节），注意地址区间通常是包含起始和结束地址的
-          return endAddr - startAddr + 1;
-        } else {
-          message('结束地址不能小于起始地址');
-          return null;
-        }
+  const parseInput = async (input: string): Promise<number | null> => {
+    // 内存区间（仅对寄存器内存模式有效）交给后端解析，与内存布局工具保持一致
+    if (selectedUnit === 'register' && /[-~]/.test(input)) {
+      try {
+        const [, length] = await invoke<[number, number]>("memory_layout_parse_range", {text: input.trim()});
+        return length;
+      } catch (error) {
+        await message(String(error));
+        return null;
       }
     }
     
@@ -94,12 +86,12 @@ export default function StorageConvertPage() {
       }
     }
     
-    message(`请输入有效的数字${selectedUnit === 'register' ? '、十六进制数值（如 0x3FFFF）或内存区间（如 0x0000-0xFFFFFFFF）' : ''}`);
+    message(`请输入有效的数字${selectedUnit === 'register' ? '、十六进制数值（如 0x3FFFF）或内存区间（如 0x0000-0x4000）' : ''}`);
     return null;
   };
 
   // 执行存储单位转换
-  const performConversion = () => {
+  const performConversion = async () => {
     const value = inputValue.trim();
     
     if (!value) {
@@ -108,7 +100,7 @@ export default function StorageConvertPage() {
     }
 
     try {
-      const bytes = parseInput(value);
+      const bytes = await parseInput(value);
       
       if (bytes === null) {
         return;
@@ -215,7 +207,7 @@ export default function StorageConvertPage() {
                 />
               </div>
               <div className="mt-2 text-xs text-gray-500" style={{ whiteSpace: 'pre-line' }}>
-                  提示：{selectedUnit === 'register' ? '\n支持输入寄存器长度（如 4000）\n十六进制数值（如 0x3FFFF）\n 或内存区间（如 0x0000-0xFFFFFFFF）' : `输入${unitOptions.find(opt => opt.value === selectedUnit)?.label}单位的数值`}
+                  提示：{selectedUnit === 'register' ? '\n支持输入寄存器长度（如 4000）\n十六进制数值（如 0x3FFFF）\n 或内存区间（如 0x0000-0x4000，不含结束地址）' : `输入${unitOptions.find(opt => opt.value === selectedUnit)?.label}单位的数值`}
               </div>
             </div>
 
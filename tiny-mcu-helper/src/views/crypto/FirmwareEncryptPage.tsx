@@ -0,0 +1,203 @@
+import {useEffect, useState} from 'react';
+import {invoke} from "@tauri-apps/api/core";
+import {message, open} from "@tauri-apps/plugin-dialog";
+import {Alert, Button, Divider, Input, Popconfirm, Select, Space, Table, Tag, Textarea} from "tdesign-react";
+import {FileImportIcon} from "tdesign-icons-react";
+
+// 与后端 FirmwareKeyInfo 对应
+interface FirmwareKeyInfo {
+  name: string;
+  key_bits: number;
+  kcv: string;
+}
+
+// 与后端 FirmwareEncryptHeader 对应
+interface FirmwareEncryptHeader {
+  key_bits: number;
+  mode: 'ctr' | 'cbc';
+  padding: 'none' | 'pkcs7' | 'zero';
+  address: number;
+  length: number;
+  crc32: number;
+  payload_crc32: number;
+  iv: string;
+}
+
+const PADDINGS = [
+  {value: 'none', label: '不填充'},
+  {value: 'pkcs7', label: 'PKCS7'},
+  {value: 'zero', label: '补 0'},
+];
+
+const OUTPUT_FORMATS = [
+  {value: 'hex', label: 'Intel HEX'},
+  {value: 'srec', label: 'S-record'},
+  {value: 'bin', label: 'BIN'},
+];
+
+const hex32 = (v: number) => '0x' + v.toString(16).toUpperCase().padStart(8, '0');
+
+// 解析十进制或 0x 开头的十六进制地址，空字符串返回 null
+const parseAddress = (v: string) => v.trim() ? Number(v.trim()) : null;
+
+const describe = (path: string, h: FirmwareEncryptHeader) => [
+  `文件: ${path}`,
+  `AES-${h.key_bits}-${h.mode.toUpperCase()}，填充: ${PADDINGS.find(p => p.value === h.padding)?.label}`,
+  `加载地址: ${hex32(h.address)}，明文长度: ${h.length} 字节`,
+  `明文 CRC-32: ${hex32(h.crc32)}，密文 CRC-32: ${hex32(h.payload_crc32)}`,
+  `IV: ${h.iv}`,
+].join('\n');
+
+// OTA 固件加密：密钥保存在机密存储中，镜像头部格式见后端 fun_firmware_crypto
+function FirmwareEncryptPage() {
+  const [keys, setKeys] = useState<FirmwareKeyInfo[]>([]);
+  const [keyName, setKeyName] = useState<string>('');
+  const [keyBits, setKeyBits] = useState<number>(128);
+  const [importKey, setImportKey] = useState<string>('');
+  const [selected, setSelected] = useState<string>('');
+  const [firmwarePath, setFirmwarePath] = useState<string>('');
+  const [baseAddress, setBaseAddress] = useState<string>('0x08000000');
+  const [rangeStart, setRangeStart] = useState<string>('');
+  const [rangeEnd, setRangeEnd] = useState<string>('');
+  const [mode, setMode] = useState<string>('ctr');
+  const [padding, setPadding] = useState<string>('none');
+  const [iv, setIv] = useState<string>('');
+  const [fileName, setFileName] = useState<string>('firmware_enc');
+  const [encryptedPath, setEncryptedPath] = useState<string>('');
+  const [format, setFormat] = useState<string>('hex');
+  const [output, setOutput] = useState<string>('');
+
+  const refresh = async () => setKeys(await invoke<FirmwareKeyInfo[]>("firmware_key_list"));
+
+  useEffect(() => {
+    refresh().catch(e => message(String(e)));
+  }, []);
+
+  const run = async (action: () => Promise<unknown>) => {
+    try {
+      await action();
+      await refresh();
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  const createKey = () => run(async () => {
+    if (importKey.trim()) {
+      await invoke("firmware_key_import", {name: keyName, key: importKey});
+    } else {
+      await invoke("firmware_key_generate", {name: keyName, keyBits});
+    }
+    setImportKey('');
+    setKeyName('');
+  });
+
+  const exportKey = (name: string) => run(async () => {
+    const result = await invoke<{ content: string, output_path: string | null }>("firmware_key_export", {
+      name, arrayName: null, fileName: `${name}_aes_key`,
+    });
+    setOutput(result.content);
+    await message(`已保存到 ${result.output_path}`);
+  });
+
+  const encrypt = () => run(async () => {
+    const result = await invoke<{ output_path: string, header: FirmwareEncryptHeader, warnings: string[] }>("firmware_encrypt", {
+      keyName: selected,
+      request: {
+        input: {path: firmwarePath, base_address: parseAddress(baseAddress)},
+        range_start: parseAddress(rangeStart),
+        range_end: parseAddress(rangeEnd),
+        mode,
+        padding,
+        iv: iv.trim() || null,
+        file_name: fileName.trim() || 'firmware_enc',
+      },
+    });
+    setEncryptedPath(result.output_path);
+    setOutput([...result.warnings.map(w => `警告: ${w}`), describe(result.output_path, result.header)].join('\n'));
+  });
+
+  const decrypt = () => run(async () => {
+    const result = await invoke<{ output_path: string, header: FirmwareEncryptHeader }>("firmware_decrypt", {
+      keyName: selected,
+      request: {
+        path: encryptedPath,
+        output: {format, file_name: `${fileName.trim() || 'firmware'}_dec`},
+      },
+    });
+    setOutput('解密成功，CRC 校验通过\n' + describe(result.output_path, result.header));
+  });
+
+  const pickFile = async (setter: (v: string) => void, extensions: string[]) => {
+    const filePath = await open({filters: [{name: extensions.join('/'), extensions}]});
+    if (filePath) setter(filePath as string);
+  };
+
+  const columns = [
+    {colKey: 'name', title: '名称', width: 160},
+    {colKey: 'key_bits', title: '算法', width: 120, cell: ({row}: { row: FirmwareKeyInfo }) => `AES-${row.key_bits}`},
+    {colKey: 'kcv', title: '校验值 (KCV)'},
+    {
+      colKey: 'op', title: '操作', width: 160,
+      cell: ({row}: { row: FirmwareKeyInfo }) => (
+          <Space size="small">
+            <Button size="small" variant="text" onClick={() => exportKey(row.name)}>导出 C 数组</Button>
+            <Popconfirm theme="danger" content={`删除固件密钥 ${row.name}？删除后用该密钥加密的镜像将无法解密`}
+                        onConfirm={() => run(() => invoke("firmware_key_delete", {name: row.name}))}>
+              <Button size="small" variant="text" theme="danger">删除</Button>
+            </Popconfirm>
+          </Space>
+      ),
+    },
+  ];
+
+  return (
+      <div className="h-full flex flex-col gap-3 p-4">
+        <Space>
+          <Input style={{width: 160}} value={keyName} placeholder="密钥名称" onChange={v => setKeyName(v)}/>
+          <Select style={{width: 120}} value={keyBits} disabled={!!importKey.trim()} onChange={v => setKeyBits(v as number)}
+                  options={[{value: 128, label: 'AES-128'}, {value: 256, label: 'AES-256'}]}/>
+          <Input style={{width: 320}} type="password" value={importKey} placeholder="导入密钥（十六进制，留空则随机生成）"
+                 onChange={v => setImportKey(v)}/>
+          <Button disabled={!keyName.trim()} onClick={createKey}>{importKey.trim() ? '导入' : '生成'}</Button>
+          <Tag variant="light">密钥保存在机密存储中，需先解锁</Tag>
+        </Space>
+        <Table rowKey="name" data={keys} columns={columns} size="small"/>
+        <Divider/>
+        <Space>
+          <Input style={{width: 360}} value={firmwarePath} placeholder="固件文件" onChange={v => setFirmwarePath(v)}/>
+          <Button variant="outline" icon={<FileImportIcon/>}
+                  onClick={() => pickFile(setFirmwarePath, ['hex', 'bin', 's19', 'srec'])}>选择固件</Button>
+          <Input style={{width: 140}} value={baseAddress} placeholder="bin 基地址" onChange={v => setBaseAddress(v)}/>
+          <Select style={{width: 160}} value={selected} placeholder="加密密钥"
+                  options={keys.map(k => ({value: k.name, label: `${k.name}（AES-${k.key_bits}）`}))}
+                  onChange={v => setSelected(v as string)}/>
+        </Space>
+        <Space>
+          <Input style={{width: 140}} value={rangeStart} placeholder="起始地址（默认最低）" onChange={v => setRangeStart(v)}/>
+          <Input style={{width: 140}} value={rangeEnd} placeholder="结束地址（默认最高）" onChange={v => setRangeEnd(v)}/>
+          <Select style={{width: 100}} value={mode} onChange={v => setMode(v as string)}
+                  options={[{value: 'ctr', label: 'CTR'}, {value: 'cbc', label: 'CBC'}]}/>
+          <Select style={{width: 110}} value={padding} options={PADDINGS} onChange={v => setPadding(v as string)}/>
+          <Input style={{width: 300}} value={iv} placeholder="IV（十六进制 16 字节，留空随机）" onChange={v => setIv(v)}/>
+          <Input style={{width: 160}} value={fileName} placeholder="输出文件名" onChange={v => setFileName(v)}/>
+          <Button disabled={!firmwarePath || !selected} onClick={encrypt}>加密</Button>
+        </Space>
+        {iv.trim() && (
+            <Alert theme="warning" message={mode === 'ctr'
+                ? 'CTR 模式下同一密钥绝不能重复使用相同 IV，否则可由两份密文推出明文；除非与 Bootloader 约定固定 IV，请留空随机生成'
+                : 'CBC 模式下同一密钥重复使用相同 IV 时，相同的明文前缀会得到相同的密文；除非与 Bootloader 约定固定 IV，请留空随机生成'}/>
+        )}
+        <Space>
+          <Input style={{width: 360}} value={encryptedPath} placeholder="加密镜像" onChange={v => setEncryptedPath(v)}/>
+          <Button variant="outline" icon={<FileImportIcon/>}
+                  onClick={() => pickFile(setEncryptedPath, ['bin'])}>选择镜像</Button>
+          <Select style={{width: 120}} value={format} options={OUTPUT_FORMATS} onChange={v => setFormat(v as string)}/>
+          <Button variant="outline" disabled={!encryptedPath || !selected} onClick={decrypt}>解密校验</Button>
+        </Space>
+        <Textarea className="font-mono" value={output} readOnly autosize={{minRows: 8}}/>
+      </div>
+  );
+}
+
+export default FirmwareEncryptPage;
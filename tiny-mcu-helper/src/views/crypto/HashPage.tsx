@@ -0,0 +1,140 @@
+import {useEffect, useRef, useState} from 'react';
+import {invoke} from "@tauri-apps/api/core";
+import {listen} from "@tauri-apps/api/event";
+import {message, open} from "@tauri-apps/plugin-dialog";
+import {Button, Checkbox, Input, Progress, Radio, RadioGroup, Select, Space, Table, Textarea} from "tdesign-react";
+import {CopyIcon, FileImportIcon} from "tdesign-icons-react";
+
+// 与后端 HashAlgorithmInfo / HashResult 对应
+interface HashAlgorithmInfo {
+  algorithm: string;
+  name: string;
+  output_bits: number;
+  hmac: boolean;
+}
+
+interface HashResult {
+  algorithm: string;
+  name: string;
+  hex: string;
+}
+
+const DEFAULT_ALGORITHMS = ['md5', 'sha1', 'sha256'];
+
+// 摘要 / HMAC 计算：文本与十六进制直接计算，文件在后端分块读取并推送进度
+function HashPage() {
+  const [algorithms, setAlgorithms] = useState<HashAlgorithmInfo[]>([]);
+  const [selected, setSelected] = useState<string[]>(DEFAULT_ALGORITHMS);
+  const [inputType, setInputType] = useState<'text' | 'hex' | 'file'>('text');
+  const [data, setData] = useState<string>('');
+  const [filePath, setFilePath] = useState<string>('');
+  const [hmacKey, setHmacKey] = useState<string>('');
+  const [keyType, setKeyType] = useState<string>('text');
+  const [uppercase, setUppercase] = useState<boolean>(false);
+  const [results, setResults] = useState<HashResult[]>([]);
+  const [percent, setPercent] = useState<number | null>(null);
+  const taskId = useRef<string | null>(null);
+
+  useEffect(() => {
+    invoke<HashAlgorithmInfo[]>("hash_list_algorithms").then(setAlgorithms);
+    const progress = listen<{ task_id: string, processed: number, total: number }>("hash-progress", e => {
+      if (e.payload.task_id === taskId.current) {
+        setPercent(e.payload.total ? Math.floor(e.payload.processed / e.payload.total * 100) : 100);
+      }
+    });
+    return () => {
+      progress.then(f => f());
+    };
+  }, []);
+
+  // 使用 HMAC 时去掉不支持的算法
+  const usable = selected.filter(a => !hmacKey || algorithms.find(i => i.algorithm === a)?.hmac);
+
+  const calculate = async () => {
+    const key = hmacKey ? {hmacKey, keyType} : {hmacKey: null, keyType: null};
+    try {
+      if (inputType === 'file') {
+        taskId.current = `hash-${Date.now()}`;
+        setPercent(0);
+        const result = await invoke<{ results: HashResult[] }>("hash_file", {
+          taskId: taskId.current, path: filePath, algorithms: usable, ...key,
+        });
+        setResults(result.results);
+      } else {
+        setResults(await invoke<HashResult[]>("hash_calculate", {
+          data, inputType, algorithms: usable, ...key,
+        }));
+      }
+    } catch (e) {
+      await message(String(e));
+    } finally {
+      taskId.current = null;
+      setPercent(null);
+    }
+  };
+
+  const cancel = async () => {
+    if (taskId.current) {
+      await invoke("hash_cancel", {taskId: taskId.current}).catch(() => undefined);
+    }
+  };
+
+  const pickFile = async () => {
+    const path = await open();
+    if (path) setFilePath(path as string);
+  };
+
+  const format = (hex: string) => uppercase ? hex.toUpperCase() : hex;
+
+  const columns = [
+    {colKey: 'name', title: '算法', width: 180},
+    {colKey: 'hex', title: '结果', cell: ({row}: { row: HashResult }) => <span className="font-mono break-all">{format(row.hex)}</span>},
+    {
+      colKey: 'op', title: '', width: 60,
+      cell: ({row}: { row: HashResult }) => (
+          <Button size="small" variant="text" icon={<CopyIcon/>}
+                  onClick={() => navigator.clipboard.writeText(format(row.hex))}/>
+      ),
+    },
+  ];
+
+  return (
+      <div className="h-full flex flex-col gap-3 p-4">
+        <Space>
+          <RadioGroup value={inputType} onChange={v => setInputType(v as 'text' | 'hex' | 'file')}>
+            <Radio value="text">文本输入</Radio>
+            <Radio value="hex">十六进制输入</Radio>
+            <Radio value="file">文件</Radio>
+          </RadioGroup>
+          <Checkbox checked={uppercase} onChange={v => setUppercase(v)}>大写输出</Checkbox>
+        </Space>
+        {inputType === 'file' ? (
+            <Space>
+              <Input style={{width: 480}} value={filePath} placeholder="文件路径" onChange={v => setFilePath(v)}/>
+              <Button variant="outline" icon={<FileImportIcon/>} onClick={pickFile}>选择文件</Button>
+            </Space>
+        ) : (
+            <Textarea className="font-mono" value={data} autosize={{minRows: 4}}
+                      placeholder={inputType === 'hex' ? '十六进制数据，例如 01 02 0A FF' : '文本（UTF-8）'}
+                      onChange={v => setData(v)}/>
+        )}
+        <Checkbox.Group value={selected} onChange={v => setSelected(v as string[])}
+                        options={algorithms.map(a => ({
+                          value: a.algorithm, label: a.name, disabled: !!hmacKey && !a.hmac,
+                        }))}/>
+        <Space>
+          <Input style={{width: 320}} type="password" value={hmacKey} placeholder="HMAC 密钥（留空则计算摘要）"
+                 onChange={v => setHmacKey(v)}/>
+          <Select style={{width: 120}} value={keyType} onChange={v => setKeyType(v as string)}
+                  options={[{value: 'text', label: '文本密钥'}, {value: 'hex', label: '十六进制'}]}/>
+          <Button disabled={!usable.length || percent !== null || (inputType === 'file' && !filePath)}
+                  onClick={calculate}>计算</Button>
+          {percent !== null && <Button variant="outline" theme="danger" onClick={cancel}>取消</Button>}
+        </Space>
+        {percent !== null && <Progress percentage={percent}/>}
+        <Table rowKey="name" data={results} columns={columns} size="small"/>
+      </div>
+  );
+}
+
+export default HashPage;
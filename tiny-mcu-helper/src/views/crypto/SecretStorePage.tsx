@@ -0,0 +1,154 @@
+import {useEffect, useState} from 'react';
+import {invoke} from "@tauri-apps/api/core";
+import {message} from "@tauri-apps/plugin-dialog";
+import {Button, Input, Space, Table, Tag, Textarea} from "tdesign-react";
+import {LockOffIcon, LockOnIcon} from "tdesign-icons-react";
+
+// 与后端 SecretStatus 对应
+interface SecretStatus {
+  initialized: boolean;
+  mode: 'install' | 'passphrase';
+  unlocked: boolean;
+  count: number;
+}
+
+// 机密存储：设备解锁密钥、客户账号等加密后保存在 app.cfg 中
+function SecretStorePage() {
+  const [status, setStatus] = useState<SecretStatus | null>(null);
+  const [names, setNames] = useState<string[]>([]);
+  const [passphrase, setPassphrase] = useState<string>('');
+  const [confirmPassphrase, setConfirmPassphrase] = useState<string>('');
+  const [currentPassphrase, setCurrentPassphrase] = useState<string>('');
+  const [newPassphrase, setNewPassphrase] = useState<string>('');
+  const [name, setName] = useState<string>('');
+  const [value, setValue] = useState<string>('');
+
+  const refresh = async () => {
+    setStatus(await invoke<SecretStatus>("secret_status"));
+    setNames(await invoke<string[]>("store_list_secrets"));
+  };
+
+  useEffect(() => {
+    refresh().catch(e => message(String(e)));
+  }, []);
+
+  const run = async (action: () => Promise<unknown>) => {
+    try {
+      await action();
+      await refresh();
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  // 首次使用时选择保护方式：填写口令为口令保护，留空使用本机安装密钥
+  const init = () => run(async () => {
+    if (passphrase !== confirmPassphrase) {
+      await message('两次输入的口令不一致');
+      return;
+    }
+    await invoke("secret_init", {passphrase: passphrase || null});
+    setPassphrase('');
+    setConfirmPassphrase('');
+  });
+
+  const unlock = () => run(async () => {
+    await invoke("secret_unlock", {passphrase: passphrase || null});
+    setPassphrase('');
+  });
+
+  const changePassphrase = () => run(async () => {
+    await invoke("secret_set_passphrase", {
+      currentPassphrase: currentPassphrase || null,
+      passphrase: newPassphrase || null,
+    });
+    setCurrentPassphrase('');
+    setNewPassphrase('');
+    await message(newPassphrase ? '已启用口令保护' : '已改为使用本机安装密钥');
+  });
+
+  // 值按 JSON 解析，解析失败时按字符串保存
+  const save = () => run(async () => {
+    let parsed: unknown = value;
+    try {
+      parsed = JSON.parse(value);
+    } catch {
+      // 保持字符串
+    }
+    await invoke("store_set_secret", {key: name.trim(), value: parsed});
+    setValue('');
+  });
+
+  const load = (key: string) => run(async () => {
+    const result = await invoke<unknown>("store_get_secret", {key});
+    setName(key);
+    setValue(typeof result === 'string' ? result : JSON.stringify(result, null, 2));
+  });
+
+  const columns = [
+    {colKey: 'name', title: '名称'},
+    {
+      colKey: 'op', title: '操作', width: 160,
+      cell: ({row}: { row: { name: string } }) => (
+          <Space size="small">
+            <Button size="small" variant="text" disabled={!status?.unlocked} onClick={() => load(row.name)}>查看</Button>
+            <Button size="small" variant="text" theme="danger" disabled={!status?.unlocked}
+                    onClick={() => run(() => invoke("store_delete_secret", {key: row.name}))}>删除</Button>
+          </Space>
+      ),
+    },
+  ];
+
+  return (
+      <div className="h-full flex flex-col gap-3 p-4">
+        {status && !status.initialized ? (
+            <Space>
+              <Tag theme="warning" variant="light">未初始化</Tag>
+              <Input style={{width: 220}} type="password" value={passphrase} placeholder="口令（留空使用本机安装密钥）"
+                     onChange={v => setPassphrase(v)}/>
+              <Input style={{width: 220}} type="password" value={confirmPassphrase} placeholder="确认口令"
+                     disabled={!passphrase} onChange={v => setConfirmPassphrase(v)}/>
+              <Button onClick={init}>初始化</Button>
+            </Space>
+        ) : (
+        <Space>
+          {status?.unlocked
+              ? <Tag theme="success" variant="light">已解锁</Tag>
+              : <Tag theme="warning" variant="light">已锁定</Tag>}
+          <Tag variant="outline">{status?.mode === 'passphrase' ? '口令保护' : '本机安装密钥'}</Tag>
+          {status?.unlocked ? (
+              <Button variant="outline" icon={<LockOnIcon/>} onClick={() => run(() => invoke("secret_lock"))}>锁定</Button>
+          ) : (
+              <>
+                {status?.mode === 'passphrase' &&
+                    <Input style={{width: 220}} type="password" value={passphrase} placeholder="口令"
+                           onChange={v => setPassphrase(v)} onEnter={unlock}/>}
+                <Button icon={<LockOffIcon/>} onClick={unlock}>解锁</Button>
+              </>
+          )}
+        </Space>
+        )}
+        {status?.unlocked && (
+            <>
+              <Space>
+                {status.mode === 'passphrase' &&
+                    <Input style={{width: 220}} type="password" value={currentPassphrase} placeholder="当前口令"
+                           onChange={v => setCurrentPassphrase(v)}/>}
+                <Input style={{width: 220}} type="password" value={newPassphrase}
+                       placeholder="新口令（留空改用安装密钥）" onChange={v => setNewPassphrase(v)}/>
+                <Button variant="outline" onClick={changePassphrase}>修改口令</Button>
+              </Space>
+              <Space>
+                <Input style={{width: 220}} value={name} placeholder="名称" onChange={v => setName(v)}/>
+                <Button disabled={!name.trim()} onClick={save}>保存</Button>
+              </Space>
+              <Textarea className="font-mono" value={value} placeholder="值（文本或 JSON）"
+                        onChange={v => setValue(v)} autosize={{minRows: 4}}/>
+            </>
+        )}
+        <Table rowKey="name" data={names.map(n => ({name: n}))} columns={columns} size="small"/>
+      </div>
+  );
+}
+
+export default SecretStorePage;
@@ -0,0 +1,214 @@
+import {useEffect, useState} from 'react';
+import {invoke} from "@tauri-apps/api/core";
+import {message, open} from "@tauri-apps/plugin-dialog";
+import {Button, Divider, Input, Popconfirm, Select, Space, Table, Tag, Textarea} from "tdesign-react";
+import {FileImportIcon} from "tdesign-icons-react";
+
+// 与后端 SigningKeyInfo 对应
+interface SigningKeyInfo {
+  name: string;
+  algorithm: 'ed25519' | 'ecdsa_p256';
+  public_key: string;
+  fingerprint: string;
+}
+
+// 与后端 SignatureInfo / SignatureCheck 对应
+interface SignatureInfo {
+  algorithm: string;
+  address: number;
+  length: number;
+  digest: string;
+  block_address: number | null;
+}
+
+interface VerifyResult {
+  valid: boolean;
+  digest_match: boolean;
+  key_match: boolean;
+  signature_valid: boolean;
+  signature: SignatureInfo;
+}
+
+const ALGORITHMS = [
+  {value: 'ed25519', label: 'Ed25519'},
+  {value: 'ecdsa_p256', label: 'ECDSA P-256'},
+];
+
+const OUTPUT_FORMATS = [
+  {value: 'hex', label: 'Intel HEX'},
+  {value: 'srec', label: 'S-record'},
+  {value: 'bin', label: 'BIN'},
+];
+
+const FIRMWARE_FILTERS = [{name: '固件', extensions: ['hex', 'bin', 's19', 'srec']}];
+
+const hex32 = (v: number) => '0x' + v.toString(16).toUpperCase().padStart(8, '0');
+
+// 解析十进制或 0x 开头的十六进制地址，空字符串返回 null
+const parseAddress = (v: string) => v.trim() ? Number(v.trim()) : null;
+
+// 固件签名：密钥对保存在机密存储中，签名块格式见后端 fun_signing
+function SigningPage() {
+  const [keys, setKeys] = useState<SigningKeyInfo[]>([]);
+  const [keyName, setKeyName] = useState<string>('');
+  const [algorithm, setAlgorithm] = useState<string>('ed25519');
+  const [privateKey, setPrivateKey] = useState<string>('');
+  const [selected, setSelected] = useState<string>('');
+  const [firmwarePath, setFirmwarePath] = useState<string>('');
+  const [baseAddress, setBaseAddress] = useState<string>('0x08000000');
+  const [rangeStart, setRangeStart] = useState<string>('');
+  const [rangeEnd, setRangeEnd] = useState<string>('');
+  const [blockAddress, setBlockAddress] = useState<string>('');
+  const [mode, setMode] = useState<string>('append');
+  const [format, setFormat] = useState<string>('hex');
+  const [fileName, setFileName] = useState<string>('signed');
+  const [signaturePath, setSignaturePath] = useState<string>('');
+  const [output, setOutput] = useState<string>('');
+  const [verify, setVerify] = useState<VerifyResult | null>(null);
+
+  const refresh = async () => setKeys(await invoke<SigningKeyInfo[]>("signing_key_list"));
+
+  useEffect(() => {
+    refresh().catch(e => message(String(e)));
+  }, []);
+
+  const run = async (action: () => Promise<unknown>) => {
+    try {
+      await action();
+      await refresh();
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  const createKey = () => run(async () => {
+    if (privateKey.trim()) {
+      await invoke("signing_key_import", {name: keyName, algorithm, privateKey});
+    } else {
+      await invoke("signing_key_generate", {name: keyName, algorithm});
+    }
+    setPrivateKey('');
+    setKeyName('');
+  });
+
+  const exportKey = (name: string) => run(async () => {
+    const result = await invoke<{ content: string, output_path: string | null }>("signing_key_export_public", {
+      name, arrayName: null, fileName: `${name}_public_key`,
+    });
+    setOutput(result.content);
+    await message(`已保存到 ${result.output_path}`);
+  });
+
+  const input = () => ({
+    path: firmwarePath,
+    base_address: parseAddress(baseAddress),
+  });
+
+  const sign = () => run(async () => {
+    const result = await invoke<{ output_path: string, signature: SignatureInfo }>("firmware_sign", {
+      keyName: selected,
+      request: {
+        input: input(),
+        range_start: parseAddress(rangeStart),
+        range_end: parseAddress(rangeEnd),
+        mode,
+        block_address: parseAddress(blockAddress),
+        output: {format, file_name: fileName.trim() || 'signed'},
+      },
+    });
+    const s = result.signature;
+    setOutput([
+      `输出文件: ${result.output_path}`,
+      `签名范围: ${hex32(s.address)} - ${hex32(s.address + s.length)}（${s.length} 字节）`,
+      s.block_address !== null ? `签名块地址: ${hex32(s.block_address)}` : '分离签名',
+      `SHA-256: ${s.digest}`,
+    ].join('\n'));
+  });
+
+  const verifyFirmware = () => run(async () => {
+    setVerify(await invoke<VerifyResult>("firmware_verify", {
+      keyName: selected || null,
+      publicKey: null,
+      request: {input: input(), signature_path: signaturePath || null},
+    }));
+  });
+
+  const pickFile = async (setter: (v: string) => void, filters: { name: string, extensions: string[] }[]) => {
+    const filePath = await open({filters});
+    if (filePath) setter(filePath as string);
+  };
+
+  const columns = [
+    {colKey: 'name', title: '名称', width: 140},
+    {colKey: 'algorithm', title: '算法', width: 120, cell: ({row}: { row: SigningKeyInfo }) =>
+          ALGORITHMS.find(a => a.value === row.algorithm)?.label},
+    {colKey: 'fingerprint', title: '指纹 (SHA-256)', ellipsis: true},
+    {
+      colKey: 'op', title: '操作', width: 160,
+      cell: ({row}: { row: SigningKeyInfo }) => (
+          <Space size="small">
+            <Button size="small" variant="text" onClick={() => exportKey(row.name)}>导出 C 数组</Button>
+            <Popconfirm theme="danger" content={`删除签名密钥 ${row.name}？私钥删除后无法恢复，已签名的固件只能用导出的公钥校验`}
+                        onConfirm={() => run(() => invoke("signing_key_delete", {name: row.name}))}>
+              <Button size="small" variant="text" theme="danger">删除</Button>
+            </Popconfirm>
+          </Space>
+      ),
+    },
+  ];
+
+  return (
+      <div className="h-full flex flex-col gap-3 p-4">
+        <Space>
+          <Input style={{width: 160}} value={keyName} placeholder="密钥名称" onChange={v => setKeyName(v)}/>
+          <Select style={{width: 140}} value={algorithm} options={ALGORITHMS} onChange={v => setAlgorithm(v as string)}/>
+          <Input style={{width: 320}} type="password" value={privateKey} placeholder="导入私钥（十六进制，留空则随机生成）"
+                 onChange={v => setPrivateKey(v)}/>
+          <Button disabled={!keyName.trim()} onClick={createKey}>{privateKey.trim() ? '导入' : '生成'}</Button>
+          <Tag variant="light">私钥保存在机密存储中，需先解锁</Tag>
+        </Space>
+        <Table rowKey="name" data={keys} columns={columns} size="small"/>
+        <Divider/>
+        <Space>
+          <Input style={{width: 360}} value={firmwarePath} placeholder="固件文件" onChange={v => setFirmwarePath(v)}/>
+          <Button variant="outline" icon={<FileImportIcon/>}
+                  onClick={() => pickFile(setFirmwarePath, FIRMWARE_FILTERS)}>选择固件</Button>
+          <Input style={{width: 140}} value={baseAddress} placeholder="bin 基地址" onChange={v => setBaseAddress(v)}/>
+          <Select style={{width: 160}} value={selected} placeholder="签名密钥"
+                  options={keys.map(k => ({value: k.name, label: k.name}))} onChange={v => setSelected(v as string)}/>
+        </Space>
+        <Space>
+          <Input style={{width: 140}} value={rangeStart} placeholder="起始地址（默认最低）" onChange={v => setRangeStart(v)}/>
+          <Input style={{width: 140}} value={rangeEnd} placeholder="结束地址（默认最高）" onChange={v => setRangeEnd(v)}/>
+          <Select style={{width: 140}} value={mode} onChange={v => setMode(v as string)} options={[
+            {value: 'append', label: '追加签名块'},
+            {value: 'detached', label: '分离签名'},
+          ]}/>
+          <Input style={{width: 160}} value={blockAddress} placeholder="签名块地址（可选）"
+                 disabled={mode !== 'append'} onChange={v => setBlockAddress(v)}/>
+          <Select style={{width: 120}} value={format} options={OUTPUT_FORMATS} disabled={mode !== 'append'}
+                  onChange={v => setFormat(v as string)}/>
+          <Input style={{width: 160}} value={fileName} placeholder="输出文件名" onChange={v => setFileName(v)}/>
+          <Button disabled={!firmwarePath || !selected} onClick={sign}>签名</Button>
+        </Space>
+        <Space>
+          <Input style={{width: 360}} value={signaturePath} placeholder="分离签名文件（留空则在固件中查找签名块）"
+                 onChange={v => setSignaturePath(v)}/>
+          <Button variant="outline" icon={<FileImportIcon/>}
+                  onClick={() => pickFile(setSignaturePath, [{name: '签名', extensions: ['sig']}])}>选择签名</Button>
+          <Button variant="outline" disabled={!firmwarePath || !selected} onClick={verifyFirmware}>验证</Button>
+          {verify && (
+              <>
+                <Tag theme={verify.valid ? 'success' : 'danger'}>{verify.valid ? '签名有效' : '签名无效'}</Tag>
+                <Tag variant="outline" theme={verify.digest_match ? 'default' : 'danger'}>数据摘要</Tag>
+                <Tag variant="outline" theme={verify.key_match ? 'default' : 'danger'}>公钥指纹</Tag>
+                <Tag variant="outline" theme={verify.signature_valid ? 'default' : 'danger'}>签名</Tag>
+              </>
+          )}
+        </Space>
+        <Textarea className="font-mono" value={output} readOnly autosize={{minRows: 8}}/>
+      </div>
+  );
+}
+
+export default SigningPage;
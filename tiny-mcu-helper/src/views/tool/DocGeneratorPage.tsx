@@ -1,18 +1,119 @@
-import React from 'react';
+import {useEffect, useState} from 'react';
 import {invoke} from "@tauri-apps/api/core";
-import {Button} from "tdesign-react";
+import {message, open} from "@tauri-apps/plugin-dialog";
+import {Button, Checkbox, Input, Select, Space, Textarea} from "tdesign-react";
+import {FileImportIcon, PlayIcon} from "tdesign-icons-react";
+
+// 生成风格，与后端 RegisterCodegenStyle 对应
+const CODEGEN_STYLES = [
+  {value: 'c_defines', label: 'C 头文件（#define 掩码/位置）'},
+  {value: 'c_struct', label: 'C 头文件（结构体 + 位域）'},
+  {value: 'rust_pac', label: 'Rust（类 svd2rust 风格）'},
+  {value: 'rust_consts', label: 'Rust（常量模块）'},
+];
+
+interface SvdDeviceRecord {
+  id: number;
+  name: string;
+  file_path: string;
+}
+
+interface RegisterMapRecord {
+  id: number;
+  name: string;
+}
 
 function DocGeneratorPage() {
+  const [devices, setDevices] = useState<SvdDeviceRecord[]>([]);
+  const [maps, setMaps] = useState<RegisterMapRecord[]>([]);
+  const [source, setSource] = useState<string>(''); // "svd:1" 或 "map:1"
+  const [style, setStyle] = useState<string>('c_defines');
+  const [peripherals, setPeripherals] = useState<string>(''); // 逗号分隔，留空生成全部
+  const [withEnums, setWithEnums] = useState<boolean>(true);
+  const [fileName, setFileName] = useState<string>('');
+  const [content, setContent] = useState<string>('');
+  const [loading, setLoading] = useState<boolean>(false);
+
+  // 刷新可用的数据来源
+  const refresh = async () => {
+    setDevices(await invoke<SvdDeviceRecord[]>("svd_list_devices"));
+    setMaps(await invoke<RegisterMapRecord[]>("register_map_list"));
+  };
+
+  useEffect(() => {
+    refresh().catch(e => message(String(e)));
+  }, []);
+
+  // 导入 SVD 文件
+  const importSvd = async () => {
+    const filePath = await open({filters: [{name: 'SVD', extensions: ['svd', 'xml']}]});
+    if (!filePath) return;
+    try {
+      setLoading(true);
+      const result = await invoke<{ device: SvdDeviceRecord }>("svd_load", {filePath});
+      await refresh();
+      setSource(`svd:${result.device.id}`);
+    } catch (e) {
+      await message(String(e));
+    } finally {
+      setLoading(false);
+    }
+  };
+
   const gen = async () => {
-    await invoke("create_file")
-  }
+    if (!source) {
+      await message('请先选择 SVD 器件或寄存器映射');
+      return;
+    }
+    const [type, id] = source.split(':');
+    const names = peripherals.split(',').map(s => s.trim()).filter(s => s);
+    try {
+      setLoading(true);
+      const result = await invoke<{ content: string, output_path: string | null }>("register_codegen_generate", {
+        source: type === 'svd' ? {type, device_id: Number(id)} : {type, map_id: Number(id)},
+        config: {style, peripherals: names.length ? names : null, with_enums: withEnums},
+        fileName: fileName.trim() || null,
+      });
+      setContent(result.content);
+      if (result.output_path) {
+        await message(`已保存到 ${result.output_path}`);
+      }
+    } catch (e) {
+      await message(String(e));
+    } finally {
+      setLoading(false);
+    }
+  };
+
   return (
-      <div>
-        <Button onClick={() => {
-          gen().then(r => r)
-        }}/>
+      <div className="h-full flex flex-col gap-3 p-4">
+        <Space>
+          <Select
+              style={{width: 280}}
+              value={source}
+              placeholder="选择 SVD 器件或寄存器映射"
+              onChange={v => setSource(v as string)}
+              options={[
+                ...devices.map(d => ({value: `svd:${d.id}`, label: `SVD: ${d.name}`})),
+                ...maps.map(m => ({value: `map:${m.id}`, label: `映射: ${m.name}`})),
+              ]}
+          />
+          <Button variant="outline" icon={<FileImportIcon/>} loading={loading} onClick={importSvd}>导入 SVD</Button>
+        </Space>
+        <Space>
+          <Select style={{width: 280}} value={style} options={CODEGEN_STYLES} onChange={v => setStyle(v as string)}/>
+          <Input style={{width: 240}} value={peripherals} placeholder="外设（逗号分隔，留空为全部）"
+                 onChange={v => setPeripherals(v)}/>
+          <Checkbox checked={withEnums} onChange={v => setWithEnums(v)}>包含枚举值</Checkbox>
+        </Space>
+        <Space>
+          <Input style={{width: 280}} value={fileName} placeholder="保存文件名（留空仅预览）"
+                 onChange={v => setFileName(v)}/>
+          <Button icon={<PlayIcon/>} loading={loading} onClick={gen}>生成</Button>
+        </Space>
+        <Textarea className="flex-1 font-mono" value={content} readOnly autosize={{minRows: 20}}/>
       </div>
   );
 }
 
-export default DocGeneratorPage;
\ No newline at end of file
+export default DocGeneratorPage;
@@ -0,0 +1,221 @@
+import {useEffect, useState} from 'react';
+import {invoke} from "@tauri-apps/api/core";
+import {message, open} from "@tauri-apps/plugin-dialog";
+import {Button, Checkbox, Input, Select, Space, Textarea} from "tdesign-react";
+import {FileImportIcon, PlayIcon, SaveIcon} from "tdesign-icons-react";
+
+// 与后端 DocTemplateRecord 对应
+interface DocTemplateRecord {
+  id: number;
+  name: string;
+  description: string | null;
+  version: number;
+}
+
+interface DocTemplateVersionRecord {
+  version: number;
+  comment: string | null;
+  created_at: string;
+}
+
+interface DocTemplatePreset {
+  name: string;
+  description: string;
+  content: string;
+}
+
+// 输出格式，与后端 DocOutputFormat 对应
+const OUTPUT_FORMATS = [
+  {value: '', label: '仅预览'},
+  {value: 'text', label: '文本（.txt）'},
+  {value: 'pdf', label: 'PDF'},
+  {value: 'html', label: 'HTML'},
+  {value: 'docx', label: 'DOCX'},
+];
+
+const DEFAULT_DATA = JSON.stringify({
+  project: "",
+  version: "v1.0.0",
+  changes: [],
+  cases: [],
+}, null, 2);
+
+// 发布说明 / 测试报告生成器：模板保存在 SQLite 中并按版本管理
+function DocTemplatePage() {
+  const [templates, setTemplates] = useState<DocTemplateRecord[]>([]);
+  const [presets, setPresets] = useState<DocTemplatePreset[]>([]);
+  const [versions, setVersions] = useState<DocTemplateVersionRecord[]>([]);
+  const [templateId, setTemplateId] = useState<number | null>(null);
+  const [version, setVersion] = useState<number | null>(null);
+  const [name, setName] = useState<string>('');
+  const [content, setContent] = useState<string>('');
+  const [comment, setComment] = useState<string>('');
+  const [data, setData] = useState<string>(DEFAULT_DATA);
+  const [firmwarePath, setFirmwarePath] = useState<string>('');
+  const [mapPath, setMapPath] = useState<string>('');
+  const [withTodos, setWithTodos] = useState<boolean>(true);
+  const [format, setFormat] = useState<string>('');
+  const [fileName, setFileName] = useState<string>('');
+  const [styles, setStyles] = useState<string[]>([]);
+  const [style, setStyle] = useState<string>('');
+  const [output, setOutput] = useState<string>('');
+  const [loading, setLoading] = useState<boolean>(false);
+
+  const refresh = async () => {
+    setTemplates(await invoke<DocTemplateRecord[]>("doc_template_list"));
+  };
+
+  useEffect(() => {
+    refresh().catch(e => message(String(e)));
+    invoke<DocTemplatePreset[]>("doc_template_presets").then(setPresets);
+    invoke<{ name: string }[]>("markdown_style_list").then(list => setStyles(list.map(s => s.name)));
+  }, []);
+
+  // 选择已保存的模板或内置模板（"preset:名称"）
+  const selectTemplate = async (value: string) => {
+    if (value.startsWith('preset:')) {
+      const preset = presets.find(p => p.name === value.slice(7));
+      setTemplateId(null);
+      setVersion(null);
+      setVersions([]);
+      setName(preset?.name ?? '');
+      setContent(preset?.content ?? '');
+      return;
+    }
+    await loadVersion(Number(value), null);
+  };
+
+  const loadVersion = async (id: number, ver: number | null) => {
+    try {
+      const detail = await invoke<{ template: DocTemplateRecord, version: number, content: string }>(
+          "doc_template_get", {templateId: id, version: ver});
+      setTemplateId(id);
+      setVersion(detail.version);
+      setName(detail.template.name);
+      setContent(detail.content);
+      setVersions(await invoke<DocTemplateVersionRecord[]>("doc_template_versions", {templateId: id}));
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  const save = async () => {
+    try {
+      const result = await invoke<{ id: number, version: number }>("doc_template_save", {
+        templateId, name, description: null, content, comment: comment.trim() || null,
+      });
+      setComment('');
+      await refresh();
+      await loadVersion(result.id, result.version);
+    } catch (e) {
+      await message(String(e));
+    }
+  };
+
+  const remove = async () => {
+    if (templateId === null) return;
+    await invoke("doc_template_delete", {templateId});
+    setTemplateId(null);
+    setVersions([]);
+    setVersion(null);
+    await refresh();
+  };
+
+  const pickFile = async (setter: (v: string) => void, extensions: string[]) => {
+    const filePath = await open({filters: [{name: extensions.join('/'), extensions}]});
+    if (filePath) setter(filePath as string);
+  };
+
+  const render = async () => {
+    let parsed;
+    try {
+      parsed = JSON.parse(data || '{}');
+    } catch (e) {
+      await message(`数据不是合法的 JSON: ${e}`);
+      return;
+    }
+    try {
+      setLoading(true);
+      const result = await invoke<{ markdown: string, output_path: string | null }>("doc_template_render", {
+        request: {
+          content,
+          data: parsed,
+          sources: {
+            firmware: firmwarePath ? {path: firmwarePath} : null,
+            memory: mapPath ? (mapPath.endsWith('.map') ? {map_path: mapPath} : {elf_path: mapPath}) : null,
+            todos: withTodos,
+          },
+          output: format ? {format, file_name: fileName.trim() || 'document', style: style || null} : null,
+        },
+      });
+      setOutput(result.markdown);
+      if (result.output_path) {
+        await message(`已保存到 ${result.output_path}`);
+      }
+    } catch (e) {
+      await message(String(e));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  return (
+      <div className="h-full flex flex-col gap-3 p-4">
+        <Space>
+          <Select
+              style={{width: 240}}
+              value={templateId !== null ? String(templateId) : undefined}
+              placeholder="选择模板"
+              onChange={v => selectTemplate(v as string)}
+              options={[
+                ...templates.map(t => ({value: String(t.id), label: `${t.name}（v${t.version}）`})),
+                ...presets.map(p => ({value: `preset:${p.name}`, label: `内置: ${p.name}`})),
+              ]}
+          />
+          <Select
+              style={{width: 220}}
+              value={version ?? undefined}
+              placeholder="版本"
+              disabled={templateId === null}
+              onChange={v => templateId !== null && loadVersion(templateId, v as number)}
+              options={versions.map(v => ({
+                value: v.version,
+                label: `v${v.version} ${v.comment ?? ''} ${v.created_at}`,
+              }))}
+          />
+          <Input style={{width: 200}} value={name} placeholder="模板名称" onChange={v => setName(v)}/>
+          <Input style={{width: 200}} value={comment} placeholder="版本说明（可选）" onChange={v => setComment(v)}/>
+          <Button variant="outline" icon={<SaveIcon/>} onClick={save}>保存</Button>
+          <Button variant="outline" theme="danger" disabled={templateId === null} onClick={remove}>删除</Button>
+        </Space>
+        <Space>
+          <Input style={{width: 320}} value={firmwarePath} placeholder="固件文件（可选）" onChange={v => setFirmwarePath(v)}/>
+          <Button variant="outline" icon={<FileImportIcon/>}
+                  onClick={() => pickFile(setFirmwarePath, ['hex', 'bin', 's19', 'srec'])}>选择固件</Button>
+          <Input style={{width: 320}} value={mapPath} placeholder="ELF / map 文件（可选）" onChange={v => setMapPath(v)}/>
+          <Button variant="outline" icon={<FileImportIcon/>}
+                  onClick={() => pickFile(setMapPath, ['elf', 'axf', 'out', 'map'])}>选择 ELF/map</Button>
+          <Checkbox checked={withTodos} onChange={v => setWithTodos(v)}>包含待办事项</Checkbox>
+        </Space>
+        <Space>
+          <Select style={{width: 140}} value={format} options={OUTPUT_FORMATS} onChange={v => setFormat(v as string)}/>
+          <Input style={{width: 200}} value={fileName} placeholder="输出文件名" disabled={!format}
+                 onChange={v => setFileName(v)}/>
+          <Select style={{width: 160}} value={style} placeholder="默认样式" clearable
+                  disabled={!format || format === 'text'}
+                  options={styles.map(s => ({value: s, label: s}))}
+                  onChange={v => setStyle((v as string) ?? '')}/>
+          <Button icon={<PlayIcon/>} loading={loading} onClick={render}>生成</Button>
+        </Space>
+        <div className="flex-1 grid grid-cols-3 gap-3">
+          <Textarea className="font-mono" value={content} placeholder="Handlebars 模板（Markdown）"
+                    onChange={v => setContent(v)} autosize={{minRows: 20}}/>
+          <Textarea className="font-mono" value={data} placeholder="模板数据（JSON）"
+                    onChange={v => setData(v)} autosize={{minRows: 20}}/>
+          <Textarea className="font-mono" value={output} readOnly autosize={{minRows: 20}}/>
+        </div>
+      </div>
+  );
+}
+
+export default DocTemplatePage;